@@ -30,7 +30,13 @@ const ALL_STRATEGIES: &[Strategy] = &[
     Strategy::NakedQuads,       // 50
     Strategy::Jellyfish,        // 52
     Strategy::HiddenQuads,      // 54
-    //Strategy::SinglesChain,
+    Strategy::SimpleColoring,   // 65
+    Strategy::RemotePairs,      // 66
+    Strategy::XyChain,          // 66
+    Strategy::AlternatingInferenceChain, // 70
+    Strategy::AlsXz,            // 72
+    Strategy::AlsXyWing,        // 74
+    Strategy::ForcingChains,    // 85+
 ];
 
 macro_rules! make_benches {
@@ -79,11 +85,16 @@ fn generate_sudoku(b: &mut test::Bencher) {
     b.iter(Sudoku::generate)
 }
 
+#[bench]
+fn generate_unique_batch_100(b: &mut test::Bencher) {
+    b.iter(|| Sudoku::generate_unique_batch(100))
+}
+
 // this test is probabilistic in nature
 // if an error occurs, note down the sudoku that it generated
 #[bench]
 fn shuffle(b: &mut test::Bencher) {
-    let mut sudoku = Sudoku::generate_solved();
+    let mut sudoku = Sudoku::generate_solved().into_sudoku();
     b.iter(|| {
         sudoku.shuffle();
         test::black_box(sudoku);
@@ -148,6 +159,30 @@ fn is_solved_on_solved(b: &mut test::Bencher) {
     })
 }
 
+#[bench]
+fn is_singles_solvable(b: &mut test::Bencher) {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    b.iter(|| {
+        for sudoku in sudokus.iter() {
+            sudoku.is_singles_solvable();
+        }
+    })
+}
+
+#[bench]
+fn solve_stress_puzzles(b: &mut test::Bencher) {
+    use sudoku::generator::StressPuzzle;
+    let sudokus = [
+        StressPuzzle::AntiBruteForce.sudoku(),
+        StressPuzzle::EasterMonster.sudoku(),
+    ];
+    b.iter(|| {
+        for sudoku in sudokus.iter() {
+            sudoku.solution().unwrap();
+        }
+    })
+}
+
 #[bench]
 fn strategy_solver_1_easy_sudokus(b: &mut test::Bencher) {
     let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
@@ -173,6 +208,42 @@ fn strategy_solver_2_medium_sudokus(b: &mut test::Bencher) {
     })
 }
 
+// Uniqueness checks of a puzzle after removing its clues one at a time, as during generation
+#[bench]
+fn remove_clues_rebuilding_solver(b: &mut test::Bencher) {
+    use sudoku::solver::Solver;
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    b.iter(|| {
+        for sudoku in sudokus.iter().take(100) {
+            let mut grid = sudoku.to_bytes();
+            for (cell, _) in sudoku.clues().collect::<Vec<_>>().into_iter().rev() {
+                grid[cell.as_index()] = 0;
+                let sudoku = Sudoku::from_bytes(grid).unwrap();
+                Solver::from_sudoku(sudoku).unwrap().solutions_count_up_to(2);
+            }
+        }
+    })
+}
+
+#[bench]
+fn remove_clues_incremental_solver(b: &mut test::Bencher) {
+    use sudoku::board::Digit;
+    use sudoku::solver::IncrementalSolver;
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    b.iter(|| {
+        for sudoku in sudokus.iter().take(100) {
+            let mut solver = IncrementalSolver::new();
+            for (cell, digit) in sudoku.clues() {
+                solver.push_clue(cell, Digit::new(digit)).unwrap();
+            }
+            while solver.n_clues() != 0 {
+                solver.pop_clue();
+                solver.solutions_count_up_to(2);
+            }
+        }
+    })
+}
+
 #[bench]
 fn canonicalize(b: &mut test::Bencher) {
     let sudokus = (0..1000).map(|_| Sudoku::generate_solved()).collect::<Vec<_>>();
@@ -0,0 +1,157 @@
+//! Colored display of sudokus in terminals
+//!
+//! [`Sudoku::display_block_colored`] prints a large grid that shows the candidates of every empty
+//! cell and marks cells and candidates with ANSI color codes, e.g. to explain a hint inline in a
+//! command line trainer. [`Highlight`]s of cells color their background and highlights of
+//! candidates color the digit.
+
+use crate::bitset::Set;
+use crate::board::{Candidate, Cell, Digit};
+use crate::Sudoku;
+use std::fmt::{self, Write};
+
+/// Terminal color of a [`Highlight`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    // ANSI codes of foreground colors are 30 + this, background colors 40 + this
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+        }
+    }
+}
+
+/// A cell or candidate to mark in color, see [`Sudoku::display_block_colored`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Highlight {
+    /// Colors the background of a cell.
+    Cell(Cell, Color),
+    /// Colors the digit of a filled cell or a candidate of an empty cell. A highlighted candidate
+    /// is shown, even if it conflicts with a filled neighbor.
+    Candidate(Candidate, Color),
+}
+
+impl Sudoku {
+    /// Returns a value that prints the sudoku as a large grid with ANSI color codes
+    /// when formatted via the `Display` trait.
+    ///
+    /// Every cell takes up 3x3 characters. Filled cells show their digit in the center, empty cells
+    /// the digits that don't conflict with any filled neighbor at the positions of a numpad.
+    /// If several highlights apply to the same cell or candidate, the last one wins.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::ansi::{Color, Highlight};
+    /// use sudoku::board::{Candidate, Cell};
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let highlights = [
+    ///     Highlight::Cell(Cell::new(0), Color::Green),
+    ///     Highlight::Candidate(Candidate::new(0, 8), Color::Red),
+    /// ];
+    /// let display = sudoku.display_block_colored(&highlights).to_string();
+    /// assert_eq!(display.lines().count(), 35);
+    /// // the background of the first cell is green, its candidate 8 red and bold
+    /// assert!(display.starts_with("\x1b[42m   \x1b[49m"));
+    /// assert!(display.contains("\x1b[1;31m8\x1b[22;39m"));
+    /// ```
+    pub fn display_block_colored(&self, highlights: &[Highlight]) -> SudokuColoredBlock {
+        let mut block = SudokuColoredBlock {
+            sudoku: *self,
+            cells: [None; 81],
+            candidates: [[None; 9]; 81],
+        };
+        for &highlight in highlights {
+            match highlight {
+                Highlight::Cell(cell, color) => block.cells[cell.as_index()] = Some(color),
+                Highlight::Candidate(Candidate { cell, digit }, color) => {
+                    block.candidates[cell.as_index()][digit.as_index()] = Some(color)
+                }
+            }
+        }
+        block
+    }
+}
+
+/// Sudoku that will be printed with colored highlights, see [`Sudoku::display_block_colored`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SudokuColoredBlock {
+    sudoku: Sudoku,
+    cells: [Option<Color>; 81],
+    candidates: [[Option<Color>; 9]; 81],
+}
+
+impl SudokuColoredBlock {
+    // Writes the characters of `cell` in the `line`th of its 3 lines
+    fn write_cell_line(&self, f: &mut fmt::Formatter, cell: Cell, line: u8) -> fmt::Result {
+        let idx = cell.as_index();
+        if let Some(color) = self.cells[idx] {
+            write!(f, "\x1b[{}m", 40 + color.code())?;
+        }
+        let entry = self.sudoku.0[idx];
+        let candidates = match entry {
+            0 => self.sudoku.basic_candidates(cell),
+            _ => Set::NONE,
+        };
+        for position in 1..=3 {
+            let digit = match entry {
+                0 => Digit::new(3 * line + position),
+                _ if line == 1 && position == 2 => Digit::new(entry),
+                _ => {
+                    f.write_char(' ')?;
+                    continue;
+                }
+            };
+            match self.candidates[idx][digit.as_index()] {
+                Some(color) => write!(f, "\x1b[1;{}m{}\x1b[22;39m", 30 + color.code(), digit.get())?,
+                None if entry != 0 || candidates.contains(digit) => write!(f, "{}", digit.get())?,
+                None => f.write_char(' ')?,
+            }
+        }
+        if self.cells[idx].is_some() {
+            f.write_str("\x1b[49m")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SudokuColoredBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..9 {
+            match row {
+                0 => {}
+                3 | 6 => f.write_str("\n------------+-------------+------------\n")?,
+                _ => f.write_str("\n\n")?,
+            }
+            for line in 0..3 {
+                if line != 0 {
+                    writeln!(f)?;
+                }
+                for col in 0..9 {
+                    match col {
+                        0 => {}
+                        3 | 6 => f.write_str(" | ")?,
+                        _ => f.write_char(' ')?,
+                    }
+                    self.write_cell_line(f, Cell::new(9 * row + col), line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,815 @@
+//! Processing of large collections of sudokus
+//!
+//! [`grade_all`] rates every sudoku of a slice on all available cores and collects the results
+//! in a [`DifficultyHistogram`], e.g. for sorting a collection of puzzles by difficulty
+//! or for checking the difficulty distribution of a generator.
+//! [`TimeCalibration`] translates the difficulties into estimated solve times.
+//!
+//! [`AnalyzedSudoku`] caches the solution and grade of a single sudoku for repeated queries.
+//!
+//! [`dedup`] removes equivalent puzzles from a stream of puzzles in the line format.
+//!
+//! [`convert`] streams puzzles from one [`Format`] to another.
+//! [`read_records`] reads puzzles together with their comments as [`PuzzleRecord`]s.
+//! [`database::Database`] stores large puzzle collections in an append-only binary file.
+//! With the `mmap` feature, the [`mmap`] module reads them without loading them into memory.
+//! [`pool::PuzzlePool`] picks random puzzles by difficulty for serving them to players.
+//! With the `csv` feature, the [`csv`] module reads and writes datasets in CSV format.
+
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod database;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod pool;
+
+use crate::consts::N_CELLS;
+use crate::errors::LineParseError;
+use crate::strategy::rating::{DifficultyModel, SeRating};
+use crate::strategy::StrategySolver;
+use crate::{SolvedSudoku, Sudoku};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::thread;
+use std::time::Duration;
+
+/// Coarse difficulty classes derived from the [`SeRating`] of a sudoku
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// The sudoku does not have a unique solution.
+    Invalid,
+    /// Solvable with singles. Rating up to 2.3.
+    Easy,
+    /// Requires locked candidates, pairs or X-Wings. Rating up to 3.4.
+    Medium,
+    /// Requires triples, Swordfish or wings. Rating up to 4.4.
+    Hard,
+    /// Requires quads, fish, coloring, chains, ALS or forcing chains. Rating above 4.4.
+    Expert,
+    /// Can't be solved within the maximum forcing chain depth of the solver, see [`SeRating::AtLeast`].
+    Unrated,
+}
+
+impl Difficulty {
+    /// All difficulties, from easiest to hardest
+    pub const ALL: [Difficulty; 6] = [
+        Difficulty::Invalid,
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+        Difficulty::Unrated,
+    ];
+
+    /// Classifies a uniquely solvable sudoku by its rating.
+    pub fn from_se_rating(rating: SeRating) -> Difficulty {
+        match rating {
+            SeRating::AtLeast(_) => Difficulty::Unrated,
+            SeRating::Rated(rating) if rating <= 2.3 => Difficulty::Easy,
+            SeRating::Rated(rating) if rating <= 3.4 => Difficulty::Medium,
+            SeRating::Rated(rating) if rating <= 4.4 => Difficulty::Hard,
+            SeRating::Rated(_) => Difficulty::Expert,
+        }
+    }
+
+    /// Returns the typical range of time a human needs to solve sudokus of this difficulty,
+    /// according to [`TimeCalibration::DEFAULT`]. `None` for [`Difficulty::Invalid`].
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use sudoku::batch::Difficulty;
+    ///
+    /// let range = Difficulty::Easy.estimated_time_range().unwrap();
+    /// assert!(range.contains(&Duration::from_secs(5 * 60)));
+    /// assert_eq!(Difficulty::Invalid.estimated_time_range(), None);
+    /// ```
+    pub fn estimated_time_range(self) -> Option<Range<Duration>> {
+        TimeCalibration::DEFAULT.estimated_time_range(self)
+    }
+}
+
+/// Estimated times a human needs to solve sudokus of each [`Difficulty`]
+///
+/// The [default](Self::DEFAULT) is a rough model of a practiced solver working with pencil marks.
+/// Solve times vary a lot between people, so the ranges can be replaced with ones measured
+/// for the intended audience.
+///
+/// ```
+/// use std::time::Duration;
+/// use sudoku::batch::{Difficulty, TimeCalibration};
+///
+/// let minutes = |m: u64| Duration::from_secs(m * 60);
+/// let beginners = TimeCalibration {
+///     easy: minutes(10)..minutes(30),
+///     ..TimeCalibration::DEFAULT
+/// };
+/// assert_eq!(beginners.estimated_time_range(Difficulty::Easy), Some(minutes(10)..minutes(30)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeCalibration {
+    /// Solve times for [`Difficulty::Easy`]
+    pub easy: Range<Duration>,
+    /// Solve times for [`Difficulty::Medium`]
+    pub medium: Range<Duration>,
+    /// Solve times for [`Difficulty::Hard`]
+    pub hard: Range<Duration>,
+    /// Solve times for [`Difficulty::Expert`]
+    pub expert: Range<Duration>,
+    /// Solve times for [`Difficulty::Unrated`]
+    pub unrated: Range<Duration>,
+}
+
+impl TimeCalibration {
+    /// Solve times of a practiced solver
+    ///
+    /// | Difficulty | Time           |
+    /// |------------|----------------|
+    /// | Easy       | 2 to 10 min    |
+    /// | Medium     | 5 to 20 min    |
+    /// | Hard       | 15 to 45 min   |
+    /// | Expert     | 30 to 90 min   |
+    /// | Unrated    | 1 to 4 hours   |
+    pub const DEFAULT: TimeCalibration = TimeCalibration {
+        easy: minutes(2)..minutes(10),
+        medium: minutes(5)..minutes(20),
+        hard: minutes(15)..minutes(45),
+        expert: minutes(30)..minutes(90),
+        unrated: minutes(60)..minutes(240),
+    };
+
+    /// Returns the range of solve times for `difficulty`. `None` for [`Difficulty::Invalid`].
+    pub fn estimated_time_range(&self, difficulty: Difficulty) -> Option<Range<Duration>> {
+        let range = match difficulty {
+            Difficulty::Invalid => return None,
+            Difficulty::Easy => &self.easy,
+            Difficulty::Medium => &self.medium,
+            Difficulty::Hard => &self.hard,
+            Difficulty::Expert => &self.expert,
+            Difficulty::Unrated => &self.unrated,
+        };
+        Some(range.clone())
+    }
+}
+
+impl Default for TimeCalibration {
+    fn default() -> Self {
+        TimeCalibration::DEFAULT
+    }
+}
+
+const fn minutes(minutes: u64) -> Duration {
+    Duration::from_secs(minutes * 60)
+}
+
+/// Difficulty of a single sudoku
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Grade {
+    /// The difficulty class
+    pub difficulty: Difficulty,
+    /// The rating of the sudoku, `None` if it doesn't have a unique solution
+    pub se_rating: Option<SeRating>,
+}
+
+impl Grade {
+    /// Grade a single sudoku.
+    pub fn new(sudoku: Sudoku) -> Grade {
+        if !sudoku.is_uniquely_solvable() {
+            return Grade::INVALID;
+        }
+        Grade::of_unique(sudoku)
+    }
+
+    /// Grade a single sudoku with a custom [`DifficultyModel`] instead of
+    /// [`SudokuExplainer`](crate::strategy::rating::SudokuExplainer).
+    /// The difficulty class is derived from the score as if it was on the SE scale.
+    pub fn with_model<M: DifficultyModel + ?Sized>(sudoku: Sudoku, model: &M) -> Grade {
+        if !sudoku.is_uniquely_solvable() {
+            return Grade::INVALID;
+        }
+        let rating = StrategySolver::from_sudoku(sudoku)
+            .assume_unique_solution(true)
+            .rate_with(model);
+        Grade {
+            difficulty: Difficulty::from_se_rating(rating),
+            se_rating: Some(rating),
+        }
+    }
+
+    const INVALID: Grade = Grade {
+        difficulty: Difficulty::Invalid,
+        se_rating: None,
+    };
+
+    // `sudoku` must be uniquely solvable
+    fn of_unique(sudoku: Sudoku) -> Grade {
+        let rating = StrategySolver::from_sudoku(sudoku)
+            .assume_unique_solution(true)
+            .se_rating();
+        Grade {
+            difficulty: Difficulty::from_se_rating(rating),
+            se_rating: Some(rating),
+        }
+    }
+}
+
+/// A sudoku together with its solution, uniqueness and grade
+///
+/// Asking a [`Sudoku`] for its solution, whether it is uniquely solvable and for its
+/// [`Grade`] solves it from scratch every time. `AnalyzedSudoku` does all of that once on
+/// construction and answers every later query from the cached results.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::batch::{AnalyzedSudoku, Difficulty};
+///
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// let analyzed = AnalyzedSudoku::new(sudoku);
+/// assert!(analyzed.is_uniquely_solvable());
+/// assert_eq!(analyzed.solution(), sudoku.solution());
+/// assert_eq!(analyzed.difficulty(), Difficulty::Easy);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AnalyzedSudoku {
+    sudoku: Sudoku,
+    // number of solutions, up to 2
+    n_solutions: u8,
+    solution: Option<SolvedSudoku>,
+    grade: Grade,
+}
+
+impl AnalyzedSudoku {
+    /// Solves and grades `sudoku`.
+    pub fn new(sudoku: Sudoku) -> AnalyzedSudoku {
+        let solutions = sudoku.solutions_up_to(2);
+        let (solution, grade) = match *solutions {
+            [solution] => (
+                Some(SolvedSudoku::new_unchecked(solution)),
+                Grade::of_unique(sudoku),
+            ),
+            _ => (None, Grade::INVALID),
+        };
+        AnalyzedSudoku {
+            sudoku,
+            n_solutions: solutions.len() as u8,
+            solution,
+            grade,
+        }
+    }
+
+    /// Returns the analyzed sudoku.
+    pub fn sudoku(&self) -> Sudoku {
+        self.sudoku
+    }
+
+    /// Returns the solution, if it is unique. See [`Sudoku::solution`].
+    pub fn solution(&self) -> Option<SolvedSudoku> {
+        self.solution
+    }
+
+    /// Returns `true` if the sudoku has at least one solution.
+    pub fn is_solvable(&self) -> bool {
+        self.n_solutions > 0
+    }
+
+    /// Returns `true` if the sudoku has exactly one solution. See [`Sudoku::is_uniquely_solvable`].
+    pub fn is_uniquely_solvable(&self) -> bool {
+        self.n_solutions == 1
+    }
+
+    /// Returns the grade of the sudoku. Equivalent to [`Grade::new`].
+    pub fn grade(&self) -> Grade {
+        self.grade
+    }
+
+    /// Returns the difficulty class of the sudoku.
+    pub fn difficulty(&self) -> Difficulty {
+        self.grade.difficulty
+    }
+
+    /// Returns the rating of the sudoku, `None` if it doesn't have a unique solution.
+    pub fn se_rating(&self) -> Option<SeRating> {
+        self.grade.se_rating
+    }
+}
+
+impl From<Sudoku> for AnalyzedSudoku {
+    fn from(sudoku: Sudoku) -> AnalyzedSudoku {
+        AnalyzedSudoku::new(sudoku)
+    }
+}
+
+/// Grades of a batch of sudokus together with the number of sudokus per difficulty
+///
+/// Returned by [`grade_all`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DifficultyHistogram {
+    grades: Vec<Grade>,
+    counts: [usize; 6],
+}
+
+impl DifficultyHistogram {
+    /// Returns the grades of all sudokus in the order they were passed in.
+    pub fn grades(&self) -> &[Grade] {
+        &self.grades
+    }
+
+    /// Returns the number of sudokus of the given `difficulty`.
+    pub fn count(&self, difficulty: Difficulty) -> usize {
+        self.counts[difficulty as usize]
+    }
+
+    /// Returns the number of sudokus per difficulty, from easiest to hardest.
+    /// Difficulties without any sudokus are included with a count of 0.
+    pub fn counts(&self) -> impl Iterator<Item = (Difficulty, usize)> + '_ {
+        Difficulty::ALL
+            .iter()
+            .map(move |&difficulty| (difficulty, self.count(difficulty)))
+    }
+
+    /// Returns the total number of graded sudokus.
+    pub fn len(&self) -> usize {
+        self.grades.len()
+    }
+
+    /// Returns `true` if no sudokus were graded.
+    pub fn is_empty(&self) -> bool {
+        self.grades.is_empty()
+    }
+}
+
+/// Grades all `sudokus` in parallel.
+///
+/// The work is split evenly across as many threads as the system reports to be available.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::batch::{grade_all, Difficulty};
+///
+/// let sudokus = [
+///     Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap(),
+///     Sudoku::from_bytes([0; 81]).unwrap(),
+/// ];
+/// let histogram = grade_all(&sudokus);
+/// assert_eq!(histogram.count(Difficulty::Easy), 1);
+/// assert_eq!(histogram.count(Difficulty::Invalid), 1);
+/// assert_eq!(histogram.grades()[1].se_rating, None);
+/// ```
+pub fn grade_all(sudokus: &[Sudoku]) -> DifficultyHistogram {
+    let grades = parallel_map(sudokus, |&sudoku| Grade::new(sudoku));
+
+    let mut counts = [0; 6];
+    for grade in &grades {
+        counts[grade.difficulty as usize] += 1;
+    }
+    DifficultyHistogram { grades, counts }
+}
+
+/// A set of puzzle fingerprints for [`dedup_with`]
+///
+/// Implemented for `HashSet<u64>`, which is exact, and, with the `bloom` feature, [`BloomFilter`],
+/// which uses a fixed amount of memory but may mistake some new fingerprints for known ones.
+pub trait FingerprintSet {
+    /// Adds the `fingerprint` to the set. Returns `true`, if it wasn't in the set already.
+    fn insert(&mut self, fingerprint: u64) -> bool;
+}
+
+impl FingerprintSet for HashSet<u64> {
+    fn insert(&mut self, fingerprint: u64) -> bool {
+        HashSet::insert(self, fingerprint)
+    }
+}
+
+/// A probabilistic [`FingerprintSet`] with a fixed memory footprint
+///
+/// Inserting a fingerprint that is already in the set is always detected, but with
+/// a small probability, a new fingerprint is mistaken for a known one.
+/// The puzzle is then dropped as a duplicate, even though it is unique.
+#[cfg(feature = "bloom")]
+#[derive(Clone, Debug)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    n_hashes: u32,
+}
+
+#[cfg(feature = "bloom")]
+impl BloomFilter {
+    /// Creates a filter sized for `capacity` fingerprints with a probability of
+    /// `false_positive_rate` of mistaking a new fingerprint for a known one, once it is full.
+    ///
+    /// # Panics
+    /// Panics, if `false_positive_rate` is not strictly between 0 and 1.
+    pub fn new(capacity: usize, false_positive_rate: f64) -> BloomFilter {
+        assert!(
+            0.0 < false_positive_rate && false_positive_rate < 1.0,
+            "false positive rate must be in (0, 1)"
+        );
+        let ln2 = std::f64::consts::LN_2;
+        let capacity = capacity.max(1) as f64;
+        let n_bits = (-capacity * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0);
+        let n_hashes = (n_bits / capacity * ln2).round().max(1.0) as u32;
+        BloomFilter {
+            bits: vec![0; (n_bits / 64.0).ceil() as usize],
+            n_hashes,
+        }
+    }
+
+    /// Returns the memory used for the filter in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+}
+
+#[cfg(feature = "bloom")]
+impl FingerprintSet for BloomFilter {
+    fn insert(&mut self, fingerprint: u64) -> bool {
+        // double hashing with a second hash derived from the first by the splitmix64 finalizer
+        let mut second = fingerprint ^ fingerprint >> 30;
+        second = second.wrapping_mul(0xbf58476d1ce4e5b9);
+        second ^= second >> 27;
+        second = second.wrapping_mul(0x94d049bb133111eb);
+        second ^= second >> 31;
+
+        let n_bits = self.bits.len() as u64 * 64;
+        let mut is_new = false;
+        for i in 0..self.n_hashes as u64 {
+            let bit = fingerprint.wrapping_add(i.wrapping_mul(second)) % n_bits;
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            is_new |= self.bits[word] & mask == 0;
+            self.bits[word] |= mask;
+        }
+        is_new
+    }
+}
+
+/// Statistics of a [`dedup`] run
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DedupStats {
+    /// Number of lines read, excluding empty lines
+    pub n_read: usize,
+    /// Number of unique puzzles written to the output
+    pub n_unique: usize,
+    /// Number of puzzles that were equivalent to an earlier one
+    pub n_duplicates: usize,
+    /// Number of lines that couldn't be parsed or whose puzzle isn't uniquely solvable
+    pub n_invalid: usize,
+}
+
+/// Copies the first of each class of equivalent puzzles from `input` to `output`.
+///
+/// The input is read line by line in the line format, like for [`Sudoku::from_str_line`].
+/// Two puzzles are equivalent, if they can be transformed into each other via the validity
+/// preserving transformations listed in [`Sudoku::shuffle`]. Unique puzzles are written out
+/// unchanged, including comments. Empty lines, lines that can't be parsed and puzzles that
+/// aren't uniquely solvable are skipped.
+///
+/// The puzzles are compared by their [canonical fingerprint](Sudoku::canonical_fingerprint),
+/// so only 8 bytes per unique puzzle are kept in memory. Fingerprint collisions are possible, but
+/// unlikely with less than billions of puzzles. For bounded memory usage, use [`dedup_with`] and
+/// a [`BloomFilter`] from the `bloom` feature.
+///
+/// For the rare puzzles whose solution has nontrivial automorphisms, the canonical form is not
+/// always unique, so some equivalent puzzles may be kept.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::batch::dedup;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let input = format!("{}\n{} shuffled\n", sudoku, sudoku.shuffled());
+/// let mut output = vec![];
+/// let stats = dedup(input.as_bytes(), &mut output).unwrap();
+/// assert_eq!(stats.n_unique, 1);
+/// assert_eq!(stats.n_duplicates, 1);
+/// assert_eq!(output, format!("{}\n", sudoku).into_bytes());
+/// ```
+pub fn dedup<R: BufRead, W: Write>(input: R, output: W) -> io::Result<DedupStats> {
+    dedup_with(input, output, &mut HashSet::new())
+}
+
+/// Like [`dedup`], but keeps track of the puzzles seen so far in `seen`.
+///
+/// Fingerprints already in `seen` are treated as duplicates, so it can be reused
+/// for deduplicating multiple files against each other.
+pub fn dedup_with<R: BufRead, W: Write, S: FingerprintSet>(
+    input: R,
+    mut output: W,
+    seen: &mut S,
+) -> io::Result<DedupStats> {
+    // lines are canonicalized in parallel, in chunks of this size
+    const CHUNK_SIZE: usize = 1 << 14;
+
+    let mut stats = DedupStats::default();
+    let mut lines = input.lines();
+    loop {
+        let chunk = lines
+            .by_ref()
+            .take(CHUNK_SIZE)
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .collect::<io::Result<Vec<_>>>()?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        let fingerprints = parallel_map(&chunk, |line| {
+            Sudoku::from_str_line(line).ok()?.canonical_fingerprint()
+        });
+        for (line, fingerprint) in chunk.iter().zip(fingerprints) {
+            stats.n_read += 1;
+            match fingerprint {
+                None => stats.n_invalid += 1,
+                Some(fingerprint) if seen.insert(fingerprint) => {
+                    stats.n_unique += 1;
+                    writeln!(output, "{}", line)?;
+                }
+                Some(_) => stats.n_duplicates += 1,
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// A sudoku in the line format together with the comment following it
+///
+/// Collections of puzzles often store ratings, sources or IDs in the comments.
+/// The comment is everything after the delimiter behind the 81 cells, see [`Sudoku::from_str_line`].
+/// Records are displayed in the line format with a space as delimiter, so reading them back
+/// gives the same record.
+///
+/// ```
+/// use sudoku::batch::PuzzleRecord;
+///
+/// let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...;SE 2.6";
+/// let record = PuzzleRecord::from_line(line).unwrap();
+/// assert_eq!(record.comment, "SE 2.6");
+/// assert_eq!(record.to_string(), line.replace(';', " "));
+/// assert_eq!(PuzzleRecord::from_line(&record.to_string()), Ok(record));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleRecord {
+    /// The puzzle
+    pub sudoku: Sudoku,
+    /// The comment without the delimiter. Empty, if the line has no comment.
+    pub comment: String,
+}
+
+impl PuzzleRecord {
+    /// Parses a line in the line format and keeps its comment.
+    pub fn from_line(line: &str) -> Result<PuzzleRecord, LineParseError> {
+        let sudoku = Sudoku::from_str_line(line)?;
+        // the 81 cells and the delimiter are all ascii
+        let comment = line.get(N_CELLS + 1..).unwrap_or("");
+        Ok(PuzzleRecord {
+            sudoku,
+            comment: comment.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for PuzzleRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.comment.is_empty() {
+            true => write!(f, "{}", self.sudoku),
+            false => write!(f, "{} {}", self.sudoku, self.comment),
+        }
+    }
+}
+
+/// Reads the puzzles of a collection in the line format one by one, together with their comments.
+///
+/// Empty lines are skipped. Lines that can't be parsed are returned as errors of the kind
+/// [`io::ErrorKind::InvalidData`] that wrap the [`LineParseError`], so they can be skipped, too.
+///
+/// ```
+/// use sudoku::batch::read_records;
+///
+/// let input = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1... #1\n\nnot a sudoku\n";
+/// let records = read_records(input.as_bytes()).collect::<Vec<_>>();
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].as_ref().unwrap().comment, "#1");
+/// assert!(records[1].is_err());
+/// ```
+pub fn read_records<R: BufRead>(input: R) -> impl Iterator<Item = io::Result<PuzzleRecord>> {
+    input
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| {
+            PuzzleRecord::from_line(line?.trim_end_matches(&['\r', '\n'][..]))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        })
+}
+
+/// Textual and binary formats for streams of sudokus, see [`convert`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// One sudoku per line, see [`Sudoku::from_str_line`]
+    Line,
+    /// Nine rows per sudoku, see [`Sudoku::from_str_block_permissive`].
+    /// Sudokus are written like [`Sudoku::display_block`] and separated by an empty line.
+    /// When reading, an empty line ends a sudoku, unless it follows its third or sixth line.
+    Block,
+    /// The SadMan Software format: nine rows of nine characters with `.` for empty cells.
+    /// Lines starting with `#` are comments. Sudokus are separated by an empty line.
+    /// When reading, comments separate sudokus like empty lines.
+    Sdk,
+    /// 41 bytes per sudoku with 4 bits per cell, starting with the high bits of the first byte.
+    /// Empty cells are 0 and the last 4 bits are unused.
+    Packed,
+}
+
+/// Statistics of a [`convert`] run
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConvertStats {
+    /// Number of sudokus written to the output
+    pub n_converted: usize,
+    /// Number of sudokus that couldn't be parsed
+    pub n_invalid: usize,
+}
+
+/// Reads sudokus in the format `from` from `input` and writes them in the format `to` to `output`.
+///
+/// The sudokus are converted one by one in a single pass, so memory usage doesn't depend on
+/// the size of the input. Sudokus that can't be parsed are skipped and counted as invalid.
+/// Comments of the line format are only preserved, if the output is in the line format, too.
+/// See [`PuzzleRecord`].
+///
+/// ```
+/// use sudoku::batch::{convert, Format};
+///
+/// let input = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...\n";
+/// let mut packed = vec![];
+/// convert(input.as_bytes(), Format::Line, &mut packed, Format::Packed).unwrap();
+/// assert_eq!(packed.len(), 41);
+///
+/// let mut line = vec![];
+/// let stats = convert(&packed[..], Format::Packed, &mut line, Format::Line).unwrap();
+/// assert_eq!(stats.n_converted, 1);
+/// assert_eq!(line, input.as_bytes());
+/// ```
+pub fn convert<R: BufRead, W: Write>(
+    mut input: R,
+    from: Format,
+    mut output: W,
+    to: Format,
+) -> io::Result<ConvertStats> {
+    let mut stats = ConvertStats::default();
+    let mut write_record = |record: Option<PuzzleRecord>| -> io::Result<()> {
+        let record = match record {
+            Some(record) => record,
+            None => {
+                stats.n_invalid += 1;
+                return Ok(());
+            }
+        };
+        if stats.n_converted != 0 && matches!(to, Format::Block | Format::Sdk) {
+            writeln!(output)?;
+        }
+        stats.n_converted += 1;
+        match to {
+            Format::Line => writeln!(output, "{}", record),
+            _ => write_sudoku(&mut output, record.sudoku, to),
+        }
+    };
+    let mut write = |sudoku: Option<Sudoku>| {
+        write_record(sudoku.map(|sudoku| PuzzleRecord {
+            sudoku,
+            comment: String::new(),
+        }))
+    };
+
+    match from {
+        Format::Packed => loop {
+            let mut bytes = [0; 41];
+            let n_read = read_up_to(&mut input, &mut bytes)?;
+            match n_read {
+                0 => break,
+                41 => write(unpack(&bytes))?,
+                // truncated at the end
+                _ => write(None)?,
+            }
+        },
+        Format::Line => {
+            for line in input.lines() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    write_record(PuzzleRecord::from_line(line.trim_end_matches('\r')).ok())?;
+                }
+            }
+        }
+        Format::Block | Format::Sdk => {
+            // each block is parsed once, so a malformed sudoku can't swallow the rows of the next one
+            let mut block = String::new();
+            let mut n_lines = 0;
+            let mut write_block = |block: &mut String, n_lines: &mut usize| {
+                let result = match block.is_empty() {
+                    true => Ok(()),
+                    false => write(Sudoku::from_str_block_permissive(block).ok()),
+                };
+                block.clear();
+                *n_lines = 0;
+                result
+            };
+            for line in input.lines() {
+                let line = line?;
+                let is_empty = line.trim().is_empty();
+                let is_separator = match from {
+                    Format::Sdk => is_empty || line.starts_with('#'),
+                    // `display_block` separates the bands with empty lines
+                    _ => is_empty && n_lines != 3 && n_lines != 6,
+                };
+                if is_separator {
+                    write_block(&mut block, &mut n_lines)?;
+                } else if !is_empty {
+                    block.push_str(&line);
+                    block.push('\n');
+                    n_lines += 1;
+                }
+            }
+            write_block(&mut block, &mut n_lines)?;
+        }
+    }
+    Ok(stats)
+}
+
+fn write_sudoku(output: &mut impl Write, sudoku: Sudoku, format: Format) -> io::Result<()> {
+    match format {
+        Format::Line => writeln!(output, "{}", sudoku),
+        Format::Block => writeln!(output, "{}", sudoku.display_block().to_string().trim_start()),
+        Format::Sdk => {
+            for row in sudoku.to_bytes().chunks(9) {
+                let row = row
+                    .iter()
+                    .map(|&digit| match digit {
+                        0 => '.',
+                        _ => char::from(b'0' + digit),
+                    })
+                    .collect::<String>();
+                writeln!(output, "{}", row)?;
+            }
+            Ok(())
+        }
+        Format::Packed => output.write_all(&pack(sudoku)),
+    }
+}
+
+fn pack(sudoku: Sudoku) -> [u8; 41] {
+    let mut bytes = [0; 41];
+    for (cell, &digit) in sudoku.to_bytes().iter().enumerate() {
+        bytes[cell / 2] |= digit << (4 * (1 - cell % 2));
+    }
+    bytes
+}
+
+fn unpack(bytes: &[u8; 41]) -> Option<Sudoku> {
+    let mut digits = [0; 81];
+    for (cell, digit) in digits.iter_mut().enumerate() {
+        *digit = bytes[cell / 2] >> (4 * (1 - cell % 2)) & 0xF;
+    }
+    Sudoku::from_bytes(digits).ok()
+}
+
+// Fills `buffer` as far as possible. Returns the number of bytes read, which is only
+// less than the length of `buffer` at the end of `input`.
+fn read_up_to(input: &mut impl io::Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut n_read = 0;
+    while n_read < buffer.len() {
+        match input.read(&mut buffer[n_read..]) {
+            Ok(0) => break,
+            Ok(n) => n_read += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(n_read)
+}
+
+// Applies `f` to all `items` on as many threads as the system reports to be available.
+// The results are in the same order as the items.
+pub(crate) fn parallel_map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync) -> Vec<U> {
+    let n_threads = thread::available_parallelism().map_or(1, |n| n.get());
+    // rounded up, but at least 1 for the empty slice
+    let chunk_size = items.len().saturating_sub(1) / n_threads + 1;
+
+    let f = &f;
+    thread::scope(|scope| {
+        let handles = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
@@ -0,0 +1,217 @@
+//! Reading and writing puzzle datasets in CSV format
+//!
+//! Large puzzle collections, like the popular sudoku datasets on Kaggle, are often distributed as
+//! CSV files with a column for the puzzles, one for their solutions and more for metadata such as
+//! the difficulty. [`CsvReader`] reads such files into [`CsvRecord`]s and [`CsvWriter`] writes them.
+//!
+//! ```
+//! use sudoku::batch::csv::{CsvReader, CsvWriter};
+//!
+//! let input = "\
+//! id,puzzle,solution,clues,difficulty
+//! 1,...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...,,27,2.5
+//! ";
+//! let mut reader = CsvReader::new(input.as_bytes()).unwrap();
+//! assert_eq!(reader.extra_headers(), ["id", "clues"]);
+//! let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+//! assert_eq!(records[0].difficulty, Some(2.5));
+//! assert_eq!(records[0].extra, ["1", "27"]);
+//!
+//! let mut output = vec![];
+//! let mut writer = CsvWriter::new(&mut output, reader.extra_headers()).unwrap();
+//! writer.write(&records[0]).unwrap();
+//! drop(writer);
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "\
+//! puzzle,solution,difficulty,id,clues
+//! 000200063300005401001003980000000090000538000030000000026300500503700008470001000,,2.5,1,27
+//! ",
+//! );
+//! ```
+
+use crate::errors::CsvError;
+use crate::{SolvedSudoku, Sudoku};
+use std::convert::TryFrom;
+use std::io;
+
+/// Accepted names of the puzzle column, compared case-insensitively
+const PUZZLE_HEADERS: [&str; 4] = ["puzzle", "puzzles", "quiz", "quizzes"];
+/// Accepted names of the solution column, compared case-insensitively
+const SOLUTION_HEADERS: [&str; 2] = ["solution", "solutions"];
+const DIFFICULTY_HEADER: &str = "difficulty";
+
+/// A puzzle of a dataset together with its metadata
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvRecord {
+    /// The puzzle
+    pub puzzle: Sudoku,
+    /// The solution, if the dataset contains one for the puzzle
+    pub solution: Option<SolvedSudoku>,
+    /// The difficulty, if the dataset contains one for the puzzle. Its scale depends on the dataset.
+    pub difficulty: Option<f64>,
+    /// The values of all other columns, in the order of [`CsvReader::extra_headers`]
+    pub extra: Vec<String>,
+}
+
+/// Reads [`CsvRecord`]s from a CSV file with a header row.
+///
+/// The puzzles are taken from the column named `puzzle`, `puzzles`, `quiz` or `quizzes`, the
+/// solutions from the column named `solution` or `solutions` and the difficulties from the column
+/// named `difficulty`, ignoring case. Only the puzzle column is required. Puzzles and solutions
+/// are read as in [`Sudoku::from_str_line`], so both `'0'` and `'.'` denote empty cells.
+/// Empty solutions and difficulties are read as `None`. Whether the solutions belong to the
+/// puzzles isn't checked.
+///
+/// Every record is returned as soon as it is read, so arbitrarily large files can be processed.
+#[derive(Debug)]
+pub struct CsvReader<R> {
+    reader: ::csv::Reader<R>,
+    puzzle_column: usize,
+    solution_column: Option<usize>,
+    difficulty_column: Option<usize>,
+    extra_columns: Vec<usize>,
+    extra_headers: Vec<String>,
+}
+
+impl<R: io::Read> CsvReader<R> {
+    /// Reads the header row of `input`. Fails, if there is no puzzle column.
+    pub fn new(input: R) -> Result<CsvReader<R>, CsvError> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .trim(::csv::Trim::All)
+            .from_reader(input);
+        let headers = reader.headers()?;
+        let find = |names: &[&str]| {
+            headers
+                .iter()
+                .position(|header| names.iter().any(|name| header.eq_ignore_ascii_case(name)))
+        };
+        let puzzle_column = find(&PUZZLE_HEADERS).ok_or(CsvError::MissingPuzzleColumn)?;
+        let solution_column = find(&SOLUTION_HEADERS);
+        let difficulty_column = find(&[DIFFICULTY_HEADER]);
+
+        let (extra_columns, extra_headers) = headers
+            .iter()
+            .enumerate()
+            .filter(|&(column, _)| {
+                column != puzzle_column
+                    && Some(column) != solution_column
+                    && Some(column) != difficulty_column
+            })
+            .map(|(column, header)| (column, header.to_string()))
+            .unzip();
+
+        Ok(CsvReader {
+            reader,
+            puzzle_column,
+            solution_column,
+            difficulty_column,
+            extra_columns,
+            extra_headers,
+        })
+    }
+
+    /// Returns the names of the columns that are collected in [`CsvRecord::extra`].
+    pub fn extra_headers(&self) -> &[String] {
+        &self.extra_headers
+    }
+
+    fn parse(&self, record: &::csv::StringRecord) -> Result<CsvRecord, CsvError> {
+        let line = record.position().map_or(0, |position| position.line());
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .filter(|field| !field.is_empty())
+        };
+
+        let puzzle = Sudoku::from_str_line(record.get(self.puzzle_column).unwrap_or(""))
+            .map_err(|err| CsvError::InvalidPuzzle(line, err))?;
+        let solution = field(self.solution_column)
+            .map(|solution| {
+                Sudoku::from_str_line(solution)
+                    .ok()
+                    .and_then(|solution| SolvedSudoku::try_from(solution).ok())
+                    .ok_or(CsvError::InvalidSolution(line))
+            })
+            .transpose()?;
+        let difficulty = field(self.difficulty_column)
+            .map(|difficulty| difficulty.parse().map_err(|_| CsvError::InvalidDifficulty(line)))
+            .transpose()?;
+        let extra = self
+            .extra_columns
+            .iter()
+            .map(|&column| record.get(column).unwrap_or("").to_string())
+            .collect();
+
+        Ok(CsvRecord {
+            puzzle,
+            solution,
+            difficulty,
+            extra,
+        })
+    }
+}
+
+impl<R: io::Read> Iterator for CsvReader<R> {
+    type Item = Result<CsvRecord, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = ::csv::StringRecord::new();
+        match self.reader.read_record(&mut record) {
+            Ok(true) => Some(self.parse(&record)),
+            Ok(false) => None,
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+/// Writes [`CsvRecord`]s to a CSV file with the columns `puzzle`, `solution`, `difficulty` and the
+/// extra columns.
+///
+/// Puzzles and solutions are written as 81 digits with `'0'` for empty cells, like in most
+/// datasets. Missing solutions and difficulties are written as empty fields. The output is
+/// flushed when the writer is dropped.
+#[derive(Debug)]
+pub struct CsvWriter<W: io::Write> {
+    writer: ::csv::Writer<W>,
+}
+
+impl<W: io::Write> CsvWriter<W> {
+    /// Writes the header row to `output`. The extra columns are named by `extra_headers`.
+    pub fn new<S: AsRef<str>>(output: W, extra_headers: &[S]) -> Result<CsvWriter<W>, CsvError> {
+        let mut writer = ::csv::Writer::from_writer(output);
+        let headers = ["puzzle", "solution", DIFFICULTY_HEADER]
+            .iter()
+            .copied()
+            .chain(extra_headers.iter().map(AsRef::as_ref));
+        writer.write_record(headers)?;
+        Ok(CsvWriter { writer })
+    }
+
+    /// Writes `record` as a row.
+    ///
+    /// Fails, if `record` has a different number of extra values than there are extra columns.
+    pub fn write(&mut self, record: &CsvRecord) -> Result<(), CsvError> {
+        let solution = record
+            .solution
+            .map_or(String::new(), |solution| digits(solution.into_sudoku()));
+        let difficulty = record
+            .difficulty
+            .map_or(String::new(), |difficulty| difficulty.to_string());
+        let fields = [digits(record.puzzle), solution, difficulty];
+        self.writer.write_record(fields.iter().chain(&record.extra))?;
+        Ok(())
+    }
+
+    /// Flushes the buffered rows to the output.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn digits(sudoku: Sudoku) -> String {
+    sudoku
+        .iter()
+        .map(|digit| char::from(b'0' + digit.unwrap_or(0)))
+        .collect()
+}
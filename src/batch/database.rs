@@ -0,0 +1,382 @@
+//! Append-only files of puzzles, e.g. for apps that bundle large puzzle collections
+//!
+//! A [`Database`] stores puzzles compactly in 43 to 57 bytes each, optionally together with
+//! their [`Grade`] and [canonical fingerprint](Sudoku::canonical_fingerprint). New puzzles can
+//! only be appended. When a database is opened, it is scanned once to build an index in memory,
+//! which enables fast access to puzzles by position and by fingerprint.
+//!
+//! # File format
+//!
+//! The file starts with the 4 bytes `b"SDB\x01"`, followed by the entries. Each entry is
+//! prefixed with its length in bytes, excluding the prefix, as a single byte. An entry consists of
+//!
+//! 1. a byte of flags: bit 0 is set, if the entry contains a grade, bit 1, if it contains a fingerprint,
+//! 2. the puzzle in 41 bytes, as in [`Format::Packed`](super::Format::Packed),
+//! 3. the grade in 6 bytes, if present: the index of the difficulty in [`Difficulty::ALL`], a byte
+//!    that is 0 for no rating, 1 for [`SeRating::Rated`] and 2 for [`SeRating::AtLeast`], and the
+//!    rating as a little endian `f32`,
+//! 4. the fingerprint as a little endian `u64`, if present.
+//!
+//! A length prefix of 0 is a single byte of padding without an entry.
+//!
+//! # Guarantees
+//!
+//! Appending never modifies complete entries. If an append is interrupted, e.g. by a crash, all
+//! previously appended entries stay readable: an incomplete entry at the end of the file is
+//! ignored when the database is opened and overwritten by the next append, with padding after a
+//! shorter entry. Any other damage to the file is reported as [`io::ErrorKind::InvalidData`].
+//! Entries are written to the storage without buffering, but not synced to disk.
+//!
+//! ```
+//! use sudoku::Sudoku;
+//! use sudoku::batch::database::{Database, DatabaseEntry};
+//! use std::io::Cursor;
+//!
+//! let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+//! let mut database = Database::new(Cursor::new(vec![])).unwrap();
+//! database.append(&DatabaseEntry::new(Sudoku::generate())).unwrap();
+//! database.append(&DatabaseEntry::analyzed(sudoku)).unwrap();
+//!
+//! // reopen
+//! let mut database = Database::new(database.into_inner()).unwrap();
+//! assert_eq!(database.len(), 2);
+//! let fingerprint = sudoku.shuffled().canonical_fingerprint().unwrap();
+//! let entry = database.get_by_fingerprint(fingerprint).unwrap().unwrap();
+//! assert_eq!(entry.sudoku, sudoku);
+//! assert!(entry.grade.is_some());
+//! assert_eq!(database.iter().count(), 2);
+//! ```
+
+use super::{pack, unpack, Difficulty, Grade};
+use crate::strategy::rating::SeRating;
+use crate::Sudoku;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::iter;
+use std::path::Path;
+
+const MAGIC: [u8; 4] = *b"SDB\x01";
+const PACKED_LEN: usize = 41;
+const GRADE_LEN: usize = 6;
+const FINGERPRINT_LEN: usize = 8;
+const HAS_GRADE: u8 = 1;
+const HAS_FINGERPRINT: u8 = 2;
+const PADDING: u8 = 0;
+
+/// A puzzle stored in a [`Database`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DatabaseEntry {
+    /// The puzzle
+    pub sudoku: Sudoku,
+    /// The grade of the puzzle, if stored
+    pub grade: Option<Grade>,
+    /// The [canonical fingerprint](Sudoku::canonical_fingerprint) of the puzzle, if stored.
+    /// Only entries with a fingerprint can be found with [`Database::get_by_fingerprint`].
+    pub fingerprint: Option<u64>,
+}
+
+impl DatabaseEntry {
+    /// Creates an entry for `sudoku` without grade and fingerprint.
+    pub fn new(sudoku: Sudoku) -> DatabaseEntry {
+        DatabaseEntry {
+            sudoku,
+            grade: None,
+            fingerprint: None,
+        }
+    }
+
+    /// Creates an entry for `sudoku` with its grade and fingerprint. The fingerprint is
+    /// only present, if `sudoku` is uniquely solvable.
+    pub fn analyzed(sudoku: Sudoku) -> DatabaseEntry {
+        DatabaseEntry {
+            sudoku,
+            grade: Some(Grade::new(sudoku)),
+            fingerprint: sudoku.canonical_fingerprint(),
+        }
+    }
+}
+
+/// An append-only collection of puzzles in a file, see the [module documentation](self)
+#[derive(Debug)]
+pub struct Database<S> {
+    storage: S,
+    index: Index,
+    // length of an incomplete entry after `index.end`, left by an interrupted append
+    n_incomplete: u64,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Index {
+    // positions of the length prefixes of the entries
+    pub(super) offsets: Vec<u64>,
+    // index of the first entry with each fingerprint
+    pub(super) fingerprints: HashMap<u64, usize>,
+    // position after the last complete entry
+    pub(super) end: u64,
+}
+
+impl Index {
+    // Reads the header and all entries of a database, except for an incomplete one at the end
+    pub(super) fn read(reader: &mut impl BufRead) -> io::Result<Index> {
+        let mut magic = [0; MAGIC.len()];
+        if read_exact(reader, &mut magic).is_err() || magic != MAGIC {
+            return Err(invalid_data("not a puzzle database"));
+        }
+
+        let mut index = Index {
+            end: MAGIC.len() as u64,
+            ..Index::default()
+        };
+        loop {
+            index.end += skip_padding(reader)?;
+            if reader.fill_buf()?.is_empty() {
+                return Ok(index);
+            }
+            match read_entry_unchecked(reader) {
+                Ok((entry, len)) => {
+                    index.push(index.end, &entry);
+                    index.end += len as u64;
+                }
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(index),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    pub(super) fn get_by_fingerprint(&self, fingerprint: u64) -> Option<u64> {
+        let &idx = self.fingerprints.get(&fingerprint)?;
+        Some(self.offsets[idx])
+    }
+
+    fn push(&mut self, offset: u64, entry: &DatabaseEntry) {
+        if let Some(fingerprint) = entry.fingerprint {
+            self.fingerprints.entry(fingerprint).or_insert(self.offsets.len());
+        }
+        self.offsets.push(offset);
+    }
+}
+
+impl Database<File> {
+    /// Opens the database at `path` for reading and appending. Creates it, if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Database<File>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        Database::new(file)
+    }
+}
+
+impl<S: Read + Write + Seek> Database<S> {
+    /// Opens the database in `storage`. Writes the header, if `storage` is empty.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`], if `storage` doesn't contain a valid database.
+    /// An incomplete entry at the end is ignored, see the [module documentation](self).
+    pub fn new(mut storage: S) -> io::Result<Database<S>> {
+        let storage_len = storage.seek(SeekFrom::End(0))?;
+        if storage_len == 0 {
+            storage.write_all(&MAGIC)?;
+        }
+        storage.seek(SeekFrom::Start(0))?;
+        let index = Index::read(&mut BufReader::new(&mut storage))?;
+        Ok(Database {
+            n_incomplete: storage_len.saturating_sub(index.end),
+            storage,
+            index,
+        })
+    }
+
+    /// Returns the number of puzzles.
+    pub fn len(&self) -> usize {
+        self.index.offsets.len()
+    }
+
+    /// Returns `true`, if the database contains no puzzles.
+    pub fn is_empty(&self) -> bool {
+        self.index.offsets.is_empty()
+    }
+
+    /// Appends `entry` to the end and returns its position.
+    pub fn append(&mut self, entry: &DatabaseEntry) -> io::Result<usize> {
+        let offset = self.storage.seek(SeekFrom::Start(self.index.end))?;
+        let mut bytes = encode(entry);
+        // overwrite all of an incomplete entry, so it can't be mistaken for the next one
+        let len = bytes.len() as u64;
+        bytes.resize(len.max(self.n_incomplete) as usize, PADDING);
+        self.storage.write_all(&bytes)?;
+        self.n_incomplete = 0;
+        self.index.push(offset, entry);
+        self.index.end += bytes.len() as u64;
+        Ok(self.index.offsets.len() - 1)
+    }
+
+    /// Returns the entry at position `idx`, or `None`, if `idx` is out of bounds.
+    pub fn get(&mut self, idx: usize) -> io::Result<Option<DatabaseEntry>> {
+        match self.index.offsets.get(idx) {
+            Some(&offset) => self.read_entry_at(offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the first entry with the [canonical fingerprint](Sudoku::canonical_fingerprint)
+    /// `fingerprint`, i.e. a puzzle equivalent to the one with the fingerprint. Entries stored
+    /// without a fingerprint are not found.
+    pub fn get_by_fingerprint(&mut self, fingerprint: u64) -> io::Result<Option<DatabaseEntry>> {
+        match self.index.get_by_fingerprint(fingerprint) {
+            Some(offset) => self.read_entry_at(offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads all entries in order. Stops after the first error.
+    pub fn iter(&mut self) -> impl Iterator<Item = io::Result<DatabaseEntry>> + '_ {
+        let mut seeked = Some(self.storage.seek(SeekFrom::Start(MAGIC.len() as u64)));
+        let mut n_remaining = self.len();
+        let mut reader = BufReader::new(&mut self.storage);
+        iter::from_fn(move || {
+            if let Some(Err(err)) = seeked.take() {
+                n_remaining = 0;
+                return Some(Err(err));
+            }
+            if n_remaining == 0 {
+                return None;
+            }
+            n_remaining -= 1;
+            let entry = skip_padding(&mut reader).and_then(|_| read_entry(&mut reader));
+            let entry = entry.map(|(entry, _)| entry);
+            if entry.is_err() {
+                n_remaining = 0;
+            }
+            Some(entry)
+        })
+    }
+
+    /// Returns the underlying storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    fn read_entry_at(&mut self, offset: u64) -> io::Result<DatabaseEntry> {
+        self.storage.seek(SeekFrom::Start(offset))?;
+        read_entry(&mut self.storage).map(|(entry, _)| entry)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Like `Read::read_exact`, but reports a premature end as invalid data
+fn read_exact(input: &mut impl Read, buffer: &mut [u8]) -> io::Result<()> {
+    input.read_exact(buffer).map_err(|err| match err.kind() {
+        io::ErrorKind::UnexpectedEof => invalid_data("truncated puzzle database"),
+        _ => err,
+    })
+}
+
+// Returns the entry and its length including the length prefix
+pub(super) fn read_entry(input: &mut impl Read) -> io::Result<(DatabaseEntry, usize)> {
+    read_entry_unchecked(input).map_err(|err| match err.kind() {
+        io::ErrorKind::UnexpectedEof => invalid_data("truncated puzzle database"),
+        _ => err,
+    })
+}
+
+// Like `read_entry`, but reports a premature end as `io::ErrorKind::UnexpectedEof`
+fn read_entry_unchecked(input: &mut impl Read) -> io::Result<(DatabaseEntry, usize)> {
+    let mut len = [0];
+    input.read_exact(&mut len)?;
+    let mut payload = vec![0; usize::from(len[0])];
+    input.read_exact(&mut payload)?;
+    Ok((decode(&payload)?, 1 + payload.len()))
+}
+
+// Skips padding bytes and returns their number
+fn skip_padding(input: &mut impl BufRead) -> io::Result<u64> {
+    let mut n_skipped = 0;
+    loop {
+        let buffer = input.fill_buf()?;
+        let n_padding = buffer.iter().take_while(|&&byte| byte == PADDING).count();
+        if n_padding == 0 {
+            return Ok(n_skipped);
+        }
+        input.consume(n_padding);
+        n_skipped += n_padding as u64;
+    }
+}
+
+fn encode(entry: &DatabaseEntry) -> Vec<u8> {
+    let mut flags = 0;
+    let mut payload = vec![0];
+    payload.extend_from_slice(&pack(entry.sudoku));
+    if let Some(grade) = entry.grade {
+        flags |= HAS_GRADE;
+        let difficulty = Difficulty::ALL
+            .iter()
+            .position(|&difficulty| difficulty == grade.difficulty)
+            .unwrap();
+        let (kind, rating) = match grade.se_rating {
+            None => (0, 0.0),
+            Some(SeRating::Rated(rating)) => (1, rating),
+            Some(SeRating::AtLeast(rating)) => (2, rating),
+        };
+        payload.extend_from_slice(&[difficulty as u8, kind]);
+        payload.extend_from_slice(&rating.to_le_bytes());
+    }
+    if let Some(fingerprint) = entry.fingerprint {
+        flags |= HAS_FINGERPRINT;
+        payload.extend_from_slice(&fingerprint.to_le_bytes());
+    }
+    payload[0] = flags;
+
+    let mut bytes = vec![payload.len() as u8];
+    bytes.extend_from_slice(&payload);
+    bytes
+}
+
+fn decode(payload: &[u8]) -> io::Result<DatabaseEntry> {
+    let invalid = || invalid_data("invalid entry in puzzle database");
+    let flags = *payload.first().ok_or_else(invalid)?;
+    let has_grade = flags & HAS_GRADE != 0;
+    let has_fingerprint = flags & HAS_FINGERPRINT != 0;
+    let expected_len = 1
+        + PACKED_LEN
+        + if has_grade { GRADE_LEN } else { 0 }
+        + if has_fingerprint { FINGERPRINT_LEN } else { 0 };
+    if flags & !(HAS_GRADE | HAS_FINGERPRINT) != 0 || payload.len() != expected_len {
+        return Err(invalid());
+    }
+
+    let (packed, mut rest) = payload[1..].split_at(PACKED_LEN);
+    let sudoku = unpack(packed.try_into().unwrap()).ok_or_else(invalid)?;
+    let mut grade = None;
+    if has_grade {
+        let difficulty = *Difficulty::ALL.get(usize::from(rest[0])).ok_or_else(invalid)?;
+        let rating = f32::from_le_bytes(rest[2..GRADE_LEN].try_into().unwrap());
+        let se_rating = match rest[1] {
+            0 => None,
+            1 => Some(SeRating::Rated(rating)),
+            2 => Some(SeRating::AtLeast(rating)),
+            _ => return Err(invalid()),
+        };
+        grade = Some(Grade {
+            difficulty,
+            se_rating,
+        });
+        rest = &rest[GRADE_LEN..];
+    }
+    let fingerprint = match has_fingerprint {
+        true => Some(u64::from_le_bytes(rest.try_into().unwrap())),
+        false => None,
+    };
+
+    Ok(DatabaseEntry {
+        sudoku,
+        grade,
+        fingerprint,
+    })
+}
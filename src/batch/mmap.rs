@@ -0,0 +1,150 @@
+//! Memory-mapped read access to large puzzle files
+//!
+//! Servers that hand out random puzzles from huge collections don't need to load them into memory.
+//! [`MappedPuzzles`] maps a file of plain 81 byte records, one byte per cell like in
+//! [`Sudoku::from_bytes`], and gives direct access to the records without copying them.
+//! [`MappedDatabase`] maps a [`Database`](super::database::Database) for reading.
+//!
+//! The operating system loads the parts of the file that are accessed on demand.
+//!
+//! # Safety
+//!
+//! A mapped file must not be modified or truncated while it is mapped, by this or any other process.
+//! Otherwise, the mapped memory may change or become inaccessible while it is read,
+//! which is undefined behavior. That's why the constructors are `unsafe`.
+
+use super::database::{read_entry, DatabaseEntry, Index};
+use crate::Sudoku;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+const RECORD_LEN: usize = 81;
+
+/// A memory-mapped file of 81 byte records, see the [module documentation](self)
+///
+/// ```no_run
+/// use sudoku::batch::mmap::MappedPuzzles;
+/// use rand::seq::SliceRandom;
+///
+/// // SAFETY: the file isn't modified while the server is running
+/// let puzzles = unsafe { MappedPuzzles::open("puzzles.bin") }.unwrap();
+/// let record = puzzles.records().choose(&mut rand::thread_rng()).unwrap();
+/// println!("{}", sudoku::Sudoku::from_bytes(*record).unwrap());
+/// ```
+#[derive(Debug)]
+pub struct MappedPuzzles {
+    mmap: Mmap,
+}
+
+impl MappedPuzzles {
+    /// Maps the file at `path`. Fails with [`io::ErrorKind::InvalidData`], if its length is not
+    /// a multiple of 81.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, see the [module documentation](self).
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<MappedPuzzles> {
+        let mmap = Mmap::map(&File::open(path)?)?;
+        if mmap.len() % RECORD_LEN != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file length is not a multiple of 81",
+            ));
+        }
+        Ok(MappedPuzzles { mmap })
+    }
+
+    /// Returns all records without copying them.
+    ///
+    /// The records are not validated. Use [`get`](Self::get) or [`Sudoku::from_bytes`] to read
+    /// them as sudokus.
+    pub fn records(&self) -> &[[u8; RECORD_LEN]] {
+        let bytes = &self.mmap[..];
+        // SAFETY: `[u8; 81]` has the same alignment as `u8` and the length of `bytes`
+        // is a multiple of 81, which was checked on construction
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / RECORD_LEN) }
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.mmap.len() / RECORD_LEN
+    }
+
+    /// Returns `true`, if the file contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.mmap.is_empty()
+    }
+
+    /// Returns the sudoku in the record at position `idx`, or `None`, if `idx` is out of bounds
+    /// or the record contains entries greater than 9.
+    pub fn get(&self, idx: usize) -> Option<Sudoku> {
+        self.records()
+            .get(idx)
+            .and_then(|&record| Sudoku::from_bytes(record).ok())
+    }
+}
+
+/// A memory-mapped, read-only [`Database`](super::database::Database)
+///
+/// Like the database, it is scanned once on construction to build an index. Reading entries
+/// doesn't involve any system calls afterwards.
+#[derive(Debug)]
+pub struct MappedDatabase {
+    mmap: Mmap,
+    index: Index,
+}
+
+impl MappedDatabase {
+    /// Maps the database at `path`.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`], if the file doesn't contain a valid database.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, see the [module documentation](self).
+    /// In particular, no puzzles may be appended.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<MappedDatabase> {
+        let mmap = Mmap::map(&File::open(path)?)?;
+        let index = Index::read(&mut &mmap[..])?;
+        Ok(MappedDatabase { mmap, index })
+    }
+
+    /// Returns the number of puzzles.
+    pub fn len(&self) -> usize {
+        self.index.offsets.len()
+    }
+
+    /// Returns `true`, if the database contains no puzzles.
+    pub fn is_empty(&self) -> bool {
+        self.index.offsets.is_empty()
+    }
+
+    /// Returns the entry at position `idx`, or `None`, if `idx` is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<DatabaseEntry> {
+        let &offset = self.index.offsets.get(idx)?;
+        Some(self.entry_at(offset))
+    }
+
+    /// Returns the first entry with the canonical fingerprint `fingerprint`.
+    /// See [`Database::get_by_fingerprint`](super::database::Database::get_by_fingerprint).
+    pub fn get_by_fingerprint(&self, fingerprint: u64) -> Option<DatabaseEntry> {
+        let offset = self.index.get_by_fingerprint(fingerprint)?;
+        Some(self.entry_at(offset))
+    }
+
+    /// Returns all entries in order.
+    pub fn iter(&self) -> impl Iterator<Item = DatabaseEntry> + '_ {
+        self.index
+            .offsets
+            .iter()
+            .map(move |&offset| self.entry_at(offset))
+    }
+
+    fn entry_at(&self, offset: u64) -> DatabaseEntry {
+        let (entry, _) = read_entry(&mut &self.mmap[offset as usize..])
+            .expect("entries are validated when the database is mapped");
+        entry
+    }
+}
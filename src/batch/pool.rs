@@ -0,0 +1,173 @@
+//! Random selection of puzzles by difficulty
+//!
+//! A [`PuzzlePool`] holds graded puzzles and serves random ones according to a weighting of the
+//! difficulties, without repeating the puzzles that were served most recently. Equivalent puzzles
+//! are recognized by their [canonical fingerprint](Sudoku::canonical_fingerprint), so a player
+//! doesn't get the same puzzle again in a shuffled form either.
+
+use super::Difficulty;
+use crate::Sudoku;
+use rand::Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A collection of graded puzzles to pick random puzzles from, see the [module documentation](self)
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::batch::{Difficulty, pool::PuzzlePool};
+///
+/// let mut pool = PuzzlePool::new(2);
+/// for _ in 0..3 {
+///     assert!(pool.insert(Sudoku::generate(), Difficulty::Easy));
+/// }
+///
+/// // mostly easy puzzles, hard ones whenever there are some
+/// let weights = [(Difficulty::Easy, 3.0), (Difficulty::Hard, 1.0)];
+/// let rng = &mut rand::thread_rng();
+/// let first = pool.pick(&weights, rng).unwrap();
+/// let second = pool.pick(&weights, rng).unwrap();
+/// let third = pool.pick(&weights, rng).unwrap();
+/// assert!(first != second && second != third && first != third);
+///
+/// // `first` is the only puzzle that wasn't served among the last 2
+/// assert_eq!(pool.pick(&weights, rng), Some(first));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PuzzlePool {
+    puzzles: HashMap<Difficulty, Vec<(Sudoku, u64)>>,
+    fingerprints: HashSet<u64>,
+    n_recent: usize,
+    recent: VecDeque<(u64, Difficulty)>,
+    recent_fingerprints: HashSet<u64>,
+    n_recent_per_difficulty: HashMap<Difficulty, usize>,
+}
+
+impl PuzzlePool {
+    /// Creates an empty pool that doesn't serve any of the last `n_recent` puzzles again.
+    pub fn new(n_recent: usize) -> PuzzlePool {
+        PuzzlePool {
+            n_recent,
+            ..PuzzlePool::default()
+        }
+    }
+
+    /// Adds `sudoku` with the given difficulty, e.g. from its [`Grade`](super::Grade).
+    ///
+    /// Returns `false` and doesn't add it, if `sudoku` doesn't have a unique solution or
+    /// the pool already contains an equivalent puzzle.
+    pub fn insert(&mut self, sudoku: Sudoku, difficulty: Difficulty) -> bool {
+        let fingerprint = match sudoku.canonical_fingerprint() {
+            Some(fingerprint) if difficulty != Difficulty::Invalid => fingerprint,
+            _ => return false,
+        };
+        if !self.fingerprints.insert(fingerprint) {
+            return false;
+        }
+        self.puzzles
+            .entry(difficulty)
+            .or_default()
+            .push((sudoku, fingerprint));
+        true
+    }
+
+    /// Returns the number of puzzles in the pool.
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Returns `true`, if the pool contains no puzzles.
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Returns the number of puzzles of `difficulty` in the pool.
+    pub fn count(&self, difficulty: Difficulty) -> usize {
+        self.puzzles.get(&difficulty).map_or(0, Vec::len)
+    }
+
+    /// Picks a random puzzle and remembers it as served.
+    ///
+    /// First, a difficulty is chosen with a probability proportional to its weight in
+    /// `difficulty_weights`. Difficulties without a positive, finite weight and difficulties whose
+    /// puzzles were all served recently are never chosen. Then, one of the puzzles of that
+    /// difficulty that weren't served recently is chosen uniformly at random.
+    ///
+    /// Returns `None`, if no difficulty can be chosen.
+    pub fn pick<R: Rng + ?Sized>(
+        &mut self,
+        difficulty_weights: &[(Difficulty, f64)],
+        rng: &mut R,
+    ) -> Option<Sudoku> {
+        let eligible = difficulty_weights
+            .iter()
+            .filter(|&&(difficulty, weight)| {
+                weight > 0.0 && weight.is_finite() && self.n_available(difficulty) > 0
+            })
+            .collect::<Vec<_>>();
+        if eligible.is_empty() {
+            return None;
+        }
+        // relative to the largest weight, so the sum of huge weights can't overflow to infinity
+        let max_weight = eligible.iter().map(|&&(_, weight)| weight).fold(0.0, f64::max);
+        let total_weight = eligible
+            .iter()
+            .map(|&&(_, weight)| weight / max_weight)
+            .sum::<f64>();
+
+        let mut threshold = rng.gen_range(0.0..total_weight);
+        let difficulty = eligible
+            .iter()
+            .find(|&&&(_, weight)| {
+                threshold -= weight / max_weight;
+                threshold < 0.0
+            })
+            // rounding errors may leave a tiny positive threshold
+            .map_or(eligible[eligible.len() - 1].0, |&&(difficulty, _)| difficulty);
+
+        let (sudoku, fingerprint) = self.pick_available(difficulty, rng);
+        self.mark_served(fingerprint, difficulty);
+        Some(sudoku)
+    }
+
+    fn n_available(&self, difficulty: Difficulty) -> usize {
+        let n_recent = self
+            .n_recent_per_difficulty
+            .get(&difficulty)
+            .copied()
+            .unwrap_or(0);
+        self.count(difficulty) - n_recent
+    }
+
+    // Requires at least one available puzzle of `difficulty`
+    fn pick_available<R: Rng + ?Sized>(&self, difficulty: Difficulty, rng: &mut R) -> (Sudoku, u64) {
+        let puzzles = &self.puzzles[&difficulty];
+        let is_available =
+            |&&(_, fingerprint): &&(Sudoku, u64)| !self.recent_fingerprints.contains(&fingerprint);
+        let n_available = self.n_available(difficulty);
+        // rejection sampling is fast, unless most puzzles were served recently
+        if n_available * 4 >= puzzles.len() {
+            loop {
+                let puzzle = &puzzles[rng.gen_range(0..puzzles.len())];
+                if is_available(&puzzle) {
+                    return *puzzle;
+                }
+            }
+        }
+        let nth = rng.gen_range(0..n_available);
+        *puzzles.iter().filter(is_available).nth(nth).unwrap()
+    }
+
+    fn mark_served(&mut self, fingerprint: u64, difficulty: Difficulty) {
+        if self.n_recent == 0 {
+            return;
+        }
+        if self.recent.len() == self.n_recent {
+            let (oldest, oldest_difficulty) = self.recent.pop_front().unwrap();
+            self.recent_fingerprints.remove(&oldest);
+            *self.n_recent_per_difficulty.get_mut(&oldest_difficulty).unwrap() -= 1;
+        }
+        self.recent.push_back((fingerprint, difficulty));
+        self.recent_fingerprints.insert(fingerprint);
+        *self.n_recent_per_difficulty.entry(difficulty).or_insert(0) += 1;
+    }
+}
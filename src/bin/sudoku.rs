@@ -0,0 +1,283 @@
+// Command line interface to the library
+//
+// All commands read sudokus from stdin and write their results to stdout, one sudoku per line
+// in the line format. Run `sudoku help` for the full usage.
+
+use std::io::{self, BufWriter, Read, Write};
+use std::process;
+use sudoku::batch::grade_all;
+#[cfg(feature = "tui")]
+use sudoku::board::GameState;
+use sudoku::strategy::rating::SeRating;
+use sudoku::{Sudoku, Symmetry};
+
+const USAGE: &str = "\
+Usage: sudoku <command> [options]
+
+Commands:
+    generate        Generate random, uniquely solvable sudokus
+        --count <n>         Number of sudokus to generate (default: 1)
+        --symmetry <name>   Symmetry of the clues (default: half-rotation)
+        --solved            Generate solved grids instead of puzzles
+    solve           Solve each sudoku. Sudokus without a unique solution produce an empty line.
+    grade           Print each sudoku with its difficulty and rating, separated by tabs
+    canonicalize    Print the canonical form of each uniquely solvable sudoku
+    convert         Print each sudoku in another format
+        --to <format>       Output format: line, block, html or markdown (default: line)
+    play            Solve a random sudoku interactively in the terminal and print it afterwards.
+                    Requires the `tui` feature.
+        --symmetry <name>   Symmetry of the clues (default: half-rotation)
+    help            Print this message
+
+Options for all commands that read sudokus:
+    --input <format>        Input format: line or block (default: line)
+                            In the block format, each sudoku ends after its ninth row.
+
+Symmetries:
+    none, half-rotation, quarter-rotation, vertical-mirror, horizontal-mirror,
+    vertical-and-horizontal-mirror, diagonal-mirror, antidiagonal-mirror,
+    bidiagonal-mirror, dihedral
+
+Sudokus that can't be parsed or processed are reported on stderr and the exit code is 1.";
+
+const SYMMETRIES: [(&str, Symmetry); 10] = [
+    ("none", Symmetry::None),
+    ("half-rotation", Symmetry::HalfRotation),
+    ("quarter-rotation", Symmetry::QuarterRotation),
+    ("vertical-mirror", Symmetry::VerticalMirror),
+    ("horizontal-mirror", Symmetry::HorizontalMirror),
+    (
+        "vertical-and-horizontal-mirror",
+        Symmetry::VerticalAndHorizontalMirror,
+    ),
+    ("diagonal-mirror", Symmetry::DiagonalMirror),
+    ("antidiagonal-mirror", Symmetry::AntidiagonalMirror),
+    ("bidiagonal-mirror", Symmetry::BidiagonalMirror),
+    ("dihedral", Symmetry::Dihedral),
+];
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Line,
+    Block,
+    Html,
+    Markdown,
+}
+
+struct Options {
+    count: usize,
+    symmetry: Symmetry,
+    solved: bool,
+    input: Format,
+    output: Format,
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (command, options) = match args.split_first() {
+        Some((command, options)) => (command.as_str(), options),
+        None => exit_with_usage("missing command"),
+    };
+    if command == "help" || command == "--help" || command == "-h" {
+        println!("{}", USAGE);
+        return;
+    }
+    let options = parse_options(options).unwrap_or_else(|err| exit_with_usage(&err));
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let result = match command {
+        "generate" => generate(&options, &mut out).map(|()| true),
+        #[cfg(feature = "tui")]
+        "play" => play(&options, &mut out).map(|()| true),
+        #[cfg(not(feature = "tui"))]
+        "play" => exit_with_usage("`play` requires the `tui` feature"),
+        "solve" | "grade" | "canonicalize" | "convert" => process_sudokus(command, &options, &mut out),
+        _ => exit_with_usage(&format!("unknown command `{}`", command)),
+    };
+    match result.and_then(|all_succeeded| out.flush().map(|()| all_succeeded)) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        // a closed pipe is not an error for a filter
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        count: 1,
+        symmetry: Symmetry::HalfRotation,
+        solved: false,
+        input: Format::Line,
+        output: Format::Line,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("missing value for `{}`", arg));
+        match arg.as_str() {
+            "--count" => {
+                let count = value()?;
+                options.count = count.parse().map_err(|_| format!("invalid count `{}`", count))?;
+            }
+            "--symmetry" => {
+                let name = value()?;
+                options.symmetry = SYMMETRIES
+                    .iter()
+                    .find(|&&(symmetry_name, _)| symmetry_name == name)
+                    .map(|&(_, symmetry)| symmetry)
+                    .ok_or_else(|| format!("unknown symmetry `{}`", name))?;
+            }
+            "--solved" => options.solved = true,
+            "--input" => {
+                options.input = match value()?.as_str() {
+                    "line" => Format::Line,
+                    "block" => Format::Block,
+                    format => return Err(format!("unknown input format `{}`", format)),
+                }
+            }
+            "--to" => {
+                options.output = match value()?.as_str() {
+                    "line" => Format::Line,
+                    "block" => Format::Block,
+                    "html" => Format::Html,
+                    "markdown" => Format::Markdown,
+                    format => return Err(format!("unknown output format `{}`", format)),
+                }
+            }
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn generate(options: &Options, out: &mut impl Write) -> io::Result<()> {
+    for _ in 0..options.count {
+        let sudoku = match options.solved {
+            true => Sudoku::generate_solved().into(),
+            false => Sudoku::generate_with_symmetry(options.symmetry),
+        };
+        writeln!(out, "{}", sudoku)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn play(options: &Options, out: &mut impl Write) -> io::Result<()> {
+    let sudoku = Sudoku::generate_with_symmetry(options.symmetry);
+    let game = GameState::new(sudoku).expect("generated sudokus are uniquely solvable");
+    sudoku::tui::run(game)?;
+    // so the puzzle can be played again or shared
+    writeln!(out, "{}", sudoku)
+}
+
+// Writes one line per sudoku, or one block in the block-like output formats.
+// Sudokus that can't be parsed or processed produce an empty line.
+// Returns `false` if that happened for any sudoku.
+fn process_sudokus(command: &str, options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    let mut all_succeeded = true;
+    let mut report = |position: usize, message: &str| {
+        eprintln!("sudoku {}: {}", position + 1, message);
+        all_succeeded = false;
+    };
+    let sudokus = parse_sudokus(&input, options.input)
+        .into_iter()
+        .enumerate()
+        .map(|(position, parsed)| parsed.map_err(|err| report(position, &err)).ok())
+        .collect::<Vec<_>>();
+
+    let grades = match command {
+        "grade" => grade_all(&sudokus.iter().flatten().copied().collect::<Vec<_>>())
+            .grades()
+            .to_vec(),
+        _ => vec![],
+    };
+    let mut grades = grades.into_iter();
+
+    for (position, &sudoku) in sudokus.iter().enumerate() {
+        let sudoku = match sudoku {
+            Some(sudoku) => sudoku,
+            None => {
+                writeln!(out)?;
+                continue;
+            }
+        };
+        match command {
+            "solve" => match sudoku.solution() {
+                Some(solution) => writeln!(out, "{}", solution)?,
+                None => {
+                    report(position, "no unique solution");
+                    writeln!(out)?;
+                }
+            },
+            "grade" => {
+                let grade = grades.next().unwrap();
+                let rating = match grade.se_rating {
+                    Some(SeRating::Rated(rating)) => format!("{:.1}", rating),
+                    Some(SeRating::AtLeast(rating)) => format!(">={:.1}", rating),
+                    None => "-".to_string(),
+                };
+                writeln!(out, "{}\t{:?}\t{}", sudoku, grade.difficulty, rating)?;
+            }
+            "canonicalize" => match sudoku.canonicalized() {
+                Some((canonical, _)) => writeln!(out, "{}", canonical)?,
+                None => {
+                    report(position, "no unique solution");
+                    writeln!(out)?;
+                }
+            },
+            "convert" => {
+                // separate blocks by an empty line
+                if position != 0 && options.output != Format::Line {
+                    writeln!(out)?;
+                }
+                match options.output {
+                    Format::Line => writeln!(out, "{}", sudoku)?,
+                    Format::Block => writeln!(out, "{}", sudoku.display_block().to_string().trim_start())?,
+                    Format::Html => writeln!(out, "{}", sudoku.display_html())?,
+                    Format::Markdown => writeln!(out, "{}", sudoku.display_markdown())?,
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(all_succeeded)
+}
+
+fn parse_sudokus(input: &str, format: Format) -> Vec<Result<Sudoku, String>> {
+    match format {
+        Format::Block => {
+            // a sudoku ends with its ninth row
+            let mut sudokus = vec![];
+            let mut block = String::new();
+            for line in input.lines() {
+                block.push_str(line);
+                block.push('\n');
+                if let Ok(sudoku) = Sudoku::from_str_block_permissive(&block) {
+                    sudokus.push(Ok(sudoku));
+                    block.clear();
+                }
+            }
+            if !block.trim().is_empty() {
+                sudokus.push(Sudoku::from_str_block_permissive(&block).map_err(|err| err.to_string()));
+            }
+            sudokus
+        }
+        _ => input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Sudoku::from_str_line(line.trim_end()).map_err(|err| err.to_string()))
+            .collect(),
+    }
+}
@@ -4,6 +4,9 @@
 //! Efficient storage is important for maximal performance, but it should not be possible
 //! to confuse bitmasks for different things. This module contains type-safe, space-efficient
 //! fixed-length bitsets for digits and various sudoku positions.
+//!
+//! The most common ones have their own names: [`DigitSet`] for candidates and [`CellSet`] for
+//! regions of the grid.
 
 use crate::board::{Cell, Col, Digit, House, Line, Position, Row};
 use crate::helper::Unsolvable;
@@ -13,6 +16,37 @@ use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, N
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Set<T: SetElement>(pub(crate) T::Storage);
 
+/// Set of digits, e.g. the candidates of a cell
+///
+/// It is displayed as its digits in ascending order, like pencil marks.
+///
+/// ```
+/// use sudoku::bitset::DigitSet;
+/// use sudoku::board::Digit;
+///
+/// let candidates = [1, 4, 7].iter().map(|&digit| Digit::new(digit)).collect::<DigitSet>();
+/// assert_eq!(candidates.to_string(), "147");
+/// assert_eq!((candidates & Digit::new(4)).len(), 1);
+/// assert_eq!((!candidates).to_string(), "235689");
+/// assert_eq!(DigitSet::NONE.to_string(), "-");
+/// ```
+pub type DigitSet = Set<Digit>;
+
+/// Set of cells, e.g. a house or the filled cells of a pattern
+///
+/// It is displayed as the cells in ascending order in `r<row>c<col>` notation, counting from 1.
+///
+/// ```
+/// use sudoku::bitset::CellSet;
+/// use sudoku::board::Cell;
+///
+/// let cells = [0, 10, 80].iter().map(|&cell| Cell::new(cell)).collect::<CellSet>();
+/// assert_eq!(cells.to_string(), "r1c1 r2c2 r9c9");
+/// assert!(cells.contains(Cell::new(10)));
+/// assert_eq!(CellSet::NONE.to_string(), "-");
+/// ```
+pub type CellSet = Set<Cell>;
+
 /// Iterator over the elements contained in a [`Set`]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Iter<T: SetElement>(T::Storage);
@@ -339,9 +373,68 @@ impl_iter_for_setiter!(
     //Position<Chute> => Position::new,
 );
 
+/// Serialized as a sequence of the contained digits in ascending order
+#[cfg(feature = "serde")]
+impl serde::Serialize for Set<Digit> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(*self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Set<Digit> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let digits = Vec::<Digit>::deserialize(deserializer)?;
+        Ok(digits.into_iter().collect())
+    }
+}
+
+impl<T: SetElement> std::iter::FromIterator<T> for Set<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Set(T::NONE);
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: SetElement> Extend<T> for Set<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            *self |= element;
+        }
+    }
+}
+
 use std::fmt;
 impl<T: SetElement> fmt::Binary for Set<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:b}", self.0)
     }
 }
+
+impl fmt::Display for DigitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("-");
+        }
+        for digit in *self {
+            write!(f, "{}", digit.get())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CellSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("-");
+        }
+        for (idx, cell) in self.into_iter().enumerate() {
+            if idx != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "r{}c{}", cell.get() / 9 + 1, cell.get() % 9 + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,24 @@
 //! Types for cells, digits and other things on a sudoku board
+mod bitboards;
 mod candidate;
 mod canonicalization;
 mod cell_state;
+mod certificate;
+mod clue_distribution;
+mod constraints;
+mod contradiction;
 mod digit;
+mod dimacs;
+mod disambiguation;
+mod game_state;
+#[cfg(test)]
 mod grid_state;
+mod house_summary;
+mod locked_candidates;
+mod pattern;
 pub mod positions;
+mod replay;
+mod solved_sudoku;
 mod sudoku;
 
 pub(crate) use self::positions::*;
@@ -12,10 +26,32 @@ pub(crate) use self::positions::*;
 #[rustfmt::skip]
 pub use self::{
     sudoku::Sudoku,
+    solved_sudoku::SolvedSudoku,
     sudoku::Symmetry,
+    sudoku::GenerationReport,
+    sudoku::DigitRemoval,
+    sudoku::Iter,
+    sudoku::IntoIter,
+    bitboards::SudokuBitboards,
     digit::Digit,
     positions::Cell,
     candidate::Candidate,
+    certificate::ProofTree,
+    certificate::UniquenessCertificate,
     cell_state::CellState,
+    clue_distribution::ClueDistribution,
+    game_state::GameState,
+    game_state::Validation,
+    game_state::WrongEntry,
+    house_summary::HouseDigits,
+    house_summary::HouseSummary,
+    locked_candidates::LockedCandidatesElimination,
+    constraints::Constraints,
+    constraints::ConstraintSet,
+    contradiction::Contradiction,
+    pattern::Pattern,
+    replay::Action,
+    replay::Replay,
+    replay::TimedAction,
     // grid_state::GridState,
 };
@@ -0,0 +1,137 @@
+use crate::bitset::{CellSet, Set};
+use crate::board::{Digit, Sudoku};
+use crate::errors::OverlappingDigits;
+use crate::solver::SudokuSolver;
+use std::convert::TryFrom;
+
+/// A sudoku as one bitmask per digit and band, the representation used by the fast solver
+///
+/// The grid is split into 3 bands of 3 rows each. For every digit and band, a `u32` holds one bit
+/// for each of the 27 cells of the band. Counting from the least significant bit, bit `n` of
+/// band `b` belongs to cell `27 * b + n`, i.e. the cells are numbered row by row from the
+/// top left like everywhere else. The upper 5 bits are always 0.
+///
+/// Bitboards make analyses cheap that would loop over cells otherwise, like finding all
+/// positions of a digit in a row or comparing the placements of two digits.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Digit, SudokuBitboards};
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let bitboards = SudokuBitboards::from_sudoku(sudoku);
+/// // the 2 in the fourth cell of the first row
+/// assert_eq!(bitboards.band(Digit::new(2), 0) & 0o777, 1 << 3);
+/// // the number of 3s
+/// let n_threes = (0..3).map(|band| bitboards.band(Digit::new(3), band).count_ones()).sum::<u32>();
+/// assert_eq!(n_threes, 7);
+/// assert_eq!(bitboards.to_sudoku(), Ok(sudoku));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SudokuBitboards([[u32; 3]; 9]);
+
+impl SudokuBitboards {
+    /// The bits of the 27 cells of a band
+    pub const BAND_MASK: u32 = (1 << 27) - 1;
+
+    /// Returns the bitboards of the clues of `sudoku`.
+    pub fn from_sudoku(sudoku: Sudoku) -> SudokuBitboards {
+        let mut bands = [[0; 3]; 9];
+        for (cell, digit) in sudoku.clues() {
+            let cell = cell.as_index();
+            bands[digit as usize - 1][cell / 27] |= 1 << (cell % 27);
+        }
+        SudokuBitboards(bands)
+    }
+
+    /// Constructs bitboards from the bands of each digit, indexed by `digit - 1` and band.
+    /// Bits outside of [`BAND_MASK`](Self::BAND_MASK) are ignored.
+    pub fn from_bands(mut bands: [[u32; 3]; 9]) -> SudokuBitboards {
+        bands
+            .iter_mut()
+            .flatten()
+            .for_each(|band| *band &= Self::BAND_MASK);
+        SudokuBitboards(bands)
+    }
+
+    /// Returns the bands of each digit, indexed by `digit - 1` and band.
+    pub fn bands(&self) -> [[u32; 3]; 9] {
+        self.0
+    }
+
+    /// Returns the cells of `digit` in `band`.
+    ///
+    /// # Panics
+    ///
+    /// panics if `band >= 3`
+    pub fn band(&self, digit: Digit, band: usize) -> u32 {
+        self.0[digit.as_index()][band]
+    }
+
+    /// Returns the cells of `digit` in the whole grid.
+    pub fn cells(&self, digit: Digit) -> CellSet {
+        let [band0, band1, band2] = self.0[digit.as_index()];
+        Set::from_bits(u128::from(band0) | u128::from(band1) << 27 | u128::from(band2) << 54)
+    }
+
+    /// Returns the cells without any digit, per band.
+    pub fn empty_cells(&self) -> [u32; 3] {
+        let mut empty = [Self::BAND_MASK; 3];
+        for bands in &self.0 {
+            for (empty, &band) in empty.iter_mut().zip(bands) {
+                *empty &= !band;
+            }
+        }
+        empty
+    }
+
+    /// Returns the candidates left by the clues: for every digit the cells in which it can
+    /// still be placed, including those where it is placed already.
+    /// Each clue excludes its digit from its row, column and block and all other digits from its
+    /// cell. Returns `None`, if two digits overlap or clues conflict.
+    ///
+    /// These are the bitmasks the fast solver starts its search with.
+    pub fn candidates(&self) -> Option<SudokuBitboards> {
+        let sudoku = self.to_sudoku().ok()?;
+        let solver = SudokuSolver::from_sudoku(sudoku).ok()?;
+        let mut bands = [[0; 3]; 9];
+        for (subband, &mask) in solver.poss_cells().iter().enumerate() {
+            bands[subband / 3][subband % 3] = mask;
+        }
+        Some(SudokuBitboards(bands))
+    }
+
+    /// Converts the bitboards into a sudoku. Fails, if a cell contains multiple digits.
+    pub fn to_sudoku(&self) -> Result<Sudoku, OverlappingDigits> {
+        let mut grid = [0; 81];
+        for (digit, bands) in (1..=9).zip(&self.0) {
+            for (band, &mask) in bands.iter().enumerate() {
+                for bit in 0..27 {
+                    if mask & 1 << bit == 0 {
+                        continue;
+                    }
+                    let cell = band * 27 + bit;
+                    if grid[cell] != 0 {
+                        return Err(OverlappingDigits(cell as u8));
+                    }
+                    grid[cell] = digit;
+                }
+            }
+        }
+        Ok(Sudoku(grid))
+    }
+}
+
+impl From<Sudoku> for SudokuBitboards {
+    fn from(sudoku: Sudoku) -> SudokuBitboards {
+        SudokuBitboards::from_sudoku(sudoku)
+    }
+}
+
+impl TryFrom<SudokuBitboards> for Sudoku {
+    type Error = OverlappingDigits;
+
+    fn try_from(bitboards: SudokuBitboards) -> Result<Sudoku, OverlappingDigits> {
+        bitboards.to_sudoku()
+    }
+}
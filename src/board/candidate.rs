@@ -2,7 +2,10 @@ use crate::bitset::Set;
 use crate::board::{Block, Cell, Col, Digit, Row};
 
 /// Represents a digit in a specific cell
+///
+/// With the `serde` feature, it is serialized as a struct with the fields `cell` and `digit`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub struct Candidate {
     pub cell: Cell,
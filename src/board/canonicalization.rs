@@ -56,12 +56,8 @@ impl Transformation {
         apply_digit_mapping(self.digit_remapping, sudoku);
     }
 
-    pub(crate) fn random() -> Self {
-        use rand::{distributions::Distribution, Rng};
-        // SmallRng is a good 10% faster, but it uses XorShiftRng which can fail some statistical tests
-        // There are some adaptions that fix this, but I don't know if Rust implements them.
-        //let rng = &mut rand::rngs::SmallRng::from_rng(rand::thread_rng()).unwrap();
-        let rng = &mut rand::thread_rng();
+    pub(crate) fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        use rand::distributions::Distribution;
 
         let mut digits = [1, 2, 3, 4, 5, 6, 7, 8, 9];
 
@@ -463,7 +459,7 @@ fn transformation_from_struct() {
     let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
 
     for sudoku in sudokus {
-        let mut solved_sudoku = sudoku.solution().unwrap();
+        let mut solved_sudoku = sudoku.solution().unwrap().into_sudoku();
         let (canonical_sudoku, transformation, _) = find_canonical_sudoku_and_transformation(solved_sudoku);
         transformation.apply(&mut solved_sudoku);
         assert_eq!(
@@ -1,12 +1,16 @@
-use crate::bitset::Set;
+use crate::bitset::DigitSet;
 use crate::board::Digit;
 
 /// Contains either a digit or all the candidates for an unsolved cell
+///
+/// With the `serde` feature, it is serialized as either `{"Digit": 5}`
+/// or `{"Candidates": [1, 2, 9]}` in self-describing formats like JSON.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum CellState {
     Digit(Digit),
-    Candidates(Set<Digit>),
+    Candidates(DigitSet),
 }
 
 impl std::fmt::Display for CellState {
@@ -0,0 +1,198 @@
+use crate::bitset::Set;
+use crate::board::{Cell, Digit, SolvedSudoku, Sudoku};
+
+/// Proof that a sudoku has exactly one solution, as returned by [`Sudoku::uniqueness_certificate`]
+///
+/// The proof is a case distinction that only relies on naked and hidden singles. Starting from the
+/// clues, singles are entered until either the grid is filled, a contradiction shows up or the
+/// singles run out. In the last case, the proof branches on every remaining candidate of one cell.
+/// Exactly one branch of the whole tree may end in a filled grid and that grid is the solution.
+///
+/// Checking the proof with [`verify`](Self::verify) doesn't need a solver, so it can be done
+/// independently, e.g. to audit generated puzzles. With the `serde` feature, the certificate can
+/// be exported for checking elsewhere.
+///
+/// ```
+/// use sudoku::Sudoku;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let certificate = sudoku.uniqueness_certificate().unwrap();
+/// assert!(certificate.verify(sudoku));
+/// assert_eq!(Some(certificate.solution()), sudoku.solution());
+///
+/// // sudokus with multiple solutions have no certificate
+/// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+/// assert!(empty.uniqueness_certificate().is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniquenessCertificate {
+    solution: SolvedSudoku,
+    proof: ProofTree,
+}
+
+/// A node of a [`UniquenessCertificate`]
+///
+/// Each node stands for the grid of the clues plus the digits of all branches leading to it,
+/// after entering all naked and hidden singles.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProofTree {
+    /// The grid is filled
+    Solved,
+    /// The grid contains a contradiction, see [`Contradiction`](crate::board::Contradiction)
+    Contradiction,
+    /// Every candidate of `cell` is tried in turn
+    Branch {
+        /// An empty cell
+        cell: Cell,
+        /// All candidates of the cell with the proof for the grid that contains it
+        branches: Vec<(Digit, ProofTree)>,
+    },
+}
+
+impl UniquenessCertificate {
+    /// Returns the unique solution
+    pub fn solution(&self) -> SolvedSudoku {
+        self.solution
+    }
+
+    /// Returns the case distinction that proves the uniqueness
+    pub fn proof(&self) -> &ProofTree {
+        &self.proof
+    }
+
+    /// Checks whether this certificate proves that `sudoku` has exactly one solution,
+    /// namely [`solution`](Self::solution).
+    pub fn verify(&self, sudoku: Sudoku) -> bool {
+        let mut n_solved = 0;
+        self.proof.verify(sudoku, self.solution, &mut n_solved) && n_solved == 1
+    }
+}
+
+impl ProofTree {
+    // Builds the proof for `sudoku` and adds the solutions of its leaves to `solutions`.
+    // Stops early, if more than one solution is found.
+    fn build(sudoku: Sudoku, solutions: &mut Vec<Sudoku>) -> ProofTree {
+        let (grid, candidates) = match propagate(sudoku) {
+            Some(propagated) => propagated,
+            None => return ProofTree::Contradiction,
+        };
+        let cell = match branching_cell(&grid, &candidates) {
+            Some(cell) => cell,
+            None => {
+                solutions.push(Sudoku(grid));
+                return ProofTree::Solved;
+            }
+        };
+        let mut branches = vec![];
+        for digit in candidates[cell.as_index()] {
+            if solutions.len() > 1 {
+                break;
+            }
+            let mut guess = Sudoku(grid);
+            guess.0[cell.as_index()] = digit.get();
+            branches.push((digit, ProofTree::build(guess, solutions)));
+        }
+        ProofTree::Branch { cell, branches }
+    }
+
+    fn verify(&self, sudoku: Sudoku, solution: SolvedSudoku, n_solved: &mut usize) -> bool {
+        let propagated = propagate(sudoku);
+        match (self, propagated) {
+            (ProofTree::Contradiction, propagated) => propagated.is_none(),
+            (ProofTree::Solved, Some((grid, _))) => {
+                *n_solved += 1;
+                grid == solution.to_bytes()
+            }
+            (ProofTree::Branch { cell, branches }, Some((grid, candidates))) => {
+                let branch_digits = branches
+                    .iter()
+                    .fold(Set::NONE, |digits, &(digit, _)| digits | digit);
+                grid[cell.as_index()] == 0
+                    && branch_digits == candidates[cell.as_index()]
+                    && branch_digits.len() as usize == branches.len()
+                    && branches.iter().all(|&(digit, ref proof)| {
+                        let mut guess = Sudoku(grid);
+                        guess.0[cell.as_index()] = digit.get();
+                        proof.verify(guess, solution, n_solved)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+// Enters all naked and hidden singles. `None`, if a contradiction is found.
+fn propagate(sudoku: Sudoku) -> Option<([u8; 81], [Set<Digit>; 81])> {
+    if sudoku.duplicate_digit().is_some() {
+        return None;
+    }
+    sudoku.propagate_singles().ok()
+}
+
+// The empty cell with the fewest candidates, `None` for a filled grid
+fn branching_cell(grid: &[u8; 81], candidates: &[Set<Digit>; 81]) -> Option<Cell> {
+    Cell::all()
+        .filter(|cell| grid[cell.as_index()] == 0)
+        .min_by_key(|cell| candidates[cell.as_index()].len())
+}
+
+impl Sudoku {
+    /// Returns a proof that the sudoku has exactly one solution or `None`, if it has none or
+    /// several solutions. See [`UniquenessCertificate`].
+    pub fn uniqueness_certificate(self) -> Option<UniquenessCertificate> {
+        let mut solutions = vec![];
+        let proof = ProofTree::build(self, &mut solutions);
+        match solutions[..] {
+            [solution] => Some(UniquenessCertificate {
+                solution: SolvedSudoku::new_unchecked(solution),
+                proof,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn certificates_of_hard_sudokus() {
+        let sudokus = include_str!("../../sudokus/Lines/hard_sudokus.txt");
+        for line in sudokus.lines().take(10) {
+            let sudoku = Sudoku::from_str_line(line).unwrap();
+            let certificate = sudoku.uniqueness_certificate().unwrap();
+            assert_eq!(Some(certificate.solution()), sudoku.solution());
+            assert!(certificate.verify(sudoku));
+        }
+    }
+
+    #[test]
+    fn forged_certificates_are_rejected() {
+        let sudokus = include_str!("../../sudokus/Lines/hard_sudokus.txt");
+        let mut sudokus = sudokus.lines().map(|line| Sudoku::from_str_line(line).unwrap());
+        // the first sudoku that singles can't solve
+        let (sudoku, certificate) = sudokus
+            .by_ref()
+            .map(|sudoku| (sudoku, sudoku.uniqueness_certificate().unwrap()))
+            .find(|(_, certificate)| matches!(certificate.proof(), ProofTree::Branch { .. }))
+            .unwrap();
+
+        // a different sudoku
+        assert!(!certificate.verify(sudokus.next().unwrap()));
+
+        // a branch left out
+        let mut forged = certificate.clone();
+        if let ProofTree::Branch { branches, .. } = &mut forged.proof {
+            branches.pop();
+        }
+        assert!(!forged.verify(sudoku));
+
+        // a contradiction claimed for the solution's branch
+        let mut forged = certificate;
+        forged.proof = ProofTree::Contradiction;
+        assert!(!forged.verify(sudoku));
+    }
+}
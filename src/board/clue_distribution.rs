@@ -0,0 +1,102 @@
+use crate::Sudoku;
+
+/// How the clues of a sudoku are distributed over its houses and digits
+///
+/// Returned by [`Sudoku::clue_distribution`]. Generators can use these metrics to filter for
+/// puzzles that look balanced. All counts are indexed from 0, i.e. the count for the digit 1 is
+/// at index 0.
+///
+/// ```
+/// use sudoku::Sudoku;
+///
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// let distribution = sudoku.clue_distribution();
+/// assert_eq!(distribution.clues_per_row[0], 3);
+/// assert_eq!(distribution.clues_per_block.iter().sum::<u8>(), sudoku.n_clues());
+/// assert!(distribution.positional_entropy() > 0.9);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClueDistribution {
+    /// Number of clues in each row
+    pub clues_per_row: [u8; 9],
+    /// Number of clues in each column
+    pub clues_per_col: [u8; 9],
+    /// Number of clues in each block
+    pub clues_per_block: [u8; 9],
+    /// Number of clues of each digit
+    pub digit_counts: [u8; 9],
+}
+
+impl ClueDistribution {
+    fn new(sudoku: &Sudoku) -> ClueDistribution {
+        let mut distribution = ClueDistribution {
+            clues_per_row: [0; 9],
+            clues_per_col: [0; 9],
+            clues_per_block: [0; 9],
+            digit_counts: [0; 9],
+        };
+        for (cell, digit) in sudoku.clues() {
+            distribution.clues_per_row[cell.row().as_index()] += 1;
+            distribution.clues_per_col[cell.col().as_index()] += 1;
+            distribution.clues_per_block[cell.block().as_index()] += 1;
+            distribution.digit_counts[digit as usize - 1] += 1;
+        }
+        distribution
+    }
+
+    /// Returns how evenly the clues are spread over the rows, columns and blocks
+    /// as the mean of the [normalized entropies](Self::digit_entropy) of the three house types.
+    ///
+    /// Ranges from 0.0 for an empty sudoku or clues in a single house of each type
+    /// to 1.0 for the same number of clues in every house.
+    pub fn positional_entropy(&self) -> f64 {
+        let entropies = [
+            normalized_entropy(&self.clues_per_row),
+            normalized_entropy(&self.clues_per_col),
+            normalized_entropy(&self.clues_per_block),
+        ];
+        entropies.iter().sum::<f64>() / 3.0
+    }
+
+    /// Returns how evenly the clues are spread over the digits as the Shannon entropy of the
+    /// digit frequencies, divided by its maximum.
+    ///
+    /// Ranges from 0.0 for an empty sudoku or clues of a single digit to 1.0 for the same
+    /// number of clues of every digit.
+    pub fn digit_entropy(&self) -> f64 {
+        normalized_entropy(&self.digit_counts)
+    }
+
+    /// Returns the difference between the numbers of clues of the most and the least common digit.
+    pub fn digit_imbalance(&self) -> u8 {
+        let max = self.digit_counts.iter().max().unwrap();
+        let min = self.digit_counts.iter().min().unwrap();
+        max - min
+    }
+}
+
+// Shannon entropy of the distribution given by `counts`, divided by the entropy of the uniform distribution
+fn normalized_entropy(counts: &[u8; 9]) -> f64 {
+    let total = counts.iter().map(|&count| f64::from(count)).sum::<f64>();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let entropy = counts
+        .iter()
+        .filter(|&&count| count != 0)
+        .map(|&count| {
+            let p = f64::from(count) / total;
+            -p * p.log2()
+        })
+        .sum::<f64>();
+    entropy / 9f64.log2()
+}
+
+impl Sudoku {
+    /// Returns the distribution of the clues over the houses and digits.
+    /// See [`ClueDistribution`].
+    pub fn clue_distribution(&self) -> ClueDistribution {
+        ClueDistribution::new(self)
+    }
+}
@@ -0,0 +1,380 @@
+use crate::bitset::Set;
+use crate::board::positions::{Block, Cell, Col, Row};
+use crate::consts::N_CELLS;
+use crate::solver::{Dlx, SolverBackend, SudokuSolver};
+use crate::Sudoku;
+
+/// A set of houses, i.e. groups of cells that must all contain different digits
+///
+/// Houses with 9 cells must therefore contain every digit exactly once. Besides the [`Constraints`]
+/// for the regular house types, this is implemented for lists of houses and for pairs of constraint
+/// sets, which combines their houses. Custom regions like diagonals or cages can be added that way
+/// or by implementing this trait.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::bitset::Set;
+/// use sudoku::board::{Cell, Constraints};
+///
+/// // sudoku x: both main diagonals must also contain every digit once
+/// let diagonals = vec![
+///     (0..9).map(|i| Cell::new(i * 10)).fold(Set::NONE, |cells, cell| cells | cell),
+///     (0..9).map(|i| Cell::new(i * 8 + 8)).fold(Set::NONE, |cells, cell| cells | cell),
+/// ];
+/// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+/// for solution in empty.solutions_up_to_with_constraints((Constraints::SUDOKU, diagonals), 5) {
+///     let bytes = solution.to_bytes();
+///     let mut digits = (0..9).map(|i| bytes[i * 10]).collect::<Vec<_>>();
+///     digits.sort();
+///     assert_eq!(digits, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// }
+/// ```
+pub trait ConstraintSet {
+    /// Returns the cells of all houses
+    fn houses(&self) -> Vec<Set<Cell>>;
+}
+
+/// The house types whose cells must each contain every digit exactly once
+///
+/// Regular sudokus use rows, columns and blocks. Without the blocks, a solved grid is a
+/// [latin square](https://en.wikipedia.org/wiki/Latin_square).
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::Constraints;
+///
+/// // a sudoku, but not as a latin square
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// assert_eq!(sudoku.solutions_count_up_to_with_constraints(Constraints::SUDOKU, 2), 1);
+/// assert_eq!(sudoku.solutions_count_up_to_with_constraints(Constraints::LATIN_SQUARE, 2), 2);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Constraints {
+    rows: bool,
+    cols: bool,
+    blocks: bool,
+}
+
+impl Constraints {
+    /// Rows, columns and blocks
+    pub const SUDOKU: Constraints = Constraints::new(true, true, true);
+    /// Rows and columns
+    pub const LATIN_SQUARE: Constraints = Constraints::new(true, true, false);
+
+    /// Constraints for the selected house types
+    pub const fn new(rows: bool, cols: bool, blocks: bool) -> Constraints {
+        Constraints { rows, cols, blocks }
+    }
+
+    /// Whether the rows are constrained
+    pub fn rows(self) -> bool {
+        self.rows
+    }
+
+    /// Whether the columns are constrained
+    pub fn cols(self) -> bool {
+        self.cols
+    }
+
+    /// Whether the blocks are constrained
+    pub fn blocks(self) -> bool {
+        self.blocks
+    }
+}
+
+impl ConstraintSet for Constraints {
+    fn houses(&self) -> Vec<Set<Cell>> {
+        let rows = Row::all().map(Row::cells).filter(|_| self.rows);
+        let cols = Col::all().map(Col::cells).filter(|_| self.cols);
+        let blocks = Block::all().map(Block::cells).filter(|_| self.blocks);
+        rows.chain(cols).chain(blocks).collect()
+    }
+}
+
+impl ConstraintSet for Vec<Set<Cell>> {
+    fn houses(&self) -> Vec<Set<Cell>> {
+        self.clone()
+    }
+}
+
+impl ConstraintSet for [Set<Cell>] {
+    fn houses(&self) -> Vec<Set<Cell>> {
+        self.to_vec()
+    }
+}
+
+impl<A: ConstraintSet, B: ConstraintSet> ConstraintSet for (A, B) {
+    fn houses(&self) -> Vec<Set<Cell>> {
+        let mut houses = self.0.houses();
+        houses.extend(self.1.houses());
+        houses
+    }
+}
+
+impl<C: ConstraintSet + ?Sized> ConstraintSet for &C {
+    fn houses(&self) -> Vec<Set<Cell>> {
+        (**self).houses()
+    }
+}
+
+// Whether `houses` are exactly the houses of a regular sudoku, which the fast solver can handle
+fn is_sudoku(houses: &[Set<Cell>]) -> bool {
+    let mut houses = houses.to_vec();
+    houses.sort();
+    houses.dedup();
+    let mut sudoku_houses = Constraints::SUDOKU.houses();
+    sudoku_houses.sort();
+    houses == sudoku_houses
+}
+
+impl Sudoku {
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but only the given `constraints` have to hold.
+    /// Returns no solutions, if the clues already violate them.
+    ///
+    /// Regular sudokus use the fast solver, all other constraints a simple backtracking search.
+    pub fn solutions_up_to_with_constraints(
+        self,
+        constraints: impl ConstraintSet,
+        limit: usize,
+    ) -> Vec<Sudoku> {
+        let houses = constraints.houses();
+        if is_sudoku(&houses) {
+            return self.solutions_up_to(limit);
+        }
+        let mut solutions = vec![];
+        if let Some(mut search) = ConstrainedSearch::new(self, &houses) {
+            search.solve(limit, &mut |sudoku| solutions.push(sudoku));
+        }
+        solutions
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but the solutions are found with `backend`.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::solver::SolverBackend;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(
+    ///     sudoku.solutions_up_to_with_backend(SolverBackend::Dlx, 2),
+    ///     sudoku.solutions_up_to_with_backend(SolverBackend::Fast, 2),
+    /// );
+    /// ```
+    pub fn solutions_up_to_with_backend(self, backend: SolverBackend, limit: usize) -> Vec<Sudoku> {
+        self.solutions_up_to_with_constraints_and_backend(Constraints::SUDOKU, backend, limit)
+    }
+
+    /// Like [`solutions_up_to_with_constraints`](Self::solutions_up_to_with_constraints), but the
+    /// solutions are found with `backend`.
+    ///
+    /// The order of the solutions depends on the backend.
+    pub fn solutions_up_to_with_constraints_and_backend(
+        self,
+        constraints: impl ConstraintSet,
+        backend: SolverBackend,
+        limit: usize,
+    ) -> Vec<Sudoku> {
+        match backend {
+            SolverBackend::Fast => self.solutions_up_to_with_constraints(constraints, limit),
+            SolverBackend::Dlx => {
+                let mut solutions = vec![];
+                if let Some(mut dlx) = Dlx::new(self, &constraints.houses()) {
+                    dlx.solve(limit, &mut |sudoku| solutions.push(sudoku));
+                }
+                solutions
+            }
+        }
+    }
+
+    /// Like [`solutions_count_up_to`](Self::solutions_count_up_to), but only the given `constraints`
+    /// have to hold.
+    /// See [`solutions_up_to_with_constraints`](Self::solutions_up_to_with_constraints).
+    pub fn solutions_count_up_to_with_constraints(
+        self,
+        constraints: impl ConstraintSet,
+        limit: usize,
+    ) -> usize {
+        let houses = constraints.houses();
+        if is_sudoku(&houses) {
+            return SudokuSolver::from_sudoku(self).map_or(0, |solver| solver.solutions_count_up_to(limit));
+        }
+        let mut count = 0;
+        if let Some(mut search) = ConstrainedSearch::new(self, &houses) {
+            search.solve(limit, &mut |_| count += 1);
+        }
+        count
+    }
+}
+
+// Backtracking search that branches on the cell with the fewest candidates
+struct ConstrainedSearch {
+    // indices of the houses of each cell
+    cell_houses: Vec<Vec<usize>>,
+    grid: [u8; N_CELLS],
+    // bitmask of the digits that are already used in each house, 1 bit per digit
+    house_digits: Vec<u16>,
+    n_solutions: usize,
+}
+
+impl ConstrainedSearch {
+    // Returns `None` if the clues conflict with each other
+    fn new(sudoku: Sudoku, houses: &[Set<Cell>]) -> Option<Self> {
+        let mut cell_houses = vec![vec![]; N_CELLS];
+        for (idx, &house) in houses.iter().enumerate() {
+            for cell in house {
+                cell_houses[cell.as_index()].push(idx);
+            }
+        }
+        let mut search = ConstrainedSearch {
+            cell_houses,
+            grid: [0; N_CELLS],
+            house_digits: vec![0; houses.len()],
+            n_solutions: 0,
+        };
+        for (cell, digit) in sudoku.clues() {
+            if search.candidates(cell.as_index()) & 1 << (digit - 1) == 0 {
+                return None;
+            }
+            search.set(cell.as_index(), digit);
+        }
+        Some(search)
+    }
+
+    fn candidates(&self, cell: usize) -> u16 {
+        let used = self.cell_houses[cell]
+            .iter()
+            .fold(0, |used, &house| used | self.house_digits[house]);
+        0o777 & !used
+    }
+
+    fn set(&mut self, cell: usize, digit: u8) {
+        self.grid[cell] = digit;
+        for &house in &self.cell_houses[cell] {
+            self.house_digits[house] |= 1 << (digit - 1);
+        }
+    }
+
+    fn unset(&mut self, cell: usize, digit: u8) {
+        self.grid[cell] = 0;
+        for &house in &self.cell_houses[cell] {
+            self.house_digits[house] &= !(1 << (digit - 1));
+        }
+    }
+
+    fn solve(&mut self, limit: usize, on_solution: &mut impl FnMut(Sudoku)) {
+        if self.n_solutions >= limit {
+            return;
+        }
+        // empty cell with the fewest candidates
+        let mut best: Option<(usize, u16)> = None;
+        for cell in (0..N_CELLS).filter(|&cell| self.grid[cell] == 0) {
+            let candidates = self.candidates(cell);
+            let is_better = match best {
+                Some((_, best_candidates)) => candidates.count_ones() < best_candidates.count_ones(),
+                None => true,
+            };
+            if is_better {
+                best = Some((cell, candidates));
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+        let (cell, candidates) = match best {
+            Some(best) => best,
+            None => {
+                self.n_solutions += 1;
+                on_solution(Sudoku(self.grid));
+                return;
+            }
+        };
+        for digit in (1..=9).filter(|digit| candidates & 1 << (digit - 1) != 0) {
+            self.set(cell, digit);
+            self.solve(limit, on_solution);
+            self.unset(cell, digit);
+            if self.n_solutions >= limit {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::positions::{col, row};
+
+    fn search_solutions(sudoku: Sudoku, constraints: Constraints, limit: usize) -> Vec<Sudoku> {
+        let mut solutions = vec![];
+        if let Some(mut search) = ConstrainedSearch::new(sudoku, &constraints.houses()) {
+            search.solve(limit, &mut |solution| solutions.push(solution));
+        }
+        solutions
+    }
+
+    #[test]
+    fn search_matches_solver() {
+        let sudokus = include_str!("../../sudokus/Lines/easy_sudokus.txt");
+        for line in sudokus.lines().take(20) {
+            let sudoku = Sudoku::from_str_line(line).unwrap();
+            let solutions = search_solutions(sudoku, Constraints::SUDOKU, 2);
+            assert_eq!(solutions, [sudoku.solution().unwrap().into_sudoku()]);
+        }
+    }
+
+    #[test]
+    fn latin_squares() {
+        let empty = Sudoku([0; N_CELLS]);
+        let solutions = empty.solutions_up_to_with_constraints(Constraints::LATIN_SQUARE, 10);
+        assert_eq!(solutions.len(), 10);
+        for solution in solutions {
+            let bytes = solution.to_bytes();
+            for cell in 0..N_CELLS as u8 {
+                let digit = bytes[cell as usize];
+                for other in 0..N_CELLS as u8 {
+                    if other != cell && (row(other) == row(cell) || col(other) == col(cell)) {
+                        assert_ne!(bytes[other as usize], digit);
+                    }
+                }
+            }
+        }
+
+        // conflicting clues in a block are allowed without the block constraint
+        let mut bytes = [0; N_CELLS];
+        bytes[0] = 1;
+        bytes[10] = 1;
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+        assert_eq!(
+            sudoku.solutions_count_up_to_with_constraints(Constraints::SUDOKU, 1),
+            0
+        );
+        assert_eq!(
+            sudoku.solutions_count_up_to_with_constraints(Constraints::LATIN_SQUARE, 1),
+            1
+        );
+    }
+
+    #[test]
+    fn custom_houses() {
+        // the regular houses in any order are solved by the fast solver
+        let sudokus = include_str!("../../sudokus/Lines/easy_sudokus.txt");
+        let sudoku = Sudoku::from_str_line(sudokus.lines().next().unwrap()).unwrap();
+        let mut houses = Constraints::SUDOKU.houses();
+        houses.reverse();
+        assert!(is_sudoku(&houses));
+        assert_eq!(
+            sudoku.solutions_up_to_with_constraints(&houses[..], 2),
+            sudoku.solutions_up_to(2)
+        );
+
+        // an extra house of 2 cells in different blocks, rows and columns
+        let extra = vec![Cell::new(0).as_set() | Cell::new(40)];
+        let empty = Sudoku([0; N_CELLS]);
+        let solutions = empty.solutions_up_to_with_constraints((Constraints::SUDOKU, extra), 20);
+        assert_eq!(solutions.len(), 20);
+        for solution in solutions {
+            assert_ne!(solution.to_bytes()[0], solution.to_bytes()[40]);
+            assert!(solution.is_solved());
+        }
+    }
+}
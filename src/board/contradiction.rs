@@ -0,0 +1,243 @@
+use crate::bitset::{DigitSet, Set};
+use crate::board::positions::House;
+use crate::board::{Cell, Digit, Sudoku};
+
+/// Reason why a sudoku has no solution, as found by [`Sudoku::contradiction`]
+///
+/// Except for [`DuplicateDigit`](Self::DuplicateDigit), the contradiction may only become visible
+/// after digits have been deduced from the clues with naked and hidden singles.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Contradiction {
+    /// Two clues with the same digit in one house
+    DuplicateDigit {
+        /// The house containing both clues
+        house: House,
+        /// The duplicated digit
+        digit: Digit,
+        /// The cells of the clues
+        cells: [Cell; 2],
+    },
+    /// An empty cell in which no digit can be placed
+    NoCandidates(Cell),
+    /// A digit that can't be placed anywhere in a house
+    NoPosition {
+        /// The house that is missing the digit
+        house: House,
+        /// The missing digit
+        digit: Digit,
+    },
+    /// The sudoku has no solution, but the contradiction only shows up after guessing
+    NoSolution,
+}
+
+impl Sudoku {
+    /// Returns a reason why the sudoku is unsolvable or `None`, if it has a solution.
+    ///
+    /// The clues are checked for duplicate digits first. After that, digits are deduced with
+    /// naked and hidden singles until a cell without candidates or a house without a position
+    /// for some digit is found. Only if that fails, too, is a full search needed to prove
+    /// that there is no solution, in which case [`Contradiction::NoSolution`] is returned.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::{Cell, Contradiction};
+    /// use sudoku::board::positions::{HouseType, Row};
+    ///
+    /// let mut bytes = [0; 81];
+    /// bytes[0] = 5;
+    /// bytes[8] = 5;
+    /// let sudoku = Sudoku::from_bytes(bytes).unwrap();
+    /// match sudoku.contradiction() {
+    ///     Some(Contradiction::DuplicateDigit { house, cells, .. }) => {
+    ///         assert_eq!(house.categorize(), HouseType::Row(Row::new(0)));
+    ///         assert_eq!(cells, [Cell::new(0), Cell::new(8)]);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn contradiction(self) -> Option<Contradiction> {
+        if let Some(contradiction) = self.duplicate_digit() {
+            return Some(contradiction);
+        }
+        if let Err(contradiction) = self.propagate_singles() {
+            return Some(contradiction);
+        }
+        match self.solutions_count_up_to(1) {
+            0 => Some(Contradiction::NoSolution),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn duplicate_digit(self) -> Option<Contradiction> {
+        for house in House::all() {
+            let mut first_cell = [None; 9];
+            for cell in house.cells() {
+                let digit = match self.0[cell.as_index()] {
+                    0 => continue,
+                    digit => Digit::new(digit),
+                };
+                match first_cell[digit.as_index()] {
+                    Some(first) => {
+                        return Some(Contradiction::DuplicateDigit {
+                            house,
+                            digit,
+                            cells: [first, cell],
+                        })
+                    }
+                    None => first_cell[digit.as_index()] = Some(cell),
+                }
+            }
+        }
+        None
+    }
+
+    /// Deduces digits and removes candidates by constraint propagation alone, without guessing.
+    ///
+    /// Naked and hidden singles are entered and locked candidates are eliminated until nothing
+    /// changes anymore. Returns the resulting grid together with the candidates of all cells.
+    /// Filled cells have their digit as the only candidate. If propagation runs into a
+    /// contradiction, it is returned instead, see [`contradiction`](Self::contradiction).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let (grid, candidates) = sudoku.propagate().unwrap();
+    /// assert!(grid.n_clues() > sudoku.n_clues());
+    /// for (digit, candidates) in grid.iter().zip(candidates.iter()) {
+    ///     match digit {
+    ///         Some(digit) => assert_eq!(candidates.len(), 1),
+    ///         None => assert!(candidates.len() > 1),
+    ///     }
+    /// }
+    /// ```
+    pub fn propagate(self) -> Result<(Sudoku, [DigitSet; 81]), Contradiction> {
+        if let Some(contradiction) = self.duplicate_digit() {
+            return Err(contradiction);
+        }
+        let (grid, candidates) = self.propagate_with(true)?;
+        Ok((Sudoku(grid), candidates))
+    }
+
+    /// Returns the number of candidates of every empty cell after [propagation](Self::propagate),
+    /// in cell order. Cells with a clue are 0.
+    ///
+    /// Low numbers mark the cells with the least freedom, so the map can be rendered directly as
+    /// a heatmap. Empty cells whose digit follows from propagation have 1 candidate. If
+    /// propagation runs into a contradiction, the sudoku is unsolvable and all empty cells are 0.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let map = sudoku.constrainedness_map();
+    /// assert_eq!(map[3], 0);
+    /// assert!(map.iter().zip(sudoku.iter()).all(|(&n, digit)| digit.is_some() || (1..=9).contains(&n)));
+    /// ```
+    pub fn constrainedness_map(self) -> [u8; 81] {
+        let mut map = [0; 81];
+        if let Ok((_, candidates)) = self.propagate() {
+            for ((n_candidates, digits), &clue) in map.iter_mut().zip(candidates.iter()).zip(self.0.iter()) {
+                if clue == 0 {
+                    *n_candidates = digits.len();
+                }
+            }
+        }
+        map
+    }
+
+    // Enter naked and hidden singles until nothing changes anymore.
+    // Returns the resulting grid and the candidates of all cells.
+    // Requires the clues to be free of duplicates.
+    pub(crate) fn propagate_singles(self) -> Result<([u8; 81], [Set<Digit>; 81]), Contradiction> {
+        self.propagate_with(false)
+    }
+
+    // Like `propagate_singles`, but optionally also eliminates locked candidates
+    fn propagate_with(self, locked_candidates: bool) -> Result<([u8; 81], [Set<Digit>; 81]), Contradiction> {
+        fn enter(cell: Cell, digit: Digit, grid: &mut [u8; 81], candidates: &mut [Set<Digit>; 81]) {
+            grid[cell.as_index()] = digit.get();
+            candidates[cell.as_index()] = digit.as_set();
+            for neighbor in cell.neighbors() {
+                candidates[neighbor.as_index()].remove(digit.as_set());
+            }
+        }
+
+        let mut grid = [0; 81];
+        let mut candidates = [Set::<Digit>::ALL; 81];
+        for (cell, digit) in self.clues() {
+            enter(cell, Digit::new(digit), &mut grid, &mut candidates);
+        }
+
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for cell in Cell::all() {
+                if grid[cell.as_index()] != 0 {
+                    continue;
+                }
+                match candidates[cell.as_index()].unique() {
+                    Ok(Some(digit)) => {
+                        enter(cell, digit, &mut grid, &mut candidates);
+                        progress = true;
+                    }
+                    Ok(None) => {}
+                    Err(_) => return Err(Contradiction::NoCandidates(cell)),
+                }
+            }
+
+            for house in House::all() {
+                for digit in Digit::all() {
+                    let mut positions = house
+                        .cells()
+                        .into_iter()
+                        .filter(|cell| candidates[cell.as_index()].contains(digit));
+                    match (positions.next(), positions.next()) {
+                        (None, _) => return Err(Contradiction::NoPosition { house, digit }),
+                        (Some(cell), None) if grid[cell.as_index()] == 0 => {
+                            enter(cell, digit, &mut grid, &mut candidates);
+                            progress = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if locked_candidates && !progress {
+                progress = eliminate_locked_candidates(&grid, &mut candidates);
+            }
+        }
+        Ok((grid, candidates))
+    }
+}
+
+// Removes candidates of a digit from a house, if the digit's positions in an intersecting house
+// all lie in the intersection. Returns whether any candidate was removed.
+fn eliminate_locked_candidates(grid: &[u8; 81], candidates: &mut [Set<Digit>; 81]) -> bool {
+    let mut progress = false;
+    for house in House::all() {
+        for digit in Digit::all() {
+            let positions = house
+                .cells()
+                .into_iter()
+                .filter(|cell| grid[cell.as_index()] == 0 && candidates[cell.as_index()].contains(digit))
+                .fold(Set::NONE, |positions, cell| positions | cell);
+            let first = match positions.into_iter().next() {
+                Some(cell) => cell,
+                None => continue,
+            };
+            for other in first.houses() {
+                if other == house || !other.cells().contains(positions) {
+                    continue;
+                }
+                for cell in other.cells().without(house.cells()) {
+                    if grid[cell.as_index()] == 0 && candidates[cell.as_index()].contains(digit) {
+                        candidates[cell.as_index()].remove(digit.as_set());
+                        progress = true;
+                    }
+                }
+            }
+        }
+    }
+    progress
+}
@@ -2,9 +2,27 @@ use std::num::NonZeroU8;
 
 // define digit separately because it has an offset
 /// A digit that can be entered in a cell of a sudoku.
+///
+/// With the `serde` feature, it is serialized as a number from 1 to 9.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Digit(NonZeroU8);
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Digit {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Digit {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let digit = u8::deserialize(deserializer)?;
+        Digit::new_checked(digit)
+            .ok_or_else(|| serde::de::Error::custom("digit must be in the range of 1..=9"))
+    }
+}
+
 impl Digit {
     /// Constructs a new `Digit`.
     ///
@@ -0,0 +1,137 @@
+use crate::board::{Candidate, Cell, ConstraintSet, Constraints};
+use crate::errors::FromSatModelError;
+use crate::Sudoku;
+use std::fmt::Write;
+
+// Number of variables, one per candidate
+const N_VARIABLES: u32 = 729;
+
+impl Candidate {
+    /// Returns the variable of this candidate in the CNF encoding of [`Sudoku::to_dimacs`],
+    /// `9 * cell + digit`, where cells are numbered from 0 to 80. Ranges from 1 to 729.
+    pub fn sat_variable(self) -> u32 {
+        9 * u32::from(self.cell.get()) + u32::from(self.digit.get())
+    }
+
+    /// Returns the candidate of a variable in the CNF encoding of [`Sudoku::to_dimacs`].
+    /// `None`, if the variable is outside of 1 to 729.
+    pub fn from_sat_variable(variable: u32) -> Option<Candidate> {
+        match variable {
+            1..=N_VARIABLES => Some(Candidate::new(
+                ((variable - 1) / 9) as u8,
+                ((variable - 1) % 9 + 1) as u8,
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Sudoku {
+    /// Encodes the sudoku as a boolean satisfiability problem in conjunctive normal form,
+    /// in the DIMACS format read by most SAT solvers.
+    ///
+    /// There is one variable per candidate, see [`Candidate::sat_variable`]. Every cell holds exactly
+    /// one digit, every house contains every digit exactly once and every clue is a unit clause.
+    /// A model found by a SAT solver can be turned back into a sudoku with
+    /// [`from_sat_model`](Self::from_sat_model).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let cnf = sudoku.to_dimacs();
+    /// assert!(cnf.lines().any(|line| line.starts_with("p cnf 729 ")));
+    /// // the clue 2 in the fourth cell
+    /// assert!(cnf.lines().any(|line| line == "29 0"));
+    /// ```
+    pub fn to_dimacs(&self) -> String {
+        self.to_dimacs_with_constraints(Constraints::SUDOKU)
+    }
+
+    /// Like [`to_dimacs`](Self::to_dimacs), but only the given `constraints` have to hold.
+    ///
+    /// Houses of 9 cells must contain every digit. In smaller houses, digits may only not repeat.
+    /// Houses with more than 9 cells can't be satisfied.
+    pub fn to_dimacs_with_constraints(&self, constraints: impl ConstraintSet) -> String {
+        let variable = |cell: Cell, digit: u8| Candidate::new(cell.get(), digit).sat_variable() as i32;
+        let mut clauses = vec![];
+        for cell in Cell::all() {
+            clauses.push((1..=9).map(|digit| variable(cell, digit)).collect::<Vec<_>>());
+            for digit in 1..=9 {
+                for other in digit + 1..=9 {
+                    clauses.push(vec![-variable(cell, digit), -variable(cell, other)]);
+                }
+            }
+        }
+        for house in constraints.houses() {
+            let cells = house.into_iter().collect::<Vec<_>>();
+            for digit in 1..=9 {
+                if cells.len() == 9 {
+                    clauses.push(cells.iter().map(|&cell| variable(cell, digit)).collect());
+                }
+                for (idx, &cell) in cells.iter().enumerate() {
+                    for &other in &cells[idx + 1..] {
+                        clauses.push(vec![-variable(cell, digit), -variable(other, digit)]);
+                    }
+                }
+            }
+        }
+        for (cell, digit) in self.clues() {
+            clauses.push(vec![variable(cell, digit)]);
+        }
+
+        let mut cnf = String::new();
+        cnf.push_str("c sudoku, variable 9 * cell + digit is true if the cell contains the digit\n");
+        cnf.push_str("c cells are numbered from 0 to 80 in rows from the top left\n");
+        let _ = writeln!(cnf, "p cnf {} {}", N_VARIABLES, clauses.len());
+        for clause in clauses {
+            for literal in clause {
+                let _ = write!(cnf, "{} ", literal);
+            }
+            cnf.push_str("0\n");
+        }
+        cnf
+    }
+
+    /// Reads the sudoku from a model of the encoding of [`to_dimacs`](Self::to_dimacs), given as the
+    /// literals a SAT solver reports, e.g. `[-1, 2, -3, ...]`.
+    ///
+    /// Literals of false variables may be left out and the `0` that terminates the model in
+    /// the output of most solvers is ignored. The model must assign exactly one digit
+    /// to every cell. Whether the digits satisfy any constraints is not checked.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Candidate;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let solution = sudoku.solution().unwrap();
+    /// let model = solution
+    ///     .to_bytes()
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(cell, &digit)| Candidate::new(cell as u8, digit).sat_variable() as i32)
+    ///     .chain(Some(0))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(Sudoku::from_sat_model(&model).unwrap(), solution.into_sudoku());
+    /// ```
+    pub fn from_sat_model(model: &[i32]) -> Result<Sudoku, FromSatModelError> {
+        let mut grid = [0; 81];
+        for &literal in model.iter().filter(|&&literal| literal != 0) {
+            let candidate = Candidate::from_sat_variable(literal.unsigned_abs())
+                .ok_or(FromSatModelError::UnknownVariable(literal))?;
+            if literal < 0 {
+                continue;
+            }
+            let cell = candidate.cell.as_index();
+            if grid[cell] != 0 && grid[cell] != candidate.digit.get() {
+                return Err(FromSatModelError::MultipleDigits(cell as u8));
+            }
+            grid[cell] = candidate.digit.get();
+        }
+        match grid.iter().position(|&digit| digit == 0) {
+            Some(cell) => Err(FromSatModelError::NoDigit(cell as u8)),
+            None => Ok(Sudoku(grid)),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::bitset::{CellSet, Set};
+use crate::board::{Candidate, Cell, Digit};
+use crate::Sudoku;
+
+// Puzzles with more solutions are not analyzed
+const SOLUTION_LIMIT: usize = 1000;
+
+impl Sudoku {
+    /// Returns the minimal sets of at most `k` clues that make the sudoku uniquely solvable when added.
+    ///
+    /// Useful for puzzle editors, after a manual change broke the uniqueness of a puzzle. Every
+    /// suggestion is a set of candidates of one of the solutions, and no proper subset of a suggestion
+    /// is enough to rule out all other solutions. The suggestions are sorted by size, smallest first.
+    ///
+    /// A uniquely solvable sudoku needs no additional clues, so the only suggestion is empty. Returns
+    /// no suggestions, if the sudoku is unsolvable, if no set of at most `k` clues makes it unique, or
+    /// if it has more than 1000 solutions, which are too many to analyze. The number of suggestions
+    /// grows quickly with `k`.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// // without the 3 in row 6, column 2
+    /// let ambiguous = Sudoku::from_str_line("...2...633....54.1..1..398........9....538.............263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(ambiguous.solutions_count_up_to(10), 2);
+    ///
+    /// // either digit in any of the 4 cells that differ between the solutions
+    /// let suggestions = ambiguous.suggest_disambiguating_clues(1);
+    /// assert_eq!(suggestions.len(), 8);
+    /// for clues in suggestions {
+    ///     let mut bytes = ambiguous.to_bytes();
+    ///     for clue in clues {
+    ///         bytes[clue.cell.as_index()] = clue.digit.get();
+    ///     }
+    ///     assert!(Sudoku::from_bytes(bytes).unwrap().is_uniquely_solvable());
+    /// }
+    /// assert_eq!(sudoku.suggest_disambiguating_clues(1), vec![vec![]]);
+    /// ```
+    pub fn suggest_disambiguating_clues(self, k: usize) -> Vec<Vec<Candidate>> {
+        let solutions = self.solutions_up_to(SOLUTION_LIMIT + 1);
+        if solutions.len() > SOLUTION_LIMIT {
+            return vec![];
+        }
+        let solutions = solutions
+            .iter()
+            .map(|solution| solution.to_bytes())
+            .collect::<Vec<_>>();
+
+        let mut suggestions = vec![];
+        for (idx, target) in solutions.iter().enumerate() {
+            // the added clues must contain a cell in which `target` differs from each other solution
+            let differences = solutions
+                .iter()
+                .enumerate()
+                .filter(|&(other_idx, _)| other_idx != idx)
+                .map(|(_, other)| {
+                    Cell::all()
+                        .filter(|cell| target[cell.as_index()] != other[cell.as_index()])
+                        .collect::<CellSet>()
+                })
+                .collect::<Vec<_>>();
+
+            let mut hitting_sets = vec![];
+            find_hitting_sets(&differences, Set::NONE, k, &mut hitting_sets);
+            hitting_sets.sort_by_key(|cells| (cells.len(), cells.bits()));
+            hitting_sets.dedup();
+
+            let mut minimal_sets: Vec<CellSet> = vec![];
+            for cells in hitting_sets {
+                if !minimal_sets.iter().any(|&minimal| cells.contains(minimal)) {
+                    minimal_sets.push(cells);
+                }
+            }
+            suggestions.extend(minimal_sets.into_iter().map(|cells| {
+                cells
+                    .into_iter()
+                    .map(|cell| Candidate {
+                        cell,
+                        digit: Digit::new(target[cell.as_index()]),
+                    })
+                    .collect::<Vec<_>>()
+            }));
+        }
+        suggestions.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        suggestions
+    }
+}
+
+// Collects all sets of at most `k` cells that include `chosen` and overlap every set of `differences`.
+// Includes every minimal such set, but also some supersets of them.
+fn find_hitting_sets(differences: &[CellSet], chosen: CellSet, k: usize, hitting_sets: &mut Vec<CellSet>) {
+    let missed = match differences.iter().find(|&&cells| !cells.overlaps(chosen)) {
+        Some(&missed) => missed,
+        None => return hitting_sets.push(chosen),
+    };
+    if usize::from(chosen.len()) == k {
+        return;
+    }
+    for cell in missed {
+        find_hitting_sets(differences, chosen | cell, k, hitting_sets);
+    }
+}
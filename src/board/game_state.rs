@@ -0,0 +1,344 @@
+use crate::bitset::{CellSet, DigitSet, Set};
+use crate::board::{Cell, CellState, Digit};
+use crate::errors::NoUniqueSolution;
+use crate::strategy::deduction::Deductions;
+use crate::strategy::{Strategy, StrategySolver};
+use crate::{SolvedSudoku, Sudoku};
+
+/// A game in progress: the clues of a puzzle together with the player's entries and notes
+///
+/// The clues must have a unique solution, against which the player's progress is checked by
+/// [`validate`](Self::validate). [`hint`](Self::hint) and
+/// [`StrategySolver::from_game_state`] continue from the effective position, in which the correct
+/// entries count as givens and incorrect entries are ignored.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Cell, Digit, GameState};
+/// use sudoku::strategy::Strategy;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let solution = sudoku.solution().unwrap().to_bytes();
+/// let mut game = GameState::new(sudoku).unwrap();
+/// // a correct and a wrong entry
+/// game.set_entry(Cell::new(0), Some(Digit::new(solution[0])));
+/// game.set_entry(Cell::new(1), Some(Digit::new(solution[1] % 9 + 1)));
+///
+/// let validation = game.validate();
+/// assert_eq!(validation.incorrect_entries.to_string(), "r1c2");
+/// assert!(!validation.is_solved);
+///
+/// let hint = game.hint(&[Strategy::NakedSingles, Strategy::HiddenSingles]).unwrap();
+/// assert_eq!(hint.len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GameState {
+    clues: Sudoku,
+    solution: SolvedSudoku,
+    entries: [u8; 81],
+    notes: [DigitSet; 81],
+    auto_remove_notes: bool,
+}
+
+/// A player's entry that differs from the solution, see [`GameState::check_against_solution`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WrongEntry {
+    /// The cell of the entry
+    pub cell: Cell,
+    /// The digit entered by the player
+    pub entry: Digit,
+    /// The digit of the solution
+    pub solution: Digit,
+}
+
+/// Result of [`GameState::validate`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Validation {
+    /// Cells whose entry differs from the solution
+    pub incorrect_entries: CellSet,
+    /// Empty cells with notes that don't include the digit of the solution
+    pub incorrect_notes: CellSet,
+    /// Whether every cell is filled with the digit of the solution
+    pub is_solved: bool,
+}
+
+impl GameState {
+    /// Starts a game without entries or notes. Fails, if `clues` doesn't have a unique solution.
+    pub fn new(clues: Sudoku) -> Result<GameState, NoUniqueSolution> {
+        let solution = clues.solution().ok_or(NoUniqueSolution)?;
+        Ok(GameState {
+            clues,
+            solution,
+            entries: [0; 81],
+            notes: [Set::NONE; 81],
+            auto_remove_notes: true,
+        })
+    }
+
+    /// Sets whether entering a digit removes it from the notes of the cells in the same row,
+    /// column and block. Enabled by default.
+    pub fn set_auto_remove_notes(&mut self, auto_remove_notes: bool) {
+        self.auto_remove_notes = auto_remove_notes;
+    }
+
+    /// Returns the clues of the puzzle.
+    pub fn clues(&self) -> Sudoku {
+        self.clues
+    }
+
+    /// Returns the unique solution of the puzzle.
+    pub fn solution(&self) -> SolvedSudoku {
+        self.solution
+    }
+
+    /// Returns the player's entry in `cell`.
+    pub fn entry(&self, cell: Cell) -> Option<Digit> {
+        Digit::new_checked(self.entries[cell.as_index()])
+    }
+
+    /// Sets or clears the player's entry in `cell`.
+    /// Unless [disabled](Self::set_auto_remove_notes), the digit is removed from the notes of all
+    /// cells in the same houses.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `cell` contains a clue.
+    pub fn set_entry(&mut self, cell: Cell, digit: Option<Digit>) {
+        assert!(
+            self.clues.0[cell.as_index()] == 0,
+            "cell {} contains a clue",
+            cell.get()
+        );
+        self.entries[cell.as_index()] = digit.map_or(0, Digit::get);
+        if let (Some(digit), true) = (digit, self.auto_remove_notes) {
+            for neighbor in cell.neighbors() {
+                self.notes[neighbor.as_index()].remove(digit.as_set());
+            }
+        }
+    }
+
+    /// Returns the player's notes for `cell`.
+    pub fn notes(&self, cell: Cell) -> DigitSet {
+        self.notes[cell.as_index()]
+    }
+
+    /// Sets the player's notes for `cell`. Notes are kept, but ignored while the cell has an entry.
+    pub fn set_notes(&mut self, cell: Cell, notes: DigitSet) {
+        self.notes[cell.as_index()] = notes;
+    }
+
+    /// Sets the notes of every empty cell to the digits that don't conflict with the clues and
+    /// entries in its row, column and block, replacing the previous notes.
+    ///
+    /// Only direct conflicts are considered, so the notes don't give away anything the player
+    /// couldn't see at a glance. Cells without any possible digit get empty notes.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::{Cell, Digit, GameState};
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let mut game = GameState::new(sudoku).unwrap();
+    /// game.fill_notes();
+    /// assert_eq!(game.notes(Cell::new(0)).to_string(), "789");
+    ///
+    /// // entering a digit removes it from the notes of its neighbors
+    /// game.set_entry(Cell::new(1), Some(Digit::new(9)));
+    /// assert_eq!(game.notes(Cell::new(0)).to_string(), "78");
+    /// ```
+    pub fn fill_notes(&mut self) {
+        let position = self.position();
+        for cell in Cell::all() {
+            if position.0[cell.as_index()] != 0 {
+                continue;
+            }
+            let seen = cell
+                .neighbors()
+                .into_iter()
+                .filter_map(|neighbor| Digit::new_checked(position.0[neighbor.as_index()]))
+                .collect::<DigitSet>();
+            self.notes[cell.as_index()] = !seen;
+        }
+    }
+
+    /// Returns the grid as the player sees it, the clues and all entries.
+    pub fn position(&self) -> Sudoku {
+        self.merge_entries(|_, _| true)
+    }
+
+    /// Returns the clues and the correct entries.
+    pub fn effective_position(&self) -> Sudoku {
+        let solution = self.solution.to_bytes();
+        self.merge_entries(|cell, digit| solution[cell] == digit)
+    }
+
+    fn merge_entries(&self, mut keep: impl FnMut(usize, u8) -> bool) -> Sudoku {
+        let mut grid = self.clues.0;
+        for (cell, &digit) in self.entries.iter().enumerate() {
+            if digit != 0 && keep(cell, digit) {
+                grid[cell] = digit;
+            }
+        }
+        Sudoku(grid)
+    }
+
+    /// Returns the candidates of the effective position: the digit of every filled cell and the
+    /// correct notes of empty cells. Empty cells without notes or with incorrect notes may
+    /// contain any digit.
+    pub fn grid_state(&self) -> [CellState; 81] {
+        let position = self.effective_position();
+        let solution = self.solution.to_bytes();
+        let mut grid_state = [CellState::Candidates(Set::ALL); 81];
+        for (cell, state) in grid_state.iter_mut().enumerate() {
+            let notes = self.notes[cell];
+            *state = match Digit::new_checked(position.0[cell]) {
+                Some(digit) => CellState::Digit(digit),
+                None if notes.contains(Digit::new(solution[cell])) => CellState::Candidates(notes),
+                None => CellState::Candidates(Set::ALL),
+            };
+        }
+        grid_state
+    }
+
+    /// Returns the entries that differ from the unique solution, in cell order.
+    ///
+    /// Unlike checking for duplicate digits in a house, this finds every mistake, even if the
+    /// wrong digit doesn't conflict with any other digit yet.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::{Cell, Digit, GameState};
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let mut game = GameState::new(sudoku).unwrap();
+    /// // 7 is a candidate of the first cell, but the solution has an 8 there
+    /// game.set_entry(Cell::new(0), Some(Digit::new(7)));
+    /// assert!(!game.position().house_summary().has_duplicates());
+    ///
+    /// let wrong_entries = game.check_against_solution();
+    /// assert_eq!(wrong_entries.len(), 1);
+    /// assert_eq!(wrong_entries[0].cell, Cell::new(0));
+    /// assert_eq!(wrong_entries[0].solution, Digit::new(8));
+    /// ```
+    pub fn check_against_solution(&self) -> Vec<WrongEntry> {
+        let solution = self.solution.to_bytes();
+        self.entries
+            .iter()
+            .zip(solution.iter())
+            .zip(Cell::all())
+            .filter(|&((&entry, &solution), _)| entry != 0 && entry != solution)
+            .map(|((&entry, &solution), cell)| WrongEntry {
+                cell,
+                entry: Digit::new(entry),
+                solution: Digit::new(solution),
+            })
+            .collect()
+    }
+
+    /// Compares the entries and notes with the solution.
+    pub fn validate(&self) -> Validation {
+        let solution = self.solution.to_bytes();
+        let mut validation = Validation {
+            incorrect_entries: self
+                .check_against_solution()
+                .into_iter()
+                .map(|wrong_entry| wrong_entry.cell)
+                .collect(),
+            incorrect_notes: Set::NONE,
+            is_solved: true,
+        };
+        for cell in Cell::all() {
+            let idx = cell.as_index();
+            let digit = match self.clues.0[idx] {
+                0 => self.entries[idx],
+                clue => clue,
+            };
+            if digit != solution[idx] {
+                validation.is_solved = false;
+            }
+            let notes = self.notes[idx];
+            if digit == 0 && !notes.is_empty() && !notes.contains(Digit::new(solution[idx])) {
+                validation.incorrect_notes |= cell;
+            }
+        }
+        validation
+    }
+
+    /// Returns the next deduction from the effective position that can be made with `strategies`
+    /// as [`Deductions`] of length 1, or `None`, if the strategies are not enough or the puzzle is solved.
+    pub fn hint(&self, strategies: &[Strategy]) -> Option<Deductions> {
+        let (_, mut deductions) = match StrategySolver::from_game_state(self).solve(strategies) {
+            Ok(result) | Err(result) => result,
+        };
+        deductions.deductions.truncate(1);
+        (!deductions.is_empty()).then_some(deductions)
+    }
+}
+
+impl StrategySolver {
+    /// Construct a new StrategySolver for the effective position of a game in progress.
+    /// See [`GameState::grid_state`].
+    ///
+    /// As the solution is known to be unique, uniqueness based strategies are enabled.
+    pub fn from_game_state(game: &GameState) -> StrategySolver {
+        StrategySolver {
+            clues: Some(game.clues),
+            ..StrategySolver::from_grid_state(game.grid_state())
+        }
+        .assume_unique_solution(true)
+    }
+}
+
+// Snapshot of a game for (de)serialization.
+// The solution is solved again from the clues when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedGameState {
+    clues: Sudoku,
+    entries: Sudoku,
+    notes: Vec<DigitSet>,
+    auto_remove_notes: bool,
+}
+
+/// Serialized as a struct with the fields `clues`, `entries`, containing the player's entries
+/// and serialized like a [`Sudoku`], `notes`, containing the notes of the 81 cells, and
+/// `auto_remove_notes`, as set by [`GameState::set_auto_remove_notes`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for GameState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedGameState {
+            clues: self.clues,
+            entries: Sudoku(self.entries),
+            notes: self.notes.to_vec(),
+            auto_remove_notes: self.auto_remove_notes,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GameState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        use std::convert::TryInto;
+        let SerializedGameState {
+            clues,
+            entries,
+            notes,
+            auto_remove_notes,
+        } = SerializedGameState::deserialize(deserializer)?;
+        let game = GameState::new(clues).map_err(D::Error::custom)?;
+        if (0..81).any(|cell| clues.0[cell] != 0 && entries.0[cell] != 0) {
+            return Err(D::Error::custom("entries must not be in cells with a clue"));
+        }
+        let notes: [DigitSet; 81] = notes
+            .try_into()
+            .map_err(|_| D::Error::custom("notes must contain 81 cells"))?;
+        Ok(GameState {
+            entries: entries.0,
+            notes,
+            auto_remove_notes,
+            ..game
+        })
+    }
+}
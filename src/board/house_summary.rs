@@ -0,0 +1,105 @@
+use crate::bitset::{DigitSet, Set};
+use crate::board::{Digit, House, HouseType};
+use crate::Sudoku;
+
+/// The digits placed in and missing from a single house
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HouseDigits {
+    /// Digits that occur in the house at least once
+    pub placed: DigitSet,
+    /// Digits that don't occur in the house, the complement of `placed`
+    pub missing: DigitSet,
+    /// Digits that occur in the house more than once
+    pub duplicates: DigitSet,
+}
+
+impl HouseDigits {
+    const EMPTY: HouseDigits = HouseDigits {
+        placed: Set::NONE,
+        missing: Set::ALL,
+        duplicates: Set::NONE,
+    };
+
+    fn insert(&mut self, digit: Digit) {
+        if self.placed.contains(digit) {
+            self.duplicates |= digit;
+        }
+        self.placed |= digit;
+        self.missing.remove(digit.as_set());
+    }
+
+    /// Checks whether every digit occurs in the house.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Which digits are placed and missing in every row, column and block of a sudoku
+///
+/// Returned by [`Sudoku::house_summary`]. All arrays are indexed from 0, i.e. the first row is at index 0.
+///
+/// ```
+/// use sudoku::Sudoku;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let summary = sudoku.house_summary();
+/// assert_eq!(summary.rows[0].placed.to_string(), "236");
+/// assert_eq!(summary.rows[0].missing.to_string(), "145789");
+/// assert_eq!(summary.cols[0].placed.to_string(), "345");
+/// assert_eq!(summary.blocks[0].missing.to_string(), "2456789");
+/// assert!(!summary.has_duplicates());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HouseSummary {
+    /// Digits of each row
+    pub rows: [HouseDigits; 9],
+    /// Digits of each column
+    pub cols: [HouseDigits; 9],
+    /// Digits of each block
+    pub blocks: [HouseDigits; 9],
+}
+
+impl HouseSummary {
+    fn new(sudoku: &Sudoku) -> HouseSummary {
+        let mut summary = HouseSummary {
+            rows: [HouseDigits::EMPTY; 9],
+            cols: [HouseDigits::EMPTY; 9],
+            blocks: [HouseDigits::EMPTY; 9],
+        };
+        for (cell, digit) in sudoku.clues() {
+            let digit = Digit::new(digit);
+            summary.rows[cell.row().as_index()].insert(digit);
+            summary.cols[cell.col().as_index()].insert(digit);
+            summary.blocks[cell.block().as_index()].insert(digit);
+        }
+        summary
+    }
+
+    /// Returns the digits of `house`.
+    pub fn house(&self, house: House) -> HouseDigits {
+        match house.categorize() {
+            HouseType::Row(row) => self.rows[row.as_index()],
+            HouseType::Col(col) => self.cols[col.as_index()],
+            HouseType::Block(block) => self.blocks[block.as_index()],
+        }
+    }
+
+    /// Checks whether any house contains a digit more than once.
+    pub fn has_duplicates(&self) -> bool {
+        self.rows
+            .iter()
+            .chain(&self.cols)
+            .chain(&self.blocks)
+            .any(|digits| !digits.duplicates.is_empty())
+    }
+}
+
+impl Sudoku {
+    /// Returns the digits placed in and missing from every house.
+    /// See [`HouseSummary`].
+    pub fn house_summary(&self) -> HouseSummary {
+        HouseSummary::new(self)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::bitset::{CellSet, DigitSet, Set};
+use crate::board::positions::MiniLine;
+use crate::board::{Cell, Digit};
+use crate::helper::CellArray;
+use crate::strategy::strategies::locked_candidates::find_locked_candidates;
+use crate::Sudoku;
+
+/// Candidates removed by locked candidates, see [`Sudoku::reduce_locked_candidates`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LockedCandidatesElimination {
+    /// The locked digit
+    pub digit: Digit,
+    /// The miniline containing all candidates of `digit` in its block or line
+    pub miniline: MiniLine,
+    /// In the "pointing" variant, the candidates in the block are locked to the miniline and
+    /// are eliminated from the rest of the line. In the "claiming" variant, it's the other way around.
+    pub is_pointing: bool,
+    /// The cells from which `digit` was eliminated
+    pub eliminated: CellSet,
+}
+
+impl Sudoku {
+    /// Eliminates candidates with pointing and claiming locked candidates alone, without any other
+    /// strategy and without entering digits.
+    ///
+    /// The candidates of empty cells start out as the digits that don't conflict with any clue in the
+    /// same row, column or block. Locked candidates are applied until nothing changes anymore, as
+    /// eliminations can enable further ones. Returns the resulting candidates together with the
+    /// eliminations in the order they were made. Filled cells have their digit as the only candidate.
+    ///
+    /// This is a lightweight alternative to [`StrategySolver`](crate::strategy::StrategySolver)
+    /// for pointing and claiming hints.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Digit;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let (candidates, eliminations) = sudoku.reduce_locked_candidates();
+    ///
+    /// // the 5 of row 1 is locked in block 1
+    /// let first = eliminations[0];
+    /// assert_eq!(first.digit, Digit::new(5));
+    /// assert!(!first.is_pointing);
+    /// assert_eq!(first.eliminated.to_string(), "r3c2");
+    /// assert!(!candidates[19].contains(Digit::new(5)));
+    /// ```
+    pub fn reduce_locked_candidates(self) -> ([DigitSet; 81], Vec<LockedCandidatesElimination>) {
+        let mut candidates = [Set::ALL; 81];
+        for (cell, digit) in self.clues() {
+            let digit = Digit::new(digit);
+            candidates[cell.as_index()] = digit.as_set();
+            for neighbor in cell.neighbors() {
+                if self.0[neighbor.as_index()] == 0 {
+                    candidates[neighbor.as_index()].remove(digit.as_set());
+                }
+            }
+        }
+
+        let mut eliminations = vec![];
+        loop {
+            // only empty cells take part
+            let mut unsolved_candidates = CellArray([Set::NONE; 81]);
+            for cell in Cell::all().filter(|cell| self.0[cell.as_index()] == 0) {
+                unsolved_candidates[cell] = candidates[cell.as_index()];
+            }
+
+            let mut found = vec![];
+            // can't fail, contradictions are left for solvers to find
+            let _ = find_locked_candidates(
+                &unsolved_candidates,
+                false,
+                |miniline, digit, _, neighbors, is_pointing| {
+                    let eliminated = neighbors
+                        .iter()
+                        .flat_map(|neighbor| neighbor.cells())
+                        .filter(|&cell| unsolved_candidates[cell].contains(digit))
+                        .collect::<CellSet>();
+                    found.push(LockedCandidatesElimination {
+                        digit,
+                        miniline,
+                        is_pointing,
+                        eliminated,
+                    });
+                    !eliminated.is_empty()
+                },
+            );
+
+            let n_eliminations = eliminations.len();
+            for mut elimination in found {
+                // the same candidates may be found by both the pointing and the claiming variant
+                let digit = elimination.digit;
+                elimination.eliminated = elimination
+                    .eliminated
+                    .into_iter()
+                    .filter(|cell| candidates[cell.as_index()].contains(digit))
+                    .collect();
+                if elimination.eliminated.is_empty() {
+                    continue;
+                }
+                for cell in elimination.eliminated {
+                    candidates[cell.as_index()].remove(digit.as_set());
+                }
+                eliminations.push(elimination);
+            }
+            if eliminations.len() == n_eliminations {
+                break;
+            }
+        }
+        (candidates, eliminations)
+    }
+}
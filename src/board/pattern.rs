@@ -0,0 +1,121 @@
+use crate::bitset::{CellSet, Set};
+use crate::board::{Cell, Sudoku, Symmetry};
+use std::fmt;
+
+/// The layout of the clues of a sudoku, i.e. which cells are filled
+///
+/// Patterns are useful for analyzing and filtering puzzles by their clue layout.
+/// Two puzzles with the same pattern have their clues in the same cells,
+/// regardless of the digits.
+///
+/// ```
+/// use sudoku::{Sudoku, Symmetry};
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let pattern = sudoku.pattern();
+/// assert_eq!(pattern.n_clues(), 27);
+/// assert_eq!(pattern.symmetries(), [Symmetry::HalfRotation]);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Pattern(Set<Cell>);
+
+impl Pattern {
+    /// Returns the pattern of the clues of `sudoku`.
+    pub fn from_sudoku(sudoku: Sudoku) -> Pattern {
+        let cells = Cell::all()
+            .zip(sudoku.iter())
+            .filter(|(_, digit)| digit.is_some())
+            .fold(Set::NONE, |cells, (cell, _)| cells | cell);
+        Pattern(cells)
+    }
+
+    /// Constructs a pattern from an array where `true` denotes a filled cell.
+    pub fn from_bools(filled: [bool; 81]) -> Pattern {
+        let cells = Cell::all()
+            .zip(filled.iter())
+            .filter(|(_, &is_filled)| is_filled)
+            .fold(Set::NONE, |cells, (cell, _)| cells | cell);
+        Pattern(cells)
+    }
+
+    /// Returns an array where `true` denotes a filled cell.
+    pub fn to_bools(self) -> [bool; 81] {
+        let mut filled = [false; 81];
+        for cell in self.0 {
+            filled[cell.as_index()] = true;
+        }
+        filled
+    }
+
+    /// Returns the set of filled cells.
+    pub fn cells(self) -> CellSet {
+        self.0
+    }
+
+    /// Checks whether `cell` is filled.
+    pub fn is_filled(self, cell: Cell) -> bool {
+        self.0.contains(cell)
+    }
+
+    /// Returns the number of filled cells.
+    pub fn n_clues(self) -> u8 {
+        self.0.len()
+    }
+
+    /// Checks whether every cell filled in `self` is also filled in `other`.
+    pub fn is_subpattern_of(self, other: Pattern) -> bool {
+        other.0.contains(self.0)
+    }
+
+    /// Checks whether the pattern is invariant under `symmetry`.
+    /// This is always the case for [`Symmetry::None`].
+    pub fn has_symmetry(self, symmetry: Symmetry) -> bool {
+        Cell::all().all(|cell| {
+            let is_filled = self.is_filled(cell);
+            symmetry
+                .corresponding_cells(cell.as_index())
+                .into_iter()
+                .all(|other| self.is_filled(Cell::new(other as u8)) == is_filled)
+        })
+    }
+
+    /// Returns all symmetries of the pattern, excluding [`Symmetry::None`].
+    ///
+    /// Composite symmetries are listed together with the simpler symmetries they consist of.
+    /// For example, a pattern with [`Symmetry::QuarterRotation`] also has [`Symmetry::HalfRotation`].
+    pub fn symmetries(self) -> Vec<Symmetry> {
+        Symmetry::ALL_NONTRIVIAL
+            .iter()
+            .copied()
+            .filter(|&symmetry| self.has_symmetry(symmetry))
+            .collect()
+    }
+}
+
+/// Renders the pattern in the block format of [`Sudoku::display_block`]
+/// with `X` for filled and `.` for empty cells.
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for cell in Cell::all() {
+            #[rustfmt::skip]
+            #[allow(clippy::write_with_newline)]
+            match (cell.row().get(), cell.col().get()) {
+                (_, 3) | (_, 6) => write!(f, " ")?,    // seperate fields in columns
+                (3, 0) | (6, 0) => write!(f, "\n\n")?, // separate fields in rows
+                (_, 0)          => write!(f, "\n")?,   // separate lines not between fields
+                _ => {},
+            };
+            match self.is_filled(cell) {
+                true => write!(f, "X")?,
+                false => write!(f, ".")?,
+            };
+        }
+        Ok(())
+    }
+}
+
+impl From<Sudoku> for Pattern {
+    fn from(sudoku: Sudoku) -> Pattern {
+        Pattern::from_sudoku(sudoku)
+    }
+}
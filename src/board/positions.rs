@@ -3,7 +3,7 @@
 //! A sudoku consists of 81 cells, arranged into 9 rows, 9 columns and 9 blocks.
 #![allow(unused, missing_docs)]
 
-use crate::bitset::{Set, SetElement};
+use crate::bitset::{CellSet, Set, SetElement};
 use crate::board::Digit;
 use crate::consts::*;
 use crate::helper::Unsolvable;
@@ -212,9 +212,26 @@ macro_rules! define_types(
 );
 
 /// One of the 81 cells of the sudoku
+///
+/// With the `serde` feature, it is serialized as its index from 0 to 80.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Cell(u8);
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cell {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Cell {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cell = u8::deserialize(deserializer)?;
+        Cell::new_checked(cell).ok_or_else(|| serde::de::Error::custom("cell must be in the range of 0..81"))
+    }
+}
+
 /// Set of 9 cells in a horizontal line
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Row(u8);
@@ -387,7 +404,7 @@ macro_rules! into_cells {
         $(
             impl $name {
                 /// Returns a bitset of the cells belonging to this grouping.
-                pub fn cells(self) -> Set<Cell> {
+                pub fn cells(self) -> CellSet {
                     let $arg = self;
                     Set($code)
                 }
@@ -477,6 +494,8 @@ define_conversion_shortcuts!(
         Row, row;
         Col, col;
         Block, block;
+        Band, band;
+        Stack, stack;
         //Position<Row>, row_pos;
         //Position<Col>, col_pos;
         //Position<Block>, block_pos;
@@ -561,6 +580,74 @@ impl Chute {
     }
 }
 
+impl Band {
+    /// Returns the rows of this band from top to bottom.
+    pub fn rows(self) -> [Row; 3] {
+        [0, 1, 2].map(|i| Row::new(self.0 * 3 + i))
+    }
+
+    /// Returns the blocks of this band from left to right.
+    pub fn blocks(self) -> [Block; 3] {
+        [0, 1, 2].map(|i| Block::new(self.0 * 3 + i))
+    }
+
+    /// Returns an iterator over the 9 minirows of this band, from left to right, then top to bottom.
+    pub fn minirows(self) -> impl Iterator<Item = MiniRow> {
+        (self.0 * 9..self.0 * 9 + 9).map(MiniRow::new)
+    }
+}
+
+impl Stack {
+    /// Returns the columns of this stack from left to right.
+    pub fn cols(self) -> [Col; 3] {
+        [0, 1, 2].map(|i| Col::new(self.0 * 3 + i))
+    }
+
+    /// Returns the blocks of this stack from top to bottom.
+    pub fn blocks(self) -> [Block; 3] {
+        [0, 1, 2].map(|i| Block::new(i * 3 + self.0))
+    }
+
+    /// Returns an iterator over the 9 minicols of this stack, from top to bottom, then left to right.
+    pub fn minicols(self) -> impl Iterator<Item = MiniCol> {
+        (self.0 * 9..self.0 * 9 + 9).map(MiniCol::new)
+    }
+}
+
+impl MiniRow {
+    /// Returns the row containing this minirow.
+    pub fn row(self) -> Row {
+        Row::new(self.0 / 3)
+    }
+
+    /// Returns the block containing this minirow.
+    pub fn block(self) -> Block {
+        Block::new(self.0 / 9 * 3 + self.0 % 3)
+    }
+
+    /// Returns the band containing this minirow.
+    pub fn band(self) -> Band {
+        Band::new(self.0 / 9)
+    }
+}
+
+impl MiniCol {
+    /// Returns the column containing this minicol.
+    pub fn col(self) -> Col {
+        Col::new(self.0 / 3)
+    }
+
+    /// Returns the block containing this minicol.
+    pub fn block(self) -> Block {
+        Block::new(self.0 % 3 * 3 + self.0 / 9)
+    }
+
+    /// Returns the stack containing this minicol.
+    pub fn stack(self) -> Stack {
+        Stack::new(self.0 / 9)
+    }
+}
+
 impl Line {
     pub(crate) const ALL_ROWS: Set<Line> = Set(0o000_777);
     pub(crate) const ALL_COLS: Set<Line> = Set(0o777_000);
@@ -0,0 +1,159 @@
+use crate::bitset::DigitSet;
+use crate::board::{Cell, Digit, GameState};
+use crate::errors::ReplayError;
+use crate::Sudoku;
+use std::time::Duration;
+
+/// A player's action on a [`GameState`], as recorded in a [`Replay`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum Action {
+    /// Enters `digit` in `cell`, see [`GameState::set_entry`]
+    Place { cell: Cell, digit: Digit },
+    /// Clears the entry of `cell`
+    Erase { cell: Cell },
+    /// Replaces the notes of `cell`, see [`GameState::set_notes`]
+    Note { cell: Cell, notes: DigitSet },
+    /// Fills the notes of all empty cells, see [`GameState::fill_notes`]
+    FillNotes,
+    /// Enables or disables the automatic removal of notes, see [`GameState::set_auto_remove_notes`].
+    /// It doesn't change the game, so [`Undo`](Self::Undo) doesn't revert it.
+    SetAutoRemoveNotes(bool),
+    /// The player asked for a hint. It doesn't change the game.
+    Hint,
+    /// Reverts the last action that changed the game and wasn't undone yet.
+    /// Does nothing, if there is none.
+    Undo,
+}
+
+/// An [`Action`] and when it happened
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedAction {
+    /// Time since the start of the game
+    pub time: Duration,
+    /// The action
+    pub action: Action,
+}
+
+/// Log of the actions of a solve session that can be applied again
+///
+/// Apps record every action of the player together with the time since the start. The game can
+/// then be restored at any point, e.g. to show the player how they solved a puzzle or to analyze
+/// where players get stuck. With the `serde` feature, replays can be serialized.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Action, Cell, Digit, Replay};
+/// use std::time::Duration;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let mut replay = Replay::new(sudoku);
+/// let cell = Cell::new(0);
+/// replay.record(Duration::from_secs(5), Action::Place { cell, digit: Digit::new(7) });
+/// replay.record(Duration::from_secs(9), Action::Undo);
+/// replay.record(Duration::from_secs(12), Action::Place { cell, digit: Digit::new(8) });
+///
+/// let game = replay.game_state().unwrap();
+/// assert_eq!(game.entry(cell), Some(Digit::new(8)));
+/// assert_eq!(replay.game_state_at(Duration::from_secs(6)).unwrap().entry(cell), Some(Digit::new(7)));
+/// assert_eq!(replay.game_state_at(Duration::from_secs(10)).unwrap().entry(cell), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    clues: Sudoku,
+    actions: Vec<TimedAction>,
+}
+
+impl Replay {
+    /// Starts an empty log for a game of `clues`.
+    pub fn new(clues: Sudoku) -> Replay {
+        Replay {
+            clues,
+            actions: vec![],
+        }
+    }
+
+    /// Returns the clues of the game.
+    pub fn clues(&self) -> Sudoku {
+        self.clues
+    }
+
+    /// Returns the recorded actions in the order they were recorded.
+    pub fn actions(&self) -> &[TimedAction] {
+        &self.actions
+    }
+
+    /// Appends `action`, which happened `time` after the start of the game.
+    /// Actions are expected to be recorded in chronological order.
+    pub fn record(&mut self, time: Duration, action: Action) {
+        self.actions.push(TimedAction { time, action });
+    }
+
+    /// Returns the game after all actions.
+    pub fn game_state(&self) -> Result<GameState, ReplayError> {
+        self.game_state_after(self.actions.len())
+    }
+
+    /// Returns the game after all actions that happened up to and including `time`.
+    pub fn game_state_at(&self, time: Duration) -> Result<GameState, ReplayError> {
+        let n_actions = self
+            .actions
+            .iter()
+            .take_while(|action| action.time <= time)
+            .count();
+        self.game_state_after(n_actions)
+    }
+
+    /// Returns the game after the first `n_actions` actions.
+    ///
+    /// Fails, if the clues don't have a unique solution or one of the actions places or erases
+    /// a digit in a cell with a clue.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `n_actions` is larger than the number of actions.
+    pub fn game_state_after(&self, n_actions: usize) -> Result<GameState, ReplayError> {
+        let mut game = GameState::new(self.clues)?;
+        let mut auto_remove_notes = true;
+        let mut history = vec![];
+        for (idx, timed_action) in self.actions[..n_actions].iter().enumerate() {
+            let changes_clue = |cell: Cell| self.clues.0[cell.as_index()] != 0;
+            match timed_action.action {
+                Action::Place { cell, .. } | Action::Erase { cell } if changes_clue(cell) => {
+                    return Err(ReplayError::ChangesClue(idx));
+                }
+                Action::Place { cell, digit } => {
+                    history.push(game.clone());
+                    game.set_entry(cell, Some(digit));
+                }
+                Action::Erase { cell } => {
+                    history.push(game.clone());
+                    game.set_entry(cell, None);
+                }
+                Action::Note { cell, notes } => {
+                    history.push(game.clone());
+                    game.set_notes(cell, notes);
+                }
+                Action::FillNotes => {
+                    history.push(game.clone());
+                    game.fill_notes();
+                }
+                Action::SetAutoRemoveNotes(enabled) => {
+                    auto_remove_notes = enabled;
+                    game.set_auto_remove_notes(enabled);
+                }
+                Action::Hint => {}
+                Action::Undo => {
+                    if let Some(previous) = history.pop() {
+                        game = previous;
+                        game.set_auto_remove_notes(auto_remove_notes);
+                    }
+                }
+            }
+        }
+        Ok(game)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::board::canonicalization::find_canonical_sudoku_and_transformation;
+use crate::board::{Cell, Digit, Pattern, Sudoku};
+use crate::errors::NotSolved;
+use std::convert::TryFrom;
+use std::{fmt, ops};
+
+/// A completely filled sudoku that obeys all the rules
+///
+/// This is returned by the solving and generation methods that always produce full grids,
+/// like [`Sudoku::solution`] or [`Sudoku::generate_solved`].
+/// Because every cell is guaranteed to contain a digit, the accessors are infallible.
+/// It dereferences to [`Sudoku`] for all other methods.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Cell, Digit};
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let solution = sudoku.solution().unwrap();
+/// assert_eq!(solution.digit(Cell::new(3)), Digit::new(2));
+/// assert!(solution.is_solved());
+/// ```
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug, Hash)]
+pub struct SolvedSudoku(Sudoku);
+
+impl SolvedSudoku {
+    /// Wrap a sudoku that is known to be solved.
+    pub(crate) fn new_unchecked(sudoku: Sudoku) -> SolvedSudoku {
+        debug_assert!(sudoku.is_solved());
+        SolvedSudoku(sudoku)
+    }
+
+    /// Returns the digit in `cell`.
+    pub fn digit(&self, cell: Cell) -> Digit {
+        Digit::new(self.0 .0[cell.as_index()])
+    }
+
+    /// Returns an iterator over the digits of all cells, going left-to-right, top-to-bottom.
+    pub fn digits(&self) -> impl Iterator<Item = Digit> + '_ {
+        self.0 .0.iter().map(|&digit| Digit::new(digit))
+    }
+
+    /// Always returns `true`. This shadows the more expensive check of [`Sudoku::is_solved`].
+    pub fn is_solved(&self) -> bool {
+        true
+    }
+
+    /// Returns a puzzle with the digits of this grid in the cells filled in `pattern`.
+    /// All other cells are empty.
+    ///
+    /// This is the inverse of [`Sudoku::solution_and_pattern`] for uniquely solvable sudokus.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let (solution, pattern) = sudoku.solution_and_pattern().unwrap();
+    /// assert_eq!(solution.with_pattern(&pattern), sudoku);
+    /// ```
+    pub fn with_pattern(&self, pattern: &Pattern) -> Sudoku {
+        let mut bytes = self.0 .0;
+        for (cell, byte) in Cell::all().zip(bytes.iter_mut()) {
+            if !pattern.is_filled(cell) {
+                *byte = 0;
+            }
+        }
+        Sudoku(bytes)
+    }
+
+    /// Returns the number of automorphisms of this grid, i.e. the number of validity preserving
+    /// transformations that map it onto itself. This is the size of its automorphism group.
+    /// Every grid has at least 1 automorphism, the identity transformation.
+    ///
+    /// See [`Sudoku::shuffle`] for the list of transformations.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use std::convert::TryFrom;
+    ///
+    /// let line = "123456789456789123789123456231564897564897231897231564312645978645978312978312645";
+    /// let solution = sudoku::SolvedSudoku::try_from(Sudoku::from_str_line(line).unwrap()).unwrap();
+    /// assert_eq!(solution.n_automorphisms(), 648);
+    /// ```
+    pub fn n_automorphisms(&self) -> usize {
+        find_canonical_sudoku_and_transformation(self.0).2
+    }
+
+    /// Returns the number of distinct grids that this grid can be transformed into,
+    /// including itself.
+    ///
+    /// By the orbit-stabilizer theorem, this is the number of all transformations,
+    /// `2 * 6^8 * 9!`, divided by the [number of automorphisms](Self::n_automorphisms).
+    pub fn n_equivalent_grids(&self) -> u64 {
+        const N_TRANSFORMATIONS: u64 = 2 * 6u64.pow(8) * 362_880;
+        N_TRANSFORMATIONS / self.n_automorphisms() as u64
+    }
+
+    /// Checks whether `other` can be obtained from this grid by validity preserving transformations.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let solution = Sudoku::generate_solved();
+    /// assert!(solution.is_equivalent_to(&Sudoku::from(solution).shuffled().solution().unwrap()));
+    /// ```
+    pub fn is_equivalent_to(&self, other: &SolvedSudoku) -> bool {
+        find_canonical_sudoku_and_transformation(self.0).0
+            == find_canonical_sudoku_and_transformation(other.0).0
+    }
+
+    /// Returns the underlying sudoku.
+    pub fn into_sudoku(self) -> Sudoku {
+        self.0
+    }
+}
+
+impl ops::Deref for SolvedSudoku {
+    type Target = Sudoku;
+
+    fn deref(&self) -> &Sudoku {
+        &self.0
+    }
+}
+
+impl AsRef<Sudoku> for SolvedSudoku {
+    fn as_ref(&self) -> &Sudoku {
+        &self.0
+    }
+}
+
+impl From<SolvedSudoku> for Sudoku {
+    fn from(solved: SolvedSudoku) -> Sudoku {
+        solved.0
+    }
+}
+
+impl TryFrom<Sudoku> for SolvedSudoku {
+    type Error = NotSolved;
+
+    fn try_from(sudoku: Sudoku) -> Result<Self, Self::Error> {
+        match sudoku.is_solved() {
+            true => Ok(SolvedSudoku(sudoku)),
+            false => Err(NotSolved),
+        }
+    }
+}
+
+impl fmt::Display for SolvedSudoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Serialized in the same format as [`Sudoku`]. Deserialization fails for grids that aren't solved.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SolvedSudoku {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SolvedSudoku {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sudoku = Sudoku::deserialize(deserializer)?;
+        SolvedSudoku::try_from(sudoku).map_err(serde::de::Error::custom)
+    }
+}
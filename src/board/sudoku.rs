@@ -1,16 +1,27 @@
 use rand::seq::SliceRandom;
 use rand::Rng;
 
+use crate::bitset::{CellSet, Set};
+use crate::board::positions::{Band, Stack};
+use crate::board::{Candidate, Cell, Contradiction, Digit, House, Pattern, SolvedSudoku};
 use crate::consts::*;
-use crate::errors::{BlockParseError, InvalidEntry, LineParseError, NotEnoughRows};
+use crate::errors::{
+    BlockParseError, ConflictError, DuplicateClue, GenerationFailed, Interrupted, InvalidEntry,
+    LineParseError, NoUniqueSolution, NotEnoughRows, NotUnique, ParseAllError, ValidatedParseError,
+};
 use crate::generator::SudokuGenerator;
-use crate::solver::SudokuSolver;
+use crate::helper::fnv1a_64;
+use crate::solver::{Budget, SudokuSolver};
+use crate::strategy::{StrategySolver, TechniqueSet};
+use crate::{CancellationToken, SolverObserver};
 
 #[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
+    array,
     convert::{From, TryFrom},
     fmt, iter, ops, slice, str,
+    time::{Duration, Instant},
 };
 
 /// The 9x9 sudoku board represented as an array of length 81
@@ -89,7 +100,10 @@ impl<'de> Deserialize<'de> for Sudoku {
     }
 }
 
-pub type Iter<'a> = iter::Map<slice::Iter<'a, u8>, fn(&u8) -> Option<u8>>; // Iter over Sudoku cells
+/// Iterator over the cells of a [`Sudoku`], see [`Sudoku::iter`]
+pub type Iter<'a> = iter::Map<slice::Iter<'a, u8>, fn(&u8) -> Option<u8>>;
+/// Owning iterator over the cells of a [`Sudoku`]
+pub type IntoIter = iter::Map<array::IntoIter<u8, N_CELLS>, fn(u8) -> Option<u8>>;
 
 /// Position symmetries for clues of generated sudokus
 ///
@@ -121,8 +135,21 @@ pub enum Symmetry {
 }
 
 impl Symmetry {
+    // All symmetries except for `Symmetry::None`
+    pub(crate) const ALL_NONTRIVIAL: [Symmetry; 9] = [
+        Symmetry::VerticalMirror,
+        Symmetry::HorizontalMirror,
+        Symmetry::VerticalAndHorizontalMirror,
+        Symmetry::DiagonalMirror,
+        Symmetry::AntidiagonalMirror,
+        Symmetry::BidiagonalMirror,
+        Symmetry::QuarterRotation,
+        Symmetry::HalfRotation,
+        Symmetry::Dihedral,
+    ];
+
     // For a given cell, returns all cells that need to be either all filled or all empty to uphold the symmetry
-    fn corresponding_cells(self, cell: usize) -> Vec<usize> {
+    pub(crate) fn corresponding_cells(self, cell: usize) -> Vec<usize> {
         let row = cell / 9;
         let col = cell % 9;
         let mirr = |line| 8 - line; // mirror along the axis orthogonal to `line`
@@ -166,15 +193,50 @@ impl Symmetry {
     }
 }
 
+/// A puzzle with all clues of one digit erased, returned by [`Sudoku::remove_digit`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DigitRemoval {
+    /// The puzzle without the digit
+    pub sudoku: Sudoku,
+    /// Number of erased clues
+    pub n_removed: u8,
+    /// Whether the puzzle without the digit still has a unique solution
+    pub is_uniquely_solvable: bool,
+}
+
+/// Statistics about the generation of a single sudoku
+///
+/// Returned by [`Sudoku::generate_unique_report`] and
+/// [`GeneratorBuilder::generate_with_report`](crate::generator::GeneratorBuilder::generate_with_report).
+/// The counts include the attempts that were rejected by the requirements of the builder.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// Number of generated puzzles, including the returned one
+    pub n_attempts: usize,
+    /// Number of symmetry classes of cells whose clues were attempted to be removed
+    pub n_removal_attempts: usize,
+    /// Number of symmetry classes whose clues were removed
+    pub n_removals: usize,
+    /// Number of clues of the generated sudoku
+    pub n_clues: usize,
+    /// Number of times the solver checked a candidate puzzle for uniqueness.
+    /// With the `threads` feature, this includes speculative checks whose results were discarded.
+    pub n_uniqueness_checks: usize,
+    /// Number of nodes of the search tree visited over all uniqueness checks
+    pub n_search_nodes: u64,
+    /// Total time spent on the generation, including the solved grid
+    pub elapsed: Duration,
+}
+
 impl Sudoku {
     /// Generate a random, solved sudoku
-    pub fn generate_solved() -> Self {
+    pub fn generate_solved() -> SolvedSudoku {
         Sudoku::generate_solved_with_rng(&mut rand::thread_rng())
     }
 
     /// Generate a random, solved sudoku. All random numbers are drawn from the given random number generator `rng`.
-    pub fn generate_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        SudokuGenerator::generate_solved(rng)
+    pub fn generate_solved_with_rng<R: Rng + ?Sized>(rng: &mut R) -> SolvedSudoku {
+        SolvedSudoku::new_unchecked(SudokuGenerator::generate_solved(rng))
     }
 
     /// Generate a random, uniquely solvable sudoku with 180° rotational symmetry.
@@ -186,13 +248,170 @@ impl Sudoku {
         Sudoku::generate_with_symmetry(Symmetry::HalfRotation)
     }
 
+    /// Generate a random, uniquely solvable sudoku with 180° rotational symmetry
+    /// and report how much work it took.
+    ///
+    /// See [`generate`](Self::generate).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let (sudoku, report) = Sudoku::generate_unique_report();
+    /// assert!(sudoku.is_uniquely_solvable());
+    /// assert_eq!(report.n_clues, sudoku.n_clues() as usize);
+    /// assert!(report.n_uniqueness_checks >= report.n_removal_attempts);
+    /// ```
+    ///
+    /// For other requirements, use [`GeneratorBuilder::generate_with_report`](crate::generator::GeneratorBuilder::generate_with_report).
+    pub fn generate_unique_report() -> (Self, GenerationReport) {
+        SudokuGenerator::builder()
+            .generate_with_report()
+            .unwrap_or_else(|_| unreachable!("the first attempt satisfies the empty requirements"))
+    }
+
     /// Generate a random, uniquely solvable sudoku with the desired symmetry.
     ///
     /// The puzzles are minimal in that no cell can be removed without losing uniquess of the solution
     /// whilst also upholding the symmetry.
     /// Most puzzles generated by this are easy.
     pub fn generate_with_symmetry(symmetry: Symmetry) -> Self {
-        Sudoku::generate_with_symmetry_from(Sudoku::generate_solved(), symmetry)
+        Sudoku::generate_with_symmetry_from(Sudoku::generate_solved().into(), symmetry)
+    }
+
+    /// Generate random, uniquely solvable sudokus with 180° rotational symmetry until one is
+    /// accepted by the `filter`.
+    ///
+    /// See [`generate`](Self::generate). Filters that reject almost every puzzle make this
+    /// run for a long time.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::generate_filtered(|sudoku| !sudoku.has_trivial_first_move());
+    /// assert!(sudoku.is_uniquely_solvable());
+    /// assert!(!sudoku.has_trivial_first_move());
+    /// ```
+    pub fn generate_filtered<F: FnMut(&Sudoku) -> bool>(mut filter: F) -> Self {
+        loop {
+            let sudoku = Sudoku::generate();
+            if filter(&sudoku) {
+                return sudoku;
+            }
+        }
+    }
+
+    /// Generate a random sudoku with 180° rotational symmetry that can be solved using only
+    /// the strategies in `techniques`, e.g. [`TechniqueSet::SINGLES`] for beginners.
+    ///
+    /// The puzzles are minimal in that no cell can be removed without requiring other strategies
+    /// or losing uniqueness of the solution whilst also upholding the symmetry.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::TechniqueSet;
+    ///
+    /// let sudoku = Sudoku::generate_with_techniques(&TechniqueSet::SINGLES);
+    /// assert!(sudoku.is_uniquely_solvable());
+    /// ```
+    ///
+    /// For other symmetries or random number generators, use
+    /// [`GeneratorBuilder::with_allowed_techniques`](crate::generator::GeneratorBuilder::with_allowed_techniques).
+    pub fn generate_with_techniques(techniques: &TechniqueSet) -> Self {
+        Sudoku::generate_with_techniques_and_rng_from(
+            Sudoku::generate_solved().into(),
+            Symmetry::HalfRotation,
+            techniques,
+            &mut rand::thread_rng(),
+            &mut Budget::unlimited(),
+            &mut GenerationReport::default(),
+        )
+        .unwrap_or_else(|_| unreachable!("unlimited budget can't run out"))
+    }
+
+    // Removes clues of `sudoku` whilst upholding the `symmetry` and keeping it solvable with
+    // `techniques`. Returns `sudoku` as is, if it has multiple solutions or needs other techniques.
+    pub(crate) fn generate_with_techniques_and_rng_from<R: Rng + ?Sized>(
+        mut sudoku: Sudoku,
+        symmetry: Symmetry,
+        techniques: &TechniqueSet,
+        rng: &mut R,
+        budget: &mut Budget,
+        report: &mut GenerationReport,
+    ) -> Result<Self, Interrupted> {
+        let strategies = techniques.strategies();
+        // uniqueness is checked separately, in case the techniques assume it
+        let is_valid = |sudoku: Sudoku, budget: &mut Budget| -> Result<bool, Interrupted> {
+            match *techniques == TechniqueSet::SINGLES {
+                // singles only fill the grid, if the solution is unique
+                true => budget.poll().map(|()| sudoku.is_singles_solvable()),
+                false => Ok(sudoku.solutions_up_to_with_budget(2, budget)?.len() == 1
+                    && StrategySolver::from_sudoku(sudoku).solve(&strategies).is_ok()),
+            }
+        };
+        report.n_uniqueness_checks += 1;
+        if !is_valid(sudoku, budget)? {
+            return Ok(sudoku);
+        }
+        for cells in Sudoku::shuffled_symmetry_classes(symmetry, rng) {
+            let mut reduced = sudoku;
+            cells.iter().for_each(|&cell| reduced.0[cell] = 0);
+            if reduced == sudoku {
+                continue;
+            }
+            report.n_removal_attempts += 1;
+            report.n_uniqueness_checks += 1;
+            if is_valid(reduced, budget)? {
+                report.n_removals += 1;
+                sudoku = reduced;
+            }
+        }
+        Ok(sudoku)
+    }
+
+    /// Generate a random, uniquely solvable sudoku with its clues in the cells filled in `pattern`.
+    ///
+    /// See [`generate_with_pattern_and_rng`](Self::generate_with_pattern_and_rng).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let pattern = Sudoku::generate().pattern();
+    /// let sudoku = Sudoku::generate_with_pattern(&pattern, 100).unwrap();
+    /// assert_eq!(sudoku.pattern(), pattern);
+    /// assert!(sudoku.is_uniquely_solvable());
+    /// ```
+    pub fn generate_with_pattern(pattern: &Pattern, max_attempts: usize) -> Result<Self, GenerationFailed> {
+        Sudoku::generate_with_pattern_and_rng(pattern, max_attempts, &mut rand::thread_rng())
+    }
+
+    /// Generate a random, uniquely solvable sudoku with its clues in the cells filled in `pattern`.
+    /// All random numbers are drawn from the given random number generator `rng`.
+    ///
+    /// Unlike the other generation functions, this starts from the empty grid instead of a solved one.
+    /// The cells of the pattern are filled one after the other, each with the digit that leaves
+    /// the fewest solutions. If the finished puzzle doesn't have a unique solution, the next attempt
+    /// starts over, up to `max_attempts` times. This is known as the pattern game and succeeds on
+    /// sparse, symmetric patterns, for which removing clues from a solved grid rarely ends up
+    /// with exactly the desired layout.
+    ///
+    /// Patterns with fewer than 17 clues never have a unique solution.
+    pub fn generate_with_pattern_and_rng<R: Rng + ?Sized>(
+        pattern: &Pattern,
+        max_attempts: usize,
+        rng: &mut R,
+    ) -> Result<Self, GenerationFailed> {
+        let max_attempts = match pattern.n_clues() >= 17 {
+            true => max_attempts,
+            false => 0,
+        };
+        for _ in 0..max_attempts {
+            if let Some(sudoku) = SudokuGenerator::pattern_game(pattern, rng) {
+                return Ok(sudoku);
+            }
+        }
+        Err(GenerationFailed {
+            n_attempts: max_attempts,
+        })
     }
 
     /// Generate a random, uniqely solvable sudoku
@@ -225,17 +444,137 @@ impl Sudoku {
     ///
     /// If the source `sudoku` is invalid or has multiple solutions, it will be returned as is.
     pub fn generate_with_symmetry_and_rng_from<R: Rng + ?Sized>(
-        mut sudoku: Sudoku,
+        sudoku: Sudoku,
         symmetry: Symmetry,
         rng: &mut R,
     ) -> Self {
+        Sudoku::_generate_with_symmetry_and_rng_from(
+            sudoku,
+            symmetry,
+            rng,
+            &mut Budget::unlimited(),
+            &mut GenerationReport::default(),
+        )
+        .unwrap_or_else(|_| unreachable!("unlimited budget can't run out"))
+    }
+
+    /// Generate `n` distinct, minimal and uniquely solvable sudokus that all have the given `solution`.
+    ///
+    /// This is faster than calling [`generate_from`](Self::generate_from) `n` times, because
+    /// the unavoidable sets of the solution are only searched once. Clues that are the last one
+    /// of such a set can be kept without checking the uniqueness of the solution.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let solution = Sudoku::generate_solved();
+    /// let sudokus = Sudoku::generate_batch_from(solution, 3);
+    /// assert_eq!(sudokus.len(), 3);
+    /// for sudoku in &sudokus {
+    ///     assert_eq!(sudoku.solution(), Some(solution));
+    /// }
+    /// assert_ne!(sudokus[0], sudokus[1]);
+    /// ```
+    pub fn generate_batch_from(solution: SolvedSudoku, n: usize) -> Vec<Self> {
+        Sudoku::generate_batch_with_rng_from(solution, n, &mut rand::thread_rng())
+    }
+
+    /// Like [`generate_batch_from`](Self::generate_batch_from), but draws all random numbers
+    /// from the given random number generator `rng`.
+    pub fn generate_batch_with_rng_from<R: Rng + ?Sized>(
+        solution: SolvedSudoku,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<Self> {
+        let solution = solution.into_sudoku();
+        let unavoidable_sets = SudokuGenerator::unavoidable_rectangles(&solution);
+        let mut cell_order = (0..N_CELLS).collect::<Vec<_>>();
+
+        let mut sudokus = Vec::with_capacity(n);
+        while sudokus.len() < n {
+            cell_order.shuffle(rng);
+            let classes = cell_order.iter().map(|&cell| vec![cell]).collect::<Vec<_>>();
+            let sudoku = Sudoku::remove_clues(
+                solution,
+                &classes,
+                &unavoidable_sets,
+                &mut Budget::unlimited(),
+                &mut GenerationReport::default(),
+            )
+            .unwrap_or_else(|_| unreachable!("unlimited budget can't run out"));
+            if !sudokus.contains(&sudoku) {
+                sudokus.push(sudoku);
+            }
+        }
+        sudokus
+    }
+
+    /// Generate `n` random, uniquely solvable sudokus with 180° rotational symmetry
+    /// on all available threads, like calling [`generate`](Self::generate) `n` times.
+    ///
+    /// Each thread sets up its random number generator once and generates its share of the
+    /// sudokus in one go. The sudokus are not guaranteed to be distinct, although duplicates
+    /// are exceedingly unlikely.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudokus = Sudoku::generate_unique_batch(4);
+    /// assert_eq!(sudokus.len(), 4);
+    /// assert!(sudokus.iter().all(|sudoku| sudoku.is_uniquely_solvable()));
+    /// ```
+    pub fn generate_unique_batch(n: usize) -> Vec<Self> {
+        let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        // the first `n % n_threads` threads generate one more sudoku
+        let shares = (0..n_threads)
+            .map(|thread| n / n_threads + usize::from(thread < n % n_threads))
+            .filter(|&share| share != 0)
+            .collect::<Vec<_>>();
+        crate::batch::parallel_map(&shares, |&share| {
+            let mut rng = rand::thread_rng();
+            (0..share)
+                .map(|_| {
+                    let solved = Sudoku::generate_solved_with_rng(&mut rng);
+                    Sudoku::generate_with_symmetry_and_rng_from(
+                        solved.into(),
+                        Symmetry::HalfRotation,
+                        &mut rng,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    pub(crate) fn _generate_with_symmetry_and_rng_from<R: Rng + ?Sized>(
+        sudoku: Sudoku,
+        symmetry: Symmetry,
+        rng: &mut R,
+        budget: &mut Budget,
+        report: &mut GenerationReport,
+    ) -> Result<Self, Interrupted> {
         // this function is following
         // the approach outlined here: https://stackoverflow.com/a/7280517
         //
         // delete numbers from a filled sudoku cells in random order
         // after each deletion check for unique solvability
         // and backtrack on error
+        let classes = Sudoku::shuffled_symmetry_classes(symmetry, rng);
+
+        #[cfg(feature = "threads")]
+        {
+            let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            if n_threads > 1 {
+                return Sudoku::remove_clues_in_parallel(sudoku, &classes, budget, report, n_threads);
+            }
+        }
+        Sudoku::remove_clues(sudoku, &classes, &[], budget, report)
+    }
 
+    // Returns the classes of cells that are equivalent under `symmetry` in random order
+    fn shuffled_symmetry_classes<R: Rng + ?Sized>(symmetry: Symmetry, rng: &mut R) -> Vec<Vec<usize>> {
         // generate random order
         let mut cell_order = [0; N_CELLS];
         cell_order
@@ -248,24 +587,172 @@ impl Sudoku {
         // If we've already visited one cell in a symmetry class, we can skip ahead
         // when encountering one of the other ones.
         let mut cell_visited = [false; 81];
-
-        // remove cell content if possible without destroying uniqueness of solution
+        let mut classes = vec![];
         for &cell in &cell_order[..] {
             let cells = symmetry.corresponding_cells(cell);
             if cell_visited[cells[0]] {
                 continue;
             }
-            let mut sudoku_tmp = sudoku;
-            for cell in cells {
+            for &cell in &cells {
                 cell_visited[cell] = true;
-                sudoku_tmp.0[cell] = 0;
             }
-            if sudoku_tmp.is_uniquely_solvable() {
-                sudoku = sudoku_tmp;
+            classes.push(cells);
+        }
+        classes
+    }
+
+    // Removes the clues of each symmetry class in `classes` in order,
+    // if the sudoku stays uniquely solvable without them.
+    // Classes whose removal would empty one of the `unavoidable_sets` are kept without a check.
+    fn remove_clues(
+        mut sudoku: Sudoku,
+        classes: &[Vec<usize>],
+        unavoidable_sets: &[[usize; 4]],
+        budget: &mut Budget,
+        report: &mut GenerationReport,
+    ) -> Result<Self, Interrupted> {
+        use crate::solver::IncrementalSolver;
+
+        // Each uniqueness check needs the clues of all undecided classes and of all
+        // classes that were kept. The undecided clues are pushed onto the solver stack
+        // in reverse order so the next class to check is always on top. The clues of kept
+        // classes are inserted below them once, when the class is kept.
+        let original = sudoku;
+        let clues_of = |cells: &[usize]| {
+            cells
+                .iter()
+                .filter(|&&cell| original.0[cell] != 0)
+                .map(|&cell| (cell as u8, original.0[cell]))
+                .collect::<Vec<_>>()
+        };
+        let mut solver = IncrementalSolver::new();
+        for cells in classes.iter().skip(1).rev() {
+            for (cell, digit) in clues_of(cells) {
+                let _ = solver.push_clue(Cell::new(cell), Digit::new(digit));
             }
         }
 
-        sudoku
+        // remove cell content if possible without destroying uniqueness of solution
+        for (i, cells) in classes.iter().enumerate() {
+            let class_clues = clues_of(cells);
+            if i != 0 {
+                class_clues.iter().for_each(|_| solver.pop_clue());
+            }
+            let keep = |solver: &mut IncrementalSolver| {
+                for &(cell, digit) in &class_clues {
+                    solver.insert_base_clue(cell, digit);
+                }
+            };
+
+            report.n_removal_attempts += 1;
+            let empties_unavoidable_set = unavoidable_sets.iter().any(|set| {
+                set.iter().any(|cell| cells.contains(cell))
+                    && set
+                        .iter()
+                        .all(|cell| cells.contains(cell) || sudoku.0[*cell] == 0)
+            });
+            if empties_unavoidable_set {
+                keep(&mut solver);
+                continue;
+            }
+
+            let is_uniquely_solvable = solver.solutions_count_up_to_with_budget(2, budget) == 1;
+            report.n_uniqueness_checks += 1;
+            budget.result()?;
+
+            match is_uniquely_solvable {
+                true => {
+                    cells.iter().for_each(|&cell| sudoku.0[cell] = 0);
+                    report.n_removals += 1;
+                }
+                false => keep(&mut solver),
+            }
+        }
+
+        Ok(sudoku)
+    }
+
+    // Same as `remove_clues`, but checks the next `n_threads` classes on as many threads at once.
+    //
+    // The check of each class in a window assumes that all classes before it in the window
+    // are removed. Removing a class can only turn a unique solution into multiple ones, never
+    // the reverse, so a successful check remains valid if some of the earlier classes are kept.
+    // A failed check is only valid if all earlier classes in the window were actually removed.
+    // Otherwise, the next window starts at that class.
+    // This decides every class exactly like `remove_clues` does.
+    #[cfg(any(feature = "threads", test))]
+    fn remove_clues_in_parallel(
+        mut sudoku: Sudoku,
+        classes: &[Vec<usize>],
+        budget: &mut Budget,
+        report: &mut GenerationReport,
+        n_threads: usize,
+    ) -> Result<Self, Interrupted> {
+        use std::sync::{mpsc, Mutex};
+
+        let (job_sender, job_receiver) = mpsc::channel::<(usize, Sudoku, Budget)>();
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job_receiver = Mutex::new(job_receiver);
+        std::thread::scope(|scope| {
+            for _ in 0..n_threads {
+                let job_receiver = &job_receiver;
+                let result_sender = result_sender.clone();
+                scope.spawn(move || loop {
+                    // release the lock before processing the job
+                    let job = job_receiver.lock().unwrap().recv();
+                    let (idx, grid, mut budget) = match job {
+                        Ok(job) => job,
+                        Err(_) => break,
+                    };
+                    let is_uniquely_solvable = match SudokuSolver::from_sudoku(grid) {
+                        Ok(solver) => solver.solutions_count_up_to_with_budget(2, &mut budget) == 1,
+                        Err(_) => false,
+                    };
+                    let _ = result_sender.send((idx, is_uniquely_solvable, budget));
+                });
+            }
+            drop(result_sender);
+
+            let mut next = 0;
+            while next < classes.len() {
+                let window = next..classes.len().min(next + n_threads);
+                let mut grid = sudoku;
+                for idx in window.clone() {
+                    classes[idx].iter().for_each(|&cell| grid.0[cell] = 0);
+                    let _ = job_sender.send((idx, grid, budget.fork()));
+                }
+                report.n_uniqueness_checks += window.len();
+
+                let mut is_uniquely_solvable = vec![false; window.len()];
+                for _ in window.clone() {
+                    let (idx, result, fork) = result_receiver.recv().expect("worker thread panicked");
+                    is_uniquely_solvable[idx - window.start] = result;
+                    budget.join(fork);
+                }
+                budget.result()?;
+
+                let mut any_kept = false;
+                next = window.end;
+                for idx in window.clone() {
+                    match is_uniquely_solvable[idx - window.start] {
+                        true => {
+                            classes[idx].iter().for_each(|&cell| sudoku.0[cell] = 0);
+                            report.n_removals += 1;
+                        }
+                        false if !any_kept => any_kept = true,
+                        // the check assumed that an earlier, kept class was removed
+                        false => {
+                            next = idx;
+                            break;
+                        }
+                    }
+                    report.n_removal_attempts += 1;
+                }
+            }
+            // lets the workers finish
+            drop(job_sender);
+            Ok(sudoku)
+        })
     }
 
     /// Creates a sudoku from a byte slice.
@@ -273,7 +760,7 @@ impl Sudoku {
     /// The slice must be of length 81.
     pub fn from_bytes_slice(bytes: &[u8]) -> Result<Sudoku, crate::errors::FromBytesSliceError> {
         use std::convert::TryInto;
-        Self::_from_bytes(
+        Self::from_bytes(
             bytes
                 .try_into()
                 .map_err(|_| crate::errors::FromBytesSliceError::WrongLength(bytes.len()))?,
@@ -281,17 +768,65 @@ impl Sudoku {
         .map_err(crate::errors::FromBytesSliceError::FromBytesError)
     }
 
-    /// Creates a sudoku from a byte array.
-    /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
-    pub fn from_bytes(bytes: SudokuArray) -> Result<Sudoku, crate::errors::FromBytesError> {
-        Self::_from_bytes(&bytes)
+    /// Creates a sudoku from the entries of its cells, going from left to right, top to bottom.
+    /// Empty cells are denoted by `None`, clues by `Some(1)` to `Some(9)`.
+    /// This is the inverse of [`iter`](Self::iter).
+    ///
+    /// The iterator must yield exactly 81 entries. It is consumed completely, even if it's too long.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// // remove all 1s
+    /// let filtered = Sudoku::try_from_iter(sudoku.iter().map(|entry| entry.filter(|&digit| digit != 1))).unwrap();
+    /// assert_eq!(filtered.n_clues(), sudoku.n_clues() - 3);
+    ///
+    /// assert!(Sudoku::try_from_iter(sudoku.into_iter().take(80)).is_err());
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = Option<u8>>>(
+        iter: I,
+    ) -> Result<Sudoku, crate::errors::FromBytesSliceError> {
+        let mut bytes = [0; N_CELLS];
+        let mut len = 0;
+        let mut is_valid = true;
+        for entry in iter {
+            if let Some(byte) = bytes.get_mut(len) {
+                *byte = match entry {
+                    None => 0,
+                    Some(digit @ 1..=9) => digit,
+                    Some(_) => {
+                        is_valid = false;
+                        0
+                    }
+                };
+            }
+            len += 1;
+        }
+        if len != N_CELLS {
+            return Err(crate::errors::FromBytesSliceError::WrongLength(len));
+        }
+        match is_valid {
+            true => Ok(Sudoku(bytes)),
+            false => Err(crate::errors::FromBytesSliceError::FromBytesError(
+                crate::errors::FromBytesError(()),
+            )),
+        }
     }
 
-    fn _from_bytes(bytes: &SudokuArray) -> Result<Sudoku, crate::errors::FromBytesError> {
-        match bytes.iter().fold(true, |valid, &byte| valid & (byte <= 9)) {
-            true => Ok(Sudoku(*bytes)),
-            false => Err(crate::errors::FromBytesError(())),
+    /// Creates a sudoku from a byte array.
+    /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
+    ///
+    /// This is a `const fn`, so sudokus can be constructed at compile time.
+    pub const fn from_bytes(bytes: SudokuArray) -> Result<Sudoku, crate::errors::FromBytesError> {
+        let mut cell = 0;
+        while cell < N_CELLS {
+            if bytes[cell] > 9 {
+                return Err(crate::errors::FromBytesError(()));
+            }
+            cell += 1;
         }
+        Ok(Sudoku(bytes))
     }
 
     /// Reads a sudoku in the line format.
@@ -311,37 +846,52 @@ impl Sudoku {
     /// ```
     ///
     /// Stops parsing after the first sudoku
-    pub fn from_str_line(s: &str) -> Result<Sudoku, LineParseError> {
+    ///
+    /// This is a `const fn`, so sudokus can be parsed at compile time:
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// const SUDOKU: Sudoku = match Sudoku::from_str_line(
+    ///     "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    /// ) {
+    ///     Ok(sudoku) => sudoku,
+    ///     Err(_) => panic!("invalid sudoku"),
+    /// };
+    /// assert_eq!(SUDOKU.n_clues(), 27);
+    /// ```
+    pub const fn from_str_line(s: &str) -> Result<Sudoku, LineParseError> {
         let chars = s.as_bytes();
-        if let Ok(sudoku) = Sudoku::_from_str_line_fast_path(chars) {
+        if let Some(sudoku) = Sudoku::_from_str_line_fast_path(chars) {
             return Ok(sudoku);
         }
 
         let mut grid = [0; N_CELLS];
         let mut i = 0;
-        for (cell, &ch) in grid.iter_mut().zip(chars) {
-            match ch {
-                b'_' | b'.' => *cell = 0,
-                b'0'..=b'9' => *cell = ch - b'0',
+        while i < N_CELLS && i < chars.len() {
+            let ch = chars[i];
+            grid[i] = match ch {
+                b'_' | b'.' => 0,
+                b'0'..=b'9' => ch - b'0',
                 // space ends sudoku before grid is filled
-                b' ' | b'\t' => return Err(LineParseError::NotEnoughCells(i)),
+                b' ' | b'\t' => return Err(LineParseError::NotEnoughCells(i as u8)),
                 _ => {
                     return Err(LineParseError::InvalidEntry(InvalidEntry {
-                        cell: i,
-                        ch: s[i as usize..].chars().next().unwrap(),
+                        cell: i as u8,
+                        ch: decode_char_at(chars, i),
                     }))
                 }
-            }
+            };
             i += 1;
         }
 
-        if i != N_CELLS as u8 {
-            return Err(LineParseError::NotEnoughCells(i));
+        if i != N_CELLS {
+            return Err(LineParseError::NotEnoughCells(i as u8));
         }
 
         // if more than 81 elements, sudoku must be delimited
-        if let Some(&ch) = chars.get(N_CELLS) {
-            match ch {
+        if chars.len() > N_CELLS {
+            match chars[N_CELLS] {
                 // delimiters, end of sudoku
                 b'\t' | b' ' | b'\r' | b'\n' | b';' | b',' => (),
                 // valid cell entry => too long
@@ -360,31 +910,31 @@ impl Sudoku {
     /// Checks only once the end if the assumption was valid.
     //
     // FIXME: there is some duplication among this and the full parser.
-    fn _from_str_line_fast_path(chars: &[u8]) -> Result<Sudoku, ()> {
-        if chars.len() < 81 {
-            return Err(());
+    const fn _from_str_line_fast_path(chars: &[u8]) -> Option<Sudoku> {
+        if chars.len() < N_CELLS {
+            return None;
         }
         // map valid ascii bytes into the range 0..=9
         // for from_bytes()
         let mut grid = [0; N_CELLS];
-        grid.copy_from_slice(&chars[..81]);
-        for cell in &mut grid[..] {
-            *cell = cell.wrapping_sub(b'0');
-            if *cell == b'_' - b'0' {
-                *cell = 0;
-            }
-            if *cell == b'.'.wrapping_sub(b'0') {
-                *cell = 0;
-            }
+        let mut cell = 0;
+        while cell < N_CELLS {
+            grid[cell] = match chars[cell] {
+                b'_' | b'.' => 0,
+                ch => ch.wrapping_sub(b'0'),
+            };
+            cell += 1;
         }
 
-        let valid_ending = chars.get(81).map_or(true, |ch| {
-            matches!(ch, b'\t' | b' ' | b'\r' | b'\n' | b';' | b',')
-        });
+        let valid_ending =
+            chars.len() == N_CELLS || matches!(chars[N_CELLS], b'\t' | b' ' | b'\r' | b'\n' | b';' | b',');
 
         match valid_ending {
-            true => Sudoku::from_bytes(grid).map_err(drop),
-            false => Err(()),
+            true => match Sudoku::from_bytes(grid) {
+                Ok(sudoku) => Some(sudoku),
+                Err(_) => None,
+            },
+            false => None,
         }
     }
 
@@ -525,16 +1075,90 @@ impl Sudoku {
         Ok(Sudoku(grid))
     }
 
+    /// Like [`from_str_line`](Self::from_str_line), but also rejects sudokus with two clues of the
+    /// same digit in one house. Reports the first such pair of cells.
+    ///
+    /// Sudokus with such clues have no solution, but otherwise that is only found out when solving them.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::errors::ValidatedParseError;
+    ///
+    /// // two 5s in the first row
+    /// let line = "5...5............................................................................";
+    /// match Sudoku::from_str_line_validated(line) {
+    ///     Err(ValidatedParseError::DuplicateClue(duplicate)) => {
+    ///         assert_eq!(duplicate.cells, [0, 4]);
+    ///         assert_eq!(duplicate.digit, 5);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn from_str_line_validated(s: &str) -> Result<Sudoku, ValidatedParseError<LineParseError>> {
+        let sudoku = Sudoku::from_str_line(s).map_err(ValidatedParseError::Parse)?;
+        sudoku.validate_clues()?;
+        Ok(sudoku)
+    }
+
+    /// Like [`from_str_block`](Self::from_str_block), but also rejects sudokus with two clues of the
+    /// same digit in one house. See [`from_str_line_validated`](Self::from_str_line_validated).
+    pub fn from_str_block_validated(s: &str) -> Result<Sudoku, ValidatedParseError<BlockParseError>> {
+        let sudoku = Sudoku::from_str_block(s).map_err(ValidatedParseError::Parse)?;
+        sudoku.validate_clues()?;
+        Ok(sudoku)
+    }
+
+    fn validate_clues<E>(self) -> Result<(), ValidatedParseError<E>> {
+        match self.duplicate_digit() {
+            Some(Contradiction::DuplicateDigit { digit, cells, .. }) => {
+                Err(ValidatedParseError::DuplicateClue(DuplicateClue {
+                    cells: [cells[0].get(), cells[1].get()],
+                    digit: digit.get(),
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Reads a sudoku in a variety of block formats with very few constraints.
     ///
     /// '_', '.' and '0' are treated as empty cells. '1' to '9' as clues.
     /// Each line needs to have 9 valid cells.
     /// Lines that don't contain 9 valid entries are ignored.
+    /// All other characters are skipped, so any delimiters can be used.
+    ///
+    /// To parse puzzles copied from websites without cleaning them up first,
+    /// the full-width forms of these characters and the middle dot '·' are accepted as well.
+    /// Box-drawing characters, dashes, non-breaking spaces and byte order marks are skipped
+    /// like all other delimiters.
     ///
     /// Stops parsing after the first sudoku.
     ///
     /// Due to the lax format rules, the only failure that can occur
     /// is that there are not enough rows.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let scraped = "\u{feff}┌───────┬───────┬───────┐
+    /// │ ·  ·  · │ ２ ·  · │ ·  ６ ３ │
+    /// │ ３ ·  · │ ·  ·  ５ │ ４ ·  １ │
+    /// │ ·  ·  １ │ ·  ·  ３ │ ９ ８ ·  │
+    /// ├–––––––┼–––––––┼–––––––┤
+    /// │ ·  ·  · │ ·  ·  · │ ·  ９ ·  │
+    /// │ ·  ·  · │ ５ ３ ８ │ ·  ·  ·  │
+    /// │ ·  ３ · │ ·  ·  · │ ·  ·  ·  │
+    /// ├–––––––┼–––––––┼–––––––┤
+    /// │ ·  ２ ６ │ ３ ·  · │ ５ ·  ·  │
+    /// │ ５ ·  ３ │ ７ ·  · │ ·  ·  ８ │
+    /// │ ４ ７ · │ ·  ·  １ │ ·  ·  ·  │
+    /// └───────┴───────┴───────┘";
+    /// let sudoku = Sudoku::from_str_block_permissive(scraped).unwrap();
+    /// assert_eq!(
+    ///     sudoku,
+    ///     Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap(),
+    /// );
+    /// ```
     pub fn from_str_block_permissive(s: &str) -> Result<Sudoku, NotEnoughRows> {
         let mut grid = [0; N_CELLS];
 
@@ -543,13 +1167,14 @@ impl Sudoku {
             let mut row_vals = [0; 9];
             let mut nums_in_row = 0;
             for ch in line.chars() {
-                if ['.', '_'].contains(&ch) {
-                    row_vals[nums_in_row] = 0;
-                    nums_in_row += 1;
-                } else if ch.is_ascii_digit() {
-                    row_vals[nums_in_row] = ch as u8 - b'0';
-                    nums_in_row += 1;
-                }
+                let entry = match ch {
+                    '.' | '_' | '·' | '．' | '＿' => 0,
+                    '0'..='9' => ch as u8 - b'0',
+                    '０'..='９' => (ch as u32 - '０' as u32) as u8,
+                    _ => continue,
+                };
+                row_vals[nums_in_row] = entry;
+                nums_in_row += 1;
                 // full sudoko row, write to grid
                 // ignore anything after in same row
                 if nums_in_row == 9 {
@@ -565,55 +1190,507 @@ impl Sudoku {
         Err(NotEnoughRows(valid_rows as u8))
     }
 
+    /// Reads a sudoku in line format that may be interrupted by whitespace anywhere, e.g. because it
+    /// was wrapped at 40 or 60 columns.
+    ///
+    /// All whitespace is removed before the remaining 81 characters are parsed like in
+    /// [`from_str_line`](Self::from_str_line). As the whitespace is gone, comments are not supported
+    /// and trailing characters are reported as [`TooManyCells`](LineParseError::TooManyCells)
+    /// or [`MissingCommentDelimiter`](LineParseError::MissingCommentDelimiter).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let wrapped = "...2...633....54.1..1..398........9....5
+    /// 38....3........263..5..5.37....847...1...";
+    /// assert!(Sudoku::from_str_line(wrapped).is_err());
+    /// assert_eq!(
+    ///     Sudoku::from_str_flexible(wrapped),
+    ///     Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1..."),
+    /// );
+    /// assert!(Sudoku::from_str_flexible("...2 ...6 33").is_err());
+    /// ```
+    pub fn from_str_flexible(s: &str) -> Result<Sudoku, LineParseError> {
+        let line = s.chars().filter(|ch| !ch.is_whitespace()).collect::<String>();
+        Sudoku::from_str_line(&line)
+    }
+
+    /// Reads all sudokus of `s`, which may be in line or block format, even mixed.
+    ///
+    /// A sudoku in line format takes up one line, see [`from_str_line`](Self::from_str_line).
+    /// A sudoku in block format takes up 9 lines or, with field delimiters, 11 lines,
+    /// see [`from_str_block`](Self::from_str_block). Blank lines between sudokus are skipped.
+    /// A line starts a sudoku in line format, if the text before its first whitespace is longer
+    /// than a row of the block format.
+    ///
+    /// An invalid sudoku is reported and parsing continues with the line after it.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let input = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1... first
+    /// ..3x
+    ///
+    /// ___|2__|_63
+    /// 3__|__5|4_1
+    /// __1|__3|98_
+    /// ---+---+---
+    /// ___|___|_9_
+    /// ___|538|___
+    /// _3_|___|___
+    /// ---+---+---
+    /// _26|3__|5__
+    /// 5_3|7__|__8
+    /// 47_|__1|___
+    /// ";
+    /// let sudokus = Sudoku::parse_all(input).collect::<Vec<_>>();
+    /// assert_eq!(sudokus.len(), 3);
+    /// assert!(sudokus[1].is_err());
+    /// assert_eq!(sudokus[0], sudokus[2]);
+    /// ```
+    pub fn parse_all(s: &str) -> impl Iterator<Item = Result<Sudoku, ParseAllError>> + '_ {
+        // longest row of the block format
+        const MAX_ROW_LEN: usize = 11;
+
+        let mut lines = s.lines().enumerate().peekable();
+        iter::from_fn(move || {
+            let (idx, line) = lines.by_ref().find(|(_, line)| !line.trim().is_empty())?;
+            let first_token_len = line
+                .split_whitespace()
+                .next()
+                .map_or(0, |token| token.chars().count());
+            if first_token_len > MAX_ROW_LEN {
+                return Some(
+                    Sudoku::from_str_line(line.trim_start()).map_err(|err| ParseAllError::Line(idx, err)),
+                );
+            }
+
+            let n_lines = match line.chars().nth(3) {
+                Some('|') => 11,
+                _ => 9,
+            };
+            let mut block = String::from(line);
+            while block.lines().count() < n_lines {
+                match lines.next_if(|(_, line)| !line.trim().is_empty()) {
+                    Some((_, line)) => {
+                        block.push('\n');
+                        block.push_str(line);
+                    }
+                    None => break,
+                }
+            }
+            Some(Sudoku::from_str_block(&block).map_err(|err| ParseAllError::Block(idx, err)))
+        })
+    }
+
     /// Find a solution to the sudoku. When a solution is found, it immediately stops searching and can therefore not guarantee uniqueness.
     /// If there is a unique solution, this will find it in, on average, half the time as [`Sudoku::solution`].
     /// Return `None` if no solution exists.
-    pub fn some_solution(self) -> Option<Sudoku> {
+    pub fn some_solution(self) -> Option<SolvedSudoku> {
         let mut buf = [[0; N_CELLS]];
         match self.solutions_up_to_buffer(&mut buf, 1) == 1 {
-            true => Some(Sudoku(buf[0])),
+            true => Some(SolvedSudoku::new_unchecked(Sudoku(buf[0]))),
             false => None,
         }
     }
 
-    /// Solve sudoku and return solution if solution is unique.
-    pub fn solution(self) -> Option<Sudoku> {
-        // without at least 8 digits present, sudoku has multiple solutions
+    /// Quickly checks for properties that rule out a unique solution without solving the sudoku.
+    ///
+    /// A sudoku with less than 17 clues or with clues of less than 8 different digits always has
+    /// multiple solutions or none. Passing these checks does not imply that the solution is unique.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::errors::NotUnique;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(sudoku.validate_puzzle(), Ok(()));
+    ///
+    /// let sparse = Sudoku::from_str_line("...2...633....54.1..1..398........9..............................................").unwrap();
+    /// assert_eq!(sparse.validate_puzzle(), Err(NotUnique::TooFewClues(12)));
+    /// ```
+    pub fn validate_puzzle(self) -> Result<(), NotUnique> {
         // bitmask
         let mut nums_contained: u16 = 0;
-        // same with less than 17 clues
         let mut n_clues = 0;
         self.iter().flatten().for_each(|num| {
             nums_contained |= 1 << num;
             n_clues += 1;
         });
-        if n_clues < 17 || nums_contained.count_ones() < 8 {
+        if n_clues < 17 {
+            return Err(NotUnique::TooFewClues(n_clues));
+        }
+        // with 2 digits missing, they can be swapped in every solution
+        let n_digits = nums_contained.count_ones() as u8;
+        if n_digits < 8 {
+            return Err(NotUnique::TooFewDigits(n_digits));
+        }
+        Ok(())
+    }
+
+    /// Solve sudoku and return solution if solution is unique.
+    pub fn solution(self) -> Option<SolvedSudoku> {
+        if self.validate_puzzle().is_err() {
             return None;
-        };
+        }
 
         let mut solution = [[0; N_CELLS]];
         let n_solutions = self.solutions_up_to_buffer(&mut solution, 2);
         match n_solutions == 1 {
-            true => Some(Sudoku(solution[0])),
+            true => Some(SolvedSudoku::new_unchecked(Sudoku(solution[0]))),
             false => None,
         }
     }
 
-    /// Counts number of solutions to sudoku up to `limit`.
-    /// This solves the sudoku but does not return the solutions which allows for slightly faster execution.
-    pub fn solutions_count_up_to(self, limit: usize) -> usize {
-        SudokuSolver::from_sudoku(self)
-            .ok()
-            .map_or(0, |solver| solver.solutions_count_up_to(limit))
+    /// Checks whether both sudokus are uniquely solvable and have the same solution.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let solution = sudoku.solution().unwrap();
+    /// let other = Sudoku::generate_from(solution.into());
+    /// assert!(sudoku.same_solution_as(&other));
+    /// assert!(!sudoku.same_solution_as(&Sudoku::generate()));
+    /// ```
+    pub fn same_solution_as(&self, other: &Sudoku) -> bool {
+        // clues in the same cell must agree, which is much cheaper to check than solving
+        let conflicting_clues = self.iter().zip(other.iter()).any(|pair| match pair {
+            (Some(digit), Some(other_digit)) => digit != other_digit,
+            _ => false,
+        });
+        if conflicting_clues {
+            return false;
+        }
+        match (self.solution(), other.solution()) {
+            (Some(solution), Some(other_solution)) => solution == other_solution,
+            _ => false,
+        }
     }
 
-    /// Checks whether sudoku has one and only one solution.
-    /// This solves the sudoku but does not return the solution which allows for slightly faster execution.
-    pub fn is_uniquely_solvable(self) -> bool {
-        self.solutions_count_up_to(2) == 1
+    /// Checks candidate eliminations made by a player against the unique solution.
+    ///
+    /// An elimination is unsound, if it removes the digit of the solution from its cell.
+    /// Returns the first unsound elimination in the order of `eliminations`, or `None`, if all of
+    /// them are sound. Fails, if `self` doesn't have a unique solution.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Candidate;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// // the solution has an 8 in the first cell
+    /// let eliminations = [Candidate::new(0, 7), Candidate::new(0, 8), Candidate::new(1, 1)];
+    /// assert_eq!(sudoku.check_eliminations(&eliminations), Ok(Some(Candidate::new(0, 8))));
+    /// assert_eq!(sudoku.check_eliminations(&eliminations[..1]), Ok(None));
+    /// ```
+    pub fn check_eliminations(
+        &self,
+        eliminations: &[Candidate],
+    ) -> Result<Option<Candidate>, NoUniqueSolution> {
+        let solution = self.solution().ok_or(NoUniqueSolution)?.to_bytes();
+        Ok(eliminations
+            .iter()
+            .copied()
+            .find(|candidate| solution[candidate.cell.as_index()] == candidate.digit.get()))
     }
 
-    /// Solve sudoku and return the first `limit` solutions it finds. If less solutions exist, return only those. Return `None` if no solution exists.
+    /// Returns a sudoku with the clues of both `self` and `other`.
+    ///
+    /// Returns an error for the first cell that contains different digits in both sudokus.
+    /// Clues that are in different cells aren't checked against each other, so the result
+    /// may break the rules even if both inputs don't.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::errors::ConflictError;
+    ///
+    /// let mut bytes = [0; 81];
+    /// bytes[0] = 1;
+    /// let first = Sudoku::from_bytes(bytes).unwrap();
+    /// bytes[1] = 2;
+    /// let second = Sudoku::from_bytes(bytes).unwrap();
+    /// assert_eq!(first.merge(&second), Ok(second));
+    ///
+    /// bytes[0] = 3;
+    /// let third = Sudoku::from_bytes(bytes).unwrap();
+    /// assert_eq!(first.merge(&third), Err(ConflictError { cell: 0, digit: 1, other_digit: 3 }));
+    /// ```
+    pub fn merge(&self, other: &Sudoku) -> Result<Sudoku, ConflictError> {
+        let mut merged = self.0;
+        for (cell, (digit, &other_digit)) in merged.iter_mut().zip(other.0.iter()).enumerate() {
+            match (*digit, other_digit) {
+                (_, 0) => {}
+                (0, _) => *digit = other_digit,
+                (digit, other_digit) if digit != other_digit => {
+                    return Err(ConflictError {
+                        cell: cell as u8,
+                        digit,
+                        other_digit,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(Sudoku(merged))
+    }
+
+    /// Returns a copy of the sudoku with the given `cells` emptied.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Cell;
+    ///
+    /// let sudoku = Sudoku::generate_solved().into_sudoku();
+    /// let puzzle = sudoku.erase_cells(&[Cell::new(0), Cell::new(80)]);
+    /// assert_eq!(puzzle.n_clues(), 79);
+    /// assert_eq!(puzzle.iter().next(), Some(None));
+    /// ```
+    pub fn erase_cells(&self, cells: &[Cell]) -> Sudoku {
+        let mut sudoku = *self;
+        for cell in cells {
+            sudoku.0[cell.as_index()] = 0;
+        }
+        sudoku
+    }
+
+    /// Returns a copy of the sudoku with `n` randomly chosen clues emptied.
+    /// If the sudoku has no more than `n` clues, all of them are removed.
+    ///
+    /// The cells are drawn from the given random number generator `rng`.
+    /// This does not check whether the result is still uniquely solvable.
+    pub fn erase_random_cells<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Sudoku {
+        let clues = Cell::all()
+            .filter(|cell| self.0[cell.as_index()] != 0)
+            .collect::<Vec<_>>();
+        let erased = clues.choose_multiple(rng, n).cloned().collect::<Vec<_>>();
+        self.erase_cells(&erased)
+    }
+
+    /// Returns a sudoku with only the clues that `self` and `other` agree on.
+    ///
+    /// Cells that are empty in either sudoku or that contain different digits are left empty.
+    pub fn intersect(&self, other: &Sudoku) -> Sudoku {
+        let mut intersection = self.0;
+        for (digit, &other_digit) in intersection.iter_mut().zip(other.0.iter()) {
+            if *digit != other_digit {
+                *digit = 0;
+            }
+        }
+        Sudoku(intersection)
+    }
+
+    /// Erases every clue of `digit`, the "vanishing digit" transformation.
+    ///
+    /// The result contains the new puzzle and whether it is still uniquely solvable.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Digit;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let removal = sudoku.remove_digit(Digit::new(3));
+    /// assert_eq!(removal.n_removed, 7);
+    /// assert_eq!(removal.sudoku.n_clues(), sudoku.n_clues() - 7);
+    /// assert_eq!(removal.is_uniquely_solvable, removal.sudoku.is_uniquely_solvable());
+    /// ```
+    pub fn remove_digit(self, digit: Digit) -> DigitRemoval {
+        let mut sudoku = self;
+        let mut n_removed = 0;
+        for clue in sudoku.0.iter_mut().filter(|clue| **clue == digit.get()) {
+            *clue = 0;
+            n_removed += 1;
+        }
+        DigitRemoval {
+            sudoku,
+            n_removed,
+            is_uniquely_solvable: sudoku.is_uniquely_solvable(),
+        }
+    }
+
+    /// Returns a 64 bit hash of the solution, if the sudoku is uniquely solvable.
+    ///
+    /// Puzzles with the same solution have the same fingerprint, so it can be used to group
+    /// puzzles in large collections by their solution without storing the full grid.
+    /// The fingerprint is guaranteed to stay the same across versions of this library.
+    /// Different solutions map to different fingerprints with overwhelming probability,
+    /// but unlike with [`same_solution_as`](Self::same_solution_as), collisions are possible.
+    pub fn solution_fingerprint(self) -> Option<u64> {
+        let solution = self.solution()?;
+        Some(fnv1a_64(&solution.to_bytes()))
+    }
+
+    /// Returns a 64 bit hash of the [canonical form](Self::canonicalized), if the sudoku is
+    /// uniquely solvable.
+    ///
+    /// Equivalent puzzles have the same fingerprint, so it can be used to recognize puzzles that
+    /// were only relabeled, rotated or otherwise shuffled. See [`canonicalized`](Self::canonicalized)
+    /// for the rare exceptions. Different puzzles map to different fingerprints with overwhelming
+    /// probability, but collisions are possible.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(sudoku.canonical_fingerprint(), sudoku.shuffled().canonical_fingerprint());
+    /// ```
+    pub fn canonical_fingerprint(self) -> Option<u64> {
+        let (canonical, _) = self.canonicalized()?;
+        Some(fnv1a_64(&canonical.to_bytes()))
+    }
+
+    /// Counts number of solutions to sudoku up to `limit`.
+    /// This solves the sudoku but does not return the solutions which allows for slightly faster execution.
+    /// It never allocates.
+    pub fn solutions_count_up_to(self, limit: usize) -> usize {
+        SudokuSolver::from_sudoku(self)
+            .ok()
+            .map_or(0, |solver| solver.solutions_count_up_to(limit))
+    }
+
+    /// Checks whether sudoku has one and only one solution.
+    /// This solves the sudoku but does not return the solution which allows for slightly faster execution.
+    pub fn is_uniquely_solvable(self) -> bool {
+        self.solutions_count_up_to(2) == 1
+    }
+
+    /// Checks whether `candidate` can be eliminated, i.e. whether no solution of the sudoku
+    /// contains its digit in its cell.
+    ///
+    /// This uses the exact solver, so it can vet manual deductions regardless of the technique
+    /// that was used to find them. A sudoku without any solution allows all eliminations.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::{Candidate, Cell, Digit};
+    ///
+    /// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+    /// let solution = sudoku.solution().unwrap();
+    /// let cell = Cell::new(0);
+    /// let solution_digit = Digit::new(solution.to_bytes()[0]);
+    ///
+    /// assert!(!sudoku.is_valid_elimination(Candidate { cell, digit: solution_digit }));
+    /// for digit in (1..=9).map(Digit::new).filter(|&digit| digit != solution_digit) {
+    ///     assert!(sudoku.is_valid_elimination(Candidate { cell, digit }));
+    /// }
+    /// ```
+    pub fn is_valid_elimination(self, candidate: Candidate) -> bool {
+        let mut bytes = self.0;
+        let entry = &mut bytes[candidate.cell.as_index()];
+        match *entry {
+            0 => {
+                *entry = candidate.digit.get();
+                Sudoku(bytes).solutions_count_up_to(1) == 0
+            }
+            // a clue can't be eliminated, any other digit in its cell can
+            clue => clue != candidate.digit.get(),
+        }
+    }
+
+    /// Checks whether the sudoku can be solved by naked and hidden singles alone.
+    ///
+    /// Equivalent to `sudoku.solvable_with(&TechniqueSet::SINGLES)`, but works directly on the
+    /// candidate bitsets instead of running the [`StrategySolver`]. That makes it cheap enough
+    /// to filter large numbers of puzzles, e.g. while generating easy puzzles.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+    /// assert!(sudoku.is_singles_solvable());
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// assert!(!empty.is_singles_solvable());
+    /// ```
+    pub fn is_singles_solvable(self) -> bool {
+        if self.duplicate_digit().is_some() {
+            return false;
+        }
+        match self.propagate_singles() {
+            Ok((grid, _)) => !grid.contains(&0),
+            Err(_) => false,
+        }
+    }
+
+    /// Checks whether the sudoku can be solved using only the strategies in `techniques`.
+    ///
+    /// Whether a technique is required can be checked by removing it from the set.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::{Strategy, TechniqueSet};
+    ///
+    /// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+    /// assert!(sudoku.solvable_with(&TechniqueSet::SINGLES));
+    /// // hidden singles alone suffice
+    /// assert!(sudoku.solvable_with(&TechniqueSet::SINGLES.without(Strategy::NakedSingles)));
+    /// ```
+    pub fn solvable_with(self, techniques: &TechniqueSet) -> bool {
+        if *techniques == TechniqueSet::SINGLES {
+            return self.is_singles_solvable();
+        }
+        StrategySolver::from_sudoku(self)
+            .solve(&techniques.strategies())
+            .is_ok()
+    }
+
+    /// Counts, for each clue, the number of solutions up to `limit` that the sudoku has
+    /// when only that clue is removed. Cells without a clue are `None`.
+    ///
+    /// This shows how much each clue contributes to the uniqueness of the solution.
+    /// In a uniquely solvable sudoku, clues with a count of 1 are redundant and
+    /// a sudoku is minimal if every clue has a count of at least 2.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku_line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    /// let sudoku = Sudoku::from_str_line(sudoku_line).unwrap();
+    ///
+    /// let counts = sudoku.clue_removal_solutions_count_up_to(100);
+    /// assert_eq!(counts[0], None);
+    /// assert!(counts[3].is_some());
+    /// ```
+    pub fn clue_removal_solutions_count_up_to(self, limit: usize) -> [Option<usize>; N_CELLS] {
+        let clues = self
+            .iter()
+            .enumerate()
+            .filter_map(|(cell, digit)| Some((cell as u8, digit?)))
+            .collect::<Vec<_>>();
+        let mut counts = vec![0; clues.len()];
+        SudokuSolver::empty().solutions_count_up_to_without_each(&clues, limit, &mut counts);
+
+        let mut cell_counts = [None; N_CELLS];
+        for (&(cell, _), count) in clues.iter().zip(counts) {
+            cell_counts[cell as usize] = Some(count);
+        }
+        cell_counts
+    }
+
+    /// Counts, for each digit, the number of solutions up to `limit` that have the digit in `cell`.
+    /// The count of digit `d` is at index `d - 1`.
+    ///
+    /// For an empty cell, this is the number of solutions the sudoku has when the digit is added
+    /// as a clue. It shows how constrained the cell is, e.g. for heatmaps in puzzle editors.
+    /// The clues are only inserted once and shared by all digits.
+    /// For a cell with a clue, only the clue's digit has solutions.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Cell;
+    ///
+    /// // without the 3 in row 6, column 2
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538.............263..5..5.37....847...1...").unwrap();
+    /// let counts = sudoku.solution_counts_per_candidate(Cell::new(46), 100);
+    /// assert_eq!(counts, [0, 0, 1, 0, 0, 0, 0, 1, 0]);
+    /// assert_eq!(counts.iter().sum::<usize>(), sudoku.solutions_count_up_to(100));
+    /// ```
+    pub fn solution_counts_per_candidate(self, cell: Cell, limit: usize) -> [usize; 9] {
+        SudokuSolver::from_sudoku(self).map_or([0; 9], |solver| {
+            solver.solutions_count_up_to_with_each_digit(cell.get(), limit)
+        })
+    }
+
+    /// Solve sudoku and return the first `limit` solutions it finds. If less solutions exist, return only those. Return `None` if no solution exists.
     /// No specific ordering of solutions is promised. It can change across versions.
     pub fn solutions_up_to(self, limit: usize) -> Vec<Sudoku> {
         SudokuSolver::from_sudoku(self)
@@ -621,10 +1698,224 @@ impl Sudoku {
             .map_or(vec![], |solver| solver.solutions_up_to(limit))
     }
 
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but each cell may only contain
+    /// the digits in its entry of `candidates`.
+    ///
+    /// This allows solving sudokus whose pencil marks have already been narrowed down,
+    /// like partially solved puzzles or sukakus, which are given only by their candidates.
+    /// Returns no solutions, if a clue is not among the candidates of its cell.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::bitset::Set;
+    /// use sudoku::board::Digit;
+    ///
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// let mut candidates = [Set::ALL; 81];
+    /// candidates[0] = Digit::new(5).as_set();
+    ///
+    /// let solutions = empty.solutions_up_to_with_candidates(&candidates, 10);
+    /// assert_eq!(solutions.len(), 10);
+    /// assert!(solutions.iter().all(|solution| solution.to_bytes()[0] == 5));
+    /// ```
+    pub fn solutions_up_to_with_candidates(
+        self,
+        candidates: &[Set<Digit>; N_CELLS],
+        limit: usize,
+    ) -> Vec<Sudoku> {
+        SudokuSolver::from_sudoku_with_candidates(self, candidates)
+            .ok()
+            .map_or(vec![], |solver| solver.solutions_up_to(limit))
+    }
+
+    /// Like [`solutions_count_up_to`](Self::solutions_count_up_to), but each cell may only contain
+    /// the digits in its entry of `candidates`.
+    /// See [`solutions_up_to_with_candidates`](Self::solutions_up_to_with_candidates).
+    pub fn solutions_count_up_to_with_candidates(
+        self,
+        candidates: &[Set<Digit>; N_CELLS],
+        limit: usize,
+    ) -> usize {
+        SudokuSolver::from_sudoku_with_candidates(self, candidates)
+            .ok()
+            .map_or(0, |solver| solver.solutions_count_up_to(limit))
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but returns the first `limit` solutions in
+    /// lexicographic order, i.e. ordered by their [`to_bytes`](Self::to_bytes) representation.
+    /// Unlike for `solutions_up_to`, the result is guaranteed to stay the same across versions.
+    ///
+    /// This is slower than `solutions_up_to`, because the search has to branch on the cells in order.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// let solutions = empty.sorted_solutions_up_to(2);
+    /// assert!(solutions[0] < solutions[1]);
+    /// assert_eq!(
+    ///     solutions[0].to_str_line().to_string(),
+    ///     "123456789456789123789123456214365897365897214897214365531642978642978531978531642",
+    /// );
+    /// ```
+    pub fn sorted_solutions_up_to(self, limit: usize) -> Vec<Sudoku> {
+        let mut solutions = vec![];
+        self.collect_sorted_solutions(0, limit, &mut solutions);
+        solutions
+    }
+
+    // Branches on the digits of the empty cells, starting with the first, in ascending order.
+    // Cells before `first_empty` must be filled.
+    fn collect_sorted_solutions(mut self, first_empty: usize, limit: usize, solutions: &mut Vec<Sudoku>) {
+        if solutions.len() == limit {
+            return;
+        }
+        match *self.solutions_up_to(2) {
+            [] => return,
+            [solution] => return solutions.push(solution),
+            _ => {}
+        }
+
+        let cell = (first_empty..N_CELLS)
+            .find(|&cell| self.0[cell] == 0)
+            .expect("filled grid with multiple solutions");
+        for digit in 1..=9 {
+            self.0[cell] = digit;
+            self.collect_sorted_solutions(cell + 1, limit, solutions);
+        }
+    }
+
+    /// Returns `n` distinct solutions, drawn approximately uniformly at random from all solutions.
+    /// If less than `n` solutions exist, all of them are returned, in random order.
+    ///
+    /// Unlike [`solutions_up_to`](Self::solutions_up_to), which returns the first solutions the search reaches,
+    /// the samples are spread out over the whole solution space. For grids with few solutions,
+    /// the sample is exactly uniform. Otherwise, each solution is found by an independent search with random guesses,
+    /// which favors solutions that require fewer guesses to reach.
+    /// All random numbers are drawn from the given random number generator `rng`.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// let samples = empty.sample_solutions(5, &mut rand::thread_rng());
+    /// assert_eq!(samples.len(), 5);
+    /// assert!(samples.iter().all(|solution| solution.is_solved()));
+    /// ```
+    pub fn sample_solutions<R: Rng + ?Sized>(self, n: usize, rng: &mut R) -> Vec<Sudoku> {
+        // With few solutions, enumerating them is fast and gives an exact sample.
+        // Otherwise, there are plenty of solutions for the random search below to find distinct ones.
+        let enumeration_limit = n.saturating_mul(4);
+        let mut solutions = self.solutions_up_to(enumeration_limit);
+        if solutions.len() < enumeration_limit {
+            solutions.shuffle(rng);
+            solutions.truncate(n);
+            return solutions;
+        }
+
+        let mut samples = Vec::with_capacity(n);
+        let mut seen = std::collections::HashSet::new();
+        while samples.len() < n {
+            let solution = SudokuGenerator::random_solution(self, rng).expect("sudoku has a solution");
+            if seen.insert(solution) {
+                samples.push(solution);
+            }
+        }
+        samples
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but gives up once `deadline` has passed.
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let deadline = Instant::now() + Duration::from_secs(10);
+    /// assert_eq!(sudoku.solutions_up_to_with_deadline(2, deadline).unwrap().len(), 1);
+    /// ```
+    pub fn solutions_up_to_with_deadline(
+        self,
+        limit: usize,
+        deadline: Instant,
+    ) -> Result<Vec<Sudoku>, Interrupted> {
+        self.solutions_up_to_with_budget(limit, &mut Budget::unlimited().with_deadline(deadline))
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but gives up when the `token` is cancelled.
+    pub fn solutions_up_to_cancellable(
+        self,
+        limit: usize,
+        token: &CancellationToken,
+    ) -> Result<Vec<Sudoku>, Interrupted> {
+        self.solutions_up_to_with_budget(limit, &mut Budget::unlimited().with_token(token))
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but gives up after visiting `max_nodes` nodes of
+    /// the search tree. On success, the solutions are returned together with the number of nodes visited.
+    ///
+    /// Unlike a deadline, this limit is deterministic and independent of the machine.
+    /// Each guess the solver has to make counts as one node, plus one for the initial grid.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::errors::Interrupted;
+    ///
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// let (solutions, nodes) = empty.solutions_up_to_limited(1, 1000).unwrap();
+    /// assert_eq!(solutions.len(), 1);
+    /// assert!(nodes <= 1000);
+    ///
+    /// assert_eq!(empty.solutions_up_to_limited(1000, 10), Err(Interrupted::NodeLimitReached));
+    /// ```
+    pub fn solutions_up_to_limited(
+        self,
+        limit: usize,
+        max_nodes: u64,
+    ) -> Result<(Vec<Sudoku>, u64), Interrupted> {
+        let mut budget = Budget::unlimited().with_max_nodes(max_nodes);
+        let solutions = self.solutions_up_to_with_budget(limit, &mut budget)?;
+        Ok((solutions, budget.nodes()))
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but reports every deduced digit, guess
+    /// and backtrack of the search to `observer`. See [`SolverObserver`] for details.
+    ///
+    /// If the clues conflict with each other, no events are reported.
+    pub fn solutions_up_to_observed<O: SolverObserver>(self, limit: usize, observer: &mut O) -> Vec<Sudoku> {
+        SudokuSolver::from_sudoku(self).ok().map_or(vec![], |solver| {
+            solver.solutions_up_to_observed(limit, &mut Budget::unlimited(), observer)
+        })
+    }
+
+    fn solutions_up_to_with_budget(
+        self,
+        limit: usize,
+        budget: &mut Budget,
+    ) -> Result<Vec<Sudoku>, Interrupted> {
+        let solutions = SudokuSolver::from_sudoku(self)
+            .ok()
+            .map_or(vec![], |solver| solver.solutions_up_to_with_budget(limit, budget));
+        budget.result().map(|()| solutions)
+    }
+
+    /// Like [`solutions_up_to`](Self::solutions_up_to), but stores the solutions in `buffer`
+    /// instead of a new `Vec`, replacing its previous contents.
+    ///
+    /// This only allocates, if the `buffer` has to grow.
+    /// See [`SolveBuffer`](crate::SolveBuffer).
+    pub fn solutions_up_to_reusing(self, buffer: &mut crate::SolveBuffer, limit: usize) -> &[Sudoku] {
+        match SudokuSolver::from_sudoku(self) {
+            Ok(solver) => solver.solutions_up_to_reusing(buffer, limit),
+            Err(_) => buffer.clear(),
+        }
+        buffer.solutions()
+    }
+
     /// Counts number of solutions to sudoku up to `limit` and writes any solution found into `target`
     /// up to its capacity. Additional solutions will be counted but not saved.
     /// No specific ordering of solutions is promised. It can change across versions.
-    /// This is primarily meant for C FFI.
+    /// This is primarily meant for C FFI. It never allocates.
     pub fn solutions_up_to_buffer(self, target: &mut [SudokuArray], limit: usize) -> usize {
         SudokuSolver::from_sudoku(self)
             .ok()
@@ -657,6 +1948,55 @@ impl Sudoku {
         house_digits == HouseArray([Set::ALL; N_HOUSES])
     }
 
+    /// Returns the pattern of the clues, i.e. which cells are filled.
+    pub fn pattern(&self) -> Pattern {
+        Pattern::from_sudoku(*self)
+    }
+
+    /// Splits a uniquely solvable sudoku into its solution and the pattern of its clues.
+    /// Returns `None`, if the sudoku isn't uniquely solvable.
+    ///
+    /// See [`SolvedSudoku::with_pattern`] for the reverse.
+    pub fn solution_and_pattern(self) -> Option<(SolvedSudoku, Pattern)> {
+        Some((self.solution()?, self.pattern()))
+    }
+
+    /// Returns `true` if the first step of solving the sudoku can be spotted at a glance:
+    /// a house with at most 2 empty cells, one of which can only hold a single digit given the clues
+    /// it sees. This includes full houses, i.e. houses with a single empty cell.
+    ///
+    /// Such puzzles are perceived as lower quality, see [`generate_filtered`](Self::generate_filtered).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let solution = Sudoku::generate_solved().to_bytes();
+    /// let mut bytes = [0; 81];
+    /// bytes[..8].copy_from_slice(&solution[..8]);
+    /// // the last cell of the first row is a full house
+    /// assert!(Sudoku::from_bytes(bytes).unwrap().has_trivial_first_move());
+    ///
+    /// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    /// assert!(!empty.has_trivial_first_move());
+    /// ```
+    pub fn has_trivial_first_move(&self) -> bool {
+        House::all().any(|house| {
+            let mut empty_cells = house
+                .cells()
+                .into_iter()
+                .filter(|cell| self.0[cell.as_index()] == 0);
+            empty_cells.clone().count() <= 2
+                && empty_cells.any(|cell| {
+                    let seen_digits = cell
+                        .neighbors()
+                        .into_iter()
+                        .filter_map(|neighbor| Digit::new_checked(self.0[neighbor.as_index()]))
+                        .fold(Set::NONE, |digits, digit| digits | digit);
+                    seen_digits.len() == 8
+                })
+        })
+    }
+
     /// Returns number of filled cells
     pub fn n_clues(&self) -> u8 {
         self.0.iter().filter(|&&num| num != 0).count() as u8
@@ -678,12 +2018,28 @@ impl Sudoku {
     /// Fewer permutations exist if the sudoku is symmetrical in respect to some combination(s) of the transformations.
     /// The vast majority of sudokus do not have any such symmetries (automorphisms). The highest number of automorphisms
     /// a sudoku can have is 648 and ~99.99% of all non-equivalent sudokus have only 1, the identity transformation.
-
+    //
     // TODO: Deduplicate the shuffle_*lines_or_chutes* functions
     //       for some reason the shuffle_bands and shuffle_stacks functions work faster in their current form
     //       rather than with a generic function abstracting over both.
     pub fn shuffle(&mut self) {
-        let transformation = crate::board::canonicalization::Transformation::random();
+        self.shuffle_with_rng(&mut rand::thread_rng());
+    }
+
+    /// [`Shuffle`](Sudoku::shuffle) the sudoku. All random numbers are drawn from the given random number generator `rng`.
+    ///
+    /// With a seeded generator, the result is reproducible:
+    ///
+    /// ```
+    /// use rand::{rngs::StdRng, SeedableRng};
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let shuffled = sudoku.shuffled_with_rng(&mut StdRng::seed_from_u64(1));
+    /// assert_eq!(shuffled, sudoku.shuffled_with_rng(&mut StdRng::seed_from_u64(1)));
+    /// ```
+    pub fn shuffle_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let transformation = crate::board::canonicalization::Transformation::random(rng);
         transformation.apply(self);
     }
 
@@ -693,6 +2049,13 @@ impl Sudoku {
         self
     }
 
+    /// Returns a [`shuffled`](Sudoku::shuffle_with_rng) copy of the sudoku.
+    /// All random numbers are drawn from the given random number generator `rng`.
+    pub fn shuffled_with_rng<R: Rng + ?Sized>(mut self, rng: &mut R) -> Self {
+        self.shuffle_with_rng(rng);
+        self
+    }
+
     /// Returns the canonical representation of this sudoku and its automorphism count.
     ///
     /// All sudokus that can be translated into each other via validity preserving transformations belong to the same
@@ -711,10 +2074,8 @@ impl Sudoku {
     pub fn canonicalized(&self) -> Option<(Sudoku, usize)> {
         let solved_sudoku = if self.is_solved() {
             *self
-        } else if let Some(solved) = self.solution() {
-            solved
         } else {
-            return None;
+            self.solution()?.into()
         };
 
         let mut sudoku = *self;
@@ -725,19 +2086,94 @@ impl Sudoku {
     }
 
     /// Returns an Iterator over sudoku, going from left to right, top to bottom
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         self.0.iter().map(num_to_opt)
     }
 
+    /// Returns an Iterator over the cells and their entries, going from left to right, top to bottom
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Cell;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let mut cells = sudoku.cells();
+    /// assert_eq!(cells.next(), Some((Cell::new(0), None)));
+    /// assert_eq!(cells.nth(2), Some((Cell::new(3), Some(2))));
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = (Cell, Option<u8>)> + '_ {
+        Cell::all().zip(self.iter())
+    }
+
+    /// Returns an Iterator over the filled cells and their clues, going from left to right, top to bottom
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::Cell;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(sudoku.clues().count(), sudoku.n_clues() as usize);
+    /// assert_eq!(sudoku.clues().next(), Some((Cell::new(3), 2)));
+    /// ```
+    pub fn clues(&self) -> impl Iterator<Item = (Cell, u8)> + '_ {
+        self.cells().filter_map(|(cell, digit)| Some((cell, digit?)))
+    }
+
+    /// Returns the entries of the 3 bands from top to bottom. Each band is given from left to right,
+    /// then top to bottom, in the same order as [`Band::cells`].
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let bands = sudoku.bands();
+    /// assert_eq!(bands[0][3], Some(2));
+    /// // the first row of the second band is the fourth row of the sudoku
+    /// assert_eq!(bands[1][..9], sudoku.iter().skip(27).take(9).collect::<Vec<_>>()[..]);
+    /// ```
+    pub fn bands(&self) -> [[Option<u8>; 27]; 3] {
+        Band::all().fold([[None; 27]; 3], |mut bands, band| {
+            self.chute_entries(band.cells(), &mut bands[band.as_index()]);
+            bands
+        })
+    }
+
+    /// Returns the entries of the 3 stacks from left to right. Each stack is given from left to
+    /// right, then top to bottom, in the same order as [`Stack::cells`].
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let stacks = sudoku.stacks();
+    /// // first row of the last stack
+    /// assert_eq!(stacks[2][..3], [None, Some(6), Some(3)]);
+    /// ```
+    pub fn stacks(&self) -> [[Option<u8>; 27]; 3] {
+        Stack::all().fold([[None; 27]; 3], |mut stacks, stack| {
+            self.chute_entries(stack.cells(), &mut stacks[stack.as_index()]);
+            stacks
+        })
+    }
+
+    fn chute_entries(&self, cells: CellSet, entries: &mut [Option<u8>; 27]) {
+        for (cell, entry) in cells.into_iter().zip(entries.iter_mut()) {
+            *entry = num_to_opt(&self.0[cell.as_index()]);
+        }
+    }
+
     /// Returns a byte array for the sudoku.
     /// Empty cells are denoted by 0, clues by the numbers 1-9.
-    pub fn to_bytes(self) -> SudokuArray {
+    pub const fn to_bytes(self) -> SudokuArray {
         self.0
     }
 
     /// Returns a representation of the sudoku in line format that can be printed
     /// and which derefs into a &str
     ///
+    /// Empty cells are written as `'.'`. The result can always be read back with
+    /// [`from_str_line`](Self::from_str_line).
+    ///
     /// ```
     /// use sudoku::Sudoku;
     ///
@@ -794,7 +2230,134 @@ impl Sudoku {
     /// );
     /// ```
     pub fn display_block(&self) -> SudokuBlock {
-        SudokuBlock(self.0)
+        SudokuBlock {
+            sudoku: self.0,
+            delimited: false,
+        }
+    }
+
+    /// Returns a representation of the sudoku in block format that can be printed
+    /// and which derefs into a &str
+    ///
+    /// Empty cells are written as `'_'`. With `delimited`, fields are separated by `'|'`
+    /// and lines of `"---+---+---"`. In contrast to the default [`display_block`](Self::display_block),
+    /// the result can be read back with [`from_str_block`](Self::from_str_block):
+    /// `Sudoku::from_str_block(&sudoku.to_str_block(delimited))` returns `Ok(sudoku)` for all sudokus.
+    /// The same holds for [`to_str_line`](Self::to_str_line) and [`from_str_line`](Self::from_str_line).
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let block = sudoku.to_str_block(true); // :SudokuStrBlock
+    /// assert_eq!(
+    ///     &*block,
+    /// "___|2__|_63
+    /// 3__|__5|4_1
+    /// __1|__3|98_
+    /// ---+---+---
+    /// ___|___|_9_
+    /// ___|538|___
+    /// _3_|___|___
+    /// ---+---+---
+    /// _26|3__|5__
+    /// 5_3|7__|__8
+    /// 47_|__1|___"
+    /// );
+    /// assert_eq!(Sudoku::from_str_block(&block), Ok(sudoku));
+    /// assert_eq!(Sudoku::from_str_block(&sudoku.to_str_block(false)), Ok(sudoku));
+    /// ```
+    pub fn to_str_block(&self, delimited: bool) -> SudokuStrBlock {
+        let mut block = String::with_capacity(if delimited { 131 } else { 89 });
+        for (cell, entry) in Cell::all().zip(self.iter()) {
+            match (cell.row().get(), cell.col().get()) {
+                (0, 0) => {}
+                (3, 0) | (6, 0) if delimited => block.push_str("\n---+---+---\n"),
+                (_, 0) => block.push('\n'),
+                (_, 3) | (_, 6) if delimited => block.push('|'),
+                _ => {}
+            }
+            block.push(match entry {
+                Some(num) => char::from(num + b'0'),
+                None => '_',
+            });
+        }
+        SudokuStrBlock(block)
+    }
+
+    /// Returns a value that prints the sudoku as an HTML table
+    /// when formatted via the `Display` trait.
+    ///
+    /// The table has the class `sudoku`. Cells with clues have the class `given`,
+    /// all others the class `empty`. Every block row is a separate `<tbody>` and every
+    /// block column a separate `<colgroup>` so the borders between blocks can be styled with CSS.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let html = sudoku.display_html().to_string();
+    /// assert!(html.starts_with("<table class=\"sudoku\">"));
+    /// assert_eq!(html.matches("<td class=\"given\">").count(), sudoku.n_clues() as usize);
+    /// assert_eq!(html.matches("<td class=\"empty\"></td>").count(), 81 - sudoku.n_clues() as usize);
+    /// ```
+    pub fn display_html(&self) -> SudokuHtml {
+        SudokuHtml(self.0)
+    }
+
+    /// Returns a value that prints the sudoku as a Markdown table
+    /// when formatted via the `Display` trait.
+    ///
+    /// Markdown tables require a header, which is left empty. Empty cells are blank.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let mut grid = [0; 81];
+    /// grid[3] = 5;
+    /// grid[36..45].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// let sudoku = Sudoku::from_bytes(grid).unwrap();
+    ///
+    /// let markdown = sudoku.display_markdown().to_string();
+    /// let lines = markdown.lines().collect::<Vec<_>>();
+    /// assert_eq!(lines.len(), 11);
+    /// assert_eq!(lines[0], "|   |   |   |   |   |   |   |   |   |");
+    /// assert_eq!(lines[1], "|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|");
+    /// assert_eq!(lines[2], "|   |   |   | 5 |   |   |   |   |   |");
+    /// assert_eq!(lines[6], "| 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 |");
+    /// ```
+    pub fn display_markdown(&self) -> SudokuMarkdown {
+        SudokuMarkdown(self.0)
+    }
+
+    // Digits that don't conflict with any neighbor of `cell`
+    #[cfg(any(feature = "svg", feature = "ansi"))]
+    pub(crate) fn basic_candidates(&self, cell: Cell) -> Set<Digit> {
+        cell.neighbors()
+            .into_iter()
+            .filter_map(|neighbor| Digit::new_checked(self.0[neighbor.as_index()]))
+            .fold(Set::ALL, |candidates, digit| candidates.without(digit.as_set()))
+    }
+}
+
+/// Decodes the char starting at byte `idx` of the UTF-8 encoded `bytes`.
+/// `idx` must lie on a char boundary.
+const fn decode_char_at(bytes: &[u8], idx: usize) -> char {
+    let first = bytes[idx] as u32;
+    let (len, mut code_point) = match first {
+        0x00..=0x7F => (1, first),
+        0xC0..=0xDF => (2, first & 0x1F),
+        0xE0..=0xEF => (3, first & 0x0F),
+        _ => (4, first & 0x07),
+    };
+    let mut i = 1;
+    while i < len {
+        code_point = code_point << 6 | (bytes[idx + i] & 0x3F) as u32;
+        i += 1;
+    }
+    match char::from_u32(code_point) {
+        Some(ch) => ch,
+        None => char::REPLACEMENT_CHARACTER,
     }
 }
 
@@ -826,6 +2389,79 @@ impl TryFrom<&[u8]> for Sudoku {
     }
 }
 
+#[cfg(feature = "ndarray")]
+impl Sudoku {
+    /// Returns the sudoku as a 9x9 array indexed by row and column.
+    /// Empty cells are denoted by 0, clues by the numbers 1-9.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let array = sudoku.to_ndarray();
+    /// assert_eq!(array[[0, 3]], 2);
+    /// // clues per column
+    /// let n_clues = array.mapv(|digit| (digit != 0) as u8).sum_axis(ndarray::Axis(0));
+    /// assert_eq!(n_clues.sum(), sudoku.n_clues());
+    /// assert_eq!(Sudoku::try_from_ndarray(array.view()).unwrap(), sudoku);
+    /// ```
+    pub fn to_ndarray(self) -> ndarray::Array2<u8> {
+        ndarray::Array2::from_shape_vec((9, 9), self.0.to_vec()).expect("81 cells form a 9x9 array")
+    }
+
+    /// Creates a sudoku from a 9x9 array indexed by row and column.
+    /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
+    pub fn try_from_ndarray(
+        array: ndarray::ArrayView2<u8>,
+    ) -> Result<Sudoku, crate::errors::FromNdarrayError> {
+        use crate::errors::FromNdarrayError;
+
+        let (n_rows, n_cols) = array.dim();
+        if (n_rows, n_cols) != (9, 9) {
+            return Err(FromNdarrayError::WrongShape(n_rows, n_cols));
+        }
+        let mut bytes = [0; N_CELLS];
+        for (byte, &entry) in bytes.iter_mut().zip(array.iter()) {
+            *byte = entry;
+        }
+        Sudoku::from_bytes(bytes).map_err(FromNdarrayError::FromBytesError)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl TryFrom<ndarray::ArrayView2<'_, u8>> for Sudoku {
+    type Error = crate::errors::FromNdarrayError;
+
+    fn try_from(array: ndarray::ArrayView2<'_, u8>) -> Result<Self, Self::Error> {
+        Self::try_from_ndarray(array)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl From<Sudoku> for ndarray::Array2<u8> {
+    fn from(sudoku: Sudoku) -> Self {
+        sudoku.to_ndarray()
+    }
+}
+
+impl IntoIterator for Sudoku {
+    type Item = Option<u8>;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIterator::into_iter(self.0).map(|num| num_to_opt(&num))
+    }
+}
+
+impl<'a> IntoIterator for &'a Sudoku {
+    type Item = Option<u8>;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
 impl From<Sudoku> for SudokuArray {
     fn from(sudoku: Sudoku) -> Self {
         sudoku.to_bytes()
@@ -873,15 +2509,72 @@ impl fmt::Display for SudokuLine {
     }
 }
 
+/// Container for the &str representation of a sudoku in block format,
+/// see [`Sudoku::to_str_block`]
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct SudokuStrBlock(String);
+
+impl fmt::Debug for SudokuStrBlock {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <str as fmt::Debug>::fmt(self, fmt)
+    }
+}
+
+impl ops::Deref for SudokuStrBlock {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for SudokuStrBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Display>::fmt(self, f)
+    }
+}
+
 /// Sudoku that will be printed in block format.
-/// This exists primarily for debugging.
+/// This exists primarily for debugging, unless it is [`delimited`](Self::delimited).
 #[derive(Copy, Clone, PartialOrd, Ord, Hash, PartialEq, Eq, Debug)]
-pub struct SudokuBlock(SudokuArray);
+pub struct SudokuBlock {
+    sudoku: SudokuArray,
+    delimited: bool,
+}
+
+impl SudokuBlock {
+    /// Prints the sudoku with `'|'` between fields in a row and lines of `"---+---+---"`
+    /// between fields in a column instead, in the format read by [`Sudoku::from_str_block`].
+    /// This is the format of [`Sudoku::to_str_block`] and common on sudoku forums.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let block = sudoku.display_block().delimited().to_string();
+    /// assert!(block.starts_with("___|2__|_63\n3__|__5|4_1\n__1|__3|98_\n---+---+---\n"));
+    /// assert_eq!(Sudoku::from_str_block(&block), Ok(sudoku));
+    /// ```
+    pub fn delimited(self) -> SudokuBlock {
+        SudokuBlock {
+            delimited: true,
+            ..self
+        }
+    }
+}
 
 impl fmt::Display for SudokuBlock {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use crate::board::{Cell, Digit};
-        for (digit, cell) in self.0.iter().cloned().map(Digit::new_checked).zip(Cell::all()) {
+        if self.delimited {
+            return f.write_str(&Sudoku(self.sudoku).to_str_block(true));
+        }
+        for (digit, cell) in self
+            .sudoku
+            .iter()
+            .cloned()
+            .map(Digit::new_checked)
+            .zip(Cell::all())
+        {
             #[rustfmt::skip]
             #[allow(clippy::write_with_newline)]
             match (cell.row().get(), cell.col().get()) {
@@ -899,11 +2592,144 @@ impl fmt::Display for SudokuBlock {
     }
 }
 
+/// Sudoku that will be printed as an HTML table.
+#[derive(Copy, Clone, PartialOrd, Ord, Hash, PartialEq, Eq, Debug)]
+pub struct SudokuHtml(SudokuArray);
+
+impl fmt::Display for SudokuHtml {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "<table class=\"sudoku\">")?;
+        for _ in 0..3 {
+            writeln!(f, "<colgroup><col><col><col></colgroup>")?;
+        }
+        for (row, digits) in self.0.chunks(9).enumerate() {
+            if row % 3 == 0 {
+                writeln!(f, "<tbody>")?;
+            }
+            write!(f, "<tr>")?;
+            for &digit in digits {
+                match digit {
+                    0 => write!(f, "<td class=\"empty\"></td>")?,
+                    _ => write!(f, "<td class=\"given\">{}</td>", digit)?,
+                }
+            }
+            writeln!(f, "</tr>")?;
+            if row % 3 == 2 {
+                writeln!(f, "</tbody>")?;
+            }
+        }
+        write!(f, "</table>")
+    }
+}
+
+/// Sudoku that will be printed as a Markdown table.
+#[derive(Copy, Clone, PartialOrd, Ord, Hash, PartialEq, Eq, Debug)]
+pub struct SudokuMarkdown(SudokuArray);
+
+impl fmt::Display for SudokuMarkdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}|", "|   ".repeat(9))?;
+        write!(f, "{}|", "|:-:".repeat(9))?;
+        for digits in self.0.chunks(9) {
+            writeln!(f)?;
+            for &digit in digits {
+                match digit {
+                    0 => write!(f, "|   ")?,
+                    _ => write!(f, "| {} ", digit)?,
+                }
+            }
+            write!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use strum::IntoEnumIterator;
 
+    #[test]
+    fn unavoidable_sets_skip_uniqueness_checks() {
+        use crate::generator::SudokuGenerator;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let rng = &mut StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let solution = Sudoku::generate_solved_with_rng(rng).into_sudoku();
+            let unavoidable_sets = SudokuGenerator::unavoidable_rectangles(&solution);
+            let mut cell_order = (0..N_CELLS).collect::<Vec<_>>();
+            cell_order.shuffle(rng);
+            let classes = cell_order.iter().map(|&cell| vec![cell]).collect::<Vec<_>>();
+
+            let (mut report, mut report_with_sets) = Default::default();
+            let budget = &mut Budget::unlimited();
+            let sudoku = Sudoku::remove_clues(solution, &classes, &[], budget, &mut report).unwrap();
+            let sudoku_with_sets = Sudoku::remove_clues(
+                solution,
+                &classes,
+                &unavoidable_sets,
+                budget,
+                &mut report_with_sets,
+            )
+            .unwrap();
+            assert_eq!(sudoku, sudoku_with_sets);
+            assert_eq!(report.n_removals, report_with_sets.n_removals);
+            assert!(report.n_uniqueness_checks >= report_with_sets.n_uniqueness_checks);
+            for set in &unavoidable_sets {
+                assert!(set.iter().any(|&cell| sudoku.0[cell] != 0));
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_clue_removal_matches_sequential() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let rng = &mut StdRng::seed_from_u64(0);
+        for symmetry in Symmetry::iter() {
+            let solution = Sudoku::generate_solved_with_rng(rng).into_sudoku();
+            let mut cell_order = (0..N_CELLS).collect::<Vec<_>>();
+            cell_order.shuffle(rng);
+            let mut classes = vec![];
+            for cell in cell_order {
+                let cells = symmetry.corresponding_cells(cell);
+                if !classes.iter().any(|class: &Vec<usize>| class.contains(&cell)) {
+                    classes.push(cells);
+                }
+            }
+
+            let (mut sequential_report, mut parallel_report) = Default::default();
+            let sequential = Sudoku::remove_clues(
+                solution,
+                &classes,
+                &[],
+                &mut Budget::unlimited(),
+                &mut sequential_report,
+            );
+            let parallel = Sudoku::remove_clues_in_parallel(
+                solution,
+                &classes,
+                &mut Budget::unlimited(),
+                &mut parallel_report,
+                4,
+            );
+            assert_eq!(sequential, parallel);
+            assert_eq!(sequential_report.n_removal_attempts, classes.len());
+            assert_eq!(parallel_report.n_removal_attempts, classes.len());
+            assert_eq!(sequential_report.n_removals, parallel_report.n_removals);
+            assert_eq!(sequential_report.n_uniqueness_checks, classes.len());
+            assert!(parallel_report.n_uniqueness_checks >= classes.len());
+
+            let token = CancellationToken::new();
+            token.cancel();
+            let budget = &mut Budget::unlimited().with_token(&token);
+            let report = &mut GenerationReport::default();
+            let cancelled = Sudoku::remove_clues_in_parallel(solution, &classes, budget, report, 4);
+            assert_eq!(cancelled, Err(Interrupted::Cancelled));
+        }
+    }
+
     // each cell in a symmetry class must map to the same set of cells
     #[test]
     fn test_symmetry_all_cells_equivalent() {
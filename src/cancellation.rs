@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A token for aborting long running operations from another thread.
+///
+/// The token is cheap to clone and all clones share the same state.
+/// Once [`cancel`](Self::cancel) is called on any of them, all operations that were handed a clone
+/// stop at the next opportunity and return [`Interrupted::Cancelled`](crate::errors::Interrupted::Cancelled).
+///
+/// ```
+/// use sudoku::{CancellationToken, Sudoku};
+/// use sudoku::errors::Interrupted;
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+///
+/// let empty = Sudoku::from_bytes([0; 81]).unwrap();
+/// assert_eq!(empty.solutions_up_to_cancellable(2, &token), Err(Interrupted::Cancelled));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all operations using this token or any of its clones.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,175 @@
+//! Daily puzzles derived from the date
+//!
+//! [`Sudoku::puzzle_of_the_day`] generates the same puzzle for the same [`Date`] and [`Difficulty`]
+//! on every platform, so all users of an app can be shown the same puzzle without a server
+//! distributing it. The random numbers are drawn from a fixed generator that is seeded with the
+//! date and difficulty.
+//!
+//! The puzzles only change with a new minor version of this crate, e.g. from 0.7 to 0.8, and
+//! such changes are listed in the changelog.
+
+use rand::{Error, RngCore};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::batch::Difficulty;
+use crate::errors::GenerationFailed;
+use crate::generator::{SudokuGenerator, MAX_ATTEMPTS_ON_DEMAND};
+use crate::Sudoku;
+
+/// A day of the proleptic Gregorian calendar
+///
+/// ```
+/// use sudoku::daily::Date;
+///
+/// let date = Date::new(2024, 2, 29).unwrap();
+/// assert_eq!(date.days_since_epoch(), 19782);
+/// assert_eq!(Date::from_days_since_epoch(19782), date);
+/// assert_eq!(Date::new(2023, 2, 29), None);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Creates a date from the year, the month from 1 to 12 and the day of the month from 1.
+    /// Returns `None`, if the day doesn't exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let n_days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=n_days).contains(&day).then_some(Date { year, month, day })
+    }
+
+    /// Returns the current date in UTC.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the system clock is set before 1970.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is set before 1970")
+            .as_secs();
+        Date::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    /// Returns the date `days` days after 1970-01-01.
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        // Howard Hinnant's `civil_from_days`
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Returns the number of days since 1970-01-01, negative for earlier dates.
+    pub fn days_since_epoch(self) -> i64 {
+        // Howard Hinnant's `days_from_civil`
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the year.
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month from 1 to 12.
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting from 1.
+    pub fn day(self) -> u8 {
+        self.day
+    }
+}
+
+impl Sudoku {
+    /// Returns the puzzle of the day for `date` and `difficulty`.
+    /// It is the same on every platform, see the [module documentation](crate::daily).
+    ///
+    /// Up to 1000 random puzzles are generated until one of the given difficulty is found.
+    /// Fails, if none is, which is likely for [`Difficulty::Unrated`]
+    /// and always the case for [`Difficulty::Invalid`].
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::batch::{Difficulty, Grade};
+    /// use sudoku::daily::Date;
+    ///
+    /// let date = Date::new(2024, 1, 1).unwrap();
+    /// let sudoku = Sudoku::puzzle_of_the_day(date, Difficulty::Medium).unwrap();
+    /// assert_eq!(Sudoku::puzzle_of_the_day(date, Difficulty::Medium), Ok(sudoku));
+    /// assert_eq!(Grade::new(sudoku).difficulty, Difficulty::Medium);
+    /// ```
+    pub fn puzzle_of_the_day(date: Date, difficulty: Difficulty) -> Result<Sudoku, GenerationFailed> {
+        if difficulty == Difficulty::Invalid {
+            return Err(GenerationFailed { n_attempts: 0 });
+        }
+        let seed = (date.days_since_epoch() as u64) << 8 | difficulty as u64;
+        SudokuGenerator::builder()
+            .with_rng(SplitMix64(seed))
+            .with_difficulty(difficulty..=difficulty)
+            .with_max_attempts(MAX_ATTEMPTS_ON_DEMAND)
+            .generate()
+    }
+}
+
+// Small generator with a fixed output sequence, unlike the generators of `rand`
+// which may change between versions
+struct SplitMix64(u64);
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
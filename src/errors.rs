@@ -1,4 +1,4 @@
-//! Errors that may occur when reading sudokus
+//! Errors that may occur when reading, solving or generating sudokus
 #[cfg(doc)]
 use crate::Sudoku;
 
@@ -18,7 +18,48 @@ pub enum FromBytesSliceError {
     FromBytesError(FromBytesError),
 }
 
+/// Error for [`Sudoku::try_from_ndarray`]
+#[cfg(feature = "ndarray")]
+#[derive(Debug, thiserror::Error)]
+pub enum FromNdarrayError {
+    /// Array is not of shape 9x9. Contains the number of rows and columns.
+    #[error("array should have shape 9x9, found {0}x{1}")]
+    WrongShape(usize, usize),
+    /// Array contains invalid entries
+    #[error(transparent)]
+    FromBytesError(FromBytesError),
+}
+
+/// Error for [`RecognizedGrid::resolve`](crate::import::RecognizedGrid::resolve)
+#[cfg(feature = "ocr")]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("no correction of the doubtful cells makes the sudoku uniquely solvable")]
+pub struct Unresolvable;
+
+/// Error for [`Sudoku::from_sat_model`]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum FromSatModelError {
+    /// The model contains a literal whose variable is above 729
+    #[error("literal {0} has no variable of the sudoku encoding")]
+    UnknownVariable(i32),
+    /// The model assigns several digits to the cell
+    #[error("cell {0} is assigned multiple digits")]
+    MultipleDigits(u8),
+    /// The model assigns no digit to the cell
+    #[error("cell {0} is assigned no digit")]
+    NoDigit(u8),
+}
+
+/// Error for [`GeneratorBuilder::generate`](crate::generator::GeneratorBuilder::generate)
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("no sudoku satisfied all requirements in {n_attempts} attempts")]
+pub struct GenerationFailed {
+    /// Number of generated sudokus that were rejected
+    pub n_attempts: usize,
+}
+
 use crate::board::{block, col, row};
+use std::fmt;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
 /// An invalid sudoku entry encountered during parsing.
@@ -96,3 +137,347 @@ pub enum LineParseError {
     #[error("missing comment delimiter")]
     MissingCommentDelimiter,
 }
+
+/// Error for [`Sudoku::parse_all`]. Contains the index of the first line of the invalid sudoku.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum ParseAllError {
+    /// Invalid sudoku in line format
+    #[error("invalid sudoku in line {0}: {1}")]
+    Line(usize, LineParseError),
+    /// Invalid sudoku in block format
+    #[error("invalid sudoku starting in line {0}: {1}")]
+    Block(usize, BlockParseError),
+}
+
+/// Error for reading and writing CSV files with [`batch::csv`](crate::batch::csv).
+/// Contains the line number of invalid records.
+#[cfg(feature = "csv")]
+#[derive(Debug, thiserror::Error)]
+pub enum CsvError {
+    /// Reading or writing failed or the file isn't valid CSV
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    /// The header row has no puzzle column
+    #[error("no puzzle column found")]
+    MissingPuzzleColumn,
+    /// The puzzle isn't a sudoku in line format
+    #[error("invalid puzzle in line {0}: {1}")]
+    InvalidPuzzle(u64, LineParseError),
+    /// The solution isn't a solved sudoku in line format
+    #[error("invalid solution in line {0}")]
+    InvalidSolution(u64),
+    /// The difficulty isn't a number
+    #[error("invalid difficulty in line {0}")]
+    InvalidDifficulty(u64),
+}
+
+/// Error for the handlers of the [`service`](crate::service) module
+///
+/// Serialized with the name of the variant in snake case in the field `error`, e.g.
+/// `{"error":"invalid_puzzle","message":"..."}`.
+#[cfg(feature = "service")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, thiserror::Error, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum ServiceError {
+    /// The puzzle can't be parsed
+    #[error("invalid puzzle: {message}")]
+    InvalidPuzzle {
+        /// Description of the parse error
+        message: String,
+    },
+    /// The requested number of solutions is not in the range from 1 to 100
+    #[error("max_solutions must be between 1 and 100, found {max_solutions}")]
+    InvalidMaxSolutions {
+        /// The requested number of solutions
+        max_solutions: usize,
+    },
+    /// No puzzle of the requested difficulty was found
+    #[error("no puzzle of the requested difficulty was found")]
+    GenerationFailed,
+    /// The puzzle couldn't be solved within the work limit
+    #[error("the puzzle is too hard to solve")]
+    SearchLimitReached,
+}
+
+#[cfg(feature = "service")]
+impl ServiceError {
+    /// Returns the HTTP status code that fits the error: 400 for invalid requests and
+    /// 422 for requests that are valid, but can't be served.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ServiceError::InvalidPuzzle { .. } | ServiceError::InvalidMaxSolutions { .. } => 400,
+            ServiceError::GenerationFailed | ServiceError::SearchLimitReached => 422,
+        }
+    }
+}
+
+/// Two clues of the same digit in one house, found while parsing a sudoku.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("cells {} and {} both contain the clue {digit}", cells[0], cells[1])]
+pub struct DuplicateClue {
+    /// Cell numbers from 0..=80 of both clues, in reading order
+    pub cells: [u8; 2],
+    /// The duplicated digit from 1..=9
+    pub digit: u8,
+}
+
+/// Error for [`Sudoku::from_str_line_validated`] and [`Sudoku::from_str_block_validated`]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum ValidatedParseError<E> {
+    /// The input is not a sudoku in the expected format
+    #[error(transparent)]
+    Parse(E),
+    /// The sudoku was read, but contains contradictory clues
+    #[error(transparent)]
+    DuplicateClue(DuplicateClue),
+}
+
+/// Error for [`Sudoku::validate_puzzle`]. The sudoku can not have a unique solution.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum NotUnique {
+    /// Every uniquely solvable sudoku has at least 17 clues. Returns the number of clues.
+    #[error("sudoku has {0} clues, a unique solution needs at least 17")]
+    TooFewClues(u8),
+    /// If two digits don't appear in the clues, they can be swapped in any solution.
+    /// Returns the number of distinct digits.
+    #[error("sudoku has clues of {0} different digits, a unique solution needs at least 8")]
+    TooFewDigits(u8),
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// An invalid hexadoku entry encountered during parsing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("cell {cell} contains invalid character '{ch}'")]
+pub struct HexadokuInvalidEntry {
+    /// Cell number goes from 0..=255, 0..=15 for first line, 16..=31 for 2nd and so on
+    pub cell: u8,
+    /// The parsed invalid char
+    pub ch: char,
+}
+
+/// Error for [`Hexadoku::from_str_line`](crate::hexadoku::Hexadoku::from_str_line)
+#[derive(Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum HexadokuLineParseError {
+    /// Accepted values are the symbols of the symbol set and '.' or '_' for empty cells
+    #[error(transparent)]
+    InvalidEntry(HexadokuInvalidEntry),
+    /// Returns number of cells supplied
+    #[error("hexadoku contains {0} cells instead of required 256")]
+    NotEnoughCells(u16),
+    /// Returned if >=257 valid cell positions are supplied
+    #[error("hexadoku contains more than 256 cells or is missing comment delimiter")]
+    TooManyCells,
+    /// Comments must be delimited by a space or tab.
+    #[error("missing comment delimiter")]
+    MissingCommentDelimiter,
+}
+
+/// Error for [`Hexadoku::from_str_block`](crate::hexadoku::Hexadoku::from_str_block)
+#[derive(Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum HexadokuBlockParseError {
+    /// Accepted values are the symbols of the symbol set and '.' or '_' for empty cells
+    #[error(transparent)]
+    InvalidEntry(HexadokuInvalidEntry),
+    /// Line contains more or less than 16 entries. Returns index of row (0-15)
+    #[error("a hexadoku line should have 16 entries, found a different number in row {0}")]
+    InvalidLineLength(u8),
+    /// Input ends with less than 16 rows. Returns number of rows encountered.
+    #[error("a hexadoku should have 16 rows, found only {0}")]
+    NotEnoughRows(u8),
+    /// More than 16 rows are supplied and a later line is not pure whitespace
+    #[error("a hexadoku should have 16 rows, found a 17th")]
+    TooManyRows,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Display adapter that renders a parse error together with a caret-annotated
+/// snippet of the input that caused it.
+///
+/// Created by [`LineParseError::detailed_display`] and [`BlockParseError::detailed_display`].
+///
+/// ```
+/// use sudoku::Sudoku;
+///
+/// let input = "..3x";
+/// let err = Sudoku::from_str_line(input).unwrap_err();
+/// assert_eq!(
+///     err.detailed_display(input).to_string(),
+///     "cell 3 contains invalid character 'x'
+///   |
+/// 1 | ..3x
+///   |    ^"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct DetailedDisplay<'a, E> {
+    error: &'a E,
+    input: &'a str,
+}
+
+impl LineParseError {
+    /// Returns a value that displays this error together with the position in `input` where it occurred.
+    /// `input` must be the string that failed to parse.
+    pub fn detailed_display<'a>(&'a self, input: &'a str) -> DetailedDisplay<'a, Self> {
+        DetailedDisplay { error: self, input }
+    }
+
+    // column of the first char that caused the error
+    fn column(&self) -> usize {
+        match *self {
+            // all chars before an invalid entry are ascii
+            LineParseError::InvalidEntry(InvalidEntry { cell, .. }) => cell as usize,
+            LineParseError::NotEnoughCells(n_cells) => n_cells as usize,
+            LineParseError::TooManyCells | LineParseError::MissingCommentDelimiter => 81,
+        }
+    }
+}
+
+impl BlockParseError {
+    /// Returns a value that displays this error together with the position in `input` where it occurred.
+    /// `input` must be the string that failed to parse.
+    pub fn detailed_display<'a>(&'a self, input: &'a str) -> DetailedDisplay<'a, Self> {
+        DetailedDisplay { error: self, input }
+    }
+
+    // line and column of the first char that caused the error, if known
+    fn location(&self, input: &str) -> Option<(usize, usize)> {
+        // mirror the format detection of the block parser
+        let is_delimited = input.lines().next().and_then(|line| line.chars().nth(3)) == Some('|');
+        let input_line = |row: u8| match is_delimited {
+            true => row as usize + row as usize / 3,
+            false => row as usize,
+        };
+        let input_col = |col: u8| match is_delimited {
+            true => col as usize + col as usize / 3,
+            false => col as usize,
+        };
+        let line_len = |line| {
+            input
+                .lines()
+                .nth(line)
+                .map_or(0, |line: &str| line.chars().count())
+        };
+
+        match *self {
+            BlockParseError::InvalidEntry(entry) => Some((input_line(entry.row()), input_col(entry.col()))),
+            BlockParseError::InvalidLineLength(row) => {
+                let line = input_line(row);
+                Some((line, line_len(line)))
+            }
+            // the char right after the 9th cell
+            BlockParseError::MissingCommentDelimiter(row) => Some((input_line(row), input_col(8) + 1)),
+            BlockParseError::TooManyRows => Some((input_line(9), 0)),
+            BlockParseError::NotEnoughRows(_) => {
+                let last_line = input.lines().count().saturating_sub(1);
+                Some((last_line, line_len(last_line)))
+            }
+            BlockParseError::IncorrectFieldDelimiter => None,
+        }
+    }
+}
+
+impl fmt::Display for DetailedDisplay<'_, LineParseError> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        write_snippet(f, self.input, 0, self.error.column())
+    }
+}
+
+impl fmt::Display for DetailedDisplay<'_, BlockParseError> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = self.error.location(self.input);
+        match (self.error, location) {
+            // name the input line shown in the snippet, not the row of the sudoku
+            (BlockParseError::MissingCommentDelimiter(_), Some((line, _))) => {
+                write!(f, "missing comment delimiter in line {}", line + 1)?
+            }
+            _ => write!(f, "{}", self.error)?,
+        }
+        match location {
+            Some((line, column)) => write_snippet(f, self.input, line, column),
+            None => Ok(()),
+        }
+    }
+}
+
+// Writes the given line of `input` with a caret under `column`, both counted from 0.
+fn write_snippet(f: &mut fmt::Formatter, input: &str, line: usize, column: usize) -> fmt::Result {
+    let text = input.lines().nth(line).unwrap_or("");
+    let line_nr = (line + 1).to_string();
+    let gutter = " ".repeat(line_nr.len());
+    // keep tabs so the caret lines up with the input
+    let padding = text
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(column)
+        .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+
+    write!(
+        f,
+        "\n{} |\n{} | {}\n{} | {}^",
+        gutter, line_nr, text, gutter, padding
+    )
+}
+
+/// Error for converting a [`Sudoku`] into a [`SolvedSudoku`](crate::SolvedSudoku)
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("sudoku is not completely filled or breaks the rules")]
+pub struct NotSolved;
+
+/// Error for [`Solver`](crate::solver::Solver): a clue is not among the candidates left by the other clues
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("clue conflicts with the other clues")]
+pub struct ClueConflict;
+
+/// Error for solving or generation that was aborted before completion
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum Interrupted {
+    /// A [`CancellationToken`](crate::CancellationToken) was cancelled
+    #[error("operation was cancelled")]
+    Cancelled,
+    /// The deadline passed before the operation could finish
+    #[error("deadline exceeded")]
+    DeadlineExceeded,
+    /// The maximum number of search nodes was visited before the operation could finish
+    #[error("node limit reached")]
+    NodeLimitReached,
+}
+
+/// Error for [`Sudoku::merge`]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("cell {cell} contains {digit} in one sudoku and {other_digit} in the other")]
+pub struct ConflictError {
+    /// Cell number goes from 0..=80, 0..=8 for first line, 9..=17 for 2nd and so on
+    pub cell: u8,
+    /// The digit in the cell of the sudoku that [`Sudoku::merge`] was called on
+    pub digit: u8,
+    /// The digit in the cell of the other sudoku
+    pub other_digit: u8,
+}
+
+/// Error for [`SudokuBitboards::to_sudoku`](crate::board::SudokuBitboards::to_sudoku).
+/// Contains the first cell in which more than one digit is set.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("cell {0} contains more than one digit")]
+pub struct OverlappingDigits(pub u8);
+
+/// Error for [`GameState::new`](crate::board::GameState::new) and
+/// [`Sudoku::check_eliminations`](crate::Sudoku::check_eliminations)
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[error("clues don't have a unique solution")]
+pub struct NoUniqueSolution;
+
+/// Error for replaying a [`Replay`](crate::board::Replay)
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+pub enum ReplayError {
+    /// The clues of the replay don't have a unique solution
+    #[error(transparent)]
+    NoUniqueSolution(#[from] NoUniqueSolution),
+    /// The action at the contained index places or erases a digit in a cell with a clue
+    #[error("action {0} changes a clue")]
+    ChangesClue(usize),
+}
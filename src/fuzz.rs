@@ -0,0 +1,85 @@
+//! Entry points for fuzzing
+//!
+//! The functions take arbitrary bytes, as provided by fuzzers like `cargo fuzz`, and exercise the
+//! parsers and the solver. They never panic, no matter the input, and finish in bounded time,
+//! so any panic or hang found by a fuzzer is a bug in this library.
+//!
+//! ```
+//! use sudoku::fuzz;
+//!
+//! assert!(fuzz::parse_any(b"...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").is_some());
+//! assert!(fuzz::parse_any(&[0xff, 0xfe, b'1']).is_none());
+//! ```
+//!
+//! A fuzz target then only needs to forward the data:
+//!
+//! ```ignore
+//! #![no_main]
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     sudoku::fuzz::parse_and_solve_bounded(data);
+//! });
+//! ```
+
+use crate::batch::PuzzleRecord;
+use crate::hexadoku::Hexadoku;
+use crate::Sudoku;
+
+// Bounds the time spent on each input
+const MAX_NODES: u64 = 10_000;
+const SOLUTION_LIMIT: usize = 2;
+
+/// Parses `data` with every parser for sudokus in textual formats and returns the result
+/// of the first one that succeeds.
+///
+/// The parsers are tried in the order of [`Sudoku::from_str_line`], [`PuzzleRecord::from_line`],
+/// [`Sudoku::from_str_block`], [`Sudoku::from_str_block_permissive`] and
+/// [`Sudoku::from_str_flexible`], including the validating variants. All of them are run, even after
+/// one succeeded, so a fuzzer covers every parser with each input. Returns `None`, if `data` isn't
+/// valid UTF-8 or no parser succeeds.
+pub fn parse_any(data: &[u8]) -> Option<Sudoku> {
+    let s = std::str::from_utf8(data).ok()?;
+    let parsed = [
+        Sudoku::from_str_line(s).ok(),
+        Sudoku::from_str_line_validated(s).ok(),
+        PuzzleRecord::from_line(s).ok().map(|record| record.sudoku),
+        Sudoku::from_str_block(s).ok(),
+        Sudoku::from_str_block_validated(s).ok(),
+        Sudoku::from_str_block_permissive(s).ok(),
+        Sudoku::from_str_flexible(s).ok(),
+    ];
+    parsed.iter().flatten().next().copied()
+}
+
+/// Parses `data` with the parsers for [`Hexadoku`]s. See [`parse_any`].
+pub fn parse_hexadoku(data: &[u8]) -> Option<Hexadoku> {
+    let s = std::str::from_utf8(data).ok()?;
+    let parsed = [Hexadoku::from_str_line(s).ok(), Hexadoku::from_str_block(s).ok()];
+    parsed.iter().flatten().next().copied()
+}
+
+/// [Parses](parse_any) `data` and searches for up to 2 solutions. Returns `None`, if `data` can't be
+/// parsed or the search gives up after 10,000 nodes of the search tree, see
+/// [`Sudoku::solutions_up_to_limited`].
+///
+/// Every solution is checked to be solved and to agree with the clues.
+///
+/// # Panics
+///
+/// Panics only if a solution is wrong, which is a bug in the solver.
+pub fn parse_and_solve_bounded(data: &[u8]) -> Option<Vec<Sudoku>> {
+    let sudoku = parse_any(data)?;
+    let (solutions, _) = sudoku.solutions_up_to_limited(SOLUTION_LIMIT, MAX_NODES).ok()?;
+    for solution in &solutions {
+        let agrees_with_clues = sudoku
+            .iter()
+            .zip(solution.iter())
+            .all(|(clue, digit)| clue.is_none() || clue == digit);
+        assert!(
+            solution.is_solved() && agrees_with_clues,
+            "wrong solution {} for {}",
+            solution,
+            sudoku
+        );
+    }
+    Some(solutions)
+}
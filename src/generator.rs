@@ -1,28 +1,58 @@
+//! Configurable generation of sudokus
+//!
+//! The generation functions of [`Sudoku`] cover the common cases. [`SudokuGenerator::builder`]
+//! combines any of their options with requirements on the difficulty, the number of clues and the
+//! techniques needed to solve the puzzle, as well as custom filters.
+//!
+//! [`PregenCache`] generates puzzles in the background, so they are ready when they are needed.
+
+mod pregen;
+
+pub use self::pregen::PregenCache;
+
+use rand::rngs::ThreadRng;
 use rand::seq::SliceRandom;
 use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::Instant;
 
+use crate::batch::{Difficulty, Grade};
 use crate::bitset::Set;
 use crate::board::Candidate;
 use crate::board::*;
 use crate::consts::*;
+use crate::errors::GenerationFailed;
 use crate::helper::{CellArray, HouseArray, Unsolvable};
-use crate::Sudoku;
+use crate::solver::Budget;
+use crate::strategy::{StrategySolver, TechniqueSet};
+use crate::{CancellationToken, GenerationReport, Sudoku};
 
+/// Generator of random sudokus
+///
+/// Configurable generation starts with [`builder`](Self::builder).
 // Sudoku generation is done via randomized solving of empty grids
 // the solver is based on jsolve
 // Helper struct for recursive solving
 #[derive(Clone, Debug)]
-pub(crate) struct SudokuGenerator {
-    pub grid: Sudoku,
-    pub n_solved_cells: u8,
-    pub cell_poss_digits: CellArray<Set<Digit>>,
-    pub house_solved_digits: HouseArray<Set<Digit>>,
-    pub last_cell: u8, // last cell checked in guess routine
+pub struct SudokuGenerator {
+    pub(crate) grid: Sudoku,
+    pub(crate) n_solved_cells: u8,
+    pub(crate) cell_poss_digits: CellArray<Set<Digit>>,
+    pub(crate) house_solved_digits: HouseArray<Set<Digit>>,
+    pub(crate) last_cell: u8, // last cell checked in guess routine
 }
 
 impl SudokuGenerator {
+    /// Returns a builder for generating sudokus that satisfy several requirements at once.
+    /// See [`GeneratorBuilder`].
+    pub fn builder() -> GeneratorBuilder {
+        GeneratorBuilder::new()
+    }
+
     #[inline]
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         SudokuGenerator {
             grid: Sudoku([0; N_CELLS]),
             n_solved_cells: 0,
@@ -85,7 +115,7 @@ impl SudokuGenerator {
         Ok(())
     }
 
-    pub fn batch_insert_entries(&mut self, stack: &mut Vec<Candidate>) -> Result<(), Unsolvable> {
+    pub(crate) fn batch_insert_entries(&mut self, stack: &mut Vec<Candidate>) -> Result<(), Unsolvable> {
         for entry in stack.drain(..) {
             // cell already solved from previous entry in stack, skip
             if self.cell_poss_digits[entry.cell].is_empty() {
@@ -126,7 +156,7 @@ impl SudokuGenerator {
     }
 
     #[inline]
-    pub fn is_solved(&self) -> bool {
+    pub(crate) fn is_solved(&self) -> bool {
         self.n_solved_cells == N_CELLS as u8
     }
 
@@ -266,7 +296,16 @@ impl SudokuGenerator {
         }
     }
 
-    pub fn generate_solved<R: Rng + ?Sized>(rng: &mut R) -> Sudoku {
+    /// Find a random solution of `sudoku` by guessing randomly. Returns `None` if it has no solution.
+    pub(crate) fn random_solution<R: Rng + ?Sized>(sudoku: Sudoku, rng: &mut R) -> Option<Sudoku> {
+        let mut stack = Cell::all()
+            .zip(sudoku.iter())
+            .filter_map(|(cell, digit)| Some(Candidate::new(cell.get(), digit?)))
+            .collect::<Vec<_>>();
+        Self::new().randomized_solve_one(rng, &mut stack).ok()
+    }
+
+    pub(crate) fn generate_solved<R: Rng + ?Sized>(rng: &mut R) -> Sudoku {
         // fill first row with a permutation of 1...9
         // not necessary, but ~15% faster
         let mut stack = Vec::with_capacity(N_CELLS);
@@ -281,4 +320,507 @@ impl SudokuGenerator {
 
         Self::new().randomized_solve_one(rng, &mut stack).unwrap()
     }
+
+    /// Fill the cells of `pattern` with digits one after the other, in random order. Each cell gets
+    /// the digit that leaves the fewest solutions, as far as they can be counted cheaply.
+    /// Returns `None` if the final puzzle doesn't have a unique solution.
+    pub(crate) fn pattern_game<R: Rng + ?Sized>(pattern: &Pattern, rng: &mut R) -> Option<Sudoku> {
+        // counting all solutions of sparse partial puzzles is expensive and small counts
+        // only matter near the end
+        const MAX_COUNTED_SOLUTIONS: usize = 16;
+
+        let mut cells = pattern.cells().into_iter().collect::<Vec<_>>();
+        cells.shuffle(rng);
+        let mut sudoku = Sudoku([0; N_CELLS]);
+        for cell in cells {
+            let mut digits = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+            digits.shuffle(rng);
+            // ties are broken by the random order of the digits
+            let (_, filled) = digits
+                .iter()
+                .map(|&digit| {
+                    let mut filled = sudoku;
+                    filled.0[cell.as_index()] = digit;
+                    (filled.solutions_count_up_to(MAX_COUNTED_SOLUTIONS), filled)
+                })
+                .filter(|&(n_solutions, _)| n_solutions != 0)
+                .min_by_key(|&(n_solutions, _)| n_solutions)?;
+            sudoku = filled;
+        }
+        sudoku.is_uniquely_solvable().then_some(sudoku)
+    }
+
+    /// Find the unavoidable rectangles of a solved `sudoku`.
+    ///
+    /// Each of them consists of 4 cells in 2 rows, 2 columns and 2 blocks with only 2 digits,
+    /// which could be swapped to get another solution. Every uniquely solvable puzzle with this
+    /// solution must keep a clue in each of them.
+    pub(crate) fn unavoidable_rectangles(sudoku: &Sudoku) -> Vec<[usize; 4]> {
+        let digit_at = |row: usize, col: usize| sudoku.0[row * 9 + col];
+        let mut rectangles = vec![];
+        for row1 in 0..8 {
+            for row2 in row1 + 1..9 {
+                for col1 in 0..8 {
+                    for col2 in col1 + 1..9 {
+                        // the rectangle has to span exactly 2 blocks
+                        if (row1 / 3 == row2 / 3) == (col1 / 3 == col2 / 3) {
+                            continue;
+                        }
+                        if digit_at(row1, col1) == digit_at(row2, col2)
+                            && digit_at(row1, col2) == digit_at(row2, col1)
+                        {
+                            rectangles.push([
+                                row1 * 9 + col1,
+                                row1 * 9 + col2,
+                                row2 * 9 + col1,
+                                row2 * 9 + col2,
+                            ]);
+                        }
+                    }
+                }
+            }
+        }
+        rectangles
+    }
+}
+
+// A custom requirement of the builder
+type Filter = Box<dyn Fn(&Sudoku) -> bool>;
+
+// Attempts to generate a single puzzle of a given difficulty on demand,
+// e.g. for the puzzle of the day, a service request or a cache miss
+pub(crate) const MAX_ATTEMPTS_ON_DEMAND: usize = 1000;
+
+/// Builder for sudokus that satisfy several requirements at once
+///
+/// Created by [`SudokuGenerator::builder`]. Every attempt generates a random solved grid and removes
+/// as many clues as possible while upholding the [symmetry](Self::with_symmetry) and, if
+/// techniques are [forbidden](Self::with_forbidden_techniques), keeping the puzzle solvable
+/// without them. Attempts whose puzzle misses any other requirement are discarded, until the
+/// [maximum number of attempts](Self::with_max_attempts) is exhausted.
+///
+/// ```
+/// use sudoku::Symmetry;
+/// use sudoku::batch::Difficulty;
+/// use sudoku::generator::SudokuGenerator;
+/// use sudoku::strategy::{Strategy, TechniqueSet};
+///
+/// let sudoku = SudokuGenerator::builder()
+///     .with_symmetry(Symmetry::VerticalMirror)
+///     .with_difficulty(Difficulty::Easy..=Difficulty::Medium)
+///     .with_forbidden_techniques(TechniqueSet::from(Strategy::XWing))
+///     .with_filter(|sudoku| !sudoku.has_trivial_first_move())
+///     .with_max_attempts(1000)
+///     .generate()
+///     .unwrap();
+/// assert!(sudoku.is_uniquely_solvable());
+/// assert!(!sudoku.has_trivial_first_move());
+/// ```
+pub struct GeneratorBuilder<R = ThreadRng> {
+    rng: R,
+    symmetry: Symmetry,
+    difficulty: Option<RangeInclusive<Difficulty>>,
+    n_clues: Option<RangeInclusive<u8>>,
+    required_techniques: TechniqueSet,
+    forbidden_techniques: TechniqueSet,
+    filters: Vec<Filter>,
+    known_puzzles: Option<HashSet<u64>>,
+    max_attempts: usize,
+    token: Option<CancellationToken>,
+}
+
+impl GeneratorBuilder {
+    /// Creates a builder without requirements, using 180° rotational symmetry, the thread local
+    /// random number generator and up to 100 attempts.
+    pub fn new() -> Self {
+        GeneratorBuilder {
+            rng: rand::thread_rng(),
+            symmetry: Symmetry::HalfRotation,
+            difficulty: None,
+            n_clues: None,
+            required_techniques: TechniqueSet::NONE,
+            forbidden_techniques: TechniqueSet::NONE,
+            filters: vec![],
+            known_puzzles: None,
+            max_attempts: 100,
+            token: None,
+        }
+    }
+}
+
+impl Default for GeneratorBuilder {
+    fn default() -> Self {
+        GeneratorBuilder::new()
+    }
+}
+
+impl<R: Rng> GeneratorBuilder<R> {
+    /// Draws all random numbers from `rng`, e.g. for reproducible puzzles.
+    pub fn with_rng<R2: Rng>(self, rng: R2) -> GeneratorBuilder<R2> {
+        GeneratorBuilder {
+            rng,
+            symmetry: self.symmetry,
+            difficulty: self.difficulty,
+            n_clues: self.n_clues,
+            required_techniques: self.required_techniques,
+            forbidden_techniques: self.forbidden_techniques,
+            filters: self.filters,
+            known_puzzles: self.known_puzzles,
+            max_attempts: self.max_attempts,
+            token: self.token,
+        }
+    }
+
+    /// Sets the symmetry of the clues.
+    pub fn with_symmetry(self, symmetry: Symmetry) -> Self {
+        GeneratorBuilder { symmetry, ..self }
+    }
+
+    /// Only accepts sudokus whose [`Difficulty`] lies within `difficulty`.
+    pub fn with_difficulty(self, difficulty: RangeInclusive<Difficulty>) -> Self {
+        GeneratorBuilder {
+            difficulty: Some(difficulty),
+            ..self
+        }
+    }
+
+    /// Only accepts sudokus whose number of clues lies within `n_clues`.
+    ///
+    /// The clues are removed until no further clue can be removed, so ranges that only allow
+    /// many clues are rarely satisfied.
+    pub fn with_clues(self, n_clues: RangeInclusive<u8>) -> Self {
+        GeneratorBuilder {
+            n_clues: Some(n_clues),
+            ..self
+        }
+    }
+
+    /// Only accepts sudokus that need every strategy of `techniques` when solved with the
+    /// strategies that aren't [forbidden](Self::with_forbidden_techniques).
+    pub fn with_required_techniques(self, techniques: TechniqueSet) -> Self {
+        GeneratorBuilder {
+            required_techniques: techniques,
+            ..self
+        }
+    }
+
+    /// Only generates sudokus that can be solved without the strategies of `techniques`.
+    pub fn with_forbidden_techniques(self, techniques: TechniqueSet) -> Self {
+        GeneratorBuilder {
+            forbidden_techniques: techniques,
+            ..self
+        }
+    }
+
+    /// Only generates sudokus that can be solved with the strategies of `techniques`, e.g.
+    /// [`TechniqueSet::SINGLES`] for beginners. Forbids all other strategies.
+    ///
+    /// ```
+    /// use sudoku::Symmetry;
+    /// use sudoku::generator::SudokuGenerator;
+    /// use sudoku::strategy::TechniqueSet;
+    ///
+    /// let sudoku = SudokuGenerator::builder()
+    ///     .with_symmetry(Symmetry::None)
+    ///     .with_allowed_techniques(TechniqueSet::SINGLES)
+    ///     .generate()
+    ///     .unwrap();
+    /// assert!(sudoku.solvable_with(&TechniqueSet::SINGLES));
+    /// ```
+    pub fn with_allowed_techniques(self, techniques: TechniqueSet) -> Self {
+        let forbidden_techniques = TechniqueSet::ALL
+            .strategies()
+            .into_iter()
+            .filter(|&strategy| !techniques.contains(strategy))
+            .collect();
+        GeneratorBuilder {
+            forbidden_techniques,
+            ..self
+        }
+    }
+
+    /// Only accepts sudokus for which `filter` returns `true`. Can be called multiple times,
+    /// all filters must accept the sudoku.
+    pub fn with_filter<F: Fn(&Sudoku) -> bool + 'static>(mut self, filter: F) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Only accepts sudokus whose [canonical fingerprint](Sudoku::canonical_fingerprint) isn't
+    /// among `fingerprints`, i.e. that aren't equivalent to any known puzzle. Can be called
+    /// multiple times to add more fingerprints.
+    ///
+    /// The fingerprint of every generated sudoku is added to the known puzzles, so repeated calls
+    /// of [`generate`](Self::generate) never return equivalent puzzles. Apps can store the
+    /// [`known_puzzles`](Self::known_puzzles) to avoid serving the same puzzle again in later sessions.
+    ///
+    /// ```
+    /// use sudoku::generator::SudokuGenerator;
+    ///
+    /// let served = SudokuGenerator::builder().generate().unwrap();
+    /// let mut builder = SudokuGenerator::builder()
+    ///     .with_known_puzzles(served.canonical_fingerprint());
+    /// let sudoku = builder.generate().unwrap();
+    /// assert_ne!(sudoku.canonical_fingerprint(), served.canonical_fingerprint());
+    /// assert_eq!(builder.known_puzzles().unwrap().len(), 2);
+    /// ```
+    pub fn with_known_puzzles<I: IntoIterator<Item = u64>>(mut self, fingerprints: I) -> Self {
+        self.known_puzzles
+            .get_or_insert_with(HashSet::new)
+            .extend(fingerprints);
+        self
+    }
+
+    /// Returns the fingerprints of the known puzzles, including the generated ones.
+    /// `None`, unless [`with_known_puzzles`](Self::with_known_puzzles) was called.
+    pub fn known_puzzles(&self) -> Option<&HashSet<u64>> {
+        self.known_puzzles.as_ref()
+    }
+
+    /// Sets how many sudokus may be generated before giving up.
+    pub fn with_max_attempts(self, max_attempts: usize) -> Self {
+        GeneratorBuilder { max_attempts, ..self }
+    }
+
+    /// Gives up early, once `token` is cancelled, e.g. by the user of an interactive app.
+    /// [`generate`](Self::generate) then returns [`GenerationFailed`] with the number of
+    /// attempts that were completed.
+    ///
+    /// ```
+    /// use sudoku::CancellationToken;
+    /// use sudoku::generator::SudokuGenerator;
+    ///
+    /// let token = CancellationToken::new();
+    /// let mut builder = SudokuGenerator::builder().with_cancellation(&token);
+    /// assert!(builder.generate().is_ok());
+    /// token.cancel();
+    /// assert_eq!(builder.generate().unwrap_err().n_attempts, 0);
+    /// ```
+    pub fn with_cancellation(self, token: &CancellationToken) -> Self {
+        GeneratorBuilder {
+            token: Some(token.clone()),
+            ..self
+        }
+    }
+
+    /// Generates a uniquely solvable sudoku that satisfies all requirements.
+    ///
+    /// Returns an error, if none of the attempts satisfied them.
+    pub fn generate(&mut self) -> Result<Sudoku, GenerationFailed> {
+        self.generate_with_report().map(|(sudoku, _)| sudoku)
+    }
+
+    /// Like [`generate`](Self::generate), but also reports how much work the generation took.
+    ///
+    /// ```
+    /// use sudoku::batch::Difficulty;
+    /// use sudoku::generator::SudokuGenerator;
+    ///
+    /// let (sudoku, report) = SudokuGenerator::builder()
+    ///     .with_difficulty(Difficulty::Easy..=Difficulty::Medium)
+    ///     .generate_with_report()
+    ///     .unwrap();
+    /// assert_eq!(report.n_clues, sudoku.n_clues() as usize);
+    /// assert!(report.n_attempts >= 1);
+    /// ```
+    pub fn generate_with_report(&mut self) -> Result<(Sudoku, GenerationReport), GenerationFailed> {
+        let start = Instant::now();
+        let mut report = GenerationReport::default();
+        let token = self.token.clone();
+        let mut budget = Budget::unlimited();
+        if let Some(token) = &token {
+            budget = budget.with_token(token);
+        }
+        let allowed_techniques = TechniqueSet::ALL
+            .strategies()
+            .into_iter()
+            .filter(|&strategy| !self.forbidden_techniques.contains(strategy))
+            .collect::<TechniqueSet>();
+        for n_attempts in 0..self.max_attempts {
+            let solution = SudokuGenerator::generate_solved(&mut self.rng);
+            let sudoku = match self.forbidden_techniques.is_empty() {
+                true => Sudoku::_generate_with_symmetry_and_rng_from(
+                    solution,
+                    self.symmetry,
+                    &mut self.rng,
+                    &mut budget,
+                    &mut report,
+                ),
+                false => Sudoku::generate_with_techniques_and_rng_from(
+                    solution,
+                    self.symmetry,
+                    &allowed_techniques,
+                    &mut self.rng,
+                    &mut budget,
+                    &mut report,
+                ),
+            };
+            let sudoku = match sudoku {
+                Ok(sudoku) => sudoku,
+                Err(_) => return Err(GenerationFailed { n_attempts }),
+            };
+            report.n_attempts += 1;
+            if self.accepts(sudoku, allowed_techniques) && self.insert_known_puzzle(sudoku) {
+                report.n_clues = sudoku.n_clues() as usize;
+                report.n_search_nodes = budget.nodes();
+                report.elapsed = start.elapsed();
+                return Ok((sudoku, report));
+            }
+        }
+        Err(GenerationFailed {
+            n_attempts: self.max_attempts,
+        })
+    }
+
+    // Returns `false`, if an equivalent sudoku is already known
+    fn insert_known_puzzle(&mut self, sudoku: Sudoku) -> bool {
+        match (&mut self.known_puzzles, sudoku.canonical_fingerprint()) {
+            (Some(known_puzzles), Some(fingerprint)) => known_puzzles.insert(fingerprint),
+            _ => true,
+        }
+    }
+
+    // Checks the requirements that weren't enforced during generation, cheapest first
+    fn accepts(&self, sudoku: Sudoku, allowed_techniques: TechniqueSet) -> bool {
+        if let Some(n_clues) = &self.n_clues {
+            if !n_clues.contains(&sudoku.n_clues()) {
+                return false;
+            }
+        }
+        if !self.filters.iter().all(|filter| filter(&sudoku)) {
+            return false;
+        }
+        if let Some(difficulty) = &self.difficulty {
+            if !difficulty.contains(&Grade::new(sudoku).difficulty) {
+                return false;
+            }
+        }
+        if self.required_techniques.is_empty() {
+            return true;
+        }
+        match StrategySolver::from_sudoku(sudoku).solve(&allowed_techniques.strategies()) {
+            Ok((_, deductions)) => {
+                let counts = deductions.technique_counts();
+                self.required_techniques
+                    .strategies()
+                    .into_iter()
+                    .all(|strategy| counts.get(strategy) > 0)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl<R> fmt::Debug for GeneratorBuilder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GeneratorBuilder")
+            .field("symmetry", &self.symmetry)
+            .field("difficulty", &self.difficulty)
+            .field("n_clues", &self.n_clues)
+            .field("required_techniques", &self.required_techniques)
+            .field("forbidden_techniques", &self.forbidden_techniques)
+            .field("n_filters", &self.filters.len())
+            .field(
+                "n_known_puzzles",
+                &self.known_puzzles.as_ref().map_or(0, HashSet::len),
+            )
+            .field("max_attempts", &self.max_attempts)
+            .field("is_cancellable", &self.token.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Fixed worst cases for backtracking solvers, for benchmarking and stress testing
+///
+/// The variants cover both extremes: grids with astronomically many solutions, where counting
+/// has to be cut off with a limit, and uniquely solvable puzzles with few clues that need a lot of
+/// guessing. Every variant documents its exact number of solutions, see
+/// [`n_solutions`](Self::n_solutions).
+///
+/// [`generate`](Self::generate) returns randomly [shuffled](Sudoku::shuffle) variants, which have
+/// the same number of solutions. Shuffling may however make a puzzle easier for a particular
+/// solver, e.g. [`AntiBruteForce`](Self::AntiBruteForce) is only a worst case for solvers that
+/// guess in the canonical order.
+///
+/// ```
+/// use sudoku::generator::StressPuzzle;
+///
+/// let sudoku = StressPuzzle::AntiBruteForce.sudoku();
+/// assert_eq!(sudoku.n_clues(), 17);
+/// assert!(sudoku.is_uniquely_solvable());
+///
+/// let sudoku = StressPuzzle::FilledRow.generate();
+/// assert_eq!(StressPuzzle::FilledRow.n_solutions(), 18_383_222_420_692_992);
+/// assert_eq!(sudoku.solutions_count_up_to(100), 100);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StressPuzzle {
+    /// The empty grid, 6,670,903,752,021,072,936,960 solutions
+    Empty,
+    /// The digits 1 to 9 in the first row and nothing else, 18,383,222,420,692,992 solutions
+    FilledRow,
+    /// 17 clues, 1 solution. The first row of the solution is `987654321`, so a solver that
+    /// fills cells from the top left and tries digits in ascending order has to backtrack a lot.
+    AntiBruteForce,
+    /// [`AntiBruteForce`](Self::AntiBruteForce) without its clue in row 8, column 5.
+    /// 16 clues, 227,707 solutions
+    AntiBruteForceMissingClue,
+    /// The "Easter Monster", 21 clues, 1 solution. One of the hardest known puzzles
+    /// for logical solvers.
+    EasterMonster,
+}
+
+impl StressPuzzle {
+    /// All variants
+    pub const ALL: [StressPuzzle; 5] = [
+        StressPuzzle::Empty,
+        StressPuzzle::FilledRow,
+        StressPuzzle::AntiBruteForce,
+        StressPuzzle::AntiBruteForceMissingClue,
+        StressPuzzle::EasterMonster,
+    ];
+
+    /// Returns the puzzle in its canonical form.
+    pub fn sudoku(self) -> Sudoku {
+        let line = match self {
+            StressPuzzle::Empty => {
+                "................................................................................."
+            }
+            StressPuzzle::FilledRow => {
+                "123456789........................................................................"
+            }
+            StressPuzzle::AntiBruteForce => {
+                "..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9"
+            }
+            StressPuzzle::AntiBruteForceMissingClue => {
+                "..............3.85..1.2.......5.7.....4...1...9.......5......73..2..........4...9"
+            }
+            StressPuzzle::EasterMonster => {
+                "1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1"
+            }
+        };
+        Sudoku::from_str_line(line).unwrap()
+    }
+
+    /// Returns the number of solutions of the puzzle and all its shuffled variants.
+    pub fn n_solutions(self) -> u128 {
+        match self {
+            StressPuzzle::Empty => 6_670_903_752_021_072_936_960,
+            StressPuzzle::FilledRow => 18_383_222_420_692_992,
+            StressPuzzle::AntiBruteForce => 1,
+            StressPuzzle::AntiBruteForceMissingClue => 227_707,
+            StressPuzzle::EasterMonster => 1,
+        }
+    }
+
+    /// Returns a randomly shuffled variant of the puzzle with the same number of solutions.
+    pub fn generate(self) -> Sudoku {
+        self.generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Returns a shuffled variant of the puzzle with the same number of solutions,
+    /// using `rng` for randomization.
+    pub fn generate_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> Sudoku {
+        self.sudoku().shuffled_with_rng(rng)
+    }
 }
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::batch::Difficulty;
+use crate::errors::GenerationFailed;
+use crate::generator::{SudokuGenerator, MAX_ATTEMPTS_ON_DEMAND};
+use crate::Sudoku;
+
+/// Cache of puzzles that are generated ahead of time on a background thread
+///
+/// Generating a puzzle of a given difficulty may take from milliseconds to seconds, which is
+/// noticeable when a player asks for a new puzzle. The cache keeps up to `n` puzzles of each of
+/// its difficulties ready, so they can be [taken](Self::take) instantly, and generates
+/// replacements in the background.
+///
+/// The background thread is limited to a share of one CPU core: after each generation attempt
+/// that took the time `t`, it pauses for `t * (1 - cpu_budget) / cpu_budget`.
+/// It stops when the cache is dropped.
+///
+/// ```
+/// use sudoku::batch::Difficulty;
+/// use sudoku::generator::PregenCache;
+///
+/// let cache = PregenCache::new(&[Difficulty::Easy, Difficulty::Medium], 3, 0.5);
+/// // instant, if the cache had time to fill up, otherwise generated on this thread
+/// let sudoku = cache.take_or_generate(Difficulty::Easy).unwrap();
+/// assert!(sudoku.is_uniquely_solvable());
+/// ```
+#[derive(Debug)]
+pub struct PregenCache {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    // signals taken puzzles and shutdown to the background thread
+    changed: Condvar,
+}
+
+#[derive(Debug)]
+struct State {
+    puzzles: HashMap<Difficulty, Vec<Sudoku>>,
+    is_shut_down: bool,
+}
+
+impl PregenCache {
+    /// Starts generating up to `n_per_difficulty` puzzles of each of `difficulties` in the
+    /// background, using up to `cpu_budget` of one CPU core, from 0 to 1.
+    ///
+    /// [`Difficulty::Expert`] puzzles are uncommon and [`Difficulty::Unrated`] ones are very rare,
+    /// so the background thread will often spend all of its budget on them.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `difficulties` contains [`Difficulty::Invalid`], for which there are no puzzles,
+    /// or if `cpu_budget` is not greater than 0 and at most 1.
+    pub fn new(difficulties: &[Difficulty], n_per_difficulty: usize, cpu_budget: f64) -> PregenCache {
+        assert!(
+            !difficulties.contains(&Difficulty::Invalid),
+            "can't generate puzzles without a unique solution"
+        );
+        assert!(
+            cpu_budget > 0.0 && cpu_budget <= 1.0,
+            "cpu budget must be in the range (0, 1], found {}",
+            cpu_budget
+        );
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                puzzles: difficulties
+                    .iter()
+                    .map(|&difficulty| (difficulty, vec![]))
+                    .collect(),
+                is_shut_down: false,
+            }),
+            changed: Condvar::new(),
+        });
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || fill(&shared, n_per_difficulty, cpu_budget))
+        };
+        PregenCache {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// Takes a cached puzzle of `difficulty`. Returns `None`, if there is none ready
+    /// or `difficulty` is not one of the cache's difficulties.
+    pub fn take(&self, difficulty: Difficulty) -> Option<Sudoku> {
+        let mut state = self.shared.state.lock().unwrap();
+        let sudoku = state.puzzles.get_mut(&difficulty)?.pop();
+        if sudoku.is_some() {
+            self.shared.changed.notify_one();
+        }
+        sudoku
+    }
+
+    /// Takes a cached puzzle of `difficulty` or, if there is none ready, generates one on the
+    /// current thread with [`GeneratorBuilder::generate`](crate::generator::GeneratorBuilder::generate).
+    /// Returns [`GenerationFailed`], if no puzzle of `difficulty` was found in the attempts.
+    pub fn take_or_generate(&self, difficulty: Difficulty) -> Result<Sudoku, GenerationFailed> {
+        match self.take(difficulty) {
+            Some(sudoku) => Ok(sudoku),
+            None => SudokuGenerator::builder()
+                .with_difficulty(difficulty..=difficulty)
+                .with_max_attempts(MAX_ATTEMPTS_ON_DEMAND)
+                .generate(),
+        }
+    }
+
+    /// Returns the number of puzzles of `difficulty` that are ready.
+    pub fn n_ready(&self, difficulty: Difficulty) -> usize {
+        let state = self.shared.state.lock().unwrap();
+        state.puzzles.get(&difficulty).map_or(0, Vec::len)
+    }
+}
+
+impl Drop for PregenCache {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().is_shut_down = true;
+        self.shared.changed.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// The loop of the background thread
+fn fill(shared: &Shared, n_per_difficulty: usize, cpu_budget: f64) {
+    let mut state = shared.state.lock().unwrap();
+    loop {
+        if state.is_shut_down {
+            return;
+        }
+        let emptiest = state
+            .puzzles
+            .iter()
+            .filter(|(_, puzzles)| puzzles.len() < n_per_difficulty)
+            .min_by_key(|&(&difficulty, puzzles)| (puzzles.len(), difficulty))
+            .map(|(&difficulty, _)| difficulty);
+        let difficulty = match emptiest {
+            Some(difficulty) => difficulty,
+            None => {
+                state = shared.changed.wait(state).unwrap();
+                continue;
+            }
+        };
+        drop(state);
+
+        // a single attempt, so the budget is kept even for rare difficulties
+        let start = Instant::now();
+        let sudoku = SudokuGenerator::builder()
+            .with_difficulty(difficulty..=difficulty)
+            .with_max_attempts(1)
+            .generate();
+        let pause = start.elapsed().mul_f64((1.0 - cpu_budget) / cpu_budget);
+
+        state = shared.state.lock().unwrap();
+        if let Ok(sudoku) = sudoku {
+            state.puzzles.get_mut(&difficulty).unwrap().push(sudoku);
+        }
+        if pause > Duration::ZERO {
+            state = shared
+                .changed
+                .wait_timeout_while(state, pause, |state| !state.is_shut_down)
+                .unwrap()
+                .0;
+        }
+    }
+}
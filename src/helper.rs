@@ -8,6 +8,15 @@ use std::ops::{Deref, DerefMut, Index, IndexMut};
 #[derive(Debug)]
 pub(crate) struct Unsolvable;
 
+/// 64 bit FNV-1a hash. Unlike the hashers of std, its output is stable across versions.
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct CellArray<T>(pub [T; N_CELLS]);
 
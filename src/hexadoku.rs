@@ -0,0 +1,230 @@
+//! 16x16 sudokus, also known as hexadokus or alphadokus
+//!
+//! A hexadoku consists of 16 rows, 16 columns and 16 blocks of 4x4 cells that must each contain
+//! all 16 symbols exactly once. Three symbol sets are in common use, see [`Symbols`].
+//! Cell values are numbered from 1 to 16 in the order of the symbol set and 0 denotes an empty cell.
+//!
+//! For now, this module only supports reading and writing hexadokus.
+
+use crate::errors::{HexadokuBlockParseError, HexadokuInvalidEntry, HexadokuLineParseError};
+use std::fmt;
+
+const N_CELLS: usize = 256;
+
+/// The symbols used for the 16 values of a [`Hexadoku`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Symbols {
+    /// Hexadecimal digits `0`-`9`, `A`-`F`
+    ZeroToF,
+    /// Digits `1`-`9` followed by `A`-`G`
+    OneToG,
+    /// Letters `A`-`P`
+    AToP,
+}
+
+impl Symbols {
+    /// Returns the value from 1 to 16 for `ch` or `None` if it is not one of the symbols.
+    /// Lowercase letters are accepted.
+    pub fn value(self, ch: char) -> Option<u8> {
+        let ch = ch.to_ascii_uppercase();
+        let value = match (self, ch) {
+            (Symbols::ZeroToF, '0'..='9') => ch as u8 - b'0',
+            (Symbols::ZeroToF, 'A'..='F') => ch as u8 - b'A' + 10,
+            (Symbols::OneToG, '1'..='9') => ch as u8 - b'1',
+            (Symbols::OneToG, 'A'..='G') => ch as u8 - b'A' + 9,
+            (Symbols::AToP, 'A'..='P') => ch as u8 - b'A',
+            _ => return None,
+        };
+        Some(value + 1)
+    }
+
+    /// Returns the symbol for `value`.
+    ///
+    /// # Panics
+    /// Panics, if `value` is not in the range of `1..=16`.
+    pub fn symbol(self, value: u8) -> char {
+        assert!((1..=16).contains(&value), "hexadoku values must be in 1..=16");
+        let first = match self {
+            Symbols::ZeroToF => b'0',
+            Symbols::OneToG => b'1',
+            Symbols::AToP => b'A',
+        };
+        let offset = value - 1;
+        match self {
+            Symbols::ZeroToF if offset >= 10 => (b'A' + offset - 10) as char,
+            Symbols::OneToG if offset >= 9 => (b'A' + offset - 9) as char,
+            _ => (first + offset) as char,
+        }
+    }
+
+    // Guesses the symbol set from the characters of an input.
+    // Only the order of the values differs between ambiguous cases,
+    // so a wrong guess just relabels the values.
+    fn detect(s: &str) -> Symbols {
+        let contains = |pred: fn(char) -> bool| s.chars().map(|ch| ch.to_ascii_uppercase()).any(pred);
+        if contains(|ch| ch == '0') {
+            Symbols::ZeroToF
+        } else if contains(|ch| ('H'..='P').contains(&ch)) || !contains(|ch| ch.is_ascii_digit()) {
+            Symbols::AToP
+        } else if contains(|ch| ch == 'G') {
+            Symbols::OneToG
+        } else {
+            Symbols::ZeroToF
+        }
+    }
+}
+
+/// A 16x16 sudoku
+///
+/// ```
+/// use sudoku::hexadoku::{Hexadoku, Symbols};
+///
+/// let line = format!("0123456789ABCDEF{}", ".".repeat(240));
+/// let hexadoku = Hexadoku::from_str_line(&line).unwrap();
+/// assert_eq!(hexadoku.n_clues(), 16);
+/// assert_eq!(hexadoku.to_str_line(Symbols::ZeroToF), line);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hexadoku([u8; N_CELLS]);
+
+impl Hexadoku {
+    /// Creates a hexadoku from its cell values. Empty cells are denoted by 0, all others by 1-16.
+    /// Returns `None`, if any value is above 16.
+    pub fn from_bytes(bytes: [u8; N_CELLS]) -> Option<Hexadoku> {
+        match bytes.iter().all(|&value| value <= 16) {
+            true => Some(Hexadoku(bytes)),
+            false => None,
+        }
+    }
+
+    /// Returns the cell values. Empty cells are denoted by 0, all others by 1-16.
+    pub fn to_bytes(&self) -> [u8; N_CELLS] {
+        self.0
+    }
+
+    /// Returns the number of filled cells.
+    pub fn n_clues(&self) -> u16 {
+        self.0.iter().filter(|&&value| value != 0).count() as u16
+    }
+
+    /// Reads a hexadoku in the line format, i.e. 256 cells in one line, going left-to-right,
+    /// top-to-bottom. Empty cells are denoted by `.` or `_`.
+    /// Like for [`Sudoku::from_str_line`](crate::Sudoku::from_str_line), a comment may follow
+    /// after a space or tab.
+    ///
+    /// The symbol set is detected automatically. `0` implies [`Symbols::ZeroToF`],
+    /// letters after `G` or the absence of digits imply [`Symbols::AToP`] and
+    /// `G` implies [`Symbols::OneToG`]. Otherwise, `ZeroToF` is assumed.
+    /// Use [`from_str_line_with_symbols`](Self::from_str_line_with_symbols) to set it explicitly.
+    pub fn from_str_line(s: &str) -> Result<Hexadoku, HexadokuLineParseError> {
+        let cells = s.split([' ', '\t']).next().unwrap_or("");
+        Hexadoku::from_str_line_with_symbols(s, Symbols::detect(cells))
+    }
+
+    /// Reads a hexadoku in the line format with the given `symbols`.
+    /// See [`from_str_line`](Self::from_str_line).
+    pub fn from_str_line_with_symbols(s: &str, symbols: Symbols) -> Result<Hexadoku, HexadokuLineParseError> {
+        let mut grid = [0; N_CELLS];
+        let mut chars = s.chars();
+        for (cell, value) in grid.iter_mut().enumerate() {
+            let ch = match chars.next() {
+                Some(' ') | Some('\t') | None => {
+                    return Err(HexadokuLineParseError::NotEnoughCells(cell as u16))
+                }
+                Some(ch) => ch,
+            };
+            *value =
+                match ch {
+                    '.' | '_' => 0,
+                    _ => symbols.value(ch).ok_or(HexadokuLineParseError::InvalidEntry(
+                        HexadokuInvalidEntry { cell: cell as u8, ch },
+                    ))?,
+                };
+        }
+
+        match chars.next() {
+            None | Some('\t') | Some(' ') | Some('\r') | Some('\n') | Some(';') | Some(',') => {
+                Ok(Hexadoku(grid))
+            }
+            Some(ch) if ch == '.' || ch == '_' || symbols.value(ch).is_some() => {
+                Err(HexadokuLineParseError::TooManyCells)
+            }
+            Some(_) => Err(HexadokuLineParseError::MissingCommentDelimiter),
+        }
+    }
+
+    /// Reads a hexadoku in the block format, i.e. 16 lines of 16 cells each.
+    /// Empty cells are denoted by `.` or `_`. Spaces and `|` between cells are ignored,
+    /// as are lines consisting only of `-`, `+`, `|` and whitespace, which may be used
+    /// for delimiting blocks.
+    ///
+    /// The symbol set is detected as in [`from_str_line`](Self::from_str_line).
+    pub fn from_str_block(s: &str) -> Result<Hexadoku, HexadokuBlockParseError> {
+        Hexadoku::from_str_block_with_symbols(s, Symbols::detect(s))
+    }
+
+    /// Reads a hexadoku in the block format with the given `symbols`.
+    /// See [`from_str_block`](Self::from_str_block).
+    pub fn from_str_block_with_symbols(
+        s: &str,
+        symbols: Symbols,
+    ) -> Result<Hexadoku, HexadokuBlockParseError> {
+        let is_delimiter_line = |line: &str| line.chars().all(|ch| "-+| \t".contains(ch));
+        let mut grid = [0; N_CELLS];
+        let mut rows = s.lines().filter(|line| !is_delimiter_line(line));
+
+        for row in 0..16 {
+            let line = rows
+                .next()
+                .ok_or(HexadokuBlockParseError::NotEnoughRows(row as u8))?;
+            let mut n_cols = 0;
+            for ch in line.chars().filter(|&ch| ch != ' ' && ch != '|') {
+                if n_cols == 16 {
+                    return Err(HexadokuBlockParseError::InvalidLineLength(row as u8));
+                }
+                let cell = row * 16 + n_cols;
+                grid[cell] = match ch {
+                    '.' | '_' => 0,
+                    _ => symbols.value(ch).ok_or(HexadokuBlockParseError::InvalidEntry(
+                        HexadokuInvalidEntry { cell: cell as u8, ch },
+                    ))?,
+                };
+                n_cols += 1;
+            }
+            if n_cols != 16 {
+                return Err(HexadokuBlockParseError::InvalidLineLength(row as u8));
+            }
+        }
+
+        match rows.all(|line| line.trim().is_empty()) {
+            true => Ok(Hexadoku(grid)),
+            false => Err(HexadokuBlockParseError::TooManyRows),
+        }
+    }
+
+    /// Returns the line format of the hexadoku with `.` for empty cells.
+    pub fn to_str_line(&self, symbols: Symbols) -> String {
+        self.0
+            .iter()
+            .map(|&value| match value {
+                0 => '.',
+                _ => symbols.symbol(value),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for Hexadoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Hexadoku")
+            .field(&self.to_str_line(Symbols::ZeroToF))
+            .finish()
+    }
+}
+
+/// Prints the line format with [`Symbols::ZeroToF`]
+impl fmt::Display for Hexadoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_str_line(Symbols::ZeroToF))
+    }
+}
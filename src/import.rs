@@ -0,0 +1,199 @@
+//! Importing sudokus from external sources
+//!
+//! [`ImportSource`] is implemented by everything a sudoku can be read from, so applications can
+//! plug in their own sources, e.g. the output of a digit recognizer, and hand them to
+//! [`Sudoku::import`]. Strings are imported in the line format or with
+//! [`Sudoku::from_str_block_permissive`].
+//!
+//! With the `ocr` feature, [`RecognizedGrid`] takes the digits recognized in a photo of a
+//! puzzle together with their confidences and corrects misreadings that make the puzzle
+//! unsolvable or ambiguous.
+
+#[cfg(feature = "ocr")]
+use crate::board::Cell;
+use crate::errors::NotEnoughRows;
+use crate::Sudoku;
+
+/// A source that a sudoku can be read from
+pub trait ImportSource {
+    /// Error for sources that don't contain a valid sudoku
+    type Error;
+
+    /// Reads the sudoku
+    fn import(self) -> Result<Sudoku, Self::Error>;
+}
+
+impl ImportSource for &str {
+    type Error = NotEnoughRows;
+
+    fn import(self) -> Result<Sudoku, NotEnoughRows> {
+        Sudoku::from_str_line(self).or_else(|_| Sudoku::from_str_block_permissive(self))
+    }
+}
+
+impl Sudoku {
+    /// Reads a sudoku from `source`. See [`ImportSource`].
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::import("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// assert_eq!(sudoku.n_clues(), 27);
+    /// ```
+    pub fn import<S: ImportSource>(source: S) -> Result<Sudoku, S::Error> {
+        source.import()
+    }
+}
+
+/// The result of recognizing the digit in a single cell
+#[cfg(feature = "ocr")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Recognition {
+    /// The recognized digit from 1 to 9 or `None` for an empty cell
+    pub digit: Option<u8>,
+    /// How certain the recognizer is, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// Digits recognized in an image of a sudoku, e.g. by a camera scan
+///
+/// Recognition errors usually make a puzzle unsolvable or give it multiple solutions.
+/// [`resolve`](Self::resolve) corrects them by trying other entries for the cells with the lowest
+/// confidence until the sudoku is uniquely solvable, preferring as few corrections as possible.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::import::{Recognition, RecognizedGrid};
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let mut recognitions = [[Recognition { digit: None, confidence: 0.99 }; 9]; 9];
+/// for (cell, digit) in sudoku.iter().enumerate() {
+///     recognitions[cell / 9][cell % 9].digit = digit;
+/// }
+/// // a 6 misread as a 5
+/// recognitions[0][7] = Recognition { digit: Some(5), confidence: 0.4 };
+///
+/// let resolution = RecognizedGrid::new(recognitions).resolve().unwrap();
+/// assert_eq!(resolution.sudoku, sudoku);
+/// assert_eq!(resolution.corrections.len(), 1);
+/// assert_eq!(Sudoku::import(RecognizedGrid::new(recognitions)).unwrap(), sudoku);
+/// ```
+#[cfg(feature = "ocr")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecognizedGrid {
+    recognitions: [[Recognition; 9]; 9],
+    max_doubtful_cells: usize,
+    max_corrections: usize,
+}
+
+/// A sudoku resolved from a [`RecognizedGrid`]
+#[cfg(feature = "ocr")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Resolution {
+    /// The uniquely solvable sudoku
+    pub sudoku: Sudoku,
+    /// The cells whose recognized entry was replaced, with their new entry
+    pub corrections: Vec<(Cell, Option<u8>)>,
+}
+
+#[cfg(feature = "ocr")]
+impl RecognizedGrid {
+    /// Creates a grid from the recognitions of all cells, indexed by row and column.
+    ///
+    /// By default, up to 2 corrections among the 4 cells with the lowest confidence are tried.
+    pub fn new(recognitions: [[Recognition; 9]; 9]) -> Self {
+        RecognizedGrid {
+            recognitions,
+            max_doubtful_cells: 4,
+            max_corrections: 2,
+        }
+    }
+
+    /// Sets how many of the cells with the lowest confidence may be corrected.
+    pub fn with_max_doubtful_cells(self, max_doubtful_cells: usize) -> Self {
+        RecognizedGrid {
+            max_doubtful_cells,
+            ..self
+        }
+    }
+
+    /// Sets how many cells may be corrected at once.
+    /// The number of solver calls grows exponentially with it.
+    pub fn with_max_corrections(self, max_corrections: usize) -> Self {
+        RecognizedGrid {
+            max_corrections,
+            ..self
+        }
+    }
+
+    /// Returns the sudoku as recognized, without corrections.
+    /// Recognized digits outside of 1 to 9 are treated as empty cells.
+    pub fn recognized_sudoku(&self) -> Sudoku {
+        let mut bytes = [0; 81];
+        for (byte, recognition) in bytes.iter_mut().zip(self.recognitions.iter().flatten()) {
+            *byte = recognition
+                .digit
+                .filter(|digit| (1..=9).contains(digit))
+                .unwrap_or(0);
+        }
+        Sudoku(bytes)
+    }
+
+    /// Finds the uniquely solvable sudoku that needs the fewest corrections of the doubtful cells.
+    /// Among equally many corrections, those of cells with lower confidence are preferred.
+    pub fn resolve(&self) -> Result<Resolution, crate::errors::Unresolvable> {
+        let recognized = self.recognized_sudoku();
+        let mut doubtful = Cell::all().collect::<Vec<_>>();
+        doubtful.sort_by(|&a, &b| {
+            let confidence =
+                |cell: Cell| self.recognitions[cell.row().as_index()][cell.col().as_index()].confidence;
+            confidence(a).total_cmp(&confidence(b))
+        });
+        doubtful.truncate(self.max_doubtful_cells);
+
+        let mut corrections = vec![];
+        for n_corrections in 0..=self.max_corrections.min(doubtful.len()) {
+            if let Some(sudoku) = correct(recognized, &doubtful, n_corrections, &mut corrections) {
+                return Ok(Resolution { sudoku, corrections });
+            }
+        }
+        Err(crate::errors::Unresolvable)
+    }
+}
+
+// Tries all ways of replacing the entries of exactly `n_corrections` cells of `doubtful` and returns
+// the first uniquely solvable sudoku. The replacements are recorded in `corrections`.
+#[cfg(feature = "ocr")]
+fn correct(
+    sudoku: Sudoku,
+    doubtful: &[Cell],
+    n_corrections: usize,
+    corrections: &mut Vec<(Cell, Option<u8>)>,
+) -> Option<Sudoku> {
+    if n_corrections == 0 {
+        return sudoku.is_uniquely_solvable().then_some(sudoku);
+    }
+    for (idx, &cell) in doubtful.iter().enumerate() {
+        let original = sudoku.0[cell.as_index()];
+        // digits before the empty cell
+        for entry in (1..=9).chain(Some(0)).filter(|&entry| entry != original) {
+            let mut corrected = sudoku;
+            corrected.0[cell.as_index()] = entry;
+            corrections.push((cell, Some(entry).filter(|&entry| entry != 0)));
+            if let Some(sudoku) = correct(corrected, &doubtful[idx + 1..], n_corrections - 1, corrections) {
+                return Some(sudoku);
+            }
+            corrections.pop();
+        }
+    }
+    None
+}
+
+#[cfg(feature = "ocr")]
+impl ImportSource for RecognizedGrid {
+    type Error = crate::errors::Unresolvable;
+
+    fn import(self) -> Result<Sudoku, Self::Error> {
+        self.resolve().map(|resolution| resolution.sudoku)
+    }
+}
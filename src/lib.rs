@@ -41,14 +41,41 @@
 //! }
 //! ```
 
+#[cfg(feature = "ansi")]
+pub mod ansi;
+pub mod batch;
 pub mod bitset;
 pub mod board;
+mod cancellation;
 mod consts;
+pub mod daily;
 pub mod errors;
-mod generator;
+pub mod fuzz;
+pub mod generator;
 mod helper;
-mod solver;
+pub mod hexadoku;
+pub mod import;
+mod observer;
+#[cfg(feature = "oracle")]
+pub mod oracle;
+#[cfg(feature = "print")]
+pub mod print;
+pub mod search;
+#[cfg(feature = "service")]
+pub mod service;
+pub mod solver;
 pub mod strategy;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(any(feature = "arbitrary", feature = "quickcheck", feature = "proptest"))]
+pub mod testing;
+#[cfg(feature = "tui")]
+pub mod tui;
 
+pub use crate::board::GenerationReport;
+pub use crate::board::SolvedSudoku;
 pub use crate::board::Sudoku;
 pub use crate::board::Symmetry;
+pub use crate::cancellation::CancellationToken;
+pub use crate::observer::SolverObserver;
+pub use crate::solver::SolveBuffer;
@@ -0,0 +1,57 @@
+use crate::board::{Cell, Digit};
+
+/// Callbacks for following the progress of the backtracking solver, e.g. to animate it.
+///
+/// All methods do nothing by default. The solver is generic over the observer, so
+/// the unit type `()`, which ignores all events, compiles down to the unobserved solver.
+///
+/// The search is reported as a tree. [`on_guess`](Self::on_guess) enters a branch,
+/// which is left again with a matching call to [`on_backtrack`](Self::on_backtrack), whether or not
+/// solutions were found in it. Within a branch, the digits deduced from the guess are reported via
+/// [`on_placement`](Self::on_placement). Like the clues, the guessed digit itself is not reported
+/// as a placement.
+///
+/// ```
+/// use sudoku::board::{Cell, Digit};
+/// use sudoku::{SolverObserver, Sudoku};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     placements: usize,
+///     guesses: usize,
+/// }
+///
+/// impl SolverObserver for Counter {
+///     fn on_placement(&mut self, _: Cell, _: Digit) {
+///         self.placements += 1;
+///     }
+///
+///     fn on_guess(&mut self, _: Cell, _: Digit) {
+///         self.guesses += 1;
+///     }
+/// }
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let mut counter = Counter::default();
+/// let solutions = sudoku.solutions_up_to_observed(1, &mut counter);
+/// assert_eq!(solutions.len(), 1);
+/// // without guesses, every empty cell is placed exactly once
+/// if counter.guesses == 0 {
+///     assert_eq!(counter.placements, 81 - sudoku.n_clues() as usize);
+/// }
+/// ```
+pub trait SolverObserver {
+    /// Called when `digit` has been deduced for `cell`.
+    ///
+    /// The digits deduced in one step of the solver are reported together, in order of the cells.
+    fn on_placement(&mut self, _cell: Cell, _digit: Digit) {}
+
+    /// Called when the solver tentatively enters `digit` into `cell`.
+    fn on_guess(&mut self, _cell: Cell, _digit: Digit) {}
+
+    /// Called when the solver undoes the most recent guess that wasn't undone yet.
+    fn on_backtrack(&mut self) {}
+}
+
+/// Ignores all events
+impl SolverObserver for () {}
@@ -0,0 +1,183 @@
+//! Differential testing of the solver against a naive reference solver
+//!
+//! The solver of this crate is heavily optimized, which makes it sensitive to miscompilation
+//! and to differences between platforms, e.g. in FFI or WASM builds. [`run`] solves random grids
+//! with both the fast solver and a simple backtracking solver and reports every grid on which
+//! they disagree. Downstream integrators can call it in their own build environment to check
+//! that the solver works there.
+//!
+//! The random grids include uniquely solvable puzzles, puzzles with multiple solutions and
+//! unsolvable grids. Up to 3 solutions of each grid are compared.
+//!
+//! ```
+//! let report = sudoku::oracle::run(10);
+//! assert_eq!(report.n_grids, 10);
+//! assert!(report.divergences.is_empty());
+//! ```
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::bitset::{DigitSet, Set};
+use crate::board::{Cell, Digit};
+use crate::{Sudoku, Symmetry};
+
+// Enough to tell unique from multiple solutions
+const SOLUTION_LIMIT: usize = 3;
+
+/// Result of [`run`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// Number of grids that were solved by both solvers
+    pub n_grids: usize,
+    /// Grids on which the solvers disagree
+    pub divergences: Vec<Divergence>,
+}
+
+/// A grid on which the fast solver disagrees with the reference solver
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The grid
+    pub sudoku: Sudoku,
+    /// Solutions found by the reference solver, sorted
+    pub expected: Vec<Sudoku>,
+    /// Solutions found by the fast solver, sorted
+    pub actual: Vec<Sudoku>,
+}
+
+/// Compares the solvers on `n_grids` random grids, see the [module documentation](self).
+pub fn run(n_grids: usize) -> Report {
+    run_with_rng(n_grids, &mut rand::thread_rng())
+}
+
+/// Like [`run`], but draws all random numbers from `rng`, so failures can be reproduced.
+pub fn run_with_rng<R: Rng + ?Sized>(n_grids: usize, rng: &mut R) -> Report {
+    let divergences = (0..n_grids).filter_map(|_| check(random_grid(rng))).collect();
+    Report { n_grids, divergences }
+}
+
+/// Solves `sudoku` with both solvers. Returns the divergence, if they disagree.
+///
+/// The solvers disagree, if they find a different number of solutions up to 3 or, if there are
+/// less than 3 solutions, different solutions.
+pub fn check(sudoku: Sudoku) -> Option<Divergence> {
+    let mut expected = reference_solutions_up_to(sudoku, SOLUTION_LIMIT);
+    let mut actual = sudoku.solutions_up_to(SOLUTION_LIMIT);
+    expected.sort();
+    actual.sort();
+
+    let is_valid_solution = |solution: &Sudoku| {
+        solution.is_solved()
+            && sudoku
+                .iter()
+                .zip(solution.iter())
+                .all(|(clue, digit)| clue.is_none() || clue == digit)
+    };
+    let agrees = match expected.len() < SOLUTION_LIMIT {
+        true => actual == expected,
+        false => actual.len() == expected.len() && actual.iter().all(is_valid_solution),
+    };
+    (!agrees).then_some(Divergence {
+        sudoku,
+        expected,
+        actual,
+    })
+}
+
+/// Returns a random grid to [`check`]: a uniquely solvable puzzle, a puzzle with likely
+/// multiple solutions or an unsolvable puzzle, with equal probability.
+pub fn random_grid<R: Rng + ?Sized>(rng: &mut R) -> Sudoku {
+    match rng.gen_range(0..3) {
+        0 => {
+            let solution = Sudoku::generate_solved_with_rng(rng).into();
+            Sudoku::generate_with_symmetry_and_rng_from(solution, Symmetry::None, rng)
+        }
+        1 => {
+            let n_erased = rng.gen_range(50..65);
+            Sudoku::generate_solved_with_rng(rng)
+                .into_sudoku()
+                .erase_random_cells(n_erased, rng)
+        }
+        _ => {
+            // a puzzle with a wrong clue that doesn't duplicate any other clue,
+            // so the contradiction only shows up while solving
+            let solution = Sudoku::generate_solved_with_rng(rng).into_sudoku();
+            let mut grid = Sudoku::generate_with_symmetry_and_rng_from(solution, Symmetry::None, rng).0;
+            let empty_cells = Cell::all()
+                .filter(|cell| grid[cell.as_index()] == 0)
+                .collect::<Vec<_>>();
+            let cell = empty_cells[rng.gen_range(0..empty_cells.len())];
+            let solution_digit = Digit::new(solution.0[cell.as_index()]);
+            let wrong_digits = (!conflicting_digits(&grid, cell))
+                .without(solution_digit.as_set())
+                .into_iter()
+                .collect::<Vec<_>>();
+            if let Some(digit) = wrong_digits.choose(rng) {
+                grid[cell.as_index()] = digit.get();
+            }
+            Sudoku(grid)
+        }
+    }
+}
+
+/// Returns up to `limit` solutions of `sudoku`, found by plain backtracking.
+///
+/// Every empty cell is tried with every digit that doesn't conflict with its row, column and
+/// block, starting with the cell with the fewest such digits. A branch is abandoned as soon as a
+/// digit has no place left in some house. This is slow, but simple enough to serve as
+/// a reference for the fast solver.
+pub fn reference_solutions_up_to(sudoku: Sudoku, limit: usize) -> Vec<Sudoku> {
+    let mut solutions = vec![];
+    let mut grid = sudoku.to_bytes();
+    let has_duplicates = sudoku
+        .clues()
+        .any(|(cell, digit)| conflicting_digits(&grid, cell).contains(Digit::new(digit)));
+    if !has_duplicates {
+        backtrack(&mut grid, limit, &mut solutions);
+    }
+    solutions
+}
+
+fn backtrack(grid: &mut [u8; 81], limit: usize, solutions: &mut Vec<Sudoku>) {
+    if solutions.len() >= limit {
+        return;
+    }
+    // the digits that are placed or still possible in each house
+    let mut house_digits = [Set::NONE; 27];
+    let mut most_constrained: Option<(Cell, DigitSet)> = None;
+    for cell in Cell::all() {
+        let digits = match Digit::new_checked(grid[cell.as_index()]) {
+            Some(digit) => digit.as_set(),
+            None => {
+                let candidates = !conflicting_digits(grid, cell);
+                if most_constrained.map_or(10, |(_, fewest)| fewest.len()) > candidates.len() {
+                    most_constrained = Some((cell, candidates));
+                }
+                candidates
+            }
+        };
+        for house in cell.houses() {
+            house_digits[house.as_index()] |= digits;
+        }
+    }
+    // Without this check, unsolvable grids can take exponential time to refute
+    if house_digits.iter().any(|&digits| digits != Set::ALL) {
+        return;
+    }
+    let (cell, candidates) = match most_constrained {
+        Some(cell_and_candidates) => cell_and_candidates,
+        None => return solutions.push(Sudoku(*grid)),
+    };
+    for digit in candidates {
+        grid[cell.as_index()] = digit.get();
+        backtrack(grid, limit, solutions);
+    }
+    grid[cell.as_index()] = 0;
+}
+
+fn conflicting_digits(grid: &[u8; 81], cell: Cell) -> DigitSet {
+    cell.neighbors()
+        .into_iter()
+        .filter_map(|neighbor| Digit::new_checked(grid[neighbor.as_index()]))
+        .fold(Set::NONE, |digits, digit| digits | digit)
+}
@@ -0,0 +1,275 @@
+//! Printable puzzle sheets in PDF format
+//!
+//! [`write_pdf`] lays out a collection of sudokus on pages of a PDF document, e.g. for
+//! printing puzzle books. The number of puzzles per page, the page size and whether the
+//! solutions are appended on separate pages are configured with a [`PageLayout`].
+//!
+//! The documents only use vector graphics and the standard Helvetica font, so they are small
+//! and don't embed any font files.
+
+use crate::Sudoku;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+// Helvetica digits are all 0.556 em wide
+const DIGIT_WIDTH: f32 = 0.556;
+// Half of the height of Helvetica digits in em, for vertical centering
+const HALF_DIGIT_HEIGHT: f32 = 0.35;
+
+/// Layout of the pages created by [`write_pdf`]
+///
+/// All lengths are in PostScript points, i.e. 1/72 inch.
+///
+/// ```
+/// use sudoku::print::PageLayout;
+///
+/// // 6 puzzles per page on US letter paper, without solutions
+/// let layout = PageLayout::new()
+///     .with_page_size(612.0, 792.0)
+///     .with_grid(2, 3)
+///     .with_solutions(false);
+/// assert_eq!(layout.puzzles_per_page(), 6);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageLayout {
+    width: f32,
+    height: f32,
+    margin: f32,
+    columns: usize,
+    rows: usize,
+    solutions: bool,
+}
+
+impl Default for PageLayout {
+    fn default() -> Self {
+        PageLayout {
+            width: 595.0,
+            height: 842.0,
+            margin: 36.0,
+            columns: 2,
+            rows: 2,
+            solutions: true,
+        }
+    }
+}
+
+impl PageLayout {
+    /// Returns the default layout: 4 puzzles per page on A4 paper
+    /// with half-inch margins, followed by the solutions.
+    pub fn new() -> Self {
+        PageLayout::default()
+    }
+
+    /// Sets the width and height of the pages.
+    pub fn with_page_size(self, width: f32, height: f32) -> Self {
+        PageLayout {
+            width,
+            height,
+            ..self
+        }
+    }
+
+    /// Sets the empty space around the content of each page.
+    pub fn with_margin(self, margin: f32) -> Self {
+        PageLayout { margin, ..self }
+    }
+
+    /// Sets how many puzzles are placed next to each other and above each other on a page.
+    /// Both numbers are raised to at least 1.
+    pub fn with_grid(self, columns: usize, rows: usize) -> Self {
+        PageLayout {
+            columns: columns.max(1),
+            rows: rows.max(1),
+            ..self
+        }
+    }
+
+    /// Sets whether the solutions are printed on separate pages after all puzzles.
+    pub fn with_solutions(self, solutions: bool) -> Self {
+        PageLayout { solutions, ..self }
+    }
+
+    /// Returns the number of puzzles on each page.
+    pub fn puzzles_per_page(&self) -> usize {
+        self.columns * self.rows
+    }
+}
+
+/// Writes a PDF document with all `sudokus` laid out according to `layout` to `writer`.
+///
+/// The puzzles are numbered in order, starting from 1. If enabled, the solutions follow on
+/// separate pages in the same arrangement, with the givens in black and the solved cells in gray.
+/// Sudokus without a unique solution are repeated as is on the solution pages.
+/// Without any sudokus, the document consists of a single empty page.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::print::{write_pdf, PageLayout};
+///
+/// let sudokus = (0..6).map(|_| Sudoku::generate()).collect::<Vec<_>>();
+/// let mut pdf = vec![];
+/// write_pdf(&sudokus, &PageLayout::new(), &mut pdf).unwrap();
+/// assert!(pdf.starts_with(b"%PDF-"));
+/// ```
+pub fn write_pdf<W: Write>(sudokus: &[Sudoku], layout: &PageLayout, mut writer: W) -> io::Result<()> {
+    let mut pages = sudokus
+        .chunks(layout.puzzles_per_page())
+        .enumerate()
+        .map(|(page, sudokus)| page_content(sudokus, page * layout.puzzles_per_page(), layout, false))
+        .collect::<Vec<_>>();
+    if layout.solutions {
+        pages.extend(
+            sudokus
+                .chunks(layout.puzzles_per_page())
+                .enumerate()
+                .map(|(page, sudokus)| page_content(sudokus, page * layout.puzzles_per_page(), layout, true)),
+        );
+    }
+    if pages.is_empty() {
+        pages.push(String::new());
+    }
+    writer.write_all(&document(&pages, layout))
+}
+
+// Assembles the PDF from the content streams of all pages.
+//
+// Object 1 is the catalog, 2 the page tree and 3 the font.
+// Each page is followed by its content stream.
+fn document(pages: &[String], layout: &PageLayout) -> Vec<u8> {
+    let page_id = |page: usize| 4 + 2 * page;
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|page| format!("{} 0 R", page_id(page)))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len(),
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+    for (page, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            layout.width,
+            layout.height,
+            page_id(page) + 1,
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = vec![];
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", idx + 1, object).as_bytes());
+    }
+    let xref_offset = pdf.len();
+    let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(xref, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        xref,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    );
+    pdf.extend_from_slice(xref.as_bytes());
+    pdf
+}
+
+// Draws the `sudokus` of a single page.
+// `first_number` is the number of the first puzzle on the page, counting from 0.
+fn page_content(sudokus: &[Sudoku], first_number: usize, layout: &PageLayout, solutions: bool) -> String {
+    let label_size = 12.0;
+    let gap = 2.0 * label_size;
+    let (columns, rows) = (layout.columns as f32, layout.rows as f32);
+    let slot_width = (layout.width - 2.0 * layout.margin - (columns - 1.0) * gap) / columns;
+    let slot_height = (layout.height - 2.0 * layout.margin - (rows - 1.0) * gap) / rows;
+    let grid_size = slot_width.min(slot_height - 1.5 * label_size).max(0.0);
+
+    let mut content = String::new();
+    for (idx, &sudoku) in sudokus.iter().enumerate() {
+        let (column, row) = ((idx % layout.columns) as f32, (idx / layout.columns) as f32);
+        // center the grid horizontally in its slot and put the label on top
+        let left = layout.margin + column * (slot_width + gap) + (slot_width - grid_size) / 2.0;
+        let top = layout.height - layout.margin - row * (slot_height + gap);
+
+        let label = match solutions {
+            true => format!("Solution {}", first_number + idx + 1),
+            false => format!("{}", first_number + idx + 1),
+        };
+        let _ = writeln!(
+            content,
+            "0 g BT /F1 {} Tf {:.2} {:.2} Td ({}) Tj ET",
+            label_size,
+            left,
+            top - label_size,
+            label
+        );
+
+        let solution = match solutions {
+            true => sudoku.solution().map(Sudoku::from),
+            false => None,
+        };
+        draw_grid(
+            &mut content,
+            sudoku,
+            solution,
+            left,
+            top - 1.5 * label_size,
+            grid_size,
+        );
+    }
+    content
+}
+
+// Draws a sudoku grid with its top left corner at (`left`, `top`).
+// The cells that are only filled in `solution` are drawn in gray.
+fn draw_grid(content: &mut String, sudoku: Sudoku, solution: Option<Sudoku>, left: f32, top: f32, size: f32) {
+    let cell_size = size / 9.0;
+    for line in 0..=9 {
+        let width = match line % 3 {
+            0 => 2.0,
+            _ => 0.5,
+        };
+        let offset = line as f32 * cell_size;
+        let _ = writeln!(
+            content,
+            "{w} w {x:.2} {top:.2} m {x:.2} {bottom:.2} l S {left:.2} {y:.2} m {right:.2} {y:.2} l S",
+            w = width,
+            x = left + offset,
+            y = top - offset,
+            top = top,
+            bottom = top - size,
+            left = left,
+            right = left + size,
+        );
+    }
+
+    let font_size = cell_size * 0.6;
+    let digits = solution.unwrap_or(sudoku);
+    for (idx, (&digit, &given)) in digits.0.iter().zip(sudoku.0.iter()).enumerate() {
+        if digit == 0 {
+            continue;
+        }
+        let color = match given {
+            0 => "0.5 g",
+            _ => "0 g",
+        };
+        let (column, row) = ((idx % 9) as f32, (idx / 9) as f32);
+        let x = left + (column + 0.5) * cell_size - DIGIT_WIDTH / 2.0 * font_size;
+        let y = top - (row + 0.5) * cell_size - HALF_DIGIT_HEIGHT * font_size;
+        let _ = writeln!(
+            content,
+            "{} BT /F1 {:.2} Tf {:.2} {:.2} Td ({}) Tj ET",
+            color, font_size, x, y, digit
+        );
+    }
+}
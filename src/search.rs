@@ -0,0 +1,185 @@
+//! Local search for hard puzzles
+//!
+//! Hard puzzles are rare among randomly generated ones. [`HardPuzzleSearch`] looks for them
+//! among the minimal puzzles of a fixed solution by simulated annealing: a puzzle is repeatedly
+//! changed a little and the change is kept, if the [rating](DifficultyModel) doesn't drop too much.
+//! The search restarts from fresh random puzzles several times, so it doesn't get stuck
+//! near a single local maximum.
+
+use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::strategy::rating::{DifficultyModel, SeRating, SudokuExplainer};
+use crate::strategy::StrategySolver;
+use crate::{SolvedSudoku, Sudoku, Symmetry};
+
+/// Simulated annealing over the clue sets of a solution, maximizing the difficulty
+///
+/// Every step adds [a few](Self::with_added_clues) random clues of the solution to the current
+/// puzzle and removes clues in random order until it is minimal again. Harder puzzles are always
+/// accepted. Easier ones are accepted with a probability of `exp(-loss / temperature)`, where the
+/// temperature decreases linearly from [its initial value](Self::with_temperature) to 0 over the
+/// steps of each restart.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::search::HardPuzzleSearch;
+///
+/// let solution = Sudoku::generate_solved();
+/// let result = HardPuzzleSearch::new()
+///     .with_restarts(2)
+///     .with_steps(5)
+///     .run(solution);
+/// assert_eq!(result.sudoku.solution(), Some(solution));
+/// assert_eq!(result.n_ratings, 2 * (5 + 1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HardPuzzleSearch<R = ThreadRng> {
+    rng: R,
+    n_restarts: usize,
+    n_steps: usize,
+    n_added_clues: usize,
+    temperature: f32,
+}
+
+/// The hardest puzzle found by a [`HardPuzzleSearch`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    /// The hardest puzzle. It is uniquely solvable and minimal.
+    pub sudoku: Sudoku,
+    /// The rating of the puzzle
+    pub rating: SeRating,
+    /// Number of puzzles that were rated during the search
+    pub n_ratings: usize,
+}
+
+impl HardPuzzleSearch {
+    /// Creates a search with 10 restarts of 100 steps each that adds 2 clues per step,
+    /// starts at a temperature of 1.0 and uses the thread local random number generator.
+    pub fn new() -> Self {
+        HardPuzzleSearch {
+            rng: rand::thread_rng(),
+            n_restarts: 10,
+            n_steps: 100,
+            n_added_clues: 2,
+            temperature: 1.0,
+        }
+    }
+}
+
+impl Default for HardPuzzleSearch {
+    fn default() -> Self {
+        HardPuzzleSearch::new()
+    }
+}
+
+impl<R: Rng> HardPuzzleSearch<R> {
+    /// Draws all random numbers from `rng`, e.g. for reproducible searches.
+    pub fn with_rng<R2: Rng>(self, rng: R2) -> HardPuzzleSearch<R2> {
+        HardPuzzleSearch {
+            rng,
+            n_restarts: self.n_restarts,
+            n_steps: self.n_steps,
+            n_added_clues: self.n_added_clues,
+            temperature: self.temperature,
+        }
+    }
+
+    /// Sets how often the search starts over from a random puzzle.
+    pub fn with_restarts(self, n_restarts: usize) -> Self {
+        HardPuzzleSearch { n_restarts, ..self }
+    }
+
+    /// Sets the number of steps after each restart.
+    pub fn with_steps(self, n_steps: usize) -> Self {
+        HardPuzzleSearch { n_steps, ..self }
+    }
+
+    /// Sets how many clues are added in each step. Larger values allow bigger changes.
+    pub fn with_added_clues(self, n_added_clues: usize) -> Self {
+        HardPuzzleSearch {
+            n_added_clues,
+            ..self
+        }
+    }
+
+    /// Sets the initial temperature in units of the rating.
+    /// A temperature of 0 turns the search into plain hill climbing.
+    pub fn with_temperature(self, temperature: f32) -> Self {
+        HardPuzzleSearch { temperature, ..self }
+    }
+
+    /// Searches for the hardest puzzle with the given `solution` according to the
+    /// [`SudokuExplainer`] model.
+    pub fn run(&mut self, solution: SolvedSudoku) -> SearchResult {
+        self.run_with_model(solution, &SudokuExplainer)
+    }
+
+    /// Searches for the hardest puzzle with the given `solution` according to `model`.
+    pub fn run_with_model<M: DifficultyModel + ?Sized>(
+        &mut self,
+        solution: SolvedSudoku,
+        model: &M,
+    ) -> SearchResult {
+        let mut n_ratings = 0;
+        let mut rate = |sudoku: Sudoku| {
+            n_ratings += 1;
+            StrategySolver::from_sudoku(sudoku)
+                .assume_unique_solution(true)
+                .rate_with(model)
+        };
+        let mut best: Option<(Sudoku, SeRating)> = None;
+
+        for _ in 0..self.n_restarts {
+            let mut sudoku =
+                Sudoku::generate_with_symmetry_and_rng_from(solution.into(), Symmetry::None, &mut self.rng);
+            let mut rating = rate(sudoku);
+            for step in 0..self.n_steps {
+                let temperature = self.temperature * (1.0 - step as f32 / self.n_steps as f32);
+                let neighbor = self.neighbor(sudoku, solution);
+                let neighbor_rating = rate(neighbor);
+                let loss = rating.value() - neighbor_rating.value();
+                if loss <= 0.0 || temperature > 0.0 && self.rng.gen::<f32>() < (-loss / temperature).exp() {
+                    sudoku = neighbor;
+                    rating = neighbor_rating;
+                }
+                if is_harder(rating, best) {
+                    best = Some((sudoku, rating));
+                }
+            }
+            if is_harder(rating, best) {
+                best = Some((sudoku, rating));
+            }
+        }
+
+        let (sudoku, rating) = best.unwrap_or_else(|| {
+            let sudoku =
+                Sudoku::generate_with_symmetry_and_rng_from(solution.into(), Symmetry::None, &mut self.rng);
+            (sudoku, rate(sudoku))
+        });
+        SearchResult {
+            sudoku,
+            rating,
+            n_ratings,
+        }
+    }
+
+    // Adds random clues of the solution and minimizes the puzzle again
+    fn neighbor(&mut self, sudoku: Sudoku, solution: SolvedSudoku) -> Sudoku {
+        let mut empty_cells = (0..81).filter(|&cell| sudoku.0[cell] == 0).collect::<Vec<_>>();
+        empty_cells.shuffle(&mut self.rng);
+        let mut neighbor = sudoku;
+        for &cell in empty_cells.iter().take(self.n_added_clues) {
+            neighbor.0[cell] = solution.to_bytes()[cell];
+        }
+        Sudoku::generate_with_symmetry_and_rng_from(neighbor, Symmetry::None, &mut self.rng)
+    }
+}
+
+fn is_harder(rating: SeRating, best: Option<(Sudoku, SeRating)>) -> bool {
+    match best {
+        Some((_, best_rating)) => rating.value() > best_rating.value(),
+        None => true,
+    }
+}
@@ -0,0 +1,159 @@
+//! Framework-agnostic handlers for a web API
+//!
+//! The request and response types can be (de)serialized with serde, e.g. as JSON, and the
+//! handlers [`generate`] and [`solve`] turn requests into responses. Serving the API with any web
+//! framework only requires deserializing the request body, calling the handler and serializing the
+//! result, using [`ServiceError::http_status`] for failures.
+//!
+//! Solving is limited to a fixed amount of work, so requests with hard or nearly empty grids can't
+//! occupy a server for long.
+//!
+//! ```
+//! use sudoku::service::{self, SolveRequest, SolveStatus};
+//!
+//! let body = r#"{ "puzzle": "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1..." }"#;
+//! let request: SolveRequest = serde_json::from_str(body).unwrap();
+//! let response = service::solve(&request).unwrap();
+//! assert_eq!(response.status, SolveStatus::Unique);
+//! let json = serde_json::to_string(&response).unwrap();
+//! assert!(json.starts_with(r#"{"status":"unique","solutions":["#));
+//! ```
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+use crate::batch::{Difficulty, Grade};
+use crate::errors::ServiceError;
+use crate::generator::{SudokuGenerator, MAX_ATTEMPTS_ON_DEMAND};
+use crate::strategy::rating::SeRating;
+use crate::{SolvedSudoku, Sudoku};
+
+// Limits the work per request
+const MAX_NODES: u64 = 1_000_000;
+const MAX_SOLUTIONS: usize = 100;
+
+/// Request for [`generate`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerateRequest {
+    /// Difficulty of the puzzle. Any difficulty, if absent.
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    /// Seed for the random numbers, so the same puzzle can be generated again.
+    /// Random, if absent.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Response of [`generate`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerateResponse {
+    /// The uniquely solvable puzzle
+    pub puzzle: Sudoku,
+    /// Its solution
+    pub solution: SolvedSudoku,
+    /// Its difficulty
+    pub difficulty: Difficulty,
+    /// Its rating, see [`Grade`]
+    pub se_rating: Option<SeRating>,
+}
+
+/// Request for [`solve`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveRequest {
+    /// The puzzle in line or block format, see [`Sudoku::parse_all`]
+    pub puzzle: String,
+    /// Maximum number of solutions to return, from 1 to 100. 1, if absent.
+    #[serde(default)]
+    pub max_solutions: Option<usize>,
+}
+
+/// Whether a puzzle has a solution and if so, whether it is unique
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveStatus {
+    /// The puzzle has no solution.
+    Unsolvable,
+    /// The puzzle has exactly one solution.
+    Unique,
+    /// The puzzle has more than one solution.
+    Multiple,
+}
+
+/// Response of [`solve`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveResponse {
+    /// Number of solutions
+    pub status: SolveStatus,
+    /// Up to [`max_solutions`](SolveRequest::max_solutions) solutions
+    pub solutions: Vec<SolvedSudoku>,
+}
+
+/// Generates a random puzzle, see [`GenerateRequest`].
+///
+/// Up to 1000 puzzles are generated until one of the requested difficulty is found.
+/// Fails with [`ServiceError::GenerationFailed`], if none is. This is likely for
+/// [`Difficulty::Unrated`] and always the case for [`Difficulty::Invalid`].
+pub fn generate(request: &GenerateRequest) -> Result<GenerateResponse, ServiceError> {
+    let rng = match request.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut builder = SudokuGenerator::builder()
+        .with_rng(rng)
+        .with_max_attempts(MAX_ATTEMPTS_ON_DEMAND);
+    if let Some(difficulty) = request.difficulty {
+        builder = builder.with_difficulty(difficulty..=difficulty);
+    }
+    let puzzle = builder.generate().map_err(|_| ServiceError::GenerationFailed)?;
+    let grade = Grade::new(puzzle);
+    Ok(GenerateResponse {
+        puzzle,
+        solution: puzzle
+            .solution()
+            .expect("generated puzzles are uniquely solvable"),
+        difficulty: grade.difficulty,
+        se_rating: grade.se_rating,
+    })
+}
+
+/// Solves the puzzle of `request`, see [`SolveRequest`].
+///
+/// Fails, if the puzzle can't be parsed, `max_solutions` is out of range or the puzzle is too
+/// hard to solve within the work limit. The latter only happens for grids with very few clues.
+pub fn solve(request: &SolveRequest) -> Result<SolveResponse, ServiceError> {
+    let max_solutions = request.max_solutions.unwrap_or(1);
+    if !(1..=MAX_SOLUTIONS).contains(&max_solutions) {
+        return Err(ServiceError::InvalidMaxSolutions { max_solutions });
+    }
+    let sudoku = match Sudoku::parse_all(&request.puzzle).next() {
+        Some(Ok(sudoku)) => sudoku,
+        Some(Err(err)) => {
+            return Err(ServiceError::InvalidPuzzle {
+                message: err.to_string(),
+            })
+        }
+        None => {
+            return Err(ServiceError::InvalidPuzzle {
+                message: "no puzzle found".to_string(),
+            })
+        }
+    };
+
+    // at least 2 to tell unique from multiple solutions
+    let (mut solutions, _) = sudoku
+        .solutions_up_to_limited(max_solutions.max(2), MAX_NODES)
+        .map_err(|_| ServiceError::SearchLimitReached)?;
+    let status = match solutions.len() {
+        0 => SolveStatus::Unsolvable,
+        1 => SolveStatus::Unique,
+        _ => SolveStatus::Multiple,
+    };
+    solutions.truncate(max_solutions);
+    let solutions = solutions
+        .into_iter()
+        .map(|solution| SolvedSudoku::try_from(solution).expect("solutions are solved"))
+        .collect();
+    Ok(SolveResponse { status, solutions })
+}
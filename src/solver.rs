@@ -1,3 +1,9 @@
+//! The backtracking solver behind [`Sudoku::solution`] and related methods
+//!
+//! The methods on [`Sudoku`] build a new solver for every call. [`Solver`] gives direct access to it,
+//! so clues can be inserted one at a time, the same grid can be searched repeatedly and the
+//! work of all searches can be inspected with [`Solver::stats`].
+
 //  This solver is based on JCZsolve,
 //  which is currently (2018-06-26) and to the best of my (emerentius) knowledge the
 //  fastest sudoku solver algorithm.
@@ -38,15 +44,379 @@
 //  for a port under the AGPLv3 license in the forum thread
 //      http://forum.enjoysudoku.com/3-77us-solver-2-8g-cpu-testcase-17sodoku-t30470-270.html#p262718
 
+mod dlx;
+
+use crate::bitset::Set;
+use crate::board::{Cell, Digit};
+use crate::errors::{ClueConflict, Interrupted};
 use crate::helper::Unsolvable;
+use crate::CancellationToken;
+use crate::SolverObserver;
 use crate::Sudoku;
 use crunchy::unroll;
+use std::time::Instant;
+
+pub(crate) use self::dlx::Dlx;
+
+/// The algorithm used for finding solutions
+///
+/// Passed to [`Sudoku::solutions_up_to_with_backend`] and
+/// [`Sudoku::solutions_up_to_with_constraints_and_backend`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SolverBackend {
+    /// The optimized solver for regular sudokus. Other
+    /// [constraints](crate::board::ConstraintSet) use a simple backtracking search.
+    #[default]
+    Fast,
+    /// Knuth's Algorithm X with dancing links, which solves sudokus as an
+    /// [exact cover problem](https://en.wikipedia.org/wiki/Exact_cover).
+    ///
+    /// Much slower than [`Fast`](Self::Fast) on regular sudokus, but handles arbitrary houses
+    /// well and is useful to cross-check the results of the fast solver.
+    Dlx,
+}
+
+/// Conditions under which a search is aborted before `limit` solutions are found
+pub(crate) struct Budget<'a> {
+    deadline: Option<Instant>,
+    token: Option<&'a CancellationToken>,
+    max_nodes: u64,
+    nodes: u64,
+    interrupted: Option<Interrupted>,
+}
+
+impl<'a> Budget<'a> {
+    /// Budget that never runs out
+    pub fn unlimited() -> Self {
+        Budget {
+            deadline: None,
+            token: None,
+            max_nodes: u64::MAX,
+            nodes: 0,
+            interrupted: None,
+        }
+    }
+
+    /// Abort the search once `max_nodes` nodes of the search tree have been visited
+    pub fn with_max_nodes(self, max_nodes: u64) -> Self {
+        Budget { max_nodes, ..self }
+    }
+
+    /// Number of nodes of the search tree visited so far
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Abort the search once `deadline` has passed
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Budget {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Abort the search once `token` is cancelled
+    pub fn with_token(self, token: &'a CancellationToken) -> Self {
+        Budget {
+            token: Some(token),
+            ..self
+        }
+    }
+
+    /// Budget for a search on another thread with the same deadline, token and remaining nodes
+    #[cfg(any(feature = "threads", test))]
+    pub fn fork(&self) -> Self {
+        Budget {
+            max_nodes: self.max_nodes - self.nodes,
+            nodes: 0,
+            ..*self
+        }
+    }
+
+    /// Account for the nodes visited and the interruption of a forked budget
+    #[cfg(any(feature = "threads", test))]
+    pub fn join(&mut self, fork: Budget) {
+        self.nodes += fork.nodes;
+        self.interrupted = self.interrupted.or(fork.interrupted);
+    }
+
+    /// Returns the reason for the interruption, if the search was aborted
+    pub fn result(&self) -> Result<(), Interrupted> {
+        self.interrupted.map_or(Ok(()), Err)
+    }
+
+    /// Checks the token and deadline outside of a search, without counting a node
+    pub fn poll(&mut self) -> Result<(), Interrupted> {
+        self.check_interruption();
+        self.result()
+    }
+
+    fn is_interrupted(&self) -> bool {
+        self.interrupted.is_some()
+    }
+
+    fn check_interruption(&mut self) {
+        if self.interrupted.is_none() {
+            if self.nodes == self.max_nodes {
+                self.interrupted = Some(Interrupted::NodeLimitReached);
+            } else if matches!(self.token, Some(token) if token.is_cancelled()) {
+                self.interrupted = Some(Interrupted::Cancelled);
+            } else if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+                self.interrupted = Some(Interrupted::DeadlineExceeded);
+            }
+        }
+    }
+
+    // Called once per node of the search tree.
+    #[inline]
+    fn is_exhausted(&mut self) -> bool {
+        self.check_interruption();
+        self.nodes += !self.is_interrupted() as u64;
+        self.is_interrupted()
+    }
+}
 
 // masks of 27 bits
 const NONE: u32 = 0;
 const ALL: u32 = 0o777_777_777;
 const LOW9: u32 = 0o000_000_777;
 
+/// Reusable storage for the solutions found by [`Sudoku::solutions_up_to_reusing`]
+///
+/// The solver itself works entirely on the stack. Keeping the solutions in the same buffer across
+/// many puzzles means that, once the buffer has grown to the largest `limit`, solving no longer
+/// allocates at all.
+///
+/// ```
+/// use sudoku::{SolveBuffer, Sudoku};
+///
+/// let mut buffer = SolveBuffer::with_capacity(2);
+/// for _ in 0..3 {
+///     let sudoku = Sudoku::generate();
+///     let solutions = sudoku.solutions_up_to_reusing(&mut buffer, 2);
+///     assert_eq!(solutions.len(), 1);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SolveBuffer {
+    solutions: Vec<Sudoku>,
+}
+
+impl SolveBuffer {
+    /// Creates an empty buffer. It allocates on first use.
+    pub fn new() -> Self {
+        SolveBuffer::default()
+    }
+
+    /// Creates an empty buffer with space for `capacity` solutions
+    pub fn with_capacity(capacity: usize) -> Self {
+        SolveBuffer {
+            solutions: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the solutions found by the last search
+    pub fn solutions(&self) -> &[Sudoku] {
+        &self.solutions
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.solutions.clear();
+    }
+}
+
+/// Statistics of all searches run by a [`Solver`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SolverStats {
+    /// Number of searches for solutions
+    pub n_searches: u64,
+    /// Number of nodes of the search trees visited over all searches.
+    /// Each guess the solver has to make counts as one node, plus one for the initial grid.
+    pub n_nodes: u64,
+    /// Number of solutions found over all searches
+    pub n_solutions: u64,
+}
+
+/// A sudoku solver that can be driven directly
+///
+/// Clues are inserted once, either all at once from a [`Sudoku`] or one at a time with
+/// [`insert_clue`](Self::insert_clue). Searching for solutions does not change the clues,
+/// so the same solver can be searched again, e.g. after every inserted clue.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Cell, Digit};
+/// use sudoku::solver::Solver;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let solution = sudoku.solution().unwrap().to_bytes();
+///
+/// let mut solver = Solver::new();
+/// for (cell, digit) in sudoku.clues() {
+///     solver.insert_clue(cell, Digit::new(digit)).unwrap();
+/// }
+/// assert_eq!(solver.solutions_count_up_to(2), 1);
+///
+/// // a clue that contradicts the others is rejected and leaves the solver unchanged
+/// assert!(solver.insert_clue(Cell::new(0), Digit::new(2)).is_err());
+/// assert_eq!(solver.solutions_up_to(2), [Sudoku::from_bytes(solution).unwrap()]);
+///
+/// assert_eq!(solver.stats().n_searches, 2);
+/// assert_eq!(solver.stats().n_solutions, 2);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Solver {
+    inner: SudokuSolver,
+    stats: SolverStats,
+}
+
+impl Solver {
+    /// Solver for the empty grid
+    pub fn new() -> Self {
+        Solver {
+            inner: SudokuSolver::empty(),
+            stats: SolverStats::default(),
+        }
+    }
+
+    /// Solver for the clues of `sudoku`.
+    /// Errors, if a clue conflicts with another one.
+    pub fn from_sudoku(sudoku: Sudoku) -> Result<Self, ClueConflict> {
+        let inner = SudokuSolver::from_sudoku(sudoku).map_err(|_| ClueConflict)?;
+        Ok(Solver {
+            inner,
+            stats: SolverStats::default(),
+        })
+    }
+
+    /// Solver for the clues of `sudoku` in which each cell may only contain the digits in its
+    /// entry of `candidates`.
+    /// Errors, if a clue conflicts with another one or is not among the candidates of its cell.
+    pub fn from_sudoku_with_candidates(
+        sudoku: Sudoku,
+        candidates: &[Set<Digit>; 81],
+    ) -> Result<Self, ClueConflict> {
+        let inner =
+            SudokuSolver::from_sudoku_with_candidates(sudoku, candidates).map_err(|_| ClueConflict)?;
+        Ok(Solver {
+            inner,
+            stats: SolverStats::default(),
+        })
+    }
+
+    /// Enter `digit` into `cell`.
+    /// Errors and leaves the solver unchanged, if the clue conflicts with the previous ones.
+    pub fn insert_clue(&mut self, cell: Cell, digit: Digit) -> Result<(), ClueConflict> {
+        let mut inner = self.inner;
+        inner
+            .insert_candidate(cell.get(), digit.get())
+            .map_err(|_| ClueConflict)?;
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Whether every cell contains a clue
+    pub fn is_solved(&self) -> bool {
+        self.inner.is_solved()
+    }
+
+    /// Returns the statistics of all searches so far
+    pub fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    /// Find up to `limit` solutions and return their count
+    pub fn solutions_count_up_to(&mut self, limit: usize) -> usize {
+        let mut solutions = Solutions::Count(0);
+        self.search(limit, &mut solutions, &mut Budget::unlimited(), &mut ());
+        solutions.len()
+    }
+
+    /// Like [`solutions_count_up_to`](Self::solutions_count_up_to), but aborts the search
+    /// after visiting `max_nodes` nodes.
+    /// See [`Sudoku::solutions_up_to_limited`].
+    pub fn solutions_count_up_to_limited(
+        &mut self,
+        limit: usize,
+        max_nodes: u64,
+    ) -> Result<usize, Interrupted> {
+        let mut solutions = Solutions::Count(0);
+        let mut budget = Budget::unlimited().with_max_nodes(max_nodes);
+        self.search(limit, &mut solutions, &mut budget, &mut ());
+        budget.result().map(|()| solutions.len())
+    }
+
+    /// Find and return up to `limit` solutions
+    pub fn solutions_up_to(&mut self, limit: usize) -> Vec<Sudoku> {
+        self.solutions_up_to_observed(limit, &mut ())
+    }
+
+    /// Find and return up to `limit` solutions.
+    /// Reports the progress of the search to `observer`.
+    pub fn solutions_up_to_observed<O: SolverObserver>(
+        &mut self,
+        limit: usize,
+        observer: &mut O,
+    ) -> Vec<Sudoku> {
+        let mut solutions = vec![];
+        self.search(
+            limit,
+            &mut Solutions::Vector(&mut solutions),
+            &mut Budget::unlimited(),
+            observer,
+        );
+        solutions
+    }
+
+    /// Find up to `limit` solutions and store them in `buffer`, replacing its previous contents.
+    /// See [`SolveBuffer`].
+    pub fn solutions_up_to_reusing<'b>(&mut self, buffer: &'b mut SolveBuffer, limit: usize) -> &'b [Sudoku] {
+        buffer.clear();
+        self.search(
+            limit,
+            &mut Solutions::Vector(&mut buffer.solutions),
+            &mut Budget::unlimited(),
+            &mut (),
+        );
+        buffer.solutions()
+    }
+
+    /// Count up to `limit` solutions and save up to `buffer.len()` of them in `buffer`.
+    /// Returns the number of solutions. Never allocates.
+    pub fn solutions_up_to_buffer(&mut self, buffer: &mut [[u8; 81]], limit: usize) -> usize {
+        let mut solutions = Solutions::Buffer(buffer, 0);
+        self.search(limit, &mut solutions, &mut Budget::unlimited(), &mut ());
+        solutions.len()
+    }
+
+    fn search<O: SolverObserver>(
+        &mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) {
+        self.inner._solutions_up_to(limit, solutions, budget, observer);
+        self.stats.n_searches += 1;
+        self.stats.n_nodes += budget.nodes();
+        self.stats.n_solutions += solutions.len() as u64;
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+impl std::fmt::Debug for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Solver")
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
 // When the solver finds a solution it can save it or just count.
 // The latter is marginally faster.
 enum Solutions<'a> {
@@ -113,15 +483,47 @@ pub(crate) struct SudokuSolver {
 }
 
 impl SudokuSolver {
-    // jczsolve equivalent: InitSudoku
-    pub fn from_sudoku(sudoku: Sudoku) -> Result<Self, Unsolvable> {
-        let mut solver = SudokuSolver {
+    /// Solver for the empty grid
+    pub fn empty() -> Self {
+        SudokuSolver {
             poss_cells: UncheckedIndexArray([ALL; 27]),
             prev_poss_cells: UncheckedIndexArray([0; 27]),
             unsolved_cells: UncheckedIndexArray([ALL; 3]),
             requirement_for_weird_optimization: UncheckedIndexArray([ALL; 3]),
             pairs: UncheckedIndexArray([0; 3]),
-        };
+        }
+    }
+
+    // jczsolve equivalent: InitSudoku
+    pub fn from_sudoku(sudoku: Sudoku) -> Result<Self, Unsolvable> {
+        let mut solver = SudokuSolver::empty();
+        for (cell, num) in (0..81).zip(sudoku.iter()) {
+            if let Some(num) = num {
+                solver.insert_candidate(cell, num)?;
+            }
+        }
+        Ok(solver)
+    }
+
+    /// Possible cells of each digit and band, indexed by subband `digit * 3 + band`
+    pub fn poss_cells(&self) -> &[u32; 27] {
+        &self.poss_cells.0
+    }
+
+    /// Solver for `sudoku` in which each cell is restricted to the digits of `candidates`.
+    /// Errors, if a clue is not among the candidates of its cell.
+    pub fn from_sudoku_with_candidates(
+        sudoku: Sudoku,
+        candidates: &[Set<Digit>; 81],
+    ) -> Result<Self, Unsolvable> {
+        let mut solver = SudokuSolver::empty();
+        for (cell, &digits) in (0..81).zip(candidates.iter()) {
+            let band = cell / 27;
+            let cell_mask = 1 << (cell % 27);
+            for digit in Set::<Digit>::ALL.without(digits) {
+                solver.poss_cells[digit.as_index() * 3 + band] &= !cell_mask;
+            }
+        }
         for (cell, num) in (0..81).zip(sudoku.iter()) {
             if let Some(num) = num {
                 solver.insert_candidate(cell, num)?;
@@ -132,36 +534,122 @@ impl SudokuSolver {
 
     /// Find and return up to `limit` solutions
     pub fn solutions_up_to(self, limit: usize) -> Vec<Sudoku> {
+        self.solutions_up_to_with_budget(limit, &mut Budget::unlimited())
+    }
+
+    /// Find and return up to `limit` solutions or less, if the `budget` runs out
+    pub fn solutions_up_to_with_budget(self, limit: usize, budget: &mut Budget) -> Vec<Sudoku> {
+        self.solutions_up_to_observed(limit, budget, &mut ())
+    }
+
+    /// Find and return up to `limit` solutions or less, if the `budget` runs out.
+    /// Reports the progress of the search to `observer`.
+    pub fn solutions_up_to_observed<O: SolverObserver>(
+        self,
+        limit: usize,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) -> Vec<Sudoku> {
         let mut solutions = vec![];
-        self._solutions_up_to(limit, &mut Solutions::Vector(&mut solutions));
+        self._solutions_up_to(limit, &mut Solutions::Vector(&mut solutions), budget, observer);
         solutions
     }
 
+    /// Find up to `limit` solutions and store them in `buffer`, replacing its previous contents
+    pub fn solutions_up_to_reusing(self, buffer: &mut SolveBuffer, limit: usize) {
+        buffer.clear();
+        self._solutions_up_to(
+            limit,
+            &mut Solutions::Vector(&mut buffer.solutions),
+            &mut Budget::unlimited(),
+            &mut (),
+        );
+    }
+
     /// Count up to `limit` solutions and save up to buffer.len() of them
     /// in `buffer`. Returns number of solutions.
     pub fn solutions_up_to_buffer(self, buffer: &mut [[u8; 81]], limit: usize) -> usize {
         let mut solutions = Solutions::Buffer(buffer, 0);
-        self._solutions_up_to(limit, &mut solutions);
+        self._solutions_up_to(limit, &mut solutions, &mut Budget::unlimited(), &mut ());
         solutions.len()
     }
 
     /// Find up to `limit` solutions and return count
     pub fn solutions_count_up_to(self, limit: usize) -> usize {
+        self.solutions_count_up_to_with_budget(limit, &mut Budget::unlimited())
+    }
+
+    /// Find up to `limit` solutions or less, if the `budget` runs out, and return count
+    pub fn solutions_count_up_to_with_budget(self, limit: usize, budget: &mut Budget) -> usize {
         let mut solutions = Solutions::Count(0);
-        self._solutions_up_to(limit, &mut solutions);
+        self._solutions_up_to(limit, &mut solutions, budget, &mut ());
         solutions.len()
     }
 
-    fn _solutions_up_to(mut self, limit: usize, solutions: &mut Solutions) {
+    /// For each `(cell, digit)` in `clues`, count up to `limit` solutions of the grid
+    /// that results from inserting all other `clues` into this solver.
+    /// The counts are written into `counts` in the same order as `clues`.
+    //
+    // Solver states are shared by recursively splitting the clues in half
+    // and inserting one half before descending into the other.
+    // This takes O(n log n) insertions rather than the O(n²) needed for
+    // building a fresh solver for each removed clue.
+    pub fn solutions_count_up_to_without_each(self, clues: &[(u8, u8)], limit: usize, counts: &mut [usize]) {
+        debug_assert_eq!(clues.len(), counts.len());
+        match clues.len() {
+            0 => {}
+            1 => counts[0] = self.solutions_count_up_to(limit),
+            n_clues => {
+                let (left, right) = clues.split_at(n_clues / 2);
+                let (left_counts, right_counts) = counts.split_at_mut(n_clues / 2);
+                for (half, half_counts, other_half) in
+                    [(left, left_counts, right), (right, right_counts, left)]
+                {
+                    let mut solver = self;
+                    let inserted = other_half
+                        .iter()
+                        .try_for_each(|&(cell, digit)| solver.insert_candidate(cell, digit));
+                    match inserted {
+                        Ok(()) => solver.solutions_count_up_to_without_each(half, limit, half_counts),
+                        // the remaining clues conflict, no matter which of `half` is removed
+                        Err(Unsolvable) => half_counts.iter_mut().for_each(|count| *count = 0),
+                    }
+                }
+            }
+        }
+    }
+
+    /// For each digit, count up to `limit` solutions of the grid that results from inserting
+    /// the digit in `cell` into this solver. Digits that can't be inserted have no solutions.
+    pub fn solutions_count_up_to_with_each_digit(self, cell: u8, limit: usize) -> [usize; 9] {
+        let mut counts = [0; 9];
+        for (digit, count) in (1..=9).zip(counts.iter_mut()) {
+            let mut solver = self;
+            if solver.insert_candidate(cell, digit).is_ok() {
+                *count = solver.solutions_count_up_to(limit);
+            }
+        }
+        counts
+    }
+
+    fn _solutions_up_to<O: SolverObserver>(
+        mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) {
+        let unsolved_cells = self.unsolved_cells;
         if self.find_naked_singles().is_err() {
             return;
         }
 
         // either solved or impossible
-        if self._solve(limit, solutions).is_err() {
+        if self._solve(limit, solutions, budget).is_err() {
             return;
         }
-        self.guess(limit, solutions);
+        self.report_placements(unsolved_cells, observer);
+        self.guess(limit, solutions, budget, observer);
     }
 
     pub(crate) fn is_solved(&self) -> bool {
@@ -171,9 +659,14 @@ impl SudokuSolver {
     /// Repeatedly use the strategies and backtracking to find solutions until
     /// the limit is reached or no more solutions exist.
     // jczsolve equivalent: FullUpdate
-    fn _solve(&mut self, limit: usize, solutions: &mut Solutions) -> Result<(), Unsolvable> {
+    fn _solve(
+        &mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+    ) -> Result<(), Unsolvable> {
         debug_assert!(solutions.len() <= limit);
-        if solutions.len() == limit {
+        if solutions.len() == limit || budget.is_exhausted() {
             return Err(Unsolvable); // not really, but it forces a recursion stop
         }
         loop {
@@ -339,8 +832,31 @@ impl SudokuSolver {
         Ok(())
     }
 
+    /// Report the cells that were unsolved in `previously_unsolved` but are solved now.
+    fn report_placements<O: SolverObserver>(
+        &self,
+        previously_unsolved: UncheckedIndexArray<u32, 3>,
+        observer: &mut O,
+    ) {
+        for band in 0..3 {
+            for cell_mask in mask_iter(previously_unsolved[band] & !self.unsolved_cells[band]) {
+                let cell = band * 27 + bit_pos(cell_mask);
+                let digit = (0..9)
+                    .find(|&digit| self.poss_cells[digit * 3 + band] & cell_mask != NONE)
+                    .expect("solved cell without digit");
+                observer.on_placement(Cell::new(cell as u8), Digit::new(digit as u8 + 1));
+            }
+        }
+    }
+
     // jczsolve equivalent: Guess
-    fn guess(&mut self, limit: usize, solutions: &mut Solutions) {
+    fn guess<O: SolverObserver>(
+        &mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) {
         if self.is_solved() {
             debug_assert!(solutions.len() < limit);
             match solutions {
@@ -353,10 +869,38 @@ impl SudokuSolver {
                     *len += 1;
                 }
             }
-        } else if self.guess_bivalue_in_cell(limit, solutions).is_ok() {
+        } else if self
+            .guess_bivalue_in_cell(limit, solutions, budget, observer)
+            .is_ok()
+        {
             // .is_ok() == found nothing
-            self.guess_some_cell(limit, solutions);
+            self.guess_some_cell(limit, solutions, budget, observer);
+        }
+    }
+
+    /// Enter the digit of `subband` into the cell of `cell_mask` and search for solutions from there.
+    fn guess_candidate<O: SolverObserver>(
+        &mut self,
+        subband: usize,
+        cell_mask: u32,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) {
+        let band = subband % 3;
+        let cell = band * 27 + bit_pos(cell_mask);
+        observer.on_guess(Cell::new(cell as u8), Digit::new((subband / 3) as u8 + 1));
+        // the guessed cell is reported by `on_guess`, not as a placement
+        let mut unsolved_cells = self.unsolved_cells;
+        unsolved_cells[band] &= !cell_mask;
+
+        self.insert_candidate_by_mask(subband, cell_mask);
+        if self._solve(limit, solutions, budget).is_ok() {
+            self.report_placements(unsolved_cells, observer);
+            self.guess(limit, solutions, budget, observer);
         }
+        observer.on_backtrack();
     }
 
     /// Find some cell with only 2 possible values and try both in order.
@@ -365,7 +909,13 @@ impl SudokuSolver {
     // with only 2 possibilities. These positions are found and saved when
     // looking for naked singles.
     // For that reason, finding such a cell is practically just a lookup.
-    fn guess_bivalue_in_cell(&mut self, limit: usize, solutions: &mut Solutions) -> Result<(), Unsolvable> {
+    fn guess_bivalue_in_cell<O: SolverObserver>(
+        &mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) -> Result<(), Unsolvable> {
         for band in 0..3 {
             // get first bivalue cell, if it exists
             let cell_mask = match mask_iter(self.pairs[band]).next() {
@@ -384,16 +934,10 @@ impl SudokuSolver {
                     if first {
                         first = false;
                         let mut solver = *self;
-                        solver.insert_candidate_by_mask(subband, cell_mask);
-                        if solver._solve(limit, solutions).is_ok() {
-                            solver.guess(limit, solutions);
-                        }
+                        solver.guess_candidate(subband, cell_mask, limit, solutions, budget, observer);
                         self.poss_cells[subband] ^= cell_mask;
                     } else {
-                        self.insert_candidate_by_mask(subband, cell_mask);
-                        if self._solve(limit, solutions).is_ok() {
-                            self.guess(limit, solutions);
-                        }
+                        self.guess_candidate(subband, cell_mask, limit, solutions, budget, observer);
                         return Err(Unsolvable);
                     }
                 }
@@ -417,7 +961,13 @@ impl SudokuSolver {
     // jczsolve_equivalent: GuessFirstCell, sort of
     //                      jczsolve picks the first unsolved cell it can find
     //                      This fn checks up to 3 cells as explained above
-    fn guess_some_cell(&mut self, limit: usize, solutions: &mut Solutions) {
+    fn guess_some_cell<O: SolverObserver>(
+        &mut self,
+        limit: usize,
+        solutions: &mut Solutions,
+        budget: &mut Budget,
+        observer: &mut O,
+    ) {
         let best_guess = (0..3)
             .flat_map(|band| {
                 // get first unsolved cell, if it exists
@@ -439,11 +989,8 @@ impl SudokuSolver {
         while subband < 27 {
             if self.poss_cells[subband] & unsolved_cell != NONE {
                 let mut solver = *self;
-                solver.insert_candidate_by_mask(subband, unsolved_cell);
-                if solver._solve(limit, solutions).is_ok() {
-                    solver.guess(limit, solutions);
-                }
-                if solutions.len() == limit {
+                solver.guess_candidate(subband, unsolved_cell, limit, solutions, budget, observer);
+                if solutions.len() == limit || budget.is_interrupted() {
                     return;
                 }
                 self.poss_cells[subband] ^= unsolved_cell;
@@ -527,6 +1074,116 @@ impl SudokuSolver {
     }
 }
 
+/// A stack of solver states for checking many grids that differ only in a few clues
+///
+/// Every clue pushed saves the previous state, so that popping it again is only a copy.
+/// Checking a grid with one clue more or less than the previous one doesn't require building
+/// a new solver from all clues, which [`Solver`] and the methods on [`Sudoku`] do.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Cell, Digit};
+/// use sudoku::solver::IncrementalSolver;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let mut solver = IncrementalSolver::new();
+/// for (cell, digit) in sudoku.clues() {
+///     solver.push_clue(cell, Digit::new(digit)).unwrap();
+/// }
+/// assert_eq!(solver.solutions_count_up_to(2), 1);
+///
+/// // a contradicting clue is recorded, but makes the grid unsolvable until it is popped again
+/// assert!(solver.push_clue(Cell::new(0), Digit::new(2)).is_err());
+/// assert_eq!(solver.solutions_count_up_to(2), 0);
+/// solver.pop_clue();
+///
+/// // without its last clue, the puzzle has multiple solutions
+/// solver.pop_clue();
+/// assert_eq!(solver.solutions_count_up_to(2), 2);
+/// ```
+#[derive(Clone)]
+pub struct IncrementalSolver {
+    // `None` marks states that are known to be unsolvable
+    states: Vec<Option<SudokuSolver>>,
+}
+
+impl IncrementalSolver {
+    /// Solver for the empty grid
+    pub fn new() -> Self {
+        IncrementalSolver {
+            states: vec![Some(SudokuSolver::empty())],
+        }
+    }
+
+    fn current(&self) -> Option<SudokuSolver> {
+        *self.states.last().expect("base state is never popped")
+    }
+
+    /// Returns the number of clues that were pushed and not popped yet.
+    pub fn n_clues(&self) -> usize {
+        self.states.len() - 1
+    }
+
+    /// Enter `digit` into `cell` on top of the current clues.
+    /// Errors, if it conflicts with any previous clue.
+    /// The clue is recorded in either case and must be removed with [`pop_clue`](Self::pop_clue).
+    pub fn push_clue(&mut self, cell: Cell, digit: Digit) -> Result<(), ClueConflict> {
+        let state = self.current().and_then(|mut solver| {
+            solver.insert_candidate(cell.get(), digit.get()).ok()?;
+            Some(solver)
+        });
+        self.states.push(state);
+        state.map(drop).ok_or(ClueConflict)
+    }
+
+    /// Remove the clue that was pushed last.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if there are no clues left.
+    pub fn pop_clue(&mut self) {
+        assert!(self.states.len() > 1, "no clue to pop");
+        self.states.pop();
+    }
+
+    /// Enter `digit` into `cell` below all pushed clues, so it stays when they are popped.
+    /// Conflicts make the affected states unsolvable.
+    pub(crate) fn insert_base_clue(&mut self, cell: u8, digit: u8) {
+        for state in &mut self.states {
+            if let Some(solver) = state {
+                if solver.insert_candidate(cell, digit).is_err() {
+                    *state = None;
+                }
+            }
+        }
+    }
+
+    /// Count up to `limit` solutions of the grid formed by the current clues
+    pub fn solutions_count_up_to(&self, limit: usize) -> usize {
+        self.solutions_count_up_to_with_budget(limit, &mut Budget::unlimited())
+    }
+
+    pub(crate) fn solutions_count_up_to_with_budget(&self, limit: usize, budget: &mut Budget) -> usize {
+        self.current().map_or(0, |solver| {
+            solver.solutions_count_up_to_with_budget(limit, budget)
+        })
+    }
+}
+
+impl Default for IncrementalSolver {
+    fn default() -> Self {
+        IncrementalSolver::new()
+    }
+}
+
+impl std::fmt::Debug for IncrementalSolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("IncrementalSolver")
+            .field("n_clues", &self.n_clues())
+            .finish_non_exhaustive()
+    }
+}
+
 // jczsolve equivalent: TblSelfMask
 #[inline]
 fn nonconflicting_cells_same_band(cell: usize) -> u32 {
@@ -0,0 +1,286 @@
+//! Exact cover solver using Knuth's Algorithm X with dancing links
+//!
+//! Every candidate (cell, digit) is a row. It covers the column of its cell and, for every house
+//! containing the cell, the column of its digit in that house. Cells and houses of 9 cells are
+//! primary columns that must be covered exactly once. Smaller houses only forbid repeated digits,
+//! so their columns are secondary and covered at most once.
+
+use crate::bitset::Set;
+use crate::board::Cell;
+use crate::consts::N_CELLS;
+use crate::Sudoku;
+
+pub(crate) struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    // column header of each node, headers point to themselves
+    column: Vec<usize>,
+    // candidate of each node as cell * 9 + digit - 1, unused for headers
+    candidate: Vec<u16>,
+    // number of nodes in each column, indexed by header
+    size: Vec<usize>,
+    // first node of each candidate row
+    row_start: Vec<usize>,
+    // chosen candidates, clues first
+    chosen: Vec<u16>,
+    n_solutions: usize,
+}
+
+// Node 0 is the root of the list of primary columns
+const ROOT: usize = 0;
+
+impl Dlx {
+    // Returns `None`, if the clues of `sudoku` violate the houses or a house has more than 9 cells.
+    pub(crate) fn new(sudoku: Sudoku, houses: &[Set<Cell>]) -> Option<Dlx> {
+        if houses.iter().any(|house| house.len() > 9) {
+            return None;
+        }
+        let n_columns = N_CELLS + 9 * houses.len();
+        let mut dlx = Dlx {
+            left: Vec::with_capacity(n_columns + 1),
+            right: Vec::with_capacity(n_columns + 1),
+            up: Vec::with_capacity(n_columns + 1),
+            down: Vec::with_capacity(n_columns + 1),
+            column: Vec::with_capacity(n_columns + 1),
+            candidate: Vec::with_capacity(n_columns + 1),
+            size: vec![0; n_columns + 1],
+            row_start: Vec::with_capacity(9 * N_CELLS),
+            chosen: vec![],
+            n_solutions: 0,
+        };
+
+        // headers
+        let is_primary = |header: usize| header <= N_CELLS || houses[(header - N_CELLS - 1) / 9].len() == 9;
+        for header in 0..=n_columns {
+            dlx.push_node(header, 0);
+        }
+        let mut last_primary = ROOT;
+        for header in 1..=n_columns {
+            if is_primary(header) {
+                dlx.right[last_primary] = header;
+                dlx.left[header] = last_primary;
+                last_primary = header;
+            }
+        }
+        dlx.right[last_primary] = ROOT;
+        dlx.left[ROOT] = last_primary;
+
+        // candidate rows
+        let mut cell_houses = vec![vec![]; N_CELLS];
+        for (idx, &house) in houses.iter().enumerate() {
+            for cell in house {
+                cell_houses[cell.as_index()].push(idx);
+            }
+        }
+        for (cell, houses) in cell_houses.iter().enumerate() {
+            for digit in 0..9 {
+                let candidate = (cell * 9 + digit) as u16;
+                let columns = std::iter::once(cell + 1)
+                    .chain(houses.iter().map(|&house| N_CELLS + 1 + house * 9 + digit));
+                let start = dlx.left.len();
+                dlx.row_start.push(start);
+                for column in columns {
+                    let node = dlx.push_node(column, candidate);
+                    // link into the row
+                    dlx.left[node] = dlx.left[start];
+                    dlx.right[node] = start;
+                    let last = dlx.left[start];
+                    dlx.right[last] = node;
+                    dlx.left[start] = node;
+                }
+            }
+        }
+
+        // clues
+        let mut is_covered = vec![false; n_columns + 1];
+        for (cell, digit) in sudoku.clues() {
+            let candidate = cell.as_index() * 9 + digit as usize - 1;
+            let start = dlx.row_start[candidate];
+            let mut node = start;
+            loop {
+                let column = dlx.column[node];
+                if is_covered[column] {
+                    return None;
+                }
+                is_covered[column] = true;
+                dlx.cover(column);
+                node = dlx.right[node];
+                if node == start {
+                    break;
+                }
+            }
+            dlx.chosen.push(candidate as u16);
+        }
+        Some(dlx)
+    }
+
+    // Appends a node to the bottom of `column` and returns it. Rows are linked by the caller.
+    fn push_node(&mut self, column: usize, candidate: u16) -> usize {
+        let node = self.left.len();
+        self.left.push(node);
+        self.right.push(node);
+        self.column.push(column);
+        self.candidate.push(candidate);
+        if node == column {
+            self.up.push(node);
+            self.down.push(node);
+        } else {
+            let last = self.up[column];
+            self.up.push(last);
+            self.down.push(column);
+            self.down[last] = node;
+            self.up[column] = node;
+            self.size[column] += 1;
+        }
+        node
+    }
+
+    fn cover(&mut self, column: usize) {
+        self.right[self.left[column]] = self.right[column];
+        self.left[self.right[column]] = self.left[column];
+        let mut row = self.down[column];
+        while row != column {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut row = self.up[column];
+        while row != column {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[column]] = column;
+        self.left[self.right[column]] = column;
+    }
+
+    // Calls `on_solution` for up to `limit` solutions. Returns the number of solutions found.
+    pub(crate) fn solve(&mut self, limit: usize, on_solution: &mut impl FnMut(Sudoku)) -> usize {
+        self.n_solutions = 0;
+        if limit != 0 {
+            self.search(limit, on_solution);
+        }
+        self.n_solutions
+    }
+
+    fn search(&mut self, limit: usize, on_solution: &mut impl FnMut(Sudoku)) {
+        // primary column with the fewest rows
+        let mut best = None;
+        let mut column = self.right[ROOT];
+        while column != ROOT {
+            let is_better = match best {
+                Some(best) => self.size[column] < self.size[best],
+                None => true,
+            };
+            if is_better {
+                best = Some(column);
+            }
+            column = self.right[column];
+        }
+        let column = match best {
+            Some(column) => column,
+            None => {
+                let mut grid = [0; N_CELLS];
+                for &candidate in &self.chosen {
+                    grid[candidate as usize / 9] = (candidate % 9) as u8 + 1;
+                }
+                self.n_solutions += 1;
+                on_solution(Sudoku(grid));
+                return;
+            }
+        };
+
+        self.cover(column);
+        let mut row = self.down[column];
+        while row != column && self.n_solutions < limit {
+            self.chosen.push(self.candidate[row]);
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            self.search(limit, on_solution);
+
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            self.chosen.pop();
+            row = self.down[row];
+        }
+        self.uncover(column);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::ConstraintSet;
+    use crate::board::Constraints;
+
+    fn dlx_solutions(sudoku: Sudoku, houses: &[Set<Cell>], limit: usize) -> Vec<Sudoku> {
+        let mut solutions = vec![];
+        if let Some(mut dlx) = Dlx::new(sudoku, houses) {
+            dlx.solve(limit, &mut |solution| solutions.push(solution));
+        }
+        solutions.sort();
+        solutions
+    }
+
+    #[test]
+    fn matches_fast_solver() {
+        let houses = Constraints::SUDOKU.houses();
+        let files = [
+            include_str!("../../sudokus/Lines/easy_sudokus.txt"),
+            include_str!("../../sudokus/Lines/hard_sudokus.txt"),
+            include_str!("../../sudokus/Lines/invalid_sudokus.txt"),
+        ];
+        for line in files.iter().flat_map(|file| file.lines().take(20)) {
+            let sudoku = Sudoku::from_str_line(line).unwrap();
+            let mut solutions = sudoku.solutions_up_to(3);
+            solutions.sort();
+            assert_eq!(dlx_solutions(sudoku, &houses, 3), solutions);
+        }
+
+        // few clues, many solutions
+        let mut bytes = [0; N_CELLS];
+        bytes[..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let sudoku = Sudoku(bytes);
+        assert_eq!(dlx_solutions(sudoku, &houses, 100).len(), 100);
+        assert!(dlx_solutions(sudoku, &houses, 100)
+            .iter()
+            .all(|solution| solution.is_solved() && solution.0[..9] == bytes[..9]));
+    }
+
+    #[test]
+    fn secondary_columns() {
+        // a house of 2 cells only forbids repeating a digit
+        let mut houses = Constraints::SUDOKU.houses();
+        houses.push(Cell::new(0).as_set() | Cell::new(40));
+        let solutions = dlx_solutions(Sudoku([0; N_CELLS]), &houses, 50);
+        assert_eq!(solutions.len(), 50);
+        assert!(solutions.iter().all(|solution| solution.0[0] != solution.0[40]));
+
+        let mut bytes = [0; N_CELLS];
+        bytes[0] = 5;
+        bytes[40] = 5;
+        assert!(Dlx::new(Sudoku(bytes), &houses).is_none());
+    }
+}
@@ -12,10 +12,15 @@
 //! slower than the fast solver.
 
 pub mod deduction;
+pub mod rating;
+mod report;
 mod solver;
-mod strategies;
+pub(crate) mod strategies;
+mod technique_set;
 pub(crate) mod utils;
 
 pub use self::deduction::Deduction;
+pub use self::report::{SolveReport, TechniqueCounts};
 pub use self::solver::StrategySolver;
 pub use self::strategies::Strategy;
+pub use self::technique_set::TechniqueSet;
@@ -1,7 +1,7 @@
 //! Results of strategy applications
 
-use super::Strategy;
-use crate::bitset::Set;
+use super::{Strategy, TechniqueCounts};
+use crate::bitset::{CellSet, DigitSet, Set};
 use crate::board::Candidate;
 use crate::board::*;
 
@@ -14,21 +14,25 @@ pub struct Deductions {
     pub(crate) deductions: Vec<_Deduction>,
     pub(crate) deduced_entries: Vec<Candidate>,
     pub(crate) eliminated_entries: Vec<Candidate>,
+    pub(crate) chain_links: Vec<Candidate>,
 }
 
 /// Borrowing iterator over [`Deductions`]
 pub struct Iter<'a> {
     deductions: std::slice::Iter<'a, _Deduction>,
     eliminated_entries: &'a [Candidate],
+    chain_links: &'a [Candidate],
 }
 
 impl<'a> Iterator for Iter<'a> {
     type Item = Deduction<&'a [Candidate]>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.deductions
-            .next()
-            .map(|deduction| deduction.clone().with_slices(self.eliminated_entries))
+        self.deductions.next().map(|deduction| {
+            deduction
+                .clone()
+                .with_slices(self.eliminated_entries, self.chain_links)
+        })
     }
 }
 
@@ -45,9 +49,11 @@ impl Deductions {
 
     /// Return the `index`th Deduction, if it exists.
     pub fn get(&self, index: usize) -> Option<Deduction<&[Candidate]>> {
-        self.deductions
-            .get(index)
-            .map(|deduction| deduction.clone().with_slices(&self.eliminated_entries))
+        self.deductions.get(index).map(|deduction| {
+            deduction
+                .clone()
+                .with_slices(&self.eliminated_entries, &self.chain_links)
+        })
     }
 
     /// Return an iterator over the deductions.
@@ -55,8 +61,14 @@ impl Deductions {
         Iter {
             deductions: self.deductions.iter(),
             eliminated_entries: &self.eliminated_entries,
+            chain_links: &self.chain_links,
         }
     }
+
+    /// Returns how often each strategy was applied in these deductions.
+    pub fn technique_counts(&self) -> TechniqueCounts {
+        self.iter().map(|deduction| deduction.strategy()).collect()
+    }
 }
 
 /// Result of a single, successful strategy application
@@ -90,7 +102,7 @@ pub enum Deduction<T> {
         positions: Set<Position<House>>,
         /// The digits that are part of the locked set. The number of digits is always equal to the number of
         /// positions
-        digits: Set<Digit>,
+        digits: DigitSet,
         conflicts: T,
     },
     /// Result of [`XWing`](super::Strategy::XWing), [`Swordfish`](super::Strategy::Swordfish) or [`Jellyfish`](super::Strategy::Jellyfish)
@@ -115,8 +127,8 @@ pub enum Deduction<T> {
         // TODO: having just an identifier of Xy-, Xyz-, etc-wing is ugly
         //       but so is just having the hinge_digits as a set and not the pincer digits
         //       Find a way to get rid of that
-        hinge_digits: Set<Digit>,
-        pincers: Set<Cell>,
+        hinge_digits: DigitSet,
+        pincers: CellSet,
         conflicts: T,
     },
     AvoidableRectangle {
@@ -124,7 +136,71 @@ pub enum Deduction<T> {
         lines: Set<Line>,
         conflicts: T,
     },
-    //SinglesChain(T),
+    /// Result of [`SimpleColoring`](super::Strategy::SimpleColoring)
+    SimpleColoring {
+        digit: Digit,
+        /// The cells connected by conjugate pairs of `digit`, split by color.
+        /// All cells of one color contain the digit.
+        colors: [CellSet; 2],
+        conflicts: T,
+    },
+    /// Result of [`RemotePairs`](super::Strategy::RemotePairs)
+    RemotePairs {
+        /// The candidates of the chain in order. Every candidate at an even index is strongly linked
+        /// to the next one, every candidate at an odd index is weakly linked to the next one.
+        chain: T,
+        conflicts: T,
+    },
+    /// Result of [`XyChain`](super::Strategy::XyChain)
+    XyChain {
+        /// The candidates of the chain, see [`RemotePairs`](Deduction::RemotePairs)
+        chain: T,
+        conflicts: T,
+    },
+    /// Result of [`AlternatingInferenceChain`](super::Strategy::AlternatingInferenceChain)
+    AlternatingInferenceChain {
+        /// The candidates of the chain, see [`RemotePairs`](Deduction::RemotePairs)
+        chain: T,
+        conflicts: T,
+    },
+    /// Result of [`UniqueRectangles`](super::Strategy::UniqueRectangles)
+    UniqueRectangle {
+        /// The 4 cells of the rectangle. They occupy 2 rows, 2 columns and 2 blocks.
+        cells: CellSet,
+        /// The 2 digits that are possible in all cells of the rectangle
+        digits: DigitSet,
+        /// The type of the unique rectangle, 1 to 4
+        rectangle_type: u8,
+        conflicts: T,
+    },
+    /// Result of [`BivalueUniversalGrave`](super::Strategy::BivalueUniversalGrave).
+    /// All other unsolved cells have exactly 2 candidates, so the candidate must be true.
+    BivalueUniversalGrave(Candidate),
+    /// Result of [`AlsXz`](super::Strategy::AlsXz)
+    AlsXz {
+        /// The cells of both almost locked sets. Each contains one more digit than cells.
+        sets: [CellSet; 2],
+        /// The digit that can only be true in one of the sets
+        restricted_common_digit: Digit,
+        conflicts: T,
+    },
+    /// Result of [`AlsXyWing`](super::Strategy::AlsXyWing)
+    AlsXyWing {
+        /// The cells of the almost locked set that is linked to both wings
+        pivot: CellSet,
+        /// The cells of the two almost locked sets at the ends of the wing
+        wings: [CellSet; 2],
+        /// The restricted common digits between the pivot and each wing
+        restricted_common_digits: [Digit; 2],
+        conflicts: T,
+    },
+    /// Result of [`ForcingChains`](super::Strategy::ForcingChains).
+    /// Assuming the candidate in `conflicts` leads to a contradiction.
+    ForcingChain {
+        /// The nesting depth that was necessary to find the contradiction
+        depth: u8,
+        conflicts: T,
+    },
 }
 
 impl Deduction<&'_ [Candidate]> {
@@ -141,7 +217,6 @@ impl Deduction<&'_ [Candidate]> {
                 4 => Strategy::Jellyfish,
                 _ => unreachable!(),
             },
-            //SinglesChain { .. } => Strategy::SinglesChain,
             Subsets {
                 house,
                 positions,
@@ -192,6 +267,15 @@ impl Deduction<&'_ [Candidate]> {
                 _ => unreachable!(),
             },
             AvoidableRectangle { .. } => unimplemented!(),
+            SimpleColoring { .. } => Strategy::SimpleColoring,
+            RemotePairs { .. } => Strategy::RemotePairs,
+            XyChain { .. } => Strategy::XyChain,
+            AlternatingInferenceChain { .. } => Strategy::AlternatingInferenceChain,
+            UniqueRectangle { .. } => Strategy::UniqueRectangles,
+            BivalueUniversalGrave(_) => Strategy::BivalueUniversalGrave,
+            AlsXz { .. } => Strategy::AlsXz,
+            AlsXyWing { .. } => Strategy::AlsXyWing,
+            ForcingChain { .. } => Strategy::ForcingChains,
         }
     }
 }
@@ -200,7 +284,7 @@ impl Deduction<&'_ [Candidate]> {
 impl _Deduction {
     /// Replace the index ranges from the internal representation with slices
     /// for the external API
-    fn with_slices(self, eliminated: &[Candidate]) -> Deduction<&[Candidate]> {
+    fn with_slices<'a>(self, eliminated: &'a [Candidate], chains: &'a [Candidate]) -> Deduction<&'a [Candidate]> {
         use self::Deduction::*;
         match self {
             NakedSingles(c) => NakedSingles(c),
@@ -236,7 +320,37 @@ impl _Deduction {
             => Wing { hinge, hinge_digits, pincers, conflicts: &eliminated[conflicts] },
 
             AvoidableRectangle { .. } => unimplemented!(),
-            //SinglesChain(x) => SinglesChain(&eliminated[x]),
+
+            SimpleColoring {
+                digit, colors,
+                conflicts,
+            }
+            => SimpleColoring { digit, colors, conflicts: &eliminated[conflicts] },
+
+            RemotePairs { chain, conflicts } => RemotePairs { chain: &chains[chain], conflicts: &eliminated[conflicts] },
+            XyChain { chain, conflicts } => XyChain { chain: &chains[chain], conflicts: &eliminated[conflicts] },
+            AlternatingInferenceChain { chain, conflicts }
+            => AlternatingInferenceChain { chain: &chains[chain], conflicts: &eliminated[conflicts] },
+
+            UniqueRectangle {
+                cells, digits, rectangle_type,
+                conflicts,
+            }
+            => UniqueRectangle { cells, digits, rectangle_type, conflicts: &eliminated[conflicts] },
+            BivalueUniversalGrave(c) => BivalueUniversalGrave(c),
+
+            AlsXz {
+                sets, restricted_common_digit,
+                conflicts,
+            }
+            => AlsXz { sets, restricted_common_digit, conflicts: &eliminated[conflicts] },
+
+            AlsXyWing {
+                pivot, wings, restricted_common_digits,
+                conflicts,
+            }
+            => AlsXyWing { pivot, wings, restricted_common_digits, conflicts: &eliminated[conflicts] },
+            ForcingChain { depth, conflicts } => ForcingChain { depth, conflicts: &eliminated[conflicts] },
         }
     }
 }
@@ -0,0 +1,292 @@
+//! Approximate difficulty ratings on the scale of [Sudoku Explainer](http://sudoku.sourceforge.net/)
+//!
+//! Sudoku Explainer (SE) rates a puzzle by repeatedly applying the easiest technique that makes
+//! progress and reporting the difficulty of the hardest step that was necessary.
+//! Its scale ranges from 1.0 for the simplest singles to beyond 11 for puzzles that require
+//! deeply nested forcing chains.
+//!
+//! This module mimics that procedure with the strategies of the [`StrategySolver`].
+//! Strategies are tried in order of increasing SE difficulty and every [`Deduction`] is
+//! assigned the value SE assigns to the equivalent technique. SE rates chains by their length
+//! and forcing chains by their nesting. Chains are given the rating of the shortest chains of their
+//! kind and [forcing chains](Strategy::ForcingChains) are rated by their depth, so puzzles that
+//! require nested forcing chains are rated above 9. Puzzles that exceed the
+//! [maximum depth](StrategySolver::limit_forcing_chain_depth) of the solver can only be given
+//! a lower bound, see [`SeRating::AtLeast`].
+//!
+//! The approximation differs from SE in some details. Full houses are rated as hidden singles
+//! and techniques that SE does not know, like mutant fish, are never used for rating.
+//!
+//! The procedure is implemented by the [`SudokuExplainer`] [`DifficultyModel`]. Other gradings can
+//! be plugged in by implementing the trait and passing the model to [`StrategySolver::rate_with`].
+
+use super::deduction::Deductions;
+use super::{Deduction, Strategy, StrategySolver};
+use crate::board::{Candidate, HouseType};
+
+/// Approximate difficulty of a sudoku on the scale of Sudoku Explainer
+///
+/// See the [module level documentation](self) for details.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeRating {
+    /// The sudoku could be solved. Contains the difficulty of the hardest step.
+    Rated(f32),
+    /// The sudoku can't be solved within the
+    /// [maximum forcing chain depth](StrategySolver::limit_forcing_chain_depth) of the solver.
+    /// Its rating is at least as high as the contained value.
+    AtLeast(f32),
+}
+
+impl SeRating {
+    /// Returns the contained rating, regardless of whether it is exact or a lower bound.
+    pub fn value(self) -> f32 {
+        match self {
+            SeRating::Rated(rating) | SeRating::AtLeast(rating) => rating,
+        }
+    }
+
+    /// Returns `true` if the sudoku could be solved and the rating is not just a lower bound.
+    pub fn is_rated(self) -> bool {
+        matches!(self, SeRating::Rated(_))
+    }
+}
+
+/// A way of grading a sudoku by the steps the [`StrategySolver`] needs to solve it
+///
+/// The solver applies the easiest of the model's [`strategies`](Self::strategies) that makes
+/// progress until the sudoku is solved or none of them applies anymore. The resulting
+/// [`SolveTrace`] is then turned into a score by [`rate`](Self::rate).
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::strategy::StrategySolver;
+/// use sudoku::strategy::rating::{DifficultyModel, SeRating, SolveTrace, SudokuExplainer};
+///
+/// // rates a sudoku by the number of steps instead of the hardest one
+/// struct StepCount;
+///
+/// impl DifficultyModel for StepCount {
+///     fn rate(&self, trace: &SolveTrace<'_>) -> SeRating {
+///         let n_steps = trace.deductions().len() as f32;
+///         match trace.is_solved() {
+///             true => SeRating::Rated(n_steps),
+///             false => SeRating::AtLeast(n_steps),
+///         }
+///     }
+/// }
+///
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// let solver = StrategySolver::from_sudoku(sudoku);
+/// assert!(solver.clone().rate_with(&StepCount).value() > 1.0);
+/// assert_eq!(solver.clone().rate_with(&SudokuExplainer), solver.se_rating());
+/// ```
+pub trait DifficultyModel {
+    /// Returns the strategies the solver may use, in order of preference.
+    ///
+    /// Defaults to the strategies known to Sudoku Explainer, in order of their SE rating.
+    fn strategies(&self) -> &[Strategy] {
+        SE_STRATEGIES
+    }
+
+    /// Computes the score of a sudoku from the steps the solver took.
+    /// [`SeRating::AtLeast`] should be returned, if the solver got stuck.
+    fn rate(&self, trace: &SolveTrace<'_>) -> SeRating;
+}
+
+/// The steps the [`StrategySolver`] took on a sudoku, as rated by a [`DifficultyModel`]
+#[derive(Debug, Clone, Copy)]
+pub struct SolveTrace<'a> {
+    deductions: &'a Deductions,
+    is_solved: bool,
+    forcing_chain_depth: u8,
+    assume_unique_solution: bool,
+}
+
+impl<'a> SolveTrace<'a> {
+    /// Returns all deductions in the order they were made.
+    pub fn deductions(&self) -> &'a Deductions {
+        self.deductions
+    }
+
+    /// Returns `true` if the solver solved the sudoku and `false` if it got stuck.
+    pub fn is_solved(&self) -> bool {
+        self.is_solved
+    }
+
+    /// Returns the maximum depth of forcing chains the solver was allowed to use.
+    /// See [`StrategySolver::limit_forcing_chain_depth`].
+    pub fn forcing_chain_depth(&self) -> u8 {
+        self.forcing_chain_depth
+    }
+
+    /// Returns whether the solver assumed a unique solution.
+    /// See [`StrategySolver::assume_unique_solution`].
+    pub fn assume_unique_solution(&self) -> bool {
+        self.assume_unique_solution
+    }
+}
+
+/// The built-in approximation of Sudoku Explainer's ratings
+///
+/// Rates a sudoku by the [hardest deduction](Deduction::se_rating). This is the model used by
+/// [`StrategySolver::se_rating`]. See the [module level documentation](self) for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SudokuExplainer;
+
+impl DifficultyModel for SudokuExplainer {
+    fn rate(&self, trace: &SolveTrace<'_>) -> SeRating {
+        let min_unsupported_rating = match (trace.forcing_chain_depth, trace.assume_unique_solution) {
+            (0, true) => MIN_UNSUPPORTED_RATING_UNIQUE,
+            (0, false) => MIN_UNSUPPORTED_RATING,
+            (depth, _) => forcing_chain_rating(depth.saturating_add(1)),
+        };
+        let hardest = trace
+            .deductions
+            .iter()
+            .map(|deduction| deduction.se_rating())
+            .fold(1.0, f32::max);
+        match trace.is_solved {
+            true => SeRating::Rated(hardest),
+            false => SeRating::AtLeast(hardest.max(min_unsupported_rating)),
+        }
+    }
+}
+
+// Strategies that are also known to Sudoku Explainer, in order of increasing difficulty.
+#[rustfmt::skip]
+pub(crate) const SE_STRATEGIES: &[Strategy] = &[
+    Strategy::HiddenSingles,    // 1.2 / 1.5
+    Strategy::NakedSingles,     // 2.3
+    Strategy::LockedCandidates, // 2.6 / 2.8
+    Strategy::NakedPairs,       // 3.0
+    Strategy::XWing,            // 3.2
+    Strategy::HiddenPairs,      // 3.4
+    Strategy::NakedTriples,     // 3.6
+    Strategy::Swordfish,        // 3.8
+    Strategy::HiddenTriples,    // 4.0
+    Strategy::XyWing,           // 4.2
+    Strategy::XyzWing,          // 4.4
+    Strategy::UniqueRectangles, // 4.5 - 4.7
+    Strategy::NakedQuads,       // 5.0
+    Strategy::Jellyfish,        // 5.2
+    Strategy::HiddenQuads,      // 5.4
+    Strategy::BivalueUniversalGrave, // 5.6
+    Strategy::SimpleColoring,   // 6.5
+    Strategy::RemotePairs,      // 6.6
+    Strategy::XyChain,          // 6.6
+    Strategy::AlternatingInferenceChain, // 7.0
+    Strategy::ForcingChains,    // 8.5+
+];
+
+// Difficulty of the easiest technique of SE that has no equivalent in `SE_STRATEGIES`.
+// A puzzle that gets stuck with forcing chains disabled needs at least this.
+// Without the assumption of a unique solution, that's unique rectangles,
+// otherwise aligned pair exclusion.
+const MIN_UNSUPPORTED_RATING: f32 = 4.5;
+const MIN_UNSUPPORTED_RATING_UNIQUE: f32 = 6.2;
+
+// Forcing chains of depth 1 only need singles, every further level adds a full point.
+fn forcing_chain_rating(depth: u8) -> f32 {
+    7.5 + f32::from(depth)
+}
+
+impl Deduction<&'_ [Candidate]> {
+    /// Returns the difficulty of this deduction as assigned to the equivalent technique
+    /// by Sudoku Explainer.
+    ///
+    /// Mutant fish are not part of Sudoku Explainer. They are rated slightly above
+    /// basic fish of the same size. Chains are given the rating of the shortest chains of their kind.
+    /// Almost locked sets are rated slightly above alternating inference chains.
+    /// Forcing chains are rated 8.5 at depth 1 and one point higher for every further level of nesting.
+    pub fn se_rating(&self) -> f32 {
+        use self::Deduction::*;
+        match *self {
+            HiddenSingles(_, HouseType::Block(_)) => 1.2,
+            HiddenSingles(..) => 1.5,
+            LockedCandidates {
+                is_pointing: true, ..
+            } => 2.6,
+            LockedCandidates {
+                is_pointing: false, ..
+            } => 2.8,
+            AvoidableRectangle { .. } => 4.5,
+            ForcingChain { depth, .. } => forcing_chain_rating(depth),
+            UniqueRectangle { rectangle_type, .. } => match rectangle_type {
+                1 => 4.5,
+                3 => 4.7,
+                _ => 4.6,
+            },
+            _ => match self.strategy() {
+                Strategy::NakedSingles => 2.3,
+                Strategy::NakedPairs => 3.0,
+                Strategy::XWing => 3.2,
+                Strategy::HiddenPairs => 3.4,
+                Strategy::NakedTriples => 3.6,
+                Strategy::Swordfish => 3.8,
+                Strategy::HiddenTriples => 4.0,
+                Strategy::XyWing => 4.2,
+                Strategy::XyzWing => 4.4,
+                Strategy::NakedQuads => 5.0,
+                Strategy::Jellyfish => 5.2,
+                Strategy::HiddenQuads => 5.4,
+                Strategy::MutantSwordfish => 4.0,
+                Strategy::MutantJellyfish => 5.4,
+                Strategy::SimpleColoring => 6.5,
+                Strategy::RemotePairs | Strategy::XyChain => 6.6,
+                Strategy::AlternatingInferenceChain => 7.0,
+                Strategy::BivalueUniversalGrave => 5.6,
+                Strategy::AlsXz => 7.2,
+                Strategy::AlsXyWing => 7.4,
+                _ => unreachable!(),
+            },
+        }
+    }
+}
+
+impl StrategySolver {
+    /// Solves the sudoku like Sudoku Explainer would and returns an approximation of its rating.
+    ///
+    /// Sudoku Explainer relies on the uniqueness of the solution for some techniques.
+    /// They are only used if the solver [assumes a unique solution](StrategySolver::assume_unique_solution).
+    /// See the [`rating`](super::rating) module for the other differences to Sudoku Explainer.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::{StrategySolver, rating::SeRating};
+    ///
+    /// // can be solved with hidden singles alone
+    /// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+    /// let rating = StrategySolver::from_sudoku(sudoku).se_rating();
+    /// assert_eq!(rating, SeRating::Rated(1.5));
+    /// ```
+    pub fn se_rating(self) -> SeRating {
+        self.se_rating_with_deductions().0
+    }
+
+    /// Solves the sudoku with the strategies of `model` and returns the score it assigns.
+    /// See [`DifficultyModel`].
+    pub fn rate_with<M: DifficultyModel + ?Sized>(self, model: &M) -> SeRating {
+        self.rate_with_deductions(model).0
+    }
+
+    pub(crate) fn se_rating_with_deductions(self) -> (SeRating, Deductions) {
+        self.rate_with_deductions(&SudokuExplainer)
+    }
+
+    fn rate_with_deductions<M: DifficultyModel + ?Sized>(self, model: &M) -> (SeRating, Deductions) {
+        let (forcing_chain_depth, assume_unique_solution) =
+            (self.forcing_chain_depth, self.assume_unique_solution);
+        let (is_solved, deductions) = match self.solve(model.strategies()) {
+            Ok((_, deductions)) => (true, deductions),
+            Err((_, deductions)) => (false, deductions),
+        };
+        let trace = SolveTrace {
+            deductions: &deductions,
+            is_solved,
+            forcing_chain_depth,
+            assume_unique_solution,
+        };
+        (model.rate(&trace), deductions)
+    }
+}
@@ -0,0 +1,111 @@
+use super::rating::SeRating;
+use super::{Strategy, StrategySolver};
+use crate::{SolvedSudoku, Sudoku};
+use std::collections::BTreeMap;
+
+/// Summary of a puzzle, its solution and its difficulty
+///
+/// This bundles the results of several separate calls into one value.
+/// With the `serde` feature, it can be serialized as a whole, e.g. for returning it from a web service.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::strategy::{SolveReport, Strategy};
+///
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// let report = SolveReport::new(sudoku);
+/// assert_eq!(report.n_clues, 32);
+/// assert!(report.solution.is_some());
+/// assert!(report.technique_counts.get(Strategy::HiddenSingles) > 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolveReport {
+    /// The puzzle this report is about
+    pub puzzle: Sudoku,
+    /// The solution of the puzzle, if it is unique
+    pub solution: Option<SolvedSudoku>,
+    /// Number of clues in the puzzle
+    pub n_clues: u8,
+    /// Approximate difficulty on the scale of Sudoku Explainer
+    pub se_rating: SeRating,
+    /// Number of times each strategy was applied while rating the puzzle
+    pub technique_counts: TechniqueCounts,
+}
+
+impl SolveReport {
+    /// Solve and rate the `puzzle`.
+    ///
+    /// The techniques are counted along the solution path of [`StrategySolver::se_rating`].
+    /// If the solution is unique, the uniqueness based strategies are used as well.
+    pub fn new(puzzle: Sudoku) -> SolveReport {
+        let solution = puzzle.solution();
+        let (se_rating, deductions) = StrategySolver::from_sudoku(puzzle)
+            .assume_unique_solution(solution.is_some())
+            .se_rating_with_deductions();
+        SolveReport {
+            puzzle,
+            solution,
+            n_clues: puzzle.n_clues(),
+            se_rating,
+            technique_counts: deductions.technique_counts(),
+        }
+    }
+}
+
+/// Number of times each strategy was applied while solving a sudoku
+///
+/// Obtained from the [`Deductions`](super::deduction::Deductions) of a solve via
+/// [`technique_counts`](super::deduction::Deductions::technique_counts) or as part of a [`SolveReport`].
+/// With the `serde` feature, it is serialized as a map from strategy names to counts
+/// that only contains the strategies that were applied.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::strategy::{Strategy, StrategySolver};
+///
+/// let sudoku = Sudoku::from_str_line("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..").unwrap();
+/// let (_, deductions) = StrategySolver::from_sudoku(sudoku)
+///     .solve(&[Strategy::HiddenSingles])
+///     .unwrap();
+/// let counts = deductions.technique_counts();
+/// assert_eq!(counts.get(Strategy::HiddenSingles), deductions.len());
+/// assert_eq!(counts.get(Strategy::XWing), 0);
+/// assert_eq!(counts.total(), deductions.len());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct TechniqueCounts(BTreeMap<Strategy, usize>);
+
+impl TechniqueCounts {
+    /// Returns the number of times `strategy` was applied.
+    pub fn get(&self, strategy: Strategy) -> usize {
+        self.0.get(&strategy).copied().unwrap_or(0)
+    }
+
+    /// Returns the strategies that were applied at least once together with their counts,
+    /// in the order of the [`Strategy`] enum.
+    pub fn iter(&self) -> impl Iterator<Item = (Strategy, usize)> + '_ {
+        self.0.iter().map(|(&strategy, &count)| (strategy, count))
+    }
+
+    /// Returns the total number of strategy applications.
+    pub fn total(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// Returns `true` if no strategy was applied.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::iter::FromIterator<Strategy> for TechniqueCounts {
+    fn from_iter<I: IntoIterator<Item = Strategy>>(strategies: I) -> Self {
+        let mut counts = BTreeMap::new();
+        for strategy in strategies {
+            *counts.entry(strategy).or_insert(0) += 1;
+        }
+        TechniqueCounts(counts)
+    }
+}
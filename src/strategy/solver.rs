@@ -4,7 +4,7 @@ use crate::board::*;
 use crate::helper::{CellArray, DigitArray, HouseArray, Unsolvable};
 use crate::strategy::{
     deduction::{Deduction, Deductions},
-    strategies::*,
+    strategies::{chains::ChainKind, *},
 };
 use crate::Sudoku;
 
@@ -39,11 +39,18 @@ pub struct StrategySolver {
     pub(crate) deductions: Vec<_Deduction>,
     pub(crate) deduced_entries: Vec<Candidate>,
     pub(crate) eliminated_entries: Vec<Candidate>,
+    // candidates of the chains in `deductions`, they don't affect the grid
+    pub(crate) chain_links: Vec<Candidate>,
     pub(crate) n_solved: u8, // deduced_entries can contain duplicates so a separate counter is necessary
 
     // optimization hints for strategies
     pub(crate) hidden_singles_last_house: u8,
 
+    // Whether strategies may rely on the sudoku having exactly one solution
+    pub(crate) assume_unique_solution: bool,
+    // Maximum nesting depth of forcing chains
+    pub(crate) forcing_chain_depth: u8,
+
     // The initial state of a sudoku given as a puzzle.
     // If the solution is unique, this can be used for the strategy of
     // AvoidableRectangles
@@ -70,8 +77,11 @@ impl StrategySolver {
             deductions: vec![],
             deduced_entries: vec![],
             eliminated_entries: vec![],
+            chain_links: vec![],
             n_solved: 0,
             hidden_singles_last_house: 0,
+            assume_unique_solution: false,
+            forcing_chain_depth: 1,
             clues: None,
             grid: State::from(Sudoku([0; 81])),
             cell_poss_digits: State::from(CellArray([Set::ALL; 81])),
@@ -103,6 +113,64 @@ impl StrategySolver {
         }
     }
 
+    /// Sets whether the solver may assume that the sudoku has exactly one solution.
+    ///
+    /// This enables the uniqueness based strategies [`UniqueRectangles`](Strategy::UniqueRectangles) and
+    /// [`BivalueUniversalGrave`](Strategy::BivalueUniversalGrave). They are skipped otherwise.
+    /// If the assumption is wrong, these strategies can eliminate candidates of valid solutions.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::{Strategy, StrategySolver};
+    ///
+    /// let sudoku = Sudoku::from_str_line("..8......5..9....2...123.9...78....1.3..5..4.1....46...9.718...8....6..4......3..").unwrap();
+    /// let strategies = [
+    ///     Strategy::NakedSingles,
+    ///     Strategy::HiddenSingles,
+    ///     Strategy::LockedCandidates,
+    ///     Strategy::NakedPairs,
+    ///     Strategy::HiddenPairs,
+    ///     Strategy::XWing,
+    ///     Strategy::BivalueUniversalGrave,
+    /// ];
+    /// assert!(StrategySolver::from_sudoku(sudoku).solve(&strategies).is_err());
+    ///
+    /// let solver = StrategySolver::from_sudoku(sudoku).assume_unique_solution(sudoku.is_uniquely_solvable());
+    /// assert!(solver.solve(&strategies).is_ok());
+    /// ```
+    pub fn assume_unique_solution(self, assume_unique_solution: bool) -> StrategySolver {
+        StrategySolver {
+            assume_unique_solution,
+            ..self
+        }
+    }
+
+    /// Sets the maximum nesting depth of [`ForcingChains`](Strategy::ForcingChains). Defaults to 1.
+    ///
+    /// At depth 1, a candidate is eliminated if assuming it leads to a contradiction by naked and hidden
+    /// singles alone. Each further level additionally uses locked candidates and forcing chains of the
+    /// previous depth to find the contradiction. The time needed grows quickly with the depth.
+    /// At depth 0, forcing chains are disabled.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::{Strategy, StrategySolver};
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let strategies = [Strategy::HiddenSingles, Strategy::ForcingChains];
+    /// let solver = StrategySolver::from_sudoku(sudoku).limit_forcing_chain_depth(0);
+    /// assert!(solver.solve(&strategies).is_err());
+    ///
+    /// let solver = StrategySolver::from_sudoku(sudoku).limit_forcing_chain_depth(1);
+    /// assert!(solver.solve(&strategies).is_ok());
+    /// ```
+    pub fn limit_forcing_chain_depth(self, forcing_chain_depth: u8) -> StrategySolver {
+        StrategySolver {
+            forcing_chain_depth,
+            ..self
+        }
+    }
+
     /// Construct a new StrategySolver from an array of [`CellState`s](crate::board::CellState).
     /// This allows communicating the impossibility of some candidates, that aren't already
     /// trivially conflicting with entries. The cell order in the array is the same as for
@@ -205,6 +273,7 @@ impl StrategySolver {
     }
 
     /// Try to insert the given candidate. Fails, if the cell already contains a digit.
+    #[allow(clippy::result_unit_err)]
     pub fn insert_candidate(&mut self, candidate: Candidate) -> Result<(), ()> {
         self.update_grid();
         Self::push_new_candidate(
@@ -223,8 +292,8 @@ impl StrategySolver {
 
     #[rustfmt::skip]
     fn into_deductions(self) -> Deductions {
-        let Self { deductions, deduced_entries, eliminated_entries, .. } = self;
-        Deductions { deductions, deduced_entries, eliminated_entries }
+        let Self { deductions, deduced_entries, eliminated_entries, chain_links, .. } = self;
+        Deductions { deductions, deduced_entries, eliminated_entries, chain_links }
     }
 
     fn update_grid(&mut self) {
@@ -245,16 +314,49 @@ impl StrategySolver {
         }
     }
 
+    /// Applies the given `strategies` until the sudoku is solved or none of them makes progress
+    /// and returns the partially filled grid together with the remaining candidates of the empty cells.
+    ///
+    /// Unlike [`solve`](Self::solve), the solver is kept, so it can be continued with other strategies
+    /// or after [inserting](Self::insert_candidate) digits.
+    /// If the sudoku turns out to be unsolvable, solving stops at the first contradiction.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::CellState;
+    /// use sudoku::strategy::{Strategy, StrategySolver};
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let mut solver = StrategySolver::from_sudoku(sudoku);
+    ///
+    /// // let the solver do the easy part
+    /// let grid_state = solver.solve_until_stuck(&[Strategy::NakedSingles, Strategy::HiddenSingles]);
+    /// let n_filled = grid_state.iter().filter(|state| matches!(state, CellState::Digit(_))).count();
+    /// assert!(usize::from(sudoku.n_clues()) < n_filled && n_filled < 81);
+    /// assert_eq!(solver.grid_state(), grid_state);
+    /// ```
+    pub fn solve_until_stuck(&mut self, strategies: &[Strategy]) -> [CellState; 81] {
+        let _ = self.apply_until_stuck(strategies);
+        self.grid_state()
+    }
+
     // FIXME: change name
     /// Try to solve the sudoku using the given `strategies`. Returns `true` if new deductions were made.
     fn try_solve(&mut self, strategies: &[Strategy]) -> bool {
+        let lens = (self.deduced_entries.len(), self.eliminated_entries.len());
+        let _ = self.apply_until_stuck(strategies);
+        lens < (self.deduced_entries.len(), self.eliminated_entries.len())
+    }
+
+    // Applies the `strategies` until the sudoku is solved or none of them makes progress.
+    // Errors, if the sudoku turns out to be unsolvable.
+    fn apply_until_stuck(&mut self, strategies: &[Strategy]) -> Result<(), Unsolvable> {
         // first strategy can be optimized
         let (first, rest) = match strategies.split_first() {
             Some(tup) => tup,
             // no chance without strategies
-            None => return false,
+            None => return Ok(()),
         };
-        let lens = (self.deduced_entries.len(), self.eliminated_entries.len());
         'outer: loop {
             if self.is_solved() {
                 break;
@@ -262,24 +364,20 @@ impl StrategySolver {
 
             let n_deductions = self.deduced_entries.len();
             let n_eliminated = self.eliminated_entries.len();
-            if first.deduce_all(self, true).is_err() {
-                break;
-            };
+            first.deduce_all(self, true)?;
             if self.deduced_entries.len() > n_deductions {
                 continue 'outer;
             }
 
             for strategy in rest {
-                if strategy.deduce_one(self).is_err() {
-                    break;
-                };
+                strategy.deduce_one(self)?;
                 if self.deduced_entries.len() > n_deductions || self.eliminated_entries.len() > n_eliminated {
                     continue 'outer;
                 }
             }
             break;
         }
-        lens < (self.deduced_entries.len(), self.eliminated_entries.len())
+        Ok(())
     }
 
     /// Check whether the sudoku has been completely solved.
@@ -604,13 +702,13 @@ impl StrategySolver {
         deduced_entries: &mut Vec<Candidate>,
         candidate: Candidate,
         deductions: &mut Vec<_Deduction>,
-        strategy: _Deduction, // either a user-given or naked or hidden singles
+        strategy: _Deduction, // either a user-given, naked or hidden single or a bivalue universal grave
     ) -> Result<(), Unsolvable> {
         #[cfg(debug_assertions)]
         {
             use self::Deduction::*;
             match strategy {
-                NakedSingles(..) | HiddenSingles(..) => (),
+                NakedSingles(..) | HiddenSingles(..) | BivalueUniversalGrave(..) => (),
                 _ => panic!("Internal error: Called push_new_candidate with wrong strategy type"),
             };
         }
@@ -973,142 +1071,213 @@ impl StrategySolver {
         )
     }
 
-    /*
-    pub(crate) fn find_singles_chain(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
-        #[derive(Copy, Clone, PartialEq, Eq)]
-        enum Color {
-            A,
-            B,
-        }
+    pub(crate) fn find_forcing_chains(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
 
-        /// Recursively visit all cells connected by being the only 2 possible candidates in a house.
-        /// mark all visited cells
-        fn follow_links(digit: Digit, cell: Cell, is_a: bool, sudoku: &StrategySolver, cell_color: &mut CellArray<Option<Color>>, link_nr: u8, cell_linked: &mut CellArray<u8>) {
-            if cell_linked[cell] <= link_nr { return }
-
-            for &(con_house, current_pos) in &[
-                (cell.row().house(), cell.row_pos()),
-                (cell.col().house(), cell.col_pos()),
-                (cell.block().house(), cell.block_pos()),
-            ] {
-                let house_poss_positions = sudoku.house_poss_positions.state[con_house][digit];
-                if house_poss_positions.len() == 2 {
-                    let other_pos = house_poss_positions.without(current_pos.as_set()).one_possibility();
-                    let other_cell = con_house.cell_at(other_pos);
-
-                    match cell_linked[other_cell] <= link_nr {
-                        true => continue,
-                        false => cell_linked[other_cell] = link_nr,
-                    };
-
-                    cell_color[other_cell] = if is_a { Some(Color::A) } else { Some(Color::B) };
-
-                    follow_links(digit, other_cell, !is_a, sudoku, cell_color, link_nr, cell_linked);
+        // use the shallowest depth that finds anything
+        for depth in 1..=self.forcing_chain_depth {
+            let mut contradictions = vec![];
+            for cell in Cell::all() {
+                for digit in self.cell_poss_digits.state[cell] {
+                    let candidate = Candidate { cell, digit };
+                    if self.leads_to_contradiction(candidate, depth) {
+                        contradictions.push(candidate);
+                        if stop_after_first {
+                            break;
+                        }
+                    }
                 }
+                if stop_after_first && !contradictions.is_empty() {
+                    break;
+                }
+            }
+            if contradictions.is_empty() {
+                continue;
+            }
+
+            for candidate in contradictions {
+                let on_conflict = |conflicts| Deduction::ForcingChain { depth, conflicts };
+                Self::enter_conflicts(
+                    &mut self.eliminated_entries,
+                    &mut self.deductions,
+                    std::iter::once(candidate),
+                    on_conflict,
+                );
             }
+            break;
         }
+        Ok(())
+    }
 
-        for digit in Set::<Digit>::ALL {
-            let mut link_nr = 0;
+    // Inserts `candidate` into a copy of the solver and checks whether that makes the sudoku unsolvable
+    // with the strategies of the given forcing chain depth.
+    fn leads_to_contradiction(&self, candidate: Candidate, depth: u8) -> bool {
+        let strategies: &[Strategy] = match depth {
+            1 => &[Strategy::NakedSingles, Strategy::HiddenSingles],
+            _ => &[
+                Strategy::NakedSingles,
+                Strategy::HiddenSingles,
+                Strategy::LockedCandidates,
+                Strategy::ForcingChains,
+            ],
+        };
+        let mut trial = StrategySolver {
+            forcing_chain_depth: depth - 1,
+            ..self.clone()
+        };
+        trial.deduced_entries.push(candidate);
+        trial.apply_until_stuck(strategies).is_err()
+    }
 
-            let mut cell_linked = CellArray([0; 81]);
-            let mut cell_color = CellArray([None; 81]);
+    pub(crate) fn find_als_xz(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
 
-            for house in House::all() {
-                let house_poss_positions = self.house_poss_positions.state[house][digit];
-                if house_poss_positions.len() == 2 {
-                    let first = house_poss_positions.one_possibility();
-                    let cell = house.cell_at(first);
+        almost_locked_sets::find_als_xz(
+            cell_poss_digits,
+            stop_after_first,
+            |sets, restricted_common_digit, conflicts| {
+                let on_conflict = |conflicts| Deduction::AlsXz {
+                    sets,
+                    restricted_common_digit,
+                    conflicts,
+                };
 
-                    if cell_color[cell].is_none() {
-                        follow_links(digit, cell, true, self, &mut cell_color, link_nr, &mut cell_linked);
-                        link_nr += 1;
-                    };
-                }
-            }
+                Self::enter_conflicts(
+                    eliminated_entries,
+                    deductions,
+                    conflicts.iter().copied(),
+                    on_conflict,
+                )
+            },
+        )
+    }
 
-            for link_nr in 0..link_nr {
-                // Rule 1:
-                // if two cells in the same row, part of the same chain
-                // have the same color, those cells must not contain the number
-                // Rule 2:
-                // if one cell is neighbor to two cells with opposite colors
-                // it can not contain the number
+    pub(crate) fn find_als_xy_wing(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
 
+        almost_locked_sets::find_als_xy_wing(
+            cell_poss_digits,
+            stop_after_first,
+            |pivot, wings, restricted_common_digits, conflicts| {
+                let on_conflict = |conflicts| Deduction::AlsXyWing {
+                    pivot,
+                    wings,
+                    restricted_common_digits,
+                    conflicts,
+                };
 
-                // ===== Rule 1 ======
-                for house in House::all() {
-                    // Collect colors in this link chain and this house
-                    let mut house_colors = [None; 9];
-                    for (pos, cell) in house.cells()
-                        .into_iter()
-                        .enumerate()
-                        // TODO: Double check the logic here
-                        // this used to take the pos for indexing
-                        .filter(|&(_, cell)| cell_linked[cell] == link_nr)
-                    {
-                        house_colors[pos] = cell_color[cell];
-                    }
+                Self::enter_conflicts(
+                    eliminated_entries,
+                    deductions,
+                    conflicts.iter().copied(),
+                    on_conflict,
+                )
+            },
+        )
+    }
 
-                    let (n_a, n_b) = house_colors.iter()
-                        .fold((0, 0), |(n_a, n_b), &color| {
-                            match color {
-                                Some(Color::A) => (n_a+1, n_b),
-                                Some(Color::B) => (n_a, n_b+1),
-                                None => (n_a, n_b),
-                            }
-                        });
+    pub(crate) fn find_unique_rectangles(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
 
-                    fn mark_impossible(digit: Digit, link_nr: u8, color: Color, cell_color: CellArray<Option<Color>>, cell_linked: CellArray<u8>, impossible_entries: &mut Vec<Candidate>) {
-                        Cell::all().zip(cell_color.iter()).zip(cell_linked.iter())
-                            .filter(|&((_, &cell_color), &cell_link_nr)| link_nr == cell_link_nr && Some(color) == cell_color)
-                            .for_each(|((cell, _), _)| impossible_entries.push( Candidate { cell, digit }));
-                    }
+        unique_rectangles::find_unique_rectangles(
+            cell_poss_digits,
+            stop_after_first,
+            |rectangle_type, cells, digits, conflicts| {
+                let on_conflict = |conflicts| Deduction::UniqueRectangle {
+                    cells,
+                    digits,
+                    rectangle_type,
+                    conflicts,
+                };
 
-                    let impossible_color;
-                    match (n_a >= 2, n_b >= 2) {
-                        (true, true) => return Err(Unsolvable),
-                        (true, false) => impossible_color = Color::A,
-                        (false, true) => impossible_color = Color::B,
-                        (false, false) => continue,
-                    };
-                    mark_impossible(digit, link_nr, impossible_color, cell_color, cell_linked, &mut self.eliminated_entries);
-                    // chain handled, go to next
-                    // note: as this eagerly marks a color impossible as soon as a double in any color is found
-                    //       a case of two doubles in some later house will not always be found
-                    //       impossibility is then detected further down the strategy chain
-                    break
-                }
+                Self::enter_conflicts(
+                    eliminated_entries,
+                    deductions,
+                    conflicts.iter().copied(),
+                    on_conflict,
+                )
+            },
+        )
+    }
 
-                // ===== Rule 2 =====
-                let mut cell_sees_color = CellArray([(false, false); 81]);
-                for ((cell, &cell_color), _) in Cell::all()
-                    .zip(cell_color.iter())
-                    .zip(cell_linked.iter())
-                    .filter(|&((_, &cell_color), &cell_link_nr)| link_nr == cell_link_nr && cell_color.is_some())
-                {
-                    for &house in &cell.houses() {
-                        for neighbor_cell in house.cells().into_iter().filter(|&c| cell != c) {
-                            let (sees_a, sees_b) = cell_sees_color[neighbor_cell];
-                            if cell_color == Some(Color::A) && !sees_a {
-                                cell_sees_color[neighbor_cell].0 = true;
-                                if sees_b {
-                                    self.eliminated_entries.push( Candidate{ cell: neighbor_cell, digit })
-                                }
-                            } else if cell_color == Some(Color::B) && !sees_b {
-                                cell_sees_color[neighbor_cell].1 = true;
-                                if sees_a {
-                                    self.eliminated_entries.push( Candidate{ cell: neighbor_cell, digit })
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    pub(crate) fn find_bivalue_universal_grave(&mut self) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+
+        {
+            let cell_poss_digits = &self.cell_poss_digits.state;
+            let grid = &mut self.grid.state;
+            let deduced_entries = &mut self.deduced_entries;
+            let deductions = &mut self.deductions;
+
+            bivalue_universal_grave::find_bivalue_universal_grave(cell_poss_digits, |candidate| {
+                let deduction = Deduction::BivalueUniversalGrave(candidate);
+                Self::push_new_candidate(grid, deduced_entries, candidate, deductions, deduction)
+            })?;
         }
-        Ok(())
+
+        // call update again so newly found entries are inserted
+        self.update_cell_poss_house_solved()
+    }
+
+    pub(crate) fn find_simple_coloring(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+
+        simple_coloring::find_simple_coloring(
+            cell_poss_digits,
+            stop_after_first,
+            |digit, colors, conflicts| {
+                let conflicts = conflicts.into_iter().map(|cell| Candidate { cell, digit });
+                let on_conflict = |conflicts| Deduction::SimpleColoring {
+                    digit,
+                    colors,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_chains(&mut self, kind: ChainKind, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let chain_links = &mut self.chain_links;
+        let deductions = &mut self.deductions;
+
+        chains::find_chains(cell_poss_digits, kind, stop_after_first, |chain, conflicts| {
+            let len_before = chain_links.len();
+            chain_links.extend_from_slice(chain);
+            let chain = len_before..chain_links.len();
+
+            let on_conflict = |conflicts| match kind {
+                ChainKind::RemotePairs => Deduction::RemotePairs { chain, conflicts },
+                ChainKind::XyChain => Deduction::XyChain { chain, conflicts },
+                ChainKind::AlternatingInferenceChain => {
+                    Deduction::AlternatingInferenceChain { chain, conflicts }
+                }
+            };
+
+            Self::enter_conflicts(
+                eliminated_entries,
+                deductions,
+                conflicts.iter().copied(),
+                on_conflict,
+            )
+        })
     }
-    */
 }
 
 impl std::fmt::Display for StrategySolver {
@@ -1176,123 +1345,6 @@ impl<T> State<T> {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    fn read_sudokus(sudokus_str: &str) -> Vec<Sudoku> {
-        sudokus_str
-            .lines()
-            .map(|line| Sudoku::from_str_line(line).unwrap_or_else(|err| panic!("{:?}", err)))
-            .collect()
-    }
-
-    fn strategy_solver_correct_solution<F>(sudokus: Vec<Sudoku>, solved_sudokus: Vec<Sudoku>, solver: F)
-    where
-        F: Fn(StrategySolver, &[Strategy]) -> Result<(Sudoku, Deductions), (Sudoku, Deductions)>,
-    {
-        let n_sudokus = sudokus.len();
-        let strategies = Strategy::ALL;
-        let mut unsolved = vec![];
-        for (i, (sudoku, solved_sudoku)) in sudokus.into_iter().zip(solved_sudokus).enumerate() {
-            let cache = StrategySolver::from_sudoku(sudoku);
-            match solver(cache, strategies) {
-                Ok((solution, _deductions)) => assert_eq!(solution, solved_sudoku),
-                Err((part_solved, _deductions)) => unsolved.push((i, sudoku, part_solved, solved_sudoku)),
-            }
-        }
-        if !unsolved.is_empty() {
-            println!("Could not solve {}/{} sudokus:\n", unsolved.len(), n_sudokus);
-
-            for (i, sudoku, part_solution, _solution) in unsolved {
-                println!(
-                    "\nsudoku nr {}:\n{}\n{}\n{}",
-                    i + 1,
-                    sudoku.to_str_line(),
-                    part_solution.to_str_line(),
-                    _solution.to_str_line()
-                );
-            }
-            panic!();
-        }
-    }
-
-    #[test]
-    fn strategy_solver_correct_solution_easy_sudokus() {
-        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
-        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
-        strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
-    }
-
-    #[test]
-    fn strategy_solver_correct_solution_medium_sudokus() {
-        // the 9th sudoku requires more advanced strategies
-        let filter_9 = |vec: Vec<_>| {
-            vec.into_iter()
-                .enumerate()
-                .filter(|&(i, _)| i != 8)
-                .map(|(_, sudoku)| sudoku)
-                .collect::<Vec<_>>()
-        };
-        let sudokus = filter_9(read_sudokus(include_str!(
-            "../../sudokus/Lines/medium_sudokus.txt"
-        )));
-        let solved_sudokus = filter_9(read_sudokus(include_str!(
-            "../../sudokus/Lines/solved_medium_sudokus.txt"
-        )));
-        strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
-    }
-
-    #[test]
-    fn roundtrip_grid_state_str() {
-        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
-
-        for sudoku in sudokus {
-            let solver = StrategySolver::from_sudoku(sudoku);
-            let grid_state_string = solver.to_string();
-            let solver2 = StrategySolver::from_grid_state_str(&grid_state_string);
-            let grid_state_string2 = solver2.to_string();
-            if grid_state_string != grid_state_string2 {
-                panic!("\n{} \n{}", grid_state_string, grid_state_string2);
-            }
-        }
-    }
-
-    #[test]
-    fn grid_state_str_impossible_sudoku() {
-        let sudoku =
-        //"12345678.........9..4.376..6..4..5...3.....7...7..2..4..521.3............7...481.";
-        "12345678.........9...............................................................";
-        let sudoku = Sudoku::from_str_line(sudoku).unwrap();
-
-        let solver = StrategySolver::from_sudoku(sudoku);
-
-        #[rustfmt::skip]
-        let expected =
-"┌──────────────────────────────┬──────────────────────────────┬──────────────────────────────┐
-│ 1         2         3        │ 4         5         6        │ 7         8         _        │
-│ 45678     45678     45678    │ 12378     12378     12378    │ 123456    123456    9        │
-│ 456789    456789    456789   │ 123789    123789    123789   │ 123456    123456    123456   │
-├──────────────────────────────┼──────────────────────────────┼──────────────────────────────┤
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-├──────────────────────────────┼──────────────────────────────┼──────────────────────────────┤
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
-└──────────────────────────────┴──────────────────────────────┴──────────────────────────────┘
-";
-
-        assert_eq!(expected, &solver.to_string());
-
-        //let solver2 = StrategySolver::from_grid_state_str(&grid_state_string);
-        //let grid_state_string2 = solver2.to_string();
-        //if grid_state_string != grid_state_string2 {
-        //    panic!("\n{}\n{}", grid_state_string, grid_state_string2);
-        //}
-    }
-}
-
 fn print_grid_state(
     f: &mut std::fmt::Formatter,
     grid_state: [CellState; 81],
@@ -1413,3 +1465,185 @@ fn _print_separator(
         right = right_junction,
     )
 }
+
+// Snapshot of the solver state for (de)serialization.
+// The history of deductions is not part of it.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedSolver {
+    clues: Option<Sudoku>,
+    grid_state: Vec<CellState>,
+    // settings, missing in snapshots of older versions
+    #[serde(default)]
+    assume_unique_solution: bool,
+    #[serde(default = "default_forcing_chain_depth")]
+    forcing_chain_depth: u8,
+}
+
+#[cfg(feature = "serde")]
+fn default_forcing_chain_depth() -> u8 {
+    StrategySolver::empty().forcing_chain_depth
+}
+
+/// Serialized as a struct with the fields `clues`, containing the initial clues or `null`
+/// if they are unknown, `grid_state`, containing the 81 [`CellState`]s
+/// as returned by [`StrategySolver::grid_state`], and the settings `assume_unique_solution` and
+/// `forcing_chain_depth`, as set by [`StrategySolver::assume_unique_solution`] and
+/// [`StrategySolver::limit_forcing_chain_depth`]. Missing settings take their default values.
+/// The history of deductions is not preserved.
+#[cfg(feature = "serde")]
+impl serde::Serialize for StrategySolver {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedSolver {
+            clues: self.clues,
+            grid_state: self.grid_state().to_vec(),
+            assume_unique_solution: self.assume_unique_solution,
+            forcing_chain_depth: self.forcing_chain_depth,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StrategySolver {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use std::convert::TryInto;
+        let SerializedSolver {
+            clues,
+            grid_state,
+            assume_unique_solution,
+            forcing_chain_depth,
+        } = SerializedSolver::deserialize(deserializer)?;
+        let grid_state: [CellState; 81] = grid_state
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("grid state must contain 81 cells"))?;
+        Ok(StrategySolver {
+            clues,
+            assume_unique_solution,
+            forcing_chain_depth,
+            ..StrategySolver::from_grid_state(grid_state)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    fn read_sudokus(sudokus_str: &str) -> Vec<Sudoku> {
+        sudokus_str
+            .lines()
+            .map(|line| Sudoku::from_str_line(line).unwrap_or_else(|err| panic!("{:?}", err)))
+            .collect()
+    }
+
+    fn strategy_solver_correct_solution<F>(sudokus: Vec<Sudoku>, solved_sudokus: Vec<Sudoku>, solver: F)
+    where
+        F: Fn(StrategySolver, &[Strategy]) -> Result<(Sudoku, Deductions), (Sudoku, Deductions)>,
+    {
+        let n_sudokus = sudokus.len();
+        let strategies = Strategy::ALL;
+        let mut unsolved = vec![];
+        for (i, (sudoku, solved_sudoku)) in sudokus.into_iter().zip(solved_sudokus).enumerate() {
+            let cache = StrategySolver::from_sudoku(sudoku);
+            match solver(cache, strategies) {
+                Ok((solution, _deductions)) => assert_eq!(solution, solved_sudoku),
+                Err((part_solved, _deductions)) => unsolved.push((i, sudoku, part_solved, solved_sudoku)),
+            }
+        }
+        if !unsolved.is_empty() {
+            println!("Could not solve {}/{} sudokus:\n", unsolved.len(), n_sudokus);
+
+            for (i, sudoku, part_solution, _solution) in unsolved {
+                println!(
+                    "\nsudoku nr {}:\n{}\n{}\n{}",
+                    i + 1,
+                    sudoku.to_str_line(),
+                    part_solution.to_str_line(),
+                    _solution.to_str_line()
+                );
+            }
+            panic!();
+        }
+    }
+
+    #[test]
+    fn strategy_solver_correct_solution_easy_sudokus() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+        strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
+    }
+
+    #[test]
+    fn strategy_solver_correct_solution_medium_sudokus() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_medium_sudokus.txt"));
+        strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
+    }
+
+    #[test]
+    fn strategy_solver_correct_solution_hard_sudokus() {
+        // nested forcing chains are slow, so only a few are tested
+        let mut sudokus = read_sudokus(include_str!("../../sudokus/Lines/hard_sudokus.txt"));
+        let mut solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_hard_sudokus.txt"));
+        sudokus.truncate(5);
+        solved_sudokus.truncate(5);
+        #[allow(clippy::result_large_err)]
+        fn solve_with_nested_forcing_chains(
+            solver: StrategySolver,
+            strategies: &[Strategy],
+        ) -> Result<(Sudoku, Deductions), (Sudoku, Deductions)> {
+            solver.limit_forcing_chain_depth(2).solve(strategies)
+        }
+        strategy_solver_correct_solution(sudokus, solved_sudokus, solve_with_nested_forcing_chains);
+    }
+
+    #[test]
+    fn roundtrip_grid_state_str() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+
+        for sudoku in sudokus {
+            let solver = StrategySolver::from_sudoku(sudoku);
+            let grid_state_string = solver.to_string();
+            let solver2 = StrategySolver::from_grid_state_str(&grid_state_string);
+            let grid_state_string2 = solver2.to_string();
+            if grid_state_string != grid_state_string2 {
+                panic!("\n{} \n{}", grid_state_string, grid_state_string2);
+            }
+        }
+    }
+
+    #[test]
+    fn grid_state_str_impossible_sudoku() {
+        let sudoku =
+        //"12345678.........9..4.376..6..4..5...3.....7...7..2..4..521.3............7...481.";
+        "12345678.........9...............................................................";
+        let sudoku = Sudoku::from_str_line(sudoku).unwrap();
+
+        let solver = StrategySolver::from_sudoku(sudoku);
+
+        #[rustfmt::skip]
+        let expected =
+"┌──────────────────────────────┬──────────────────────────────┬──────────────────────────────┐
+│ 1         2         3        │ 4         5         6        │ 7         8         _        │
+│ 45678     45678     45678    │ 12378     12378     12378    │ 123456    123456    9        │
+│ 456789    456789    456789   │ 123789    123789    123789   │ 123456    123456    123456   │
+├──────────────────────────────┼──────────────────────────────┼──────────────────────────────┤
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+├──────────────────────────────┼──────────────────────────────┼──────────────────────────────┤
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+│ 23456789  13456789  12456789 │ 12356789  12346789  12345789 │ 12345689  12345679  12345678 │
+└──────────────────────────────┴──────────────────────────────┴──────────────────────────────┘
+";
+
+        assert_eq!(expected, &solver.to_string());
+
+        //let solver2 = StrategySolver::from_grid_state_str(&grid_state_string);
+        //let grid_state_string2 = solver2.to_string();
+        //if grid_state_string != grid_state_string2 {
+        //    panic!("\n{}\n{}", grid_state_string, grid_state_string2);
+        //}
+    }
+}
@@ -3,15 +3,20 @@ pub(crate) mod prelude;
 pub(crate) mod almost_locked_sets;
 pub(crate) mod avoidable_rectangles;
 pub(crate) mod basic_fish;
+pub(crate) mod bivalue_universal_grave;
+pub(crate) mod chains;
 pub(crate) mod hidden_singles;
 pub(crate) mod hidden_subsets;
 pub(crate) mod locked_candidates;
 pub(crate) mod mutant_fish;
 pub(crate) mod naked_singles;
 pub(crate) mod naked_subsets;
+pub(crate) mod simple_coloring;
+pub(crate) mod unique_rectangles;
 pub(crate) mod xy_wing;
 pub(crate) mod xyz_wing;
 
+use self::chains::ChainKind;
 use super::StrategySolver;
 use crate::helper::Unsolvable;
 
@@ -20,7 +25,8 @@ use crate::helper::Unsolvable;
 /// This can be used with [`StrategySolver::solve`].
 /// May be expanded in the future.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Strategy {
     NakedSingles,
@@ -40,7 +46,21 @@ pub enum Strategy {
     MutantSwordfish,
     MutantJellyfish,
     AvoidableRectangles,
-    //SinglesChain,
+    SimpleColoring,
+    RemotePairs,
+    XyChain,
+    AlternatingInferenceChain,
+    /// Unique rectangles of types 1 to 4.
+    /// Only used if the solver [assumes a unique solution](StrategySolver::assume_unique_solution).
+    UniqueRectangles,
+    /// Bivalue universal grave + 1.
+    /// Only used if the solver [assumes a unique solution](StrategySolver::assume_unique_solution).
+    BivalueUniversalGrave,
+    AlsXz,
+    AlsXyWing,
+    /// Eliminates candidates whose assumption leads to a contradiction.
+    /// The maximum nesting depth is [configured on the solver](StrategySolver::limit_forcing_chain_depth).
+    ForcingChains,
 }
 
 impl Strategy {
@@ -64,7 +84,13 @@ impl Strategy {
         Strategy::NakedQuads,       // 50
         Strategy::Jellyfish,        // 52
         Strategy::HiddenQuads,      // 54
-        //Strategy::SinglesChain,
+        Strategy::SimpleColoring,   // 65
+        Strategy::RemotePairs,      // 66
+        Strategy::XyChain,          // 66
+        Strategy::AlternatingInferenceChain, // 70
+        Strategy::AlsXz,            // 72
+        Strategy::AlsXyWing,        // 74
+        Strategy::ForcingChains,    // 85+
     ];
 
     // is_first_strategy is an optimization hint
@@ -96,7 +122,18 @@ impl Strategy {
             XyzWing => state.find_xyz_wing(stop_after_first),
             MutantSwordfish => state.find_mutant_fish(3, stop_after_first),
             MutantJellyfish => state.find_mutant_fish(4, stop_after_first),
-            //SinglesChain => state.find_singles_chain(stop_after_first), // TODO: Implement non-eager SinglesChain
+            SimpleColoring => state.find_simple_coloring(stop_after_first),
+            RemotePairs => state.find_chains(ChainKind::RemotePairs, stop_after_first),
+            XyChain => state.find_chains(ChainKind::XyChain, stop_after_first),
+            AlternatingInferenceChain => {
+                state.find_chains(ChainKind::AlternatingInferenceChain, stop_after_first)
+            }
+            AlsXz => state.find_als_xz(stop_after_first),
+            AlsXyWing => state.find_als_xy_wing(stop_after_first),
+            ForcingChains => state.find_forcing_chains(stop_after_first),
+            UniqueRectangles | BivalueUniversalGrave if !state.assume_unique_solution => Ok(()),
+            UniqueRectangles => state.find_unique_rectangles(stop_after_first),
+            BivalueUniversalGrave => state.find_bivalue_universal_grave(),
             _ => unimplemented!(),
         }
     }
@@ -1,63 +1,109 @@
-// WIP
-#![allow(unused)]
 use super::prelude::*;
 
-pub(crate) fn find_almost_locked_sets(
+// An almost locked set (ALS) consists of N cells in one house with N+1 candidates in total.
+// If any of its candidates is removed, the remaining ones are locked in its cells.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AlmostLockedSet {
+    pub(crate) cells: Set<Cell>,
+    pub(crate) digits: Set<Digit>,
+    // for each digit, the cells of the set that contain it
+    digit_cells: DigitArray<Set<Cell>>,
+}
+
+impl AlmostLockedSet {
+    // Digits which can be true in at most one of the two sets, because all their
+    // candidates in one set see all their candidates in the other set.
+    // If one of these digits is removed from one set, it must be true in the other one.
+    fn restricted_common_digits(&self, other: &AlmostLockedSet) -> Set<Digit> {
+        if self.cells.overlaps(other.cells) {
+            return Set::NONE;
+        }
+        (self.digits & other.digits)
+            .into_iter()
+            .filter(|&digit| {
+                let other_cells = other.digit_cells[digit];
+                self.digit_cells[digit]
+                    .into_iter()
+                    .all(|cell| cell.neighbors_set().contains(other_cells))
+            })
+            .fold(Set::NONE, |digits, digit| digits | digit)
+    }
+}
+
+// ALS-XZ: Two almost locked sets A and B with a restricted common digit X.
+// X can only be true in one of them, so the other one is locked.
+// For any other common digit Z, at least one candidate of Z in A or B is true.
+// Z is therefore impossible in all cells that see all candidates of Z in both sets.
+pub(crate) fn find_als_xz(
     cells_poss_digits: &CellArray<Set<Digit>>,
-    //house_solved_digits: &HouseArray<Set<Digit>>,
-    //subset_size: u8,
     stop_after_first: bool,
-    mut on_subset: impl FnMut(
-        //House,
-        //Set<Position<House>>,
-        //Set<Digit>,
-        (), // rustfmt bug: deletes comments unless something is here
+    mut on_als_xz: impl FnMut(
+        [Set<Cell>; 2], // the cells of both sets
+        Digit,          // restricted common digit
+        &[Candidate],   // conflicts
     ) -> bool,
 ) -> Result<(), Unsolvable> {
-    let als = _find_almost_locked_sets(cells_poss_digits);
-    for first_set_size in 2..=8 {
-        for second_set_size in 1..=first_set_size {
-            let sets1 = &als[first_set_size];
-            let sets2 = &als[second_set_size];
-            // TODO: special case equal sized sets
-            //       so there's no repetition
-
-            // iterate over all house combinations
-            // except combinations of the same house kind
-            // because they have no overlap
-            for (house1, set1) in sets1.iter().enumerate() {
-                for &(cells1, digits1) in set1 {
-                    for (house2, set2) in sets2.iter().enumerate().filter(|&(h2, _)| h2 / 9 != house1 / 9) {
-                        for &(cells2, digits2) in set2 {
-                            let common_digits = digits1 & digits2;
-                            if common_digits.is_empty() {
-                                continue;
-                            }
-
-                            //let mut restricted_common_digits = Set::NONE;
+    let sets = find_almost_locked_sets(cells_poss_digits);
+    for (idx, set1) in sets.iter().enumerate() {
+        for set2 in &sets[idx + 1..] {
+            for restricted_digit in set1.restricted_common_digits(set2) {
+                let other_digits = (set1.digits & set2.digits).without(restricted_digit.as_set());
+                let conflicts = common_digit_conflicts(cells_poss_digits, [set1, set2], other_digits);
+                if conflicts.is_empty() {
+                    continue;
+                }
+                if on_als_xz([set1.cells, set2.cells], restricted_digit, &conflicts) && stop_after_first {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
-                            for digit in common_digits {
-                                let cells_of_digit = |digit: Digit, cells| {
-                                    let mut cells_of_digit = Set::NONE;
-                                    let digit_set = digit.as_set();
-                                    for cell in cells {
-                                        if cells_poss_digits[cell].overlaps(digit_set) {
-                                            cells_of_digit |= cell;
-                                        }
-                                    }
-                                    cells_of_digit
-                                };
-                                let cells_of_digit1 = cells_of_digit(digit, cells1);
-                                let cells_of_digit2 = cells_of_digit(digit, cells2);
+// ALS-XY-Wing: A pivot ALS C with restricted common digits X and Y with two other ALS A and B.
+// If X is not true in A, C is locked and Y can't be true in C, so B is locked.
+// Either A or B is locked and for any common digit Z of A and B other than X and Y,
+// at least one candidate of Z in A or B is true.
+pub(crate) fn find_als_xy_wing(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    stop_after_first: bool,
+    mut on_als_xy_wing: impl FnMut(
+        Set<Cell>,      // pivot
+        [Set<Cell>; 2], // wings
+        [Digit; 2],     // restricted common digits between the pivot and each wing
+        &[Candidate],   // conflicts
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    let sets = find_almost_locked_sets(cells_poss_digits);
+    for pivot in &sets {
+        let wings = sets
+            .iter()
+            .map(|wing| (wing, pivot.restricted_common_digits(wing)))
+            .filter(|&(_, restricted_digits)| !restricted_digits.is_empty())
+            .collect::<Vec<_>>();
 
-                                // ALS with overlapping cells are possible
-                                // but not yet supported
-                                // also, the restricted common digit must not be in an
-                                // overlapping cell
-                                if cells_of_digit1.overlaps(cells_of_digit2) {
-                                    continue;
-                                }
-                            }
+        for (idx, &(wing1, restricted_digits1)) in wings.iter().enumerate() {
+            for &(wing2, restricted_digits2) in &wings[idx + 1..] {
+                if wing1.cells.overlaps(wing2.cells) {
+                    continue;
+                }
+                for digit1 in restricted_digits1 {
+                    for digit2 in restricted_digits2.without(digit1.as_set()) {
+                        let other_digits = (wing1.digits & wing2.digits).without(digit1.as_set() | digit2);
+                        let conflicts =
+                            common_digit_conflicts(cells_poss_digits, [wing1, wing2], other_digits);
+                        if conflicts.is_empty() {
+                            continue;
+                        }
+                        let found_conflicts = on_als_xy_wing(
+                            pivot.cells,
+                            [wing1.cells, wing2.cells],
+                            [digit1, digit2],
+                            &conflicts,
+                        );
+                        if found_conflicts && stop_after_first {
+                            return Ok(());
                         }
                     }
                 }
@@ -67,60 +113,142 @@ pub(crate) fn find_almost_locked_sets(
     Ok(())
 }
 
-type AlmostLockedSets = [[Vec<(Set<Cell>, Set<Digit>)>; 27]; 8];
+// Candidates of `digits` outside of both sets that see all candidates of the same digit in both sets
+fn common_digit_conflicts(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    sets: [&AlmostLockedSet; 2],
+    digits: Set<Digit>,
+) -> Vec<Candidate> {
+    let mut conflicts = vec![];
+    for digit in digits {
+        let digit_cells = sets[0].digit_cells[digit] | sets[1].digit_cells[digit];
+        let common_neighbors = digit_cells
+            .into_iter()
+            .fold(Set::ALL, |neighbors, cell| neighbors & cell.neighbors_set());
+        for cell in common_neighbors.without(sets[0].cells | sets[1].cells) {
+            if cells_poss_digits[cell].contains(digit) {
+                conflicts.push(Candidate { cell, digit });
+            }
+        }
+    }
+    conflicts
+}
 
-// 27 houses
-// 1-8 cells
-// 2-9 candidates
-// [[Vec<(Set<Cell>, Set<Digit>)>; 9]; 27]
-pub(crate) fn _find_almost_locked_sets(cells_poss_digits: &CellArray<Set<Digit>>) -> AlmostLockedSets {
-    let mut sets = AlmostLockedSets::default(); //[[vec![]; 9]; 27];
+// Finds all almost locked sets in all houses. Sets that lie in two houses are only returned once.
+pub(crate) fn find_almost_locked_sets(cells_poss_digits: &CellArray<Set<Digit>>) -> Vec<AlmostLockedSet> {
+    let mut sets = vec![];
     for house in House::all() {
-        let cells = house.cells();
-        _walk_combinations(
-            cells_poss_digits,
-            cells.into_iter(),
-            Set::NONE,
-            Set::NONE,
-            &mut sets,
-            house.as_index(),
-            0,
-        );
+        let cells = house
+            .cells()
+            .into_iter()
+            .filter(|&cell| cells_poss_digits[cell].len() >= 2)
+            .collect::<Vec<_>>();
+        walk_combinations(cells_poss_digits, &cells, Set::NONE, Set::NONE, &mut sets);
     }
+    sets.sort_by_key(|set| set.cells);
+    sets.dedup_by_key(|set| set.cells);
     sets
 }
 
-pub(crate) fn _walk_combinations(
+fn walk_combinations(
     cells_poss_digits: &CellArray<Set<Digit>>,
-    mut cells: SetIter<Cell>,
+    cells: &[Cell],
     cell_set: Set<Cell>,
     digits: Set<Digit>,
-    almost_locked_sets: &mut AlmostLockedSets,
-    house: usize,
-    depth: u8,
+    sets: &mut Vec<AlmostLockedSet>,
 ) {
-    while let Some(cell) = cells.next() {
-        let new_cell_set = cell_set | cell.as_set();
-        let candidates = cells_poss_digits[cell];
-        if candidates.len() <= 1 {
-            continue;
-        }
-        let new_digits = digits | candidates;
-
-        assert!(new_cell_set.len() == depth + 1);
-
+    for (idx, &cell) in cells.iter().enumerate() {
+        let new_cell_set = cell_set | cell;
+        let new_digits = digits | cells_poss_digits[cell];
         if new_digits.len() == new_cell_set.len() + 1 {
-            almost_locked_sets[new_cell_set.len() as usize - 1][house].push((new_cell_set, new_digits));
+            let mut digit_cells = DigitArray([Set::NONE; 9]);
+            for cell in new_cell_set {
+                for digit in cells_poss_digits[cell] {
+                    digit_cells[digit] |= cell;
+                }
+            }
+            sets.push(AlmostLockedSet {
+                cells: new_cell_set,
+                digits: new_digits,
+                digit_cells,
+            });
         }
 
-        _walk_combinations(
+        walk_combinations(
             cells_poss_digits,
-            cells.clone(),
+            &cells[idx + 1..],
             new_cell_set,
             new_digits,
-            almost_locked_sets,
-            house,
-            depth + 1,
+            sets,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    // Checks that the conflicts of all deductions of `strategy` don't remove the solution
+    fn check_conflicts(sudoku: &str, strategy: Strategy) {
+        let sudoku = Sudoku::from_str_line(sudoku).unwrap();
+        let solution = sudoku.solution().unwrap();
+        let strategies = [
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::NakedPairs,
+            Strategy::XWing,
+            Strategy::HiddenPairs,
+            Strategy::NakedTriples,
+            Strategy::Swordfish,
+            Strategy::HiddenTriples,
+            Strategy::XyWing,
+            Strategy::XyzWing,
+            strategy,
+        ];
+        let (_, deductions) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+
+        let mut n_found = 0;
+        for deduction in deductions.iter() {
+            let conflicts = match deduction {
+                Deduction::AlsXz { sets, conflicts, .. } => {
+                    assert!(!sets[0].overlaps(sets[1]));
+                    conflicts
+                }
+                Deduction::AlsXyWing {
+                    pivot,
+                    wings,
+                    restricted_common_digits,
+                    conflicts,
+                } => {
+                    assert!(!pivot.overlaps(wings[0] | wings[1]));
+                    assert_ne!(restricted_common_digits[0], restricted_common_digits[1]);
+                    conflicts
+                }
+                _ => continue,
+            };
+            n_found += 1;
+            for conflict in conflicts {
+                assert_ne!(solution.0[conflict.cell.as_index()], conflict.digit.get());
+            }
+        }
+        assert!(n_found > 0);
+    }
+
+    #[test]
+    fn als_xz() {
+        check_conflicts(
+            "5....298..849.1..2......1...7...9..53.......18..5...3...7......9..1.546..154....9",
+            Strategy::AlsXz,
+        );
+    }
+
+    #[test]
+    fn als_xy_wing() {
+        check_conflicts(
+            "94..........63..4..61.5...3..95..2...5..2..7...6..83..3...4.16..2..71..........92",
+            Strategy::AlsXyWing,
         );
     }
 }
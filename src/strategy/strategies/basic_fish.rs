@@ -36,6 +36,7 @@ pub(crate) fn find_fish(
 // x-wing          2
 // swordfish       3
 // jellyfish       4
+#[allow(clippy::only_used_in_recursion)]
 fn basic_fish_walk_combinations(
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
     digit: Digit,
@@ -0,0 +1,78 @@
+use super::prelude::*;
+
+// If all unsolved cells had exactly 2 candidates and every candidate appeared exactly twice in
+// each of its houses, the sudoku would have at least 2 solutions. Assuming the solution is unique,
+// the extra candidate of the only cell with 3 candidates must be true (BUG+1).
+pub(crate) fn find_bivalue_universal_grave(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    mut on_bug: impl FnMut(Candidate) -> Result<(), Unsolvable>,
+) -> Result<(), Unsolvable> {
+    let mut trivalue_cell = None;
+    for cell in Cell::all() {
+        match cells_poss_digits[cell].len() {
+            0 | 2 => {}
+            3 if trivalue_cell.is_none() => trivalue_cell = Some(cell),
+            _ => return Ok(()),
+        }
+    }
+    let cell = match trivalue_cell {
+        Some(cell) => cell,
+        None => return Ok(()),
+    };
+
+    // the extra candidate appears 3 times in each house of the cell
+    let n_appearances = |house: House, digit: Digit| {
+        house
+            .cells()
+            .into_iter()
+            .filter(|&other| cells_poss_digits[other].contains(digit))
+            .count()
+    };
+    let mut extra_digits = cells_poss_digits[cell].into_iter().filter(|&digit| {
+        IntoIterator::into_iter(cell.houses()).all(|house| n_appearances(house, digit) == 3)
+    });
+    match (extra_digits.next(), extra_digits.next()) {
+        (Some(digit), None) => on_bug(Candidate { cell, digit }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    #[test]
+    fn bivalue_universal_grave() {
+        let sudoku = Sudoku::from_str_line(
+            "..8......5..9....2...123.9...78....1.3..5..4.1....46...9.718...8....6..4......3..",
+        )
+        .unwrap();
+        let solution = sudoku.solution().unwrap();
+        let strategies = [
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::NakedPairs,
+            Strategy::HiddenPairs,
+            Strategy::XWing,
+            Strategy::BivalueUniversalGrave,
+        ];
+        let (_, deductions) = StrategySolver::from_sudoku(sudoku)
+            .assume_unique_solution(true)
+            .solve(&strategies)
+            .unwrap();
+
+        let bugs = deductions
+            .iter()
+            .filter_map(|deduction| match deduction {
+                Deduction::BivalueUniversalGrave(candidate) => Some(candidate),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!bugs.is_empty());
+        for candidate in bugs {
+            assert_eq!(solution.0[candidate.cell.as_index()], candidate.digit.get());
+        }
+    }
+}
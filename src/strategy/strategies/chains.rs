@@ -0,0 +1,278 @@
+use super::prelude::*;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChainKind {
+    /// Bivalue cells that all contain the same pair of digits
+    RemotePairs,
+    /// Bivalue cells, linked by a common digit
+    XyChain,
+    /// Any strong and weak links between candidates
+    AlternatingInferenceChain,
+}
+
+impl ChainKind {
+    // Minimum number of candidates in a chain. Shorter chains are covered by simpler strategies.
+    fn min_len(self) -> usize {
+        match self {
+            ChainKind::RemotePairs => 8,
+            ChainKind::XyChain => 6,
+            ChainKind::AlternatingInferenceChain => 4,
+        }
+    }
+}
+
+// A candidate that is assumed to be either true (on) or false (off)
+type Node = (Candidate, bool);
+
+// Searches for chains of candidates with alternating strong and weak links.
+//
+// Strong links connect two candidates of which at least one is true, weak links connect two
+// candidates of which at most one is true. Starting with a candidate that is assumed false,
+// every strong link turns a false candidate into a true one and every weak link a true one
+// into a false one. If the chain ends on a true candidate, one of its two ends must be true.
+// Every candidate that is weakly linked to both ends is therefore false.
+//
+// The chains are passed to `on_chain` as the sequence of their candidates, starting and ending
+// with a strong link, together with the candidates that can be eliminated.
+// Each candidate is only eliminated once.
+pub(crate) fn find_chains(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    kind: ChainKind,
+    stop_after_first: bool,
+    mut on_chain: impl FnMut(
+        &[Candidate], // chain
+        &[Candidate], // conflicts
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    let mut eliminated = CellArray([Set::NONE; 81]);
+
+    for start_cell in Cell::all() {
+        let start_digits = cells_poss_digits[start_cell];
+        // cells that are allowed in the chain
+        let is_chain_cell = |cell: Cell| match kind {
+            ChainKind::RemotePairs => cells_poss_digits[cell] == start_digits,
+            ChainKind::XyChain => cells_poss_digits[cell].len() == 2,
+            ChainKind::AlternatingInferenceChain => !cells_poss_digits[cell].is_empty(),
+        };
+        if !is_chain_cell(start_cell) {
+            continue;
+        }
+
+        for start_digit in start_digits {
+            let start = Candidate {
+                cell: start_cell,
+                digit: start_digit,
+            };
+            let mut predecessors = [[None; 2]; 729];
+            let mut queue = VecDeque::new();
+            predecessors[node_index((start, false))][0] = Some((start, false));
+            queue.push_back((start, false));
+
+            while let Some(node @ (candidate, is_on)) = queue.pop_front() {
+                if is_on {
+                    let conflicts = chain_conflicts(cells_poss_digits, kind, start, candidate)
+                        .filter(|conflict| !eliminated[conflict.cell].contains(conflict.digit))
+                        .collect::<Vec<_>>();
+                    if !conflicts.is_empty() {
+                        let chain = trace_chain(&predecessors, node);
+                        if chain.len() >= kind.min_len() {
+                            for conflict in &conflicts {
+                                eliminated[conflict.cell] |= conflict.digit;
+                            }
+                            if on_chain(&chain, &conflicts) && stop_after_first {
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+
+                let links = match is_on {
+                    false => strong_links(cells_poss_digits, kind, candidate).collect::<Vec<_>>(),
+                    true => weak_links(cells_poss_digits, kind, candidate).collect(),
+                };
+                for next in links.into_iter().filter(|next| is_chain_cell(next.cell)) {
+                    let next_node = (next, !is_on);
+                    let predecessor = &mut predecessors[node_index(next_node)][!is_on as usize];
+                    if predecessor.is_none() {
+                        *predecessor = Some(node);
+                        queue.push_back(next_node);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn node_index((candidate, _): Node) -> usize {
+    candidate.cell.as_index() * 9 + candidate.digit.as_index()
+}
+
+// Follows the predecessors back to the start of the chain.
+fn trace_chain(predecessors: &[[Option<Node>; 2]; 729], end: Node) -> Vec<Candidate> {
+    let mut chain = vec![end.0];
+    let mut node = end;
+    loop {
+        let predecessor = predecessors[node_index(node)][node.1 as usize].unwrap();
+        if predecessor == node {
+            break;
+        }
+        chain.push(predecessor.0);
+        node = predecessor;
+    }
+    chain.reverse();
+    chain
+}
+
+// Candidates of which at least one is true together with `candidate`
+fn strong_links<'a>(
+    cells_poss_digits: &'a CellArray<Set<Digit>>,
+    kind: ChainKind,
+    candidate: Candidate,
+) -> impl Iterator<Item = Candidate> + 'a {
+    let Candidate { cell, digit } = candidate;
+
+    // bivalue cell
+    let cell_digits = cells_poss_digits[cell];
+    let in_cell = match cell_digits.len() == 2 {
+        true => Some(Candidate {
+            cell,
+            digit: cell_digits.without(digit.as_set()).one_possibility(),
+        }),
+        false => None,
+    };
+
+    // bilocal house
+    let in_houses = IntoIterator::into_iter(cell.houses())
+        .filter(move |_| kind == ChainKind::AlternatingInferenceChain)
+        .filter_map(move |house| {
+            let house_cells = house
+                .cells()
+                .into_iter()
+                .filter(|&other| cells_poss_digits[other].contains(digit))
+                .fold(Set::NONE, |cells, other| cells | other);
+            match house_cells.len() == 2 {
+                true => Some(Candidate {
+                    cell: house_cells.without(cell.as_set()).one_possibility(),
+                    digit,
+                }),
+                false => None,
+            }
+        });
+
+    in_cell.into_iter().chain(in_houses)
+}
+
+// Candidates of which at most one is true together with `candidate`
+fn weak_links<'a>(
+    cells_poss_digits: &'a CellArray<Set<Digit>>,
+    kind: ChainKind,
+    candidate: Candidate,
+) -> impl Iterator<Item = Candidate> + 'a {
+    let Candidate { cell, digit } = candidate;
+
+    // the links inside of bivalue cells are already used as strong links in xy-chains
+    let in_cell = match kind {
+        ChainKind::AlternatingInferenceChain => cells_poss_digits[cell].without(digit.as_set()),
+        ChainKind::RemotePairs | ChainKind::XyChain => Set::NONE,
+    };
+    let in_cell = in_cell.into_iter().map(move |other_digit| Candidate {
+        cell,
+        digit: other_digit,
+    });
+
+    let in_houses = cell
+        .neighbors_set()
+        .into_iter()
+        .filter(move |&other| cells_poss_digits[other].contains(digit))
+        .map(move |other| Candidate { cell: other, digit });
+
+    in_cell.chain(in_houses)
+}
+
+// Candidates that are weakly linked to both ends of a chain
+fn chain_conflicts<'a>(
+    cells_poss_digits: &'a CellArray<Set<Digit>>,
+    kind: ChainKind,
+    start: Candidate,
+    end: Candidate,
+) -> impl Iterator<Item = Candidate> + 'a {
+    let sees_start = move |other: &Candidate| match other.cell == start.cell {
+        true => other.digit != start.digit,
+        false => other.digit == start.digit && other.cell.neighbors_set().contains(start.cell),
+    };
+    // xy-chains and remote pairs only eliminate the digit that both ends have in common
+    let is_allowed = kind == ChainKind::AlternatingInferenceChain || start.digit == end.digit;
+
+    weak_links(cells_poss_digits, ChainKind::AlternatingInferenceChain, end)
+        .filter(move |other| is_allowed && *other != start && sees_start(other))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    // Checks that the chains are valid and their conflicts don't remove the solution.
+    fn check_chains(sudoku: Sudoku, strategy: Strategy) {
+        let solution = sudoku.solution().unwrap();
+        let strategies = [
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            strategy,
+        ];
+        let (_, deductions) = match StrategySolver::from_sudoku(sudoku).solve(&strategies) {
+            Ok(result) | Err(result) => result,
+        };
+
+        let mut n_chains = 0;
+        for deduction in deductions.iter() {
+            let (chain, conflicts) = match deduction {
+                Deduction::RemotePairs { chain, conflicts }
+                | Deduction::XyChain { chain, conflicts }
+                | Deduction::AlternatingInferenceChain { chain, conflicts } => (chain, conflicts),
+                _ => continue,
+            };
+            assert_eq!(deduction.strategy(), strategy);
+            n_chains += 1;
+
+            assert_eq!(chain.len() % 2, 0);
+            for link in chain.windows(2) {
+                assert!(link[0].cell == link[1].cell || link[0].digit == link[1].digit);
+            }
+            for conflict in conflicts {
+                assert_ne!(solution.0[conflict.cell.as_index()], conflict.digit.get());
+            }
+        }
+        assert!(n_chains > 0);
+    }
+
+    #[test]
+    fn remote_pairs() {
+        let sudoku = Sudoku::from_str_line(
+            "6.....4.8....7..9..9..3..5.3....9.6.9..164..7.1.3....5.8..5..2..2..9....1.9.....3",
+        )
+        .unwrap();
+        check_chains(sudoku, Strategy::RemotePairs);
+    }
+
+    #[test]
+    fn xy_chain() {
+        let sudoku = Sudoku::from_str_line(
+            "6.....4.8....7..9..9..3..5.3....9.6.9..164..7.1.3....5.8..5..2..2..9....1.9.....3",
+        )
+        .unwrap();
+        check_chains(sudoku, Strategy::XyChain);
+    }
+
+    #[test]
+    fn alternating_inference_chain() {
+        let sudoku = Sudoku::from_str_line(
+            "2......9......96..8.756....72..93.....3.4.9.....28..67....327.9..14......3......4",
+        )
+        .unwrap();
+        check_chains(sudoku, Strategy::AlternatingInferenceChain);
+    }
+}
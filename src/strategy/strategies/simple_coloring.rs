@@ -0,0 +1,125 @@
+use super::prelude::*;
+
+// Conjugate pairs of a digit are the only two possible cells for it in some house.
+// Exactly one of them contains the digit, so every connected group of conjugate pairs
+// can be colored with two colors, one of which is true.
+pub(crate) fn find_simple_coloring(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    stop_after_first: bool,
+    mut on_coloring: impl FnMut(
+        Digit,
+        [Set<Cell>; 2], // cells of both colors
+        Set<Cell>,      // cells that can't contain the digit
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for digit in Set::<Digit>::ALL {
+        let poss_cells = Cell::all()
+            .filter(|&cell| cells_poss_digits[cell].contains(digit))
+            .fold(Set::NONE, |cells, cell| cells | cell);
+
+        let mut uncolored = poss_cells;
+        for start in poss_cells {
+            if !uncolored.contains(start) {
+                continue;
+            }
+            let colors = color_conjugate_pairs(poss_cells, start)?;
+            uncolored = uncolored.without(colors[0] | colors[1]);
+            if colors[1].is_empty() {
+                continue;
+            }
+
+            // color wrap: two cells of the same color see each other,
+            // so all cells of that color are false
+            let is_wrapped =
+                |color: Set<Cell>| color.into_iter().any(|cell| cell.neighbors_set().overlaps(color));
+            let conflicts = match (is_wrapped(colors[0]), is_wrapped(colors[1])) {
+                (true, true) => return Err(Unsolvable),
+                (true, false) => colors[0],
+                (false, true) => colors[1],
+                // color trap: one of the colors is true, so uncolored cells that see
+                // both colors are false
+                (false, false) => {
+                    let sees_color = |color: Set<Cell>| {
+                        color
+                            .into_iter()
+                            .fold(Set::NONE, |seen, cell| seen | cell.neighbors_set())
+                    };
+                    sees_color(colors[0]) & sees_color(colors[1]) & poss_cells.without(colors[0] | colors[1])
+                }
+            };
+            if conflicts.is_empty() {
+                continue;
+            }
+
+            if on_coloring(digit, colors, conflicts) && stop_after_first {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Colors all cells that are connected to `start` via conjugate pairs.
+// Errors, if the links can't be colored consistently.
+fn color_conjugate_pairs(poss_cells: Set<Cell>, start: Cell) -> Result<[Set<Cell>; 2], Unsolvable> {
+    let mut colors = [start.as_set(), Set::NONE];
+    let mut stack = vec![(start, 0)];
+    while let Some((cell, color)) = stack.pop() {
+        for &house in &cell.houses() {
+            let house_cells = house.cells() & poss_cells;
+            if house_cells.len() != 2 {
+                continue;
+            }
+            let other_cell = house_cells.without(cell.as_set()).one_possibility();
+            let other_color = 1 - color;
+            if colors[color].contains(other_cell) {
+                return Err(Unsolvable);
+            }
+            if !colors[other_color].contains(other_cell) {
+                colors[other_color] |= other_cell;
+                stack.push((other_cell, other_color));
+            }
+        }
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    #[test]
+    fn simple_coloring() {
+        let sudoku = Sudoku::from_str_line(
+            "....4..2..3.1.2..6.6..9...1.1.2..9.3.8..1..5.2.3..5.8.8...7..1.9..8.6.7..5..2....",
+        )
+        .unwrap();
+        let solution = sudoku.solution().unwrap();
+        let strategies = [
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::SimpleColoring,
+        ];
+        let (_, deductions) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+
+        let mut n_colorings = 0;
+        for deduction in deductions.iter() {
+            if let Deduction::SimpleColoring {
+                digit,
+                colors,
+                conflicts,
+            } = deduction
+            {
+                n_colorings += 1;
+                assert!(!colors[0].overlaps(colors[1]));
+                for conflict in conflicts {
+                    assert_eq!(conflict.digit, digit);
+                    assert_ne!(solution.0[conflict.cell.as_index()], digit.get());
+                }
+            }
+        }
+        assert!(n_colorings > 0);
+    }
+}
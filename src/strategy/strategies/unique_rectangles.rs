@@ -0,0 +1,226 @@
+use super::prelude::*;
+
+// A unique rectangle consists of 4 cells in 2 rows, 2 columns and 2 blocks that all contain the
+// same 2 digits as candidates. If only those 2 digits remained, they could be swapped in all 4 cells
+// and the sudoku would have 2 solutions. Assuming the solution is unique, some of the additional
+// candidates in the rectangle must be true.
+pub(crate) fn find_unique_rectangles(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    stop_after_first: bool,
+    mut on_rectangle: impl FnMut(
+        u8,           // type of the rectangle
+        Set<Cell>,    // cells of the rectangle
+        Set<Digit>,   // digits of the rectangle
+        &[Candidate], // conflicts
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for row1 in 0..8 {
+        for row2 in row1 + 1..9 {
+            for col1 in 0..8 {
+                for col2 in col1 + 1..9 {
+                    // the rectangle has to span exactly 2 blocks
+                    if (row1 / 3 == row2 / 3) == (col1 / 3 == col2 / 3) {
+                        continue;
+                    }
+                    let corners = [
+                        Cell::from_coords(row1, col1),
+                        Cell::from_coords(row1, col2),
+                        Cell::from_coords(row2, col1),
+                        Cell::from_coords(row2, col2),
+                    ];
+                    let common_digits = corners
+                        .iter()
+                        .fold(Set::ALL, |digits, &cell| digits & cells_poss_digits[cell]);
+                    if common_digits.len() < 2 {
+                        continue;
+                    }
+                    let cells = corners.iter().fold(Set::NONE, |cells, &cell| cells | cell);
+
+                    for digit1 in common_digits {
+                        for digit2 in common_digits {
+                            if digit2.get() <= digit1.get() {
+                                continue;
+                            }
+                            let digits = digit1.as_set() | digit2;
+                            let (rectangle_type, conflicts) =
+                                match rectangle_conflicts(cells_poss_digits, corners, digits) {
+                                    Some(found) => found,
+                                    None => continue,
+                                };
+                            if on_rectangle(rectangle_type, cells, digits, &conflicts) && stop_after_first {
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Returns the type of the unique rectangle and its conflicts, if there are any.
+fn rectangle_conflicts(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    corners: [Cell; 4],
+    digits: Set<Digit>,
+) -> Option<(u8, Vec<Candidate>)> {
+    // the cells with extra candidates
+    let roof = corners
+        .iter()
+        .copied()
+        .filter(|&cell| cells_poss_digits[cell] != digits)
+        .collect::<Vec<_>>();
+
+    let candidates = |cells: &[Cell], digits: Set<Digit>| {
+        cells
+            .iter()
+            .flat_map(|&cell| {
+                (cells_poss_digits[cell] & digits)
+                    .into_iter()
+                    .map(move |digit| Candidate { cell, digit })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    match roof.len() {
+        // type 1: the digits of the rectangle are impossible in the only cell with extra candidates
+        1 => return Some((1, candidates(&roof, digits))),
+        // all other types need 2 cells with extra candidates on one side of the rectangle
+        2 if roof[0].row() == roof[1].row() || roof[0].col() == roof[1].col() => {}
+        _ => return None,
+    }
+    let roof_cells = roof[0].as_set() | roof[1];
+    let extra_digits = (cells_poss_digits[roof[0]] | cells_poss_digits[roof[1]]).without(digits);
+
+    // type 2: both roof cells have the same single extra candidate, one of them contains it
+    if cells_poss_digits[roof[0]] == cells_poss_digits[roof[1]] && extra_digits.len() == 1 {
+        let common_neighbors = roof[0].neighbors_set() & roof[1].neighbors_set();
+        let conflicts = candidates(&common_neighbors.into_iter().collect::<Vec<_>>(), extra_digits);
+        if !conflicts.is_empty() {
+            return Some((2, conflicts));
+        }
+    }
+
+    let houses = IntoIterator::into_iter(roof[0].houses())
+        .filter(|house| house.cells().contains(roof[1]))
+        .collect::<Vec<_>>();
+
+    // type 3: the extra candidates of the roof cells form a naked subset
+    // together with other cells of a house that contains both
+    for &house in &houses {
+        let other_cells = house
+            .cells()
+            .without(roof_cells)
+            .into_iter()
+            .filter(|&cell| !cells_poss_digits[cell].is_empty())
+            .collect::<Vec<_>>();
+        for subset in 1..1u32 << other_cells.len() {
+            let n_cells = subset.count_ones();
+            if n_cells > 3 {
+                continue;
+            }
+            let subset_cells = other_cells
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| subset & 1 << idx != 0)
+                .map(|(_, &cell)| cell)
+                .collect::<Vec<_>>();
+            let subset_digits = subset_cells
+                .iter()
+                .fold(extra_digits, |digits, &cell| digits | cells_poss_digits[cell]);
+            if subset_digits.len() as u32 != n_cells + 1 {
+                continue;
+            }
+            let remaining_cells = other_cells
+                .iter()
+                .copied()
+                .filter(|cell| !subset_cells.contains(cell))
+                .collect::<Vec<_>>();
+            let conflicts = candidates(&remaining_cells, subset_digits);
+            if !conflicts.is_empty() {
+                return Some((3, conflicts));
+            }
+        }
+    }
+
+    // type 4: one of the digits must be in the roof cells, so the other one is impossible there
+    for &house in &houses {
+        for digit in digits {
+            let digit_cells = house
+                .cells()
+                .into_iter()
+                .filter(|&cell| cells_poss_digits[cell].contains(digit))
+                .fold(Set::NONE, |cells, cell| cells | cell);
+            if digit_cells == roof_cells {
+                return Some((4, candidates(&roof, digits.without(digit.as_set()))));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    #[test]
+    fn unique_rectangles() {
+        let sudokus = [
+            (
+                1,
+                "4.1.85.9.5..9....7..9..1..........58...728...84..........5..3..7....2..6.5.64.7.9",
+            ),
+            (
+                2,
+                ".......1...4...7.29..2.4..3.8.7...2.69.3.2.51.3...5.4.1..5.3..43.2...6...4.......",
+            ),
+            (
+                3,
+                "...6...95...2..8.3..8.93.....7...45..4.....2..86...1.....13.5..4.5..9...31...2...",
+            ),
+            (
+                4,
+                "61.8...........23.4..5...1...8..97..1.......3..94..6...6...1..2.24...........5.49",
+            ),
+        ];
+        for &(expected_type, sudoku) in &sudokus {
+            let sudoku = Sudoku::from_str_line(sudoku).unwrap();
+            let solution = sudoku.solution().unwrap();
+            let strategies = [
+                Strategy::NakedSingles,
+                Strategy::HiddenSingles,
+                Strategy::LockedCandidates,
+                Strategy::NakedPairs,
+                Strategy::HiddenPairs,
+                Strategy::XWing,
+                Strategy::UniqueRectangles,
+            ];
+            let (_, deductions) = match StrategySolver::from_sudoku(sudoku)
+                .assume_unique_solution(true)
+                .solve(&strategies)
+            {
+                Ok(result) | Err(result) => result,
+            };
+
+            let mut types = Set::<Digit>::NONE;
+            for deduction in deductions.iter() {
+                if let Deduction::UniqueRectangle {
+                    cells,
+                    digits,
+                    rectangle_type,
+                    conflicts,
+                } = deduction
+                {
+                    assert_eq!((cells.len(), digits.len()), (4, 2));
+                    types |= Digit::new(rectangle_type);
+                    for conflict in conflicts {
+                        assert_ne!(solution.0[conflict.cell.as_index()], conflict.digit.get());
+                    }
+                }
+            }
+            assert!(types.contains(Digit::new(expected_type)));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Sets of strategies for restricting the techniques available to a solver
+
+use super::Strategy;
+
+/// A set of [`Strategy`]s
+///
+/// Used to ask whether a sudoku can be solved with a limited set of techniques,
+/// see [`Sudoku::solvable_with`](crate::Sudoku::solvable_with).
+/// Iteration always yields the strategies in order of increasing difficulty, independent of
+/// the order in which they were inserted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TechniqueSet(u32);
+
+// All implemented strategies in order of increasing difficulty.
+// This is the order in which a solver should try them.
+#[rustfmt::skip]
+const ORDERED: &[Strategy] = &[
+    Strategy::HiddenSingles,
+    Strategy::NakedSingles,
+    Strategy::LockedCandidates,
+    Strategy::NakedPairs,
+    Strategy::XWing,
+    Strategy::HiddenPairs,
+    Strategy::NakedTriples,
+    Strategy::Swordfish,
+    Strategy::MutantSwordfish,
+    Strategy::HiddenTriples,
+    Strategy::XyWing,
+    Strategy::XyzWing,
+    Strategy::UniqueRectangles,
+    Strategy::NakedQuads,
+    Strategy::Jellyfish,
+    Strategy::MutantJellyfish,
+    Strategy::HiddenQuads,
+    Strategy::BivalueUniversalGrave,
+    Strategy::SimpleColoring,
+    Strategy::RemotePairs,
+    Strategy::XyChain,
+    Strategy::AlternatingInferenceChain,
+    Strategy::AlsXz,
+    Strategy::AlsXyWing,
+    Strategy::ForcingChains,
+];
+
+impl TechniqueSet {
+    /// The empty set
+    pub const NONE: TechniqueSet = TechniqueSet(0);
+
+    /// Naked and hidden singles
+    pub const SINGLES: TechniqueSet = TechniqueSet::NONE
+        .with(Strategy::NakedSingles)
+        .with(Strategy::HiddenSingles);
+
+    /// All strategies that are currently implemented
+    pub const ALL: TechniqueSet = {
+        let mut set = TechniqueSet::NONE;
+        let mut idx = 0;
+        while idx < ORDERED.len() {
+            set = set.with(ORDERED[idx]);
+            idx += 1;
+        }
+        set
+    };
+
+    /// Creates a set from the given strategies.
+    pub fn new(strategies: &[Strategy]) -> TechniqueSet {
+        strategies.iter().copied().collect()
+    }
+
+    /// Returns this set with `strategy` added.
+    pub const fn with(self, strategy: Strategy) -> TechniqueSet {
+        TechniqueSet(self.0 | 1 << strategy as u32)
+    }
+
+    /// Returns this set with `strategy` removed.
+    pub const fn without(self, strategy: Strategy) -> TechniqueSet {
+        TechniqueSet(self.0 & !(1 << strategy as u32))
+    }
+
+    /// Adds `strategy` to the set.
+    pub fn insert(&mut self, strategy: Strategy) {
+        *self = self.with(strategy);
+    }
+
+    /// Removes `strategy` from the set.
+    pub fn remove(&mut self, strategy: Strategy) {
+        *self = self.without(strategy);
+    }
+
+    /// Checks whether the set contains `strategy`.
+    pub const fn contains(self, strategy: Strategy) -> bool {
+        self.0 & 1 << strategy as u32 != 0
+    }
+
+    /// Returns the number of strategies in the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Checks whether the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the strategies in the set in order of increasing difficulty.
+    ///
+    /// Strategies that aren't implemented yet are skipped.
+    pub fn strategies(self) -> Vec<Strategy> {
+        ORDERED
+            .iter()
+            .copied()
+            .filter(|&strategy| self.contains(strategy))
+            .collect()
+    }
+}
+
+impl std::iter::FromIterator<Strategy> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = Strategy>>(iter: I) -> Self {
+        iter.into_iter().fold(TechniqueSet::NONE, TechniqueSet::with)
+    }
+}
+
+impl From<Strategy> for TechniqueSet {
+    fn from(strategy: Strategy) -> Self {
+        TechniqueSet::NONE.with(strategy)
+    }
+}
@@ -0,0 +1,245 @@
+//! Rendering of sudokus as SVG images
+//!
+//! [`Sudoku::to_svg`] draws the grid as a standalone SVG document that can be saved to a file
+//! or embedded into HTML directly. The appearance is configured with [`SvgOptions`].
+
+use crate::board::Cell;
+use crate::Sudoku;
+use std::fmt::Write;
+
+// All coordinates are in units of the view box, in which every cell is 30 units wide.
+const CELL_SIZE: u32 = 30;
+const GRID_SIZE: u32 = 9 * CELL_SIZE;
+const THIN_LINE: u32 = 1;
+const THICK_LINE: u32 = 3;
+
+/// Options for [`Sudoku::to_svg`]
+///
+/// Colors are inserted into the `fill` attributes of the SVG as is,
+/// so any color that SVG understands can be used.
+///
+/// ```
+/// use sudoku::Sudoku;
+/// use sudoku::svg::SvgOptions;
+///
+/// let options = SvgOptions::new()
+///     .with_size(300)
+///     .with_candidates(true)
+///     .with_given_color("navy");
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let svg = sudoku.to_svg(&options);
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(r#"width="300""#));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SvgOptions {
+    size: u32,
+    givens: Option<Sudoku>,
+    show_candidates: bool,
+    background_color: String,
+    given_color: String,
+    solved_color: String,
+    candidate_color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            size: 450,
+            givens: None,
+            show_candidates: false,
+            background_color: "white".to_string(),
+            given_color: "black".to_string(),
+            solved_color: "#1565c0".to_string(),
+            candidate_color: "#757575".to_string(),
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Returns the default options: 450 pixels wide, no candidates,
+    /// black givens and blue solved cells on a white background.
+    pub fn new() -> Self {
+        SvgOptions::default()
+    }
+
+    /// Sets the width and height of the image in pixels.
+    pub fn with_size(self, size: u32) -> Self {
+        SvgOptions { size, ..self }
+    }
+
+    /// Sets the original puzzle of the rendered sudoku. Digits in cells that are filled in
+    /// `givens` are drawn as givens, all other digits as solved cells.
+    ///
+    /// Without this, all digits are drawn as givens.
+    pub fn with_givens(self, givens: Sudoku) -> Self {
+        SvgOptions {
+            givens: Some(givens),
+            ..self
+        }
+    }
+
+    /// Sets whether the candidates of empty cells are drawn.
+    /// The candidates are all digits that don't conflict with a digit in the same row, column or block.
+    pub fn with_candidates(self, show_candidates: bool) -> Self {
+        SvgOptions {
+            show_candidates,
+            ..self
+        }
+    }
+
+    /// Sets the color of the grid background.
+    pub fn with_background_color(self, color: impl Into<String>) -> Self {
+        SvgOptions {
+            background_color: color.into(),
+            ..self
+        }
+    }
+
+    /// Sets the color of the givens.
+    pub fn with_given_color(self, color: impl Into<String>) -> Self {
+        SvgOptions {
+            given_color: color.into(),
+            ..self
+        }
+    }
+
+    /// Sets the color of the digits in solved cells.
+    pub fn with_solved_color(self, color: impl Into<String>) -> Self {
+        SvgOptions {
+            solved_color: color.into(),
+            ..self
+        }
+    }
+
+    /// Sets the color of the candidates.
+    pub fn with_candidate_color(self, color: impl Into<String>) -> Self {
+        SvgOptions {
+            candidate_color: color.into(),
+            ..self
+        }
+    }
+}
+
+impl Sudoku {
+    /// Renders the sudoku as a standalone SVG document.
+    ///
+    /// The digits are grouped by their kind into `<g>` elements with the classes
+    /// `givens`, `solved` and `candidates` for further styling with CSS.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::svg::SvgOptions;
+    ///
+    /// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+    /// let solution = sudoku.solution().unwrap();
+    /// let svg = solution.to_svg(&SvgOptions::new().with_givens(sudoku));
+    /// assert_eq!(svg.matches("<text").count(), 81);
+    /// ```
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let mut svg = String::new();
+        // writing to a `String` can't fail
+        let _ = self.write_svg(&mut svg, options);
+        svg
+    }
+
+    fn write_svg(&self, svg: &mut String, options: &SvgOptions) -> std::fmt::Result {
+        let margin = THICK_LINE;
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="-{margin} -{margin} {view_size} {view_size}" font-family="sans-serif">"#,
+            size = options.size,
+            margin = margin,
+            view_size = GRID_SIZE + 2 * margin,
+        )?;
+        writeln!(
+            svg,
+            r#"<rect x="0" y="0" width="{size}" height="{size}" fill="{color}"/>"#,
+            size = GRID_SIZE,
+            color = options.background_color,
+        )?;
+
+        writeln!(svg, r#"<g stroke="black" stroke-linecap="square">"#)?;
+        for line in 0..=9 {
+            let pos = line * CELL_SIZE;
+            let width = match line % 3 {
+                0 => THICK_LINE,
+                _ => THIN_LINE,
+            };
+            writeln!(
+                svg,
+                r#"<line x1="{pos}" y1="0" x2="{pos}" y2="{size}" stroke-width="{width}"/>"#,
+                pos = pos,
+                size = GRID_SIZE,
+                width = width,
+            )?;
+            writeln!(
+                svg,
+                r#"<line x1="0" y1="{pos}" x2="{size}" y2="{pos}" stroke-width="{width}"/>"#,
+                pos = pos,
+                size = GRID_SIZE,
+                width = width,
+            )?;
+        }
+        writeln!(svg, "</g>")?;
+
+        let is_given = |cell: Cell| match options.givens {
+            Some(givens) => givens.0[cell.as_index()] != 0,
+            None => true,
+        };
+        let filled_cells = || Cell::all().filter(|cell| self.0[cell.as_index()] != 0);
+        let digit_groups = [
+            ("givens", &options.given_color, true),
+            ("solved", &options.solved_color, false),
+        ];
+        for &(class, color, givens) in &digit_groups {
+            writeln!(
+                svg,
+                r#"<g class="{}" fill="{}" font-size="20" text-anchor="middle" dominant-baseline="central">"#,
+                class, color
+            )?;
+            for cell in filled_cells().filter(|&cell| is_given(cell) == givens) {
+                let (x, y) = top_left_corner(cell);
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}">{}</text>"#,
+                    x + CELL_SIZE / 2,
+                    y + CELL_SIZE / 2,
+                    self.0[cell.as_index()],
+                )?;
+            }
+            writeln!(svg, "</g>")?;
+        }
+
+        if options.show_candidates {
+            writeln!(
+                svg,
+                r#"<g class="candidates" fill="{}" font-size="9" text-anchor="middle" dominant-baseline="central">"#,
+                options.candidate_color
+            )?;
+            for cell in Cell::all().filter(|cell| self.0[cell.as_index()] == 0) {
+                let (x, y) = top_left_corner(cell);
+                for digit in self.basic_candidates(cell) {
+                    let idx = digit.get() as u32 - 1;
+                    writeln!(
+                        svg,
+                        r#"<text x="{}" y="{}">{}</text>"#,
+                        x + 5 + 10 * (idx % 3),
+                        y + 5 + 10 * (idx / 3),
+                        digit.get(),
+                    )?;
+                }
+            }
+            writeln!(svg, "</g>")?;
+        }
+
+        write!(svg, "</svg>")
+    }
+}
+
+fn top_left_corner(cell: Cell) -> (u32, u32) {
+    (
+        cell.col().get() as u32 * CELL_SIZE,
+        cell.row().get() as u32 * CELL_SIZE,
+    )
+}
@@ -0,0 +1,95 @@
+//! Random sudokus for property based testing
+//!
+//! With the `arbitrary`, `quickcheck` or `proptest` features enabled, [`Sudoku`] implements the
+//! respective `Arbitrary` trait and produces random, uniquely solvable puzzles.
+//! [`SolvedSudoku`] implements them as well and produces random, solved grids.
+//!
+//! All values are generated from a seed drawn from the respective framework's source of randomness,
+//! so failing cases can be reproduced by the framework.
+//! Shrinking is not supported, because removing or changing clues would not uphold the guarantees
+//! of the generated values.
+use crate::{SolvedSudoku, Sudoku};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn puzzle_from_seed(seed: [u8; 32]) -> Sudoku {
+    let rng = &mut StdRng::from_seed(seed);
+    let solved = Sudoku::generate_solved_with_rng(rng);
+    Sudoku::generate_with_symmetry_and_rng_from(solved.into(), crate::Symmetry::None, rng)
+}
+
+fn solved_from_seed(seed: [u8; 32]) -> SolvedSudoku {
+    Sudoku::generate_solved_with_rng(&mut StdRng::from_seed(seed))
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Sudoku {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(puzzle_from_seed)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SolvedSudoku {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(solved_from_seed)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+fn quickcheck_seed(g: &mut quickcheck::Gen) -> [u8; 32] {
+    let mut seed = [0; 32];
+    for byte in &mut seed {
+        *byte = quickcheck::Arbitrary::arbitrary(g);
+    }
+    seed
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for Sudoku {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        puzzle_from_seed(quickcheck_seed(g))
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for SolvedSudoku {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        solved_from_seed(quickcheck_seed(g))
+    }
+}
+
+/// Strategy producing random, uniquely solvable puzzles. This is the same as `any::<Sudoku>()`.
+#[cfg(feature = "proptest")]
+pub fn puzzles() -> impl proptest::strategy::Strategy<Value = Sudoku> {
+    use proptest::strategy::Strategy;
+    proptest::arbitrary::any::<[u8; 32]>().prop_map(puzzle_from_seed)
+}
+
+/// Strategy producing random, solved sudokus. This is the same as `any::<SolvedSudoku>()`.
+#[cfg(feature = "proptest")]
+pub fn solved_sudokus() -> impl proptest::strategy::Strategy<Value = SolvedSudoku> {
+    use proptest::strategy::Strategy;
+    proptest::arbitrary::any::<[u8; 32]>().prop_map(solved_from_seed)
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Sudoku {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<proptest::arbitrary::StrategyFor<[u8; 32]>, fn([u8; 32]) -> Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::arbitrary::any::<[u8; 32]>().prop_map(puzzle_from_seed)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for SolvedSudoku {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<proptest::arbitrary::StrategyFor<[u8; 32]>, fn([u8; 32]) -> Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::arbitrary::any::<[u8; 32]>().prop_map(solved_from_seed)
+    }
+}
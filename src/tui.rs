@@ -0,0 +1,374 @@
+//! Interactive sudoku trainer in the terminal
+//!
+//! [`run`] lets a player solve a [`GameState`] in the terminal. The player moves a cursor over the
+//! grid, enters digits and notes, asks the [strategy solver](crate::strategy) for hints and checks
+//! their progress against the solution while a timer runs.
+//!
+//! [`Trainer`] contains the state and drawing of the trainer without any terminal handling, so it
+//! can also be embedded into other ratatui applications.
+//!
+//! | Key                   | Action                                                    |
+//! |-----------------------|-----------------------------------------------------------|
+//! | arrow keys            | move the cursor                                           |
+//! | `1`-`9`               | enter a digit, or toggle a note in notes mode             |
+//! | `0`, backspace, del   | clear the entry, or the notes in notes mode               |
+//! | `n`                   | switch between entering digits and notes                  |
+//! | `f`                   | fill in the notes of all empty cells                      |
+//! | `h`                   | show a hint for the next step                             |
+//! | `c`                   | mark incorrect entries and notes                          |
+//! | `q`, esc              | quit                                                      |
+
+use crate::bitset::{CellSet, DigitSet, Set};
+use crate::board::{Candidate, Cell, Digit, GameState, Validation};
+use crate::strategy::deduction::Deduction;
+use crate::strategy::Strategy;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Span, Spans};
+use ratatui::widgets::{Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use std::io;
+use std::time::{Duration, Instant};
+
+// Width and height of the grid in characters
+const GRID_WIDTH: u16 = 39;
+const GRID_HEIGHT: u16 = 35;
+
+/// Runs the trainer for `game` in the terminal until the player quits.
+///
+/// Switches the terminal to an alternate screen in raw mode and restores it afterwards.
+/// Returns the game as the player left it.
+///
+/// ```no_run
+/// use sudoku::Sudoku;
+/// use sudoku::board::GameState;
+///
+/// let game = GameState::new(Sudoku::generate()).unwrap();
+/// let game = sudoku::tui::run(game).unwrap();
+/// println!("{} incorrect entries", game.check_against_solution().len());
+/// ```
+pub fn run(game: GameState) -> io::Result<GameState> {
+    terminal::enable_raw_mode()?;
+    let result = execute!(io::stdout(), EnterAlternateScreen).and_then(|()| {
+        let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        let mut trainer = Trainer::new(game);
+        loop {
+            terminal.draw(|frame| trainer.render(frame))?;
+            // redraw at least every quarter of a second for the timer
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if !trainer.handle_key(key) {
+                    return Ok(trainer.into_game_state());
+                }
+            }
+        }
+    });
+    // restore the terminal, even if drawing failed
+    let restored = execute!(io::stdout(), LeaveAlternateScreen).and(terminal::disable_raw_mode());
+    let game = result?;
+    restored.map(|()| game)
+}
+
+/// State of the interactive trainer, see the [module documentation](self)
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use sudoku::Sudoku;
+/// use sudoku::board::{Cell, Digit, GameState};
+/// use sudoku::tui::Trainer;
+///
+/// let sudoku = Sudoku::from_str_line("...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...").unwrap();
+/// let mut trainer = Trainer::new(GameState::new(sudoku).unwrap());
+/// let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+///
+/// // the cursor starts in the top left cell
+/// assert!(trainer.handle_key(key(KeyCode::Char('8'))));
+/// assert_eq!(trainer.game().entry(Cell::new(0)), Some(Digit::new(8)));
+/// assert!(!trainer.handle_key(key(KeyCode::Char('q'))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Trainer {
+    game: GameState,
+    cursor: Cell,
+    is_notes_mode: bool,
+    hint: Option<Hint>,
+    // result of the last check, shown until the next change
+    check: Option<Validation>,
+    message: String,
+    started: Instant,
+    solved_after: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+struct Hint {
+    placement: Option<Candidate>,
+    eliminations: Vec<Candidate>,
+}
+
+impl Trainer {
+    /// Starts the trainer for `game` with the cursor in the top left cell and the timer running.
+    pub fn new(game: GameState) -> Trainer {
+        Trainer {
+            game,
+            cursor: Cell::new(0),
+            is_notes_mode: false,
+            hint: None,
+            check: None,
+            message: String::new(),
+            started: Instant::now(),
+            solved_after: None,
+        }
+    }
+
+    /// Returns the game in its current state.
+    pub fn game(&self) -> &GameState {
+        &self.game
+    }
+
+    /// Returns the game in its current state.
+    pub fn into_game_state(self) -> GameState {
+        self.game
+    }
+
+    /// Returns the time since the start, or the time it took to solve the puzzle.
+    pub fn elapsed(&self) -> Duration {
+        self.solved_after.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    /// Returns `true`, if the player solved the puzzle.
+    pub fn is_solved(&self) -> bool {
+        self.solved_after.is_some()
+    }
+
+    /// Handles a key pressed by the player. Returns `false`, if the player wants to quit.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return true;
+        }
+        self.message.clear();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Up => self.move_cursor(8, 0),
+            KeyCode::Down => self.move_cursor(1, 0),
+            KeyCode::Left => self.move_cursor(0, 8),
+            KeyCode::Right => self.move_cursor(0, 1),
+            KeyCode::Char(ch @ '1'..='9') => self.enter(Some(Digit::new(ch as u8 - b'0'))),
+            KeyCode::Char('0') | KeyCode::Backspace | KeyCode::Delete => self.enter(None),
+            KeyCode::Char('n') => self.is_notes_mode = !self.is_notes_mode,
+            KeyCode::Char('f') if !self.is_solved() => {
+                self.game.fill_notes();
+                self.changed();
+            }
+            KeyCode::Char('h') if !self.is_solved() => self.show_hint(),
+            KeyCode::Char('c') if !self.is_solved() => self.show_check(),
+            _ => {}
+        }
+        true
+    }
+
+    /// Draws the grid and a status panel into the whole area of `frame`.
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>) {
+        let areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(GRID_WIDTH + 4), Constraint::Min(0)])
+            .split(frame.size());
+        let grid = (0..GRID_HEIGHT)
+            .map(|line| self.grid_line(line))
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(grid), areas[0]);
+
+        let elapsed = self.elapsed().as_secs();
+        let time_label = match self.is_solved() {
+            true => "Solved in",
+            false => "Time",
+        };
+        let mode = match self.is_notes_mode {
+            true => "notes",
+            false => "digits",
+        };
+        let status = vec![
+            Spans::from(format!("{}: {:02}:{:02}", time_label, elapsed / 60, elapsed % 60)),
+            Spans::from(format!("Mode: {}", mode)),
+            Spans::from(""),
+            Spans::from(self.message.as_str()),
+            Spans::from(""),
+            Spans::from("arrows: move   1-9: enter   0: clear   n: notes mode"),
+            Spans::from("f: fill notes   h: hint   c: check   q: quit"),
+        ];
+        frame.render_widget(Paragraph::new(status).wrap(Wrap { trim: false }), areas[1]);
+    }
+
+    fn move_cursor(&mut self, rows: u8, cols: u8) {
+        let row = (self.cursor.row().get() + rows) % 9;
+        let col = (self.cursor.col().get() + cols) % 9;
+        self.cursor = Cell::new(9 * row + col);
+    }
+
+    fn enter(&mut self, digit: Option<Digit>) {
+        if self.is_solved() {
+            return;
+        }
+        if self.game.clues().0[self.cursor.as_index()] != 0 {
+            self.message = format!("{} contains a clue", self.cursor.as_set());
+            return;
+        }
+        match (self.is_notes_mode, digit) {
+            (true, Some(digit)) => {
+                let notes = self.game.notes(self.cursor) ^ digit;
+                self.game.set_notes(self.cursor, notes);
+            }
+            (true, None) => self.game.set_notes(self.cursor, Set::NONE),
+            (false, digit) => self.game.set_entry(self.cursor, digit),
+        }
+        self.changed();
+        if self.game.validate().is_solved {
+            self.solved_after = Some(self.started.elapsed());
+        }
+    }
+
+    // Removes marks that may not apply anymore
+    fn changed(&mut self) {
+        self.hint = None;
+        self.check = None;
+    }
+
+    fn show_hint(&mut self) {
+        let deductions = match self.game.hint(Strategy::ALL) {
+            Some(deductions) => deductions,
+            None => {
+                self.message = "No hint available".to_string();
+                return;
+            }
+        };
+        let deduction = deductions.get(0).expect("hints contain a deduction");
+        let strategy = deduction.strategy();
+        let hint = match deduction {
+            Deduction::NakedSingles(candidate)
+            | Deduction::HiddenSingles(candidate, _)
+            | Deduction::BivalueUniversalGrave(candidate) => {
+                self.message = format!("Hint: {:?} in {}", strategy, candidate.cell.as_set());
+                Hint {
+                    placement: Some(candidate),
+                    eliminations: vec![],
+                }
+            }
+            Deduction::LockedCandidates { conflicts, .. }
+            | Deduction::Subsets { conflicts, .. }
+            | Deduction::BasicFish { conflicts, .. }
+            | Deduction::Fish { conflicts, .. }
+            | Deduction::Wing { conflicts, .. }
+            | Deduction::AvoidableRectangle { conflicts, .. }
+            | Deduction::SimpleColoring { conflicts, .. }
+            | Deduction::RemotePairs { conflicts, .. }
+            | Deduction::XyChain { conflicts, .. }
+            | Deduction::AlternatingInferenceChain { conflicts, .. }
+            | Deduction::UniqueRectangle { conflicts, .. }
+            | Deduction::AlsXz { conflicts, .. }
+            | Deduction::AlsXyWing { conflicts, .. }
+            | Deduction::ForcingChain { conflicts, .. } => {
+                self.message = format!(
+                    "Hint: {:?} eliminates {} from {}",
+                    strategy,
+                    conflicts
+                        .iter()
+                        .map(|candidate| candidate.digit)
+                        .collect::<DigitSet>(),
+                    conflicts
+                        .iter()
+                        .map(|candidate| candidate.cell)
+                        .collect::<CellSet>(),
+                );
+                Hint {
+                    placement: None,
+                    eliminations: conflicts.to_vec(),
+                }
+            }
+        };
+        self.hint = Some(hint);
+    }
+
+    fn show_check(&mut self) {
+        let check = self.game.validate();
+        self.message = match (check.incorrect_entries.len(), check.incorrect_notes.len()) {
+            (0, 0) => "No mistakes so far".to_string(),
+            (entries, notes) => format!(
+                "{} incorrect entries, {} cells with incorrect notes",
+                entries, notes
+            ),
+        };
+        self.check = Some(check);
+    }
+
+    // The `line`th line of the grid, in the same layout as `Sudoku::display_block_colored`
+    fn grid_line(&self, line: u16) -> Spans<'static> {
+        let (row, cell_line) = match line {
+            11 | 23 => return Spans::from("------------+-------------+------------"),
+            _ if line % 4 == 3 => return Spans::from(""),
+            _ => (line / 4, line % 4),
+        };
+        let mut spans = vec![];
+        for col in 0..9 {
+            match col {
+                0 => {}
+                3 | 6 => spans.push(Span::raw(" | ")),
+                _ => spans.push(Span::raw(" ")),
+            }
+            let cell = Cell::new(9 * row as u8 + col);
+            spans.extend(self.cell_line(cell, cell_line as u8));
+        }
+        Spans::from(spans)
+    }
+
+    // The 3 characters of `cell` in the `line`th of its 3 lines
+    fn cell_line(&self, cell: Cell, line: u8) -> Vec<Span<'static>> {
+        let (placement, eliminations) = match &self.hint {
+            Some(hint) => (hint.placement, &hint.eliminations[..]),
+            None => (None, &[][..]),
+        };
+        let incorrect = self
+            .check
+            .map_or(Set::NONE, |check| check.incorrect_entries | check.incorrect_notes);
+        let background = if cell == self.cursor {
+            Some(Color::Blue)
+        } else if placement.map(|candidate| candidate.cell) == Some(cell) {
+            Some(Color::Green)
+        } else if incorrect.contains(cell) {
+            Some(Color::Red)
+        } else {
+            None
+        };
+        let style = background.map_or(Style::default(), |color| Style::default().bg(color));
+
+        let clue = Digit::new_checked(self.game.clues().0[cell.as_index()]);
+        let digit = clue.or_else(|| self.game.entry(cell));
+        (1..=3)
+            .map(|position| match digit {
+                Some(digit) if line == 1 && position == 2 => {
+                    let style = match clue {
+                        Some(_) => style.add_modifier(Modifier::BOLD),
+                        None => style.fg(Color::Cyan),
+                    };
+                    Span::styled(digit.get().to_string(), style)
+                }
+                Some(_) => Span::styled(" ", style),
+                None => {
+                    let note = Digit::new(3 * line + position);
+                    if eliminations.contains(&Candidate { cell, digit: note }) {
+                        let style = style.fg(Color::Red).add_modifier(Modifier::BOLD);
+                        Span::styled(note.get().to_string(), style)
+                    } else if self.game.notes(cell).contains(note) {
+                        Span::styled(note.get().to_string(), style.fg(Color::DarkGray))
+                    } else {
+                        Span::styled(" ", style)
+                    }
+                }
+            })
+            .collect()
+    }
+}
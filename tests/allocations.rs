@@ -0,0 +1,71 @@
+//! Checks that the solver doesn't allocate on the paths that are documented as allocation free.
+//! The counting allocator is global, so this lives in its own test binary.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use sudoku::{SolveBuffer, Sudoku};
+
+struct CountingAllocator;
+
+thread_local! {
+    static N_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Number of allocations on this thread during `f`
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = N_ALLOCATIONS.with(Cell::get);
+    f();
+    N_ALLOCATIONS.with(Cell::get) - before
+}
+
+fn read_sudokus(sudokus_str: &str) -> Vec<Sudoku> {
+    sudokus_str
+        .lines()
+        .map(|line| Sudoku::from_str_line(line).unwrap_or_else(|err| panic!("{:?}", err)))
+        .collect()
+}
+
+#[test]
+fn solving_does_not_allocate() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let mut buffer = SolveBuffer::with_capacity(2);
+    let mut target = [[0; 81]; 2];
+
+    let n_allocations = count_allocations(|| {
+        for &sudoku in &sudokus {
+            assert_eq!(sudoku.solutions_count_up_to(2), 1);
+            assert_eq!(sudoku.solutions_up_to_buffer(&mut target, 2), 1);
+            assert_eq!(sudoku.solutions_up_to_reusing(&mut buffer, 2).len(), 1);
+            assert!(sudoku.is_uniquely_solvable());
+        }
+    });
+    assert_eq!(n_allocations, 0);
+    assert_eq!(buffer.solutions(), [Sudoku::from_bytes(target[0]).unwrap()]);
+}
+
+#[test]
+fn solve_buffer_grows_once() {
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    let mut buffer = SolveBuffer::new();
+    assert_eq!(empty.solutions_up_to_reusing(&mut buffer, 100).len(), 100);
+
+    let n_allocations = count_allocations(|| {
+        for limit in (0..=100).rev() {
+            assert_eq!(empty.solutions_up_to_reusing(&mut buffer, limit).len(), limit);
+        }
+    });
+    assert_eq!(n_allocations, 0);
+}
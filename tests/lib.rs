@@ -1,3 +1,4 @@
+use sudoku::errors::{InvalidEntry, LineParseError};
 use sudoku::Sudoku;
 
 fn read_sudokus(sudokus_str: &str) -> Vec<Sudoku> {
@@ -240,7 +241,7 @@ fn unsolved_hard() {
 #[test]
 fn generate_solved_sudoku_correctness() {
     for _ in 0..1000 {
-        let sudoku = Sudoku::generate_solved();
+        let sudoku = Sudoku::generate_solved().into_sudoku();
         let solved_sudoku = sudoku.some_solution();
         if solved_sudoku.is_none() {
             panic!(
@@ -267,6 +268,737 @@ fn generate_sudoku_uniqueness() {
     }
 }
 
+#[test]
+fn generate_batch_from_solution() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let rng = &mut StdRng::seed_from_u64(7);
+    let solution = Sudoku::generate_solved_with_rng(rng);
+    let sudokus = Sudoku::generate_batch_with_rng_from(solution, 5, rng);
+    assert_eq!(sudokus.len(), 5);
+    for (idx, sudoku) in sudokus.iter().enumerate() {
+        assert!(!sudokus[..idx].contains(sudoku));
+        assert_eq!(sudoku.solution(), Some(solution));
+        // minimal: every clue is necessary
+        let counts = sudoku.clue_removal_solutions_count_up_to(2);
+        assert!(counts.iter().flatten().all(|&count| count == 2));
+    }
+}
+
+#[test]
+fn singles_solvable() {
+    use sudoku::strategy::{StrategySolver, TechniqueSet};
+
+    let files = [
+        include_str!("../sudokus/Lines/easy_sudokus.txt"),
+        include_str!("../sudokus/Lines/medium_sudokus.txt"),
+        include_str!("../sudokus/Lines/hard_sudokus.txt"),
+    ];
+    let (mut n_solvable, mut n_unsolvable) = (0, 0);
+    for sudoku in files
+        .iter()
+        .flat_map(|file| read_sudokus(file).into_iter().take(50))
+    {
+        let expected = StrategySolver::from_sudoku(sudoku)
+            .solve(&TechniqueSet::SINGLES.strategies())
+            .is_ok();
+        assert_eq!(sudoku.is_singles_solvable(), expected);
+        match expected {
+            true => n_solvable += 1,
+            false => n_unsolvable += 1,
+        }
+    }
+    assert!(n_solvable > 0 && n_unsolvable > 0);
+
+    // multiple solutions or conflicting clues
+    assert!(!Sudoku::from_bytes([0; 81]).unwrap().is_singles_solvable());
+    let mut bytes = read_sudokus(files[0])[0].to_bytes();
+    let empty = bytes.iter().position(|&digit| digit == 0).unwrap();
+    let neighbor = (0..81)
+        .find(|&cell| cell / 9 == empty / 9 && bytes[cell] != 0)
+        .unwrap();
+    bytes[empty] = bytes[neighbor];
+    assert!(!Sudoku::from_bytes(bytes).unwrap().is_singles_solvable());
+}
+
+#[test]
+fn propagation() {
+    use sudoku::board::{CellState, Contradiction};
+    use sudoku::strategy::{Strategy, StrategySolver};
+
+    let strategies = [
+        Strategy::HiddenSingles,
+        Strategy::NakedSingles,
+        Strategy::LockedCandidates,
+    ];
+    let files = [
+        include_str!("../sudokus/Lines/easy_sudokus.txt"),
+        include_str!("../sudokus/Lines/medium_sudokus.txt"),
+        include_str!("../sudokus/Lines/hard_sudokus.txt"),
+    ];
+    for sudoku in files
+        .iter()
+        .flat_map(|file| read_sudokus(file).into_iter().take(20))
+    {
+        let (grid, candidates) = sudoku.propagate().unwrap();
+        let expected = StrategySolver::from_sudoku(sudoku).solve_until_stuck(&strategies);
+        for cell in 0..81 {
+            match expected[cell] {
+                CellState::Digit(digit) => {
+                    assert_eq!(grid.to_bytes()[cell], digit.get());
+                    assert_eq!(candidates[cell], digit.as_set());
+                }
+                CellState::Candidates(expected) => {
+                    assert_eq!(grid.to_bytes()[cell], 0);
+                    assert_eq!(candidates[cell], expected);
+                }
+            }
+        }
+    }
+
+    let mut bytes = [0; 81];
+    bytes[0] = 1;
+    bytes[1] = 1;
+    let result = Sudoku::from_bytes(bytes).unwrap().propagate();
+    assert!(matches!(result, Err(Contradiction::DuplicateDigit { .. })));
+}
+
+#[test]
+fn digit_and_cell_sets() {
+    use sudoku::bitset::{CellSet, DigitSet};
+    use sudoku::board::{Cell, CellState, Digit};
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let solver = sudoku::strategy::StrategySolver::from_sudoku(sudoku);
+    for state in solver.grid_state().iter() {
+        if let CellState::Candidates(digits) = state {
+            assert_eq!(digits.to_string(), state.to_string());
+        }
+    }
+
+    let digits = [9, 3, 3, 5]
+        .iter()
+        .map(|&digit| Digit::new(digit))
+        .collect::<DigitSet>();
+    assert_eq!(digits.len(), 3);
+    assert_eq!(digits.to_string(), "359");
+    assert_eq!(DigitSet::ALL.to_string(), "123456789");
+
+    let mut cells = sudoku.clues().map(|(cell, _)| cell).collect::<CellSet>();
+    assert_eq!(cells.len(), sudoku.n_clues());
+    cells.extend(Cell::all());
+    assert!(cells.is_full());
+    let corners = [0, 8, 72, 80]
+        .iter()
+        .map(|&cell| Cell::new(cell))
+        .collect::<CellSet>();
+    assert_eq!(corners.to_string(), "r1c1 r1c9 r9c1 r9c9");
+}
+
+#[test]
+fn house_summary() {
+    use sudoku::board::positions::{House, HouseType};
+
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))
+        .into_iter()
+        .take(20)
+    {
+        let summary = sudoku.house_summary();
+        let bytes = sudoku.to_bytes();
+        for house in (0..27).map(House::new) {
+            let digits = summary.house(house);
+            let cells = house.cells();
+            assert_eq!(
+                digits.placed.len() as usize,
+                cells
+                    .into_iter()
+                    .filter(|cell| bytes[cell.as_index()] != 0)
+                    .count()
+            );
+            assert_eq!(digits.placed | digits.missing, sudoku::bitset::DigitSet::ALL);
+            assert!(digits.duplicates.is_empty());
+            for cell in cells {
+                if bytes[cell.as_index()] != 0 {
+                    assert!(digits
+                        .placed
+                        .contains(sudoku::board::Digit::new(bytes[cell.as_index()])));
+                }
+            }
+            let expected = match house.categorize() {
+                HouseType::Row(row) => summary.rows[row.as_index()],
+                HouseType::Col(col) => summary.cols[col.as_index()],
+                HouseType::Block(block) => summary.blocks[block.as_index()],
+            };
+            assert_eq!(digits, expected);
+        }
+        assert!(!summary.has_duplicates());
+    }
+
+    let solved = Sudoku::generate_solved().into_sudoku().house_summary();
+    assert!(solved
+        .rows
+        .iter()
+        .chain(&solved.cols)
+        .chain(&solved.blocks)
+        .all(|digits| digits.is_complete()));
+
+    let mut bytes = [0; 81];
+    bytes[0] = 4;
+    bytes[80] = 4;
+    bytes[8] = 4;
+    let summary = Sudoku::from_bytes(bytes).unwrap().house_summary();
+    assert!(summary.has_duplicates());
+    assert_eq!(summary.rows[0].duplicates.to_string(), "4");
+    assert_eq!(summary.cols[8].duplicates.to_string(), "4");
+    assert!(summary.blocks[0].duplicates.is_empty());
+}
+
+#[test]
+fn stress_puzzles() {
+    use sudoku::generator::StressPuzzle;
+
+    for &puzzle in StressPuzzle::ALL.iter() {
+        let sudoku = puzzle.sudoku();
+        let shuffled = puzzle.generate();
+        assert_eq!(sudoku.n_clues(), shuffled.n_clues());
+        let limit = 300_000;
+        let expected = puzzle.n_solutions().min(limit as u128) as usize;
+        assert_eq!(sudoku.solutions_count_up_to(limit), expected, "{:?}", puzzle);
+        if puzzle.n_solutions() == 1 {
+            assert!(shuffled.is_uniquely_solvable());
+        }
+    }
+    let solution = StressPuzzle::AntiBruteForce.sudoku().solution().unwrap();
+    assert!(solution.to_string().starts_with("987654321"));
+}
+
+#[test]
+fn puzzle_of_the_day() {
+    use sudoku::batch::{Difficulty, Grade};
+    use sudoku::daily::Date;
+
+    for days in (-800_000..800_000).step_by(997) {
+        let date = Date::from_days_since_epoch(days);
+        assert_eq!(Date::new(date.year(), date.month(), date.day()), Some(date));
+        assert_eq!(date.days_since_epoch(), days);
+    }
+    assert_eq!(Date::from_days_since_epoch(0), Date::new(1970, 1, 1).unwrap());
+    assert_eq!(Date::new(2000, 2, 29).unwrap().days_since_epoch(), 11_016);
+    assert_eq!(Date::new(1900, 2, 29), None);
+    assert_eq!(Date::new(2024, 13, 1), None);
+
+    // changing these puzzles requires a new minor version
+    let date = Date::new(2024, 1, 1).unwrap();
+    let expected = [
+        (
+            Difficulty::Easy,
+            "4....1......46.2...1.....35...3.5..4.72.9.86.3..6.2...14.....5...9.16......8....2",
+        ),
+        (
+            Difficulty::Hard,
+            "...51..8...9.....2.138......7..8.3...5..2..4...4.9..5......873.2.....8...9..76...",
+        ),
+    ];
+    for &(difficulty, line) in expected.iter() {
+        let sudoku = Sudoku::puzzle_of_the_day(date, difficulty).unwrap();
+        assert_eq!(sudoku.to_str_line().to_string(), line);
+        assert_eq!(Grade::new(sudoku).difficulty, difficulty);
+    }
+    let next_day = Date::from_days_since_epoch(date.days_since_epoch() + 1);
+    assert_ne!(
+        Sudoku::puzzle_of_the_day(next_day, Difficulty::Easy),
+        Sudoku::puzzle_of_the_day(date, Difficulty::Easy)
+    );
+    assert!(Sudoku::puzzle_of_the_day(date, Difficulty::Invalid).is_err());
+}
+
+#[test]
+fn game_state() {
+    use sudoku::board::{Cell, Digit, GameState};
+    use sudoku::errors::NoUniqueSolution;
+    use sudoku::strategy::rating::{DifficultyModel, SudokuExplainer};
+    use sudoku::strategy::{Deduction, Strategy, StrategySolver};
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"))[0];
+    let solution = sudoku.solution().unwrap().to_bytes();
+    let mut game = GameState::new(sudoku).unwrap();
+    let empty_cells = Cell::all()
+        .filter(|cell| sudoku.to_bytes()[cell.as_index()] == 0)
+        .collect::<Vec<_>>();
+
+    // half of the empty cells correct, one wrong
+    for &cell in empty_cells.iter().step_by(2) {
+        game.set_entry(cell, Some(Digit::new(solution[cell.as_index()])));
+    }
+    let wrong_cell = empty_cells[1];
+    let wrong_digit = Digit::new(solution[wrong_cell.as_index()] % 9 + 1);
+    game.set_entry(wrong_cell, Some(wrong_digit));
+    let noted_cell = empty_cells[3];
+    game.set_notes(noted_cell, Digit::new(solution[noted_cell.as_index()]).as_set());
+    let bad_notes_cell = empty_cells[5];
+    game.set_notes(
+        bad_notes_cell,
+        !Digit::new(solution[bad_notes_cell.as_index()]).as_set(),
+    );
+
+    let validation = game.validate();
+    let wrong_entries = game.check_against_solution();
+    assert_eq!(wrong_entries.len(), 1);
+    assert_eq!(wrong_entries[0].cell, wrong_cell);
+    assert_eq!(wrong_entries[0].entry, wrong_digit);
+    assert_eq!(wrong_entries[0].solution.get(), solution[wrong_cell.as_index()]);
+    assert_eq!(validation.incorrect_entries, wrong_cell.as_set());
+    assert_eq!(validation.incorrect_notes, bad_notes_cell.as_set());
+    assert!(!validation.is_solved);
+    assert_eq!(
+        game.position().to_bytes()[wrong_cell.as_index()],
+        wrong_digit.get()
+    );
+    assert_eq!(game.effective_position().to_bytes()[wrong_cell.as_index()], 0);
+    assert_eq!(game.entry(wrong_cell), Some(wrong_digit));
+
+    // the correct note leaves a single candidate
+    let strategies = [Strategy::NakedSingles, Strategy::HiddenSingles];
+    let hint = game.hint(&strategies).unwrap();
+    assert_eq!(hint.len(), 1);
+    match hint.get(0).unwrap() {
+        Deduction::NakedSingles(candidate) | Deduction::HiddenSingles(candidate, _) => {
+            assert_eq!(candidate.digit.get(), solution[candidate.cell.as_index()]);
+            assert_eq!(game.effective_position().to_bytes()[candidate.cell.as_index()], 0);
+        }
+        deduction => panic!("unexpected hint {:?}", deduction),
+    }
+    let (solved, _) = StrategySolver::from_game_state(&game)
+        .solve(SudokuExplainer.strategies())
+        .unwrap();
+    assert_eq!(solved.to_bytes(), solution);
+
+    #[cfg(feature = "serde")]
+    {
+        game.set_auto_remove_notes(false);
+        let json = serde_json::to_value(&game).unwrap();
+        assert_eq!(serde_json::from_value::<GameState>(json.clone()).unwrap(), game);
+        let mut with_entry_on_clue = json;
+        let clue = sudoku.to_bytes().iter().position(|&digit| digit != 0).unwrap();
+        let mut entries = with_entry_on_clue["entries"].as_str().unwrap().to_string();
+        entries.replace_range(clue..=clue, "1");
+        with_entry_on_clue["entries"] = entries.into();
+        assert!(serde_json::from_value::<GameState>(with_entry_on_clue).is_err());
+    }
+
+    for &cell in &empty_cells {
+        game.set_entry(cell, Some(Digit::new(solution[cell.as_index()])));
+    }
+    assert!(game.validate().is_solved);
+    assert!(game.hint(&strategies).is_none());
+
+    let mut bytes = sudoku.to_bytes();
+    let first_clue = bytes.iter().position(|&digit| digit != 0).unwrap();
+    bytes[first_clue] = 0;
+    let mut sparse = Sudoku::from_bytes(bytes).unwrap();
+    while sparse.is_uniquely_solvable() {
+        let clue = sparse.to_bytes().iter().position(|&digit| digit != 0).unwrap();
+        bytes[clue] = 0;
+        sparse = Sudoku::from_bytes(bytes).unwrap();
+    }
+    assert_eq!(GameState::new(sparse), Err(NoUniqueSolution));
+}
+
+#[test]
+fn game_state_notes() {
+    use sudoku::board::{Cell, CellState, Digit, GameState};
+    use sudoku::strategy::StrategySolver;
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let mut game = GameState::new(sudoku).unwrap();
+    game.fill_notes();
+    let expected = StrategySolver::from_sudoku(sudoku).grid_state();
+    for cell in Cell::all() {
+        match expected[cell.as_index()] {
+            CellState::Candidates(candidates) => assert_eq!(game.notes(cell), candidates),
+            CellState::Digit(_) => assert!(game.notes(cell).is_empty()),
+        }
+    }
+    assert!(game.validate().incorrect_notes.is_empty());
+
+    let solution = sudoku.solution().unwrap().to_bytes();
+    let cell = Cell::all()
+        .find(|cell| sudoku.to_bytes()[cell.as_index()] == 0)
+        .unwrap();
+    let digit = Digit::new(solution[cell.as_index()]);
+    let neighbors = cell.row().cells() | cell.col().cells() | cell.block().cells();
+    let others = Cell::all()
+        .filter(|&other| other != cell && !neighbors.contains(other))
+        .collect::<Vec<_>>();
+    let before = others.iter().map(|&other| game.notes(other)).collect::<Vec<_>>();
+    game.set_entry(cell, Some(digit));
+    for neighbor in neighbors.into_iter().filter(|&neighbor| neighbor != cell) {
+        assert!(!game.notes(neighbor).contains(digit));
+    }
+    assert_eq!(
+        others.iter().map(|&other| game.notes(other)).collect::<Vec<_>>(),
+        before
+    );
+    assert!(game.validate().incorrect_notes.is_empty());
+
+    // without automatic removal, the notes stay as they are
+    let mut game = GameState::new(sudoku).unwrap();
+    game.fill_notes();
+    game.set_auto_remove_notes(false);
+    let notes = Cell::all().map(|cell| game.notes(cell)).collect::<Vec<_>>();
+    game.set_entry(cell, Some(digit));
+    assert_eq!(
+        Cell::all().map(|cell| game.notes(cell)).collect::<Vec<_>>(),
+        notes
+    );
+}
+
+#[test]
+fn replay() {
+    use std::time::Duration;
+    use sudoku::board::{Action, Cell, Digit, GameState, Replay};
+    use sudoku::errors::ReplayError;
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let solution = sudoku.solution().unwrap().to_bytes();
+    let empty_cells = Cell::all()
+        .filter(|cell| sudoku.to_bytes()[cell.as_index()] == 0)
+        .collect::<Vec<_>>();
+
+    // play the game directly and record it at the same time
+    let mut replay = Replay::new(sudoku);
+    let mut game = GameState::new(sudoku).unwrap();
+    let mut states = vec![game.clone()];
+    let mut time = Duration::from_secs(0);
+    let mut record = |replay: &mut Replay, action| {
+        time += Duration::from_secs(3);
+        replay.record(time, action);
+    };
+    record(&mut replay, Action::FillNotes);
+    game.fill_notes();
+    states.push(game.clone());
+    for &cell in &empty_cells[..5] {
+        let digit = Digit::new(solution[cell.as_index()]);
+        record(&mut replay, Action::Place { cell, digit });
+        game.set_entry(cell, Some(digit));
+        states.push(game.clone());
+    }
+    record(&mut replay, Action::Hint);
+    states.push(game.clone());
+    let cell = empty_cells[0];
+    record(&mut replay, Action::Erase { cell });
+    game.set_entry(cell, None);
+    states.push(game.clone());
+    let notes = Digit::new(1).as_set() | Digit::new(2);
+    record(&mut replay, Action::Note { cell, notes });
+    game.set_notes(cell, notes);
+    states.push(game.clone());
+
+    for (n_actions, state) in states.iter().enumerate() {
+        assert_eq!(&replay.game_state_after(n_actions).unwrap(), state);
+    }
+    assert_eq!(replay.game_state().unwrap(), game);
+    assert_eq!(replay.game_state_at(Duration::from_secs(7)).unwrap(), states[2]);
+    assert_eq!(replay.actions().len(), states.len() - 1);
+
+    // undo reverts the note, the erasure and the last placement, but skips the hint
+    for _ in 0..3 {
+        record(&mut replay, Action::Undo);
+    }
+    assert_eq!(replay.game_state().unwrap(), states[5]);
+    for _ in 0..10 {
+        record(&mut replay, Action::Undo);
+    }
+    assert_eq!(replay.game_state().unwrap(), states[0]);
+
+    let clue = Cell::all()
+        .find(|cell| sudoku.to_bytes()[cell.as_index()] != 0)
+        .unwrap();
+    record(&mut replay, Action::Erase { cell: clue });
+    assert_eq!(
+        replay.game_state(),
+        Err(ReplayError::ChangesClue(replay.actions().len() - 1))
+    );
+
+    // the setting for removing notes is replayed and not undone
+    let mut replay = Replay::new(sudoku);
+    let mut game = GameState::new(sudoku).unwrap();
+    replay.record(Duration::from_secs(1), Action::FillNotes);
+    game.fill_notes();
+    replay.record(Duration::from_secs(2), Action::SetAutoRemoveNotes(false));
+    game.set_auto_remove_notes(false);
+    let [first, second] = [empty_cells[0], empty_cells[1]];
+    let digit = Digit::new(solution[first.as_index()]);
+    replay.record(Duration::from_secs(3), Action::Place { cell: first, digit });
+    game.set_entry(first, Some(digit));
+    assert_eq!(replay.game_state().unwrap(), game);
+    replay.record(Duration::from_secs(4), Action::Undo);
+    let digit = Digit::new(solution[second.as_index()]);
+    replay.record(Duration::from_secs(5), Action::Place { cell: second, digit });
+    game.set_entry(first, None);
+    game.set_entry(second, Some(digit));
+    assert_eq!(replay.game_state().unwrap(), game);
+}
+
+#[test]
+fn bitboards() {
+    use std::convert::TryFrom;
+    use sudoku::board::{Digit, SudokuBitboards};
+
+    let sees =
+        |a: usize, b: usize| a / 9 == b / 9 || a % 9 == b % 9 || (a / 27 == b / 27 && a % 9 / 3 == b % 9 / 3);
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for &sudoku in sudokus.iter().take(20) {
+        let bitboards = SudokuBitboards::from(sudoku);
+        assert_eq!(Sudoku::try_from(bitboards), Ok(sudoku));
+        assert_eq!(SudokuBitboards::from_bands(bitboards.bands()), bitboards);
+
+        let bytes = sudoku.to_bytes();
+        let candidates = bitboards.candidates().unwrap();
+        for digit in (1..=9).map(Digit::new) {
+            let cells = bitboards.cells(digit);
+            let candidate_cells = candidates.cells(digit);
+            for cell in 0..81 {
+                let is_clue = bytes[cell] == digit.get();
+                let is_candidate = match bytes[cell] {
+                    0 => (0..81).all(|other| bytes[other] != digit.get() || !sees(cell, other)),
+                    _ => is_clue,
+                };
+                assert_eq!(cells.contains(sudoku::board::Cell::new(cell as u8)), is_clue);
+                assert_eq!(
+                    candidate_cells.contains(sudoku::board::Cell::new(cell as u8)),
+                    is_candidate
+                );
+            }
+        }
+        let empty = bitboards.empty_cells();
+        let n_empty = empty.iter().map(|band| band.count_ones()).sum::<u32>();
+        assert_eq!(n_empty as usize, 81 - sudoku.n_clues() as usize);
+    }
+
+    let mut bands = [[0; 3]; 9];
+    bands[0][1] = 1 << 4;
+    bands[5][1] = 1 << 4;
+    let overlapping = SudokuBitboards::from_bands(bands);
+    assert_eq!(overlapping.to_sudoku().unwrap_err().0, 31);
+    assert_eq!(overlapping.candidates(), None);
+}
+
+#[test]
+fn trivial_first_moves() {
+    let solution = Sudoku::generate_solved().to_bytes();
+
+    // two empty cells in the first row, each with 2 candidates
+    let mut bytes = [0; 81];
+    bytes[..7].copy_from_slice(&solution[..7]);
+    assert!(!Sudoku::from_bytes(bytes).unwrap().has_trivial_first_move());
+
+    // the digit of the last cell in the column of the other one leaves a naked single
+    let cell = (27..81)
+        .step_by(9)
+        .map(|cell| cell + 7)
+        .find(|&cell| solution[cell] == solution[8])
+        .unwrap();
+    bytes[cell] = solution[cell];
+    assert!(Sudoku::from_bytes(bytes).unwrap().has_trivial_first_move());
+
+    for _ in 0..3 {
+        let sudoku = Sudoku::generate_filtered(|sudoku| !sudoku.has_trivial_first_move());
+        assert!(sudoku.is_uniquely_solvable());
+        assert!(!sudoku.has_trivial_first_move());
+    }
+}
+
+#[test]
+fn generator_builder() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::batch::{Difficulty, Grade};
+    use sudoku::errors::GenerationFailed;
+    use sudoku::generator::SudokuGenerator;
+    use sudoku::strategy::{Strategy, StrategySolver, TechniqueSet};
+    use sudoku::Symmetry;
+
+    let singles = TechniqueSet::SINGLES;
+    let mut builder = SudokuGenerator::builder()
+        .with_rng(StdRng::seed_from_u64(0))
+        .with_symmetry(Symmetry::None)
+        .with_clues(20..=40)
+        .with_required_techniques(Strategy::NakedSingles.into())
+        .with_forbidden_techniques(
+            TechniqueSet::ALL
+                .strategies()
+                .into_iter()
+                .filter(|&strategy| !singles.contains(strategy))
+                .collect(),
+        )
+        .with_difficulty(Difficulty::Easy..=Difficulty::Easy)
+        .with_max_attempts(200);
+    for _ in 0..3 {
+        let sudoku = builder.generate().unwrap();
+        assert!(sudoku.is_uniquely_solvable());
+        assert!((20..=40).contains(&sudoku.n_clues()));
+        assert!(sudoku.solvable_with(&singles));
+        assert_eq!(Grade::new(sudoku).difficulty, Difficulty::Easy);
+        let (_, deductions) = StrategySolver::from_sudoku(sudoku)
+            .solve(&singles.strategies())
+            .unwrap();
+        assert!(deductions.technique_counts().get(Strategy::NakedSingles) > 0);
+    }
+
+    // the same seed generates the same sudoku
+    let generate = |seed| {
+        SudokuGenerator::builder()
+            .with_rng(StdRng::seed_from_u64(seed))
+            .generate()
+            .unwrap()
+    };
+    assert_eq!(generate(1), generate(1));
+
+    let result = SudokuGenerator::builder()
+        .with_filter(|sudoku| sudoku.n_clues() > 0)
+        .with_filter(|_| false)
+        .with_max_attempts(3)
+        .generate();
+    assert_eq!(result, Err(GenerationFailed { n_attempts: 3 }));
+}
+
+#[test]
+fn generator_rejects_known_puzzles() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::generator::SudokuGenerator;
+
+    let builder = || SudokuGenerator::builder().with_rng(StdRng::seed_from_u64(1));
+    let first = builder().generate().unwrap();
+    let fingerprint = first.shuffled().canonical_fingerprint().unwrap();
+    assert_eq!(first.canonical_fingerprint(), Some(fingerprint));
+    assert_eq!(builder().known_puzzles(), None);
+
+    // the same seed would generate an equivalent puzzle first
+    let mut builder = builder().with_known_puzzles(vec![fingerprint]);
+    let second = builder.generate().unwrap();
+    assert_ne!(second, first);
+    let third = builder.generate().unwrap();
+
+    let known = builder.known_puzzles().unwrap();
+    assert_eq!(known.len(), 3);
+    assert!(known.contains(&second.canonical_fingerprint().unwrap()));
+    assert!(known.contains(&third.canonical_fingerprint().unwrap()));
+    assert_ne!(second.canonical_fingerprint(), third.canonical_fingerprint());
+}
+
+#[test]
+fn hard_puzzle_search() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::search::HardPuzzleSearch;
+    use sudoku::strategy::rating::{DifficultyModel, SeRating, SolveTrace};
+    use sudoku::strategy::StrategySolver;
+
+    // rates puzzles by the number of steps needed to solve them
+    struct StepCount;
+
+    impl DifficultyModel for StepCount {
+        fn rate(&self, trace: &SolveTrace<'_>) -> SeRating {
+            SeRating::Rated(trace.deductions().len() as f32)
+        }
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let solution = Sudoku::generate_solved_with_rng(rng);
+    let mut search = HardPuzzleSearch::new()
+        .with_rng(StdRng::seed_from_u64(1))
+        .with_restarts(3)
+        .with_steps(10);
+    let result = search.run_with_model(solution, &StepCount);
+    assert_eq!(result.n_ratings, 3 * 11);
+    assert_eq!(result.sudoku.solution(), Some(solution));
+    let rating = StrategySolver::from_sudoku(result.sudoku)
+        .assume_unique_solution(true)
+        .rate_with(&StepCount);
+    assert_eq!(result.rating, rating);
+
+    // plain hill climbing
+    let mut search = HardPuzzleSearch::new()
+        .with_rng(StdRng::seed_from_u64(2))
+        .with_restarts(1)
+        .with_steps(5)
+        .with_temperature(0.0);
+    let result = search.run(solution);
+    assert!(result.sudoku.is_uniquely_solvable());
+    assert!(result.rating.value() >= 1.2);
+}
+
+#[test]
+fn generate_with_pattern() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::board::Pattern;
+    use sudoku::errors::GenerationFailed;
+    use sudoku::Symmetry;
+
+    // a sparse, symmetric pattern
+    let pattern = Sudoku::from_str_line(
+        "8..3...6...5...3..97..5.........7..22.1...7.85..4.........2..57..8...9...3...8..4",
+    )
+    .unwrap()
+    .pattern();
+    assert_eq!(pattern.n_clues(), 24);
+    let rng = &mut StdRng::seed_from_u64(0);
+    for _ in 0..3 {
+        let sudoku = Sudoku::generate_with_pattern_and_rng(&pattern, 100, rng).unwrap();
+        assert_eq!(sudoku.pattern(), pattern);
+        assert!(sudoku.is_uniquely_solvable());
+        assert!(sudoku.pattern().has_symmetry(Symmetry::HalfRotation));
+    }
+
+    let mut sparse = [false; 81];
+    sparse[..16].iter_mut().for_each(|filled| *filled = true);
+    let result = Sudoku::generate_with_pattern(&Pattern::from_bools(sparse), 100);
+    assert_eq!(result, Err(GenerationFailed { n_attempts: 0 }));
+}
+
+#[test]
+fn generate_with_techniques() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::generator::SudokuGenerator;
+    use sudoku::strategy::{Strategy, TechniqueSet};
+    use sudoku::Symmetry;
+
+    let hidden_singles = TechniqueSet::NONE.with(Strategy::HiddenSingles);
+    let mut builder = SudokuGenerator::builder()
+        .with_rng(StdRng::seed_from_u64(3))
+        .with_symmetry(Symmetry::None)
+        .with_allowed_techniques(hidden_singles);
+    for _ in 0..5 {
+        let sudoku = builder.generate().unwrap();
+        assert!(sudoku.is_uniquely_solvable());
+        assert!(sudoku.solvable_with(&hidden_singles));
+        // no clue can be removed without requiring other strategies
+        for cell in 0..81 {
+            let mut bytes = sudoku.to_bytes();
+            if bytes[cell] != 0 {
+                bytes[cell] = 0;
+                assert!(!Sudoku::from_bytes(bytes).unwrap().solvable_with(&hidden_singles));
+            }
+        }
+    }
+
+    let sudoku = Sudoku::generate_with_techniques(&TechniqueSet::SINGLES);
+    assert!(sudoku.is_uniquely_solvable());
+    assert!(sudoku.solvable_with(&TechniqueSet::SINGLES));
+}
+
+#[test]
+fn generate_unique_batch() {
+    assert!(Sudoku::generate_unique_batch(0).is_empty());
+    let sudokus = Sudoku::generate_unique_batch(13);
+    assert_eq!(sudokus.len(), 13);
+    for sudoku in sudokus {
+        assert!(sudoku.is_uniquely_solvable());
+    }
+}
+
 // this test is probabilistic in nature
 // if an error occurs, note down the sudoku that it generated
 #[test]
@@ -280,7 +1012,7 @@ fn shuffle_unsolved() {
 #[test]
 fn shuffle_solved() {
     let sudoku = Sudoku::generate_solved();
-    test_shuffle_sudoku(sudoku);
+    test_shuffle_sudoku(sudoku.into());
 }
 
 // test if any two sudokus are equal
@@ -367,6 +1099,42 @@ fn parse_permissive() {
     }
 }
 
+#[test]
+fn parse_permissive_unicode() {
+    let line = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    let expected = Sudoku::from_str_line(line).unwrap();
+
+    // full-width digits and empty cells, windows line endings and a byte order mark
+    let to_full_width = |ch: char| match ch {
+        '.' => '．',
+        '0'..='9' => char::from_u32(ch as u32 - '0' as u32 + '０' as u32).unwrap(),
+        _ => ch,
+    };
+    let rows = line.as_bytes().chunks(9).map(|row| {
+        let row = std::str::from_utf8(row).unwrap();
+        row.chars().map(to_full_width).collect::<String>()
+    });
+    let block = format!("\u{feff}{}", rows.collect::<Vec<_>>().join("\r\n"));
+    assert_eq!(Sudoku::from_str_block_permissive(&block), Ok(expected));
+
+    // box drawing, en-dashes, non-breaking spaces and mixed forms of empty cells
+    let block = "\
+╔═══╦═══╦═══╗
+║4_·║＿.\u{a0}.║8.5║
+║.3.║...║...║
+║...║7..║...║
+╠–––╬–––╬–––╣
+║.2.║...║.6.║
+║...║.8.║4..║
+║...║.1.║...║
+╠–––╬–––╬–––╣
+║...║6.3║.7.║
+║5..║2..║...║
+║1.4║...║...║
+╚═══╩═══╩═══╝";
+    assert_eq!(Sudoku::from_str_block_permissive(block), Ok(expected));
+}
+
 #[test]
 fn canonicalize() {
     let sudoku = Sudoku::generate_solved();
@@ -395,6 +1163,58 @@ fn canonicalize_idempotency() {
     }
 }
 
+#[test]
+fn grid_equivalence_and_automorphisms() {
+    let total_transformations = 2 * 6u64.pow(8) * 362_880;
+    for _ in 0..100 {
+        let solution = Sudoku::generate_solved();
+        let shuffled = Sudoku::from(solution).shuffled().solution().unwrap();
+        assert!(solution.is_equivalent_to(&shuffled));
+        assert_eq!(solution.n_automorphisms(), shuffled.n_automorphisms());
+        assert_eq!(solution.n_automorphisms(), solution.canonicalized().unwrap().1);
+        assert_eq!(
+            solution.n_equivalent_grids() * solution.n_automorphisms() as u64,
+            total_transformations
+        );
+    }
+
+    // there are about 5.5 billion equivalence classes, two random grids are practically never equivalent
+    let line = "123456789456789123789123456231564897564897231897231564312645978645978312978312645";
+    let most_canonical = Sudoku::from_str_line(line).unwrap().solution().unwrap();
+    assert_eq!(most_canonical.n_automorphisms(), 648);
+    assert!(!Sudoku::generate_solved().is_equivalent_to(&most_canonical));
+}
+
+#[test]
+fn clue_distribution() {
+    let solution = Sudoku::generate_solved();
+    let distribution = solution.clue_distribution();
+    assert_eq!(distribution.clues_per_row, [9; 9]);
+    assert_eq!(distribution.clues_per_col, [9; 9]);
+    assert_eq!(distribution.clues_per_block, [9; 9]);
+    assert_eq!(distribution.digit_counts, [9; 9]);
+    assert!((distribution.positional_entropy() - 1.0).abs() < 1e-9);
+    assert!((distribution.digit_entropy() - 1.0).abs() < 1e-9);
+    assert_eq!(distribution.digit_imbalance(), 0);
+
+    let empty = Sudoku::from_bytes([0; 81]).unwrap().clue_distribution();
+    assert_eq!(empty.positional_entropy(), 0.0);
+    assert_eq!(empty.digit_entropy(), 0.0);
+
+    // a single row of clues is spread over all columns, but not over the rows
+    let mut bytes = [0; 81];
+    bytes[..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let row = Sudoku::from_bytes(bytes).unwrap().clue_distribution();
+    assert_eq!(row.clues_per_row[0], 9);
+    assert!((row.positional_entropy() - 1.0 / 3.0 - 0.5 / 3.0).abs() < 1e-9);
+    assert!((row.digit_entropy() - 1.0).abs() < 1e-9);
+
+    let generated = Sudoku::generate();
+    let distribution = generated.clue_distribution();
+    assert_eq!(distribution.digit_counts.iter().sum::<u8>(), generated.n_clues());
+    assert!(distribution.digit_imbalance() <= 9);
+}
+
 #[allow(unused)]
 // as it stands SudokuLine seems to be unnameable because it is not exported
 // compile time check to see if it is constructable and printable
@@ -404,3 +1224,2635 @@ fn print_line() {
     let dereffed_line: &str = &line;
     println!("{}", line);
 }
+
+#[test]
+fn clue_removal_solutions_count() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(10) {
+        let counts = sudoku.clue_removal_solutions_count_up_to(50);
+        for (cell, count) in counts.iter().enumerate() {
+            let mut bytes = sudoku.to_bytes();
+            match bytes[cell] {
+                0 => assert_eq!(*count, None),
+                _ => {
+                    bytes[cell] = 0;
+                    let expected = Sudoku::from_bytes(bytes).unwrap().solutions_count_up_to(50);
+                    assert_eq!(*count, Some(expected));
+                }
+            }
+        }
+    }
+
+    let generated = Sudoku::generate_from(Sudoku::generate_solved().into());
+    assert!(generated
+        .clue_removal_solutions_count_up_to(2)
+        .iter()
+        .flatten()
+        .all(|&count| count == 2));
+}
+
+#[test]
+fn solution_counts_per_candidate() {
+    use sudoku::board::Cell;
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(3) {
+        // remove some clues to get multiple solutions
+        let mut bytes = sudoku.to_bytes();
+        bytes
+            .iter_mut()
+            .filter(|digit| **digit != 0)
+            .take(4)
+            .for_each(|digit| *digit = 0);
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+
+        for cell in Cell::all() {
+            let counts = sudoku.solution_counts_per_candidate(cell, 20);
+            for (digit, &count) in (1..=9).zip(counts.iter()) {
+                let expected = match bytes[cell.as_index()] {
+                    0 => {
+                        let mut with_digit = bytes;
+                        with_digit[cell.as_index()] = digit;
+                        Sudoku::from_bytes(with_digit).unwrap().solutions_count_up_to(20)
+                    }
+                    clue if clue == digit => sudoku.solutions_count_up_to(20),
+                    _ => 0,
+                };
+                assert_eq!(count, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn constrainedness_map() {
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let (grid, candidates) = sudoku.propagate().unwrap();
+    let map = sudoku.constrainedness_map();
+    for cell in 0..81 {
+        match (sudoku.to_bytes()[cell], grid.to_bytes()[cell]) {
+            (0, 0) => assert!(map[cell] > 1),
+            (0, _) => assert_eq!(map[cell], 1),
+            _ => assert_eq!(map[cell], 0),
+        }
+        if sudoku.to_bytes()[cell] == 0 {
+            assert_eq!(map[cell], candidates[cell].len());
+        }
+    }
+
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert_eq!(empty.constrainedness_map(), [9; 81]);
+
+    let mut bytes = [0; 81];
+    bytes[..2].copy_from_slice(&[1, 1]);
+    assert_eq!(Sudoku::from_bytes(bytes).unwrap().constrainedness_map(), [0; 81]);
+}
+
+#[test]
+fn remove_digit() {
+    use sudoku::board::Digit;
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    for digit in (1..=9).map(Digit::new) {
+        let removal = sudoku.remove_digit(digit);
+        let expected_bytes = sudoku
+            .to_bytes()
+            .map(|clue| if clue == digit.get() { 0 } else { clue });
+        assert_eq!(removal.sudoku.to_bytes(), expected_bytes);
+        assert_eq!(removal.n_removed, sudoku.n_clues() - removal.sudoku.n_clues());
+        assert_eq!(
+            removal.is_uniquely_solvable,
+            removal.sudoku.is_uniquely_solvable()
+        );
+    }
+
+    // a solved grid stays unique without any one digit
+    let solved = Sudoku::generate_solved().into_sudoku();
+    let removal = solved.remove_digit(Digit::new(5));
+    assert_eq!(removal.n_removed, 9);
+    assert!(removal.is_uniquely_solvable);
+    // but not without a second one
+    assert!(!removal.sudoku.remove_digit(Digit::new(6)).is_uniquely_solvable);
+}
+
+#[test]
+fn chutes() {
+    use sudoku::bitset::Set;
+    use sudoku::board::positions::{Band, Stack};
+    use sudoku::board::Cell;
+
+    for band in Band::all() {
+        let mut minirow_cells = Set::NONE;
+        for minirow in band.minirows() {
+            assert_eq!(minirow.band(), band);
+            assert!(band.rows().contains(&minirow.row()));
+            assert!(band.blocks().contains(&minirow.block()));
+            assert_eq!(minirow.cells(), minirow.row().cells() & minirow.block().cells());
+            minirow_cells |= minirow.cells();
+        }
+        assert_eq!(minirow_cells, band.cells());
+        let row_cells = band
+            .rows()
+            .iter()
+            .fold(Set::NONE, |cells, row| cells | row.cells());
+        let block_cells = band
+            .blocks()
+            .iter()
+            .fold(Set::NONE, |cells, block| cells | block.cells());
+        assert_eq!(row_cells, band.cells());
+        assert_eq!(block_cells, band.cells());
+    }
+    for stack in Stack::all() {
+        let mut minicol_cells = Set::NONE;
+        for minicol in stack.minicols() {
+            assert_eq!(minicol.stack(), stack);
+            assert!(stack.cols().contains(&minicol.col()));
+            assert!(stack.blocks().contains(&minicol.block()));
+            assert_eq!(minicol.cells(), minicol.col().cells() & minicol.block().cells());
+            minicol_cells |= minicol.cells();
+        }
+        assert_eq!(minicol_cells, stack.cells());
+        let col_cells = stack
+            .cols()
+            .iter()
+            .fold(Set::NONE, |cells, col| cells | col.cells());
+        let block_cells = stack
+            .blocks()
+            .iter()
+            .fold(Set::NONE, |cells, block| cells | block.cells());
+        assert_eq!(col_cells, stack.cells());
+        assert_eq!(block_cells, stack.cells());
+    }
+    for cell in Cell::all() {
+        assert!(cell.band().cells().contains(cell));
+        assert!(cell.stack().cells().contains(cell));
+    }
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let entries = sudoku.iter().collect::<Vec<_>>();
+    let (bands, stacks) = (sudoku.bands(), sudoku.stacks());
+    for (chute_cells, chute_entries) in Band::all()
+        .map(|band| (band.cells(), bands[band.as_index()]))
+        .chain(Stack::all().map(|stack| (stack.cells(), stacks[stack.as_index()])))
+    {
+        for (cell, entry) in chute_cells.into_iter().zip(chute_entries.iter()) {
+            assert_eq!(*entry, entries[cell.as_index()]);
+        }
+    }
+}
+
+#[test]
+fn reduce_locked_candidates() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    let mut n_eliminations = 0;
+    for sudoku in sudokus.into_iter().take(20) {
+        let solution = sudoku.solution().unwrap().to_bytes();
+        let (candidates, eliminations) = sudoku.reduce_locked_candidates();
+        for (cell, digits) in candidates.iter().enumerate() {
+            assert!(digits.contains(sudoku::board::Digit::new(solution[cell])));
+        }
+        for elimination in &eliminations {
+            let miniline = elimination.miniline.cells();
+            assert!(!elimination.eliminated.is_empty());
+            assert!(!miniline.overlaps(elimination.eliminated));
+            for cell in elimination.eliminated {
+                assert_eq!(sudoku.to_bytes()[cell.as_index()], 0);
+                assert!(!candidates[cell.as_index()].contains(elimination.digit));
+                // the eliminated cell shares the line or the block with the whole miniline
+                let shared = cell.row().cells() | cell.col().cells() | cell.block().cells();
+                assert!(shared.contains(miniline));
+            }
+        }
+        n_eliminations += eliminations.len();
+    }
+    assert!(n_eliminations > 0);
+}
+
+#[test]
+fn disambiguating_clues() {
+    use sudoku::board::Candidate;
+
+    // without the 2 in row 7, column 2, 88 solutions
+    let ambiguous = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3.........63..5..5.37....847...1...",
+    )
+    .unwrap();
+    let with_clues = |clues: &[Candidate]| {
+        let mut bytes = ambiguous.to_bytes();
+        for clue in clues {
+            bytes[clue.cell.as_index()] = clue.digit.get();
+        }
+        Sudoku::from_bytes(bytes).unwrap()
+    };
+
+    let suggestions = ambiguous.suggest_disambiguating_clues(2);
+    assert_eq!(suggestions[0].len(), 1);
+    for clues in &suggestions {
+        assert!((1..=2).contains(&clues.len()));
+        assert!(with_clues(clues).is_uniquely_solvable());
+        // minimal
+        for idx in 0..clues.len() {
+            let mut subset = clues.clone();
+            subset.remove(idx);
+            assert!(!with_clues(&subset).is_uniquely_solvable());
+        }
+    }
+    let singles = ambiguous.suggest_disambiguating_clues(1);
+    assert!(singles.iter().all(|clues| suggestions.contains(clues)));
+    assert_eq!(
+        singles.len(),
+        suggestions.iter().filter(|clues| clues.len() == 1).count()
+    );
+    assert!(ambiguous.suggest_disambiguating_clues(0).is_empty());
+
+    let unsolvable = Sudoku::from_str_line(
+        "11...............................................................................",
+    )
+    .unwrap();
+    assert!(unsolvable.suggest_disambiguating_clues(3).is_empty());
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert!(empty.suggest_disambiguating_clues(3).is_empty());
+}
+
+#[test]
+fn const_construction() {
+    const LINE: Sudoku = match Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1... comment",
+    ) {
+        Ok(sudoku) => sudoku,
+        Err(_) => panic!(),
+    };
+    const BYTES: Sudoku = match Sudoku::from_bytes(LINE.to_bytes()) {
+        Ok(sudoku) => sudoku,
+        Err(_) => panic!(),
+    };
+    const INVALID: Result<Sudoku, LineParseError> = Sudoku::from_str_line("..ä");
+
+    assert_eq!(LINE, BYTES);
+    assert_eq!(LINE.n_clues(), 27);
+    assert_eq!(
+        INVALID,
+        Err(LineParseError::InvalidEntry(InvalidEntry { cell: 2, ch: 'ä' }))
+    );
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_sudokus() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use sudoku::SolvedSudoku;
+
+    let bytes = (0..=255).collect::<Vec<u8>>();
+    let mut u = Unstructured::new(&bytes);
+    assert!(Sudoku::arbitrary(&mut u).unwrap().is_uniquely_solvable());
+    assert!(SolvedSudoku::arbitrary(&mut u).unwrap().into_sudoku().is_solved());
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_sudokus() {
+    use sudoku::SolvedSudoku;
+
+    fn puzzle_is_unique(sudoku: Sudoku) -> bool {
+        sudoku.is_uniquely_solvable()
+    }
+    fn grid_is_solved(sudoku: SolvedSudoku) -> bool {
+        sudoku.into_sudoku().is_solved()
+    }
+    quickcheck::quickcheck(puzzle_is_unique as fn(Sudoku) -> bool);
+    quickcheck::quickcheck(grid_is_solved as fn(SolvedSudoku) -> bool);
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn proptest_sudokus(puzzle: Sudoku, solved in sudoku::testing::solved_sudokus()) {
+        proptest::prop_assert!(puzzle.is_uniquely_solvable());
+        proptest::prop_assert!(solved.into_sudoku().is_solved());
+    }
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray_conversions() {
+    use sudoku::errors::FromNdarrayError;
+
+    let sudoku = Sudoku::generate();
+    let array = sudoku.to_ndarray();
+    assert_eq!(array.dim(), (9, 9));
+    assert_eq!(array.row(4).to_vec(), sudoku.to_bytes()[36..45].to_vec());
+
+    // the transposed view is read in its logical order
+    let transposed = Sudoku::try_from_ndarray(array.t()).unwrap();
+    assert_eq!(transposed.to_ndarray(), array.t());
+
+    let wrong_shape = array.clone().into_shape((3, 27)).unwrap();
+    assert!(matches!(
+        Sudoku::try_from_ndarray(wrong_shape.view()),
+        Err(FromNdarrayError::WrongShape(3, 27))
+    ));
+    let mut invalid = array;
+    invalid[[8, 8]] = 10;
+    assert!(matches!(
+        Sudoku::try_from_ndarray(invalid.view()),
+        Err(FromNdarrayError::FromBytesError(_))
+    ));
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn resolve_recognized_grid() {
+    use sudoku::errors::Unresolvable;
+    use sudoku::import::{Recognition, RecognizedGrid};
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let solution = sudoku.solution().unwrap().to_bytes();
+    let mut recognitions = [[Recognition {
+        digit: None,
+        confidence: 0.9,
+    }; 9]; 9];
+    for (cell, digit) in sudoku.cells() {
+        recognitions[cell.row().get() as usize][cell.col().get() as usize].digit = digit;
+    }
+    // a clue of the wrong digit in an empty cell, which the recognizer isn't sure about
+    let empty = sudoku.cells().find(|(_, digit)| digit.is_none()).unwrap().0;
+    let wrong_digit = solution[empty.as_index()] % 9 + 1;
+    recognitions[empty.row().get() as usize][empty.col().get() as usize] = Recognition {
+        digit: Some(wrong_digit),
+        confidence: 0.3,
+    };
+
+    let grid = RecognizedGrid::new(recognitions);
+    assert_eq!(grid.recognized_sudoku().solutions_count_up_to(1), 0);
+    let resolution = grid.clone().resolve().unwrap();
+    assert_eq!(resolution.corrections.len(), 1);
+    assert_eq!(resolution.corrections[0].0, empty);
+    assert_eq!(resolution.sudoku.solution().unwrap().to_bytes(), solution);
+
+    assert_eq!(grid.with_max_corrections(0).resolve(), Err(Unresolvable));
+}
+
+#[test]
+fn parse_validated() {
+    use sudoku::errors::{BlockParseError, DuplicateClue, ValidatedParseError};
+
+    let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    let sudoku = Sudoku::from_str_line(line).unwrap();
+    assert_eq!(Sudoku::from_str_line_validated(line), Ok(sudoku));
+
+    // second 3 in the last column
+    let line = "...2...633....54.1..1..398........93...538....3........263..5..5.37....847...1...";
+    let err = Sudoku::from_str_line_validated(line).unwrap_err();
+    let duplicate = DuplicateClue {
+        cells: [8, 35],
+        digit: 3,
+    };
+    assert_eq!(err, ValidatedParseError::DuplicateClue(duplicate));
+    assert_eq!(err.to_string(), "cells 8 and 35 both contain the clue 3");
+
+    // parse errors take precedence
+    let line = "5...5....x";
+    assert!(matches!(
+        Sudoku::from_str_line_validated(line),
+        Err(ValidatedParseError::Parse(LineParseError::InvalidEntry(_)))
+    ));
+
+    // two 4s in the last row
+    let block = "___2___63
+3____54_1
+__1__398_
+_______9_
+___538___
+_3_______
+_263__5__
+5_37____8
+47___1_4_";
+    assert_eq!(
+        Sudoku::from_str_block_validated(block),
+        Err(ValidatedParseError::DuplicateClue(DuplicateClue {
+            cells: [72, 79],
+            digit: 4
+        }))
+    );
+    assert!(matches!(
+        Sudoku::from_str_block_validated("___2___63"),
+        Err(ValidatedParseError::Parse(BlockParseError::NotEnoughRows(_)))
+    ));
+}
+
+#[test]
+fn validate_puzzle() {
+    use sudoku::errors::NotUnique;
+
+    let mut bytes = Sudoku::generate_solved().into_sudoku().to_bytes();
+    // remove all 8s and 9s, leaving 63 clues of 7 digits. They can be swapped in the solution.
+    for byte in bytes.iter_mut().filter(|byte| **byte >= 8) {
+        *byte = 0;
+    }
+    let sudoku = Sudoku::from_bytes(bytes).unwrap();
+    assert_eq!(sudoku.validate_puzzle(), Err(NotUnique::TooFewDigits(7)));
+    assert_eq!(sudoku.solutions_count_up_to(2), 2);
+    assert_eq!(sudoku.solution(), None);
+
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt")) {
+        assert_eq!(sudoku.validate_puzzle(), Ok(()));
+    }
+}
+
+#[test]
+fn iterator_round_trip() {
+    use sudoku::errors::FromBytesSliceError;
+
+    let sudoku = Sudoku::generate();
+    let entries: Vec<Option<u8>> = sudoku.into_iter().collect();
+    assert_eq!(entries, (&sudoku).into_iter().collect::<Vec<_>>());
+    assert_eq!(Sudoku::try_from_iter(entries.clone()).unwrap(), sudoku);
+
+    let mut too_long = entries.clone();
+    too_long.push(None);
+    assert!(matches!(
+        Sudoku::try_from_iter(too_long),
+        Err(FromBytesSliceError::WrongLength(82))
+    ));
+
+    let mut invalid = entries;
+    invalid[10] = Some(0);
+    assert!(matches!(
+        Sudoku::try_from_iter(invalid),
+        Err(FromBytesSliceError::FromBytesError(_))
+    ));
+}
+
+#[test]
+fn public_solver() {
+    use sudoku::errors::{ClueConflict, Interrupted};
+    use sudoku::solver::Solver;
+
+    let solved = Sudoku::generate_solved().into_sudoku();
+    let mut solver = Solver::from_sudoku(solved).unwrap();
+    assert!(solver.is_solved());
+    assert_eq!(solver.solutions_up_to(2), [solved]);
+
+    let mut bytes = solved.to_bytes();
+    bytes[1] = bytes[0];
+    assert_eq!(
+        Solver::from_sudoku(Sudoku::from_bytes(bytes).unwrap()).unwrap_err(),
+        ClueConflict
+    );
+
+    let mut empty = Solver::new();
+    assert!(!empty.is_solved());
+    assert_eq!(
+        empty.solutions_count_up_to_limited(1000, 10),
+        Err(Interrupted::NodeLimitReached)
+    );
+    let mut buffer = [[0; 81]; 3];
+    assert_eq!(empty.solutions_up_to_buffer(&mut buffer, 5), 5);
+    let stats = empty.stats();
+    assert_eq!(stats.n_searches, 2);
+    assert_eq!(stats.n_solutions, 5);
+    assert!(stats.n_nodes > 10);
+}
+
+#[test]
+fn incremental_solver() {
+    use sudoku::board::{Cell, Digit};
+    use sudoku::solver::{IncrementalSolver, Solver};
+
+    let sudoku = Sudoku::generate();
+    let clues: Vec<_> = sudoku.clues().collect();
+    let mut solver = IncrementalSolver::new();
+    for &(cell, digit) in &clues {
+        solver.push_clue(cell, Digit::new(digit)).unwrap();
+    }
+    assert_eq!(solver.n_clues(), clues.len());
+    assert_eq!(solver.solutions_count_up_to(2), 1);
+
+    // the same counts as a solver built from scratch for every prefix of the clues
+    let mut bytes = sudoku.to_bytes();
+    for &(cell, _) in clues.iter().rev() {
+        solver.pop_clue();
+        bytes[cell.as_index()] = 0;
+        let expected = Solver::from_sudoku(Sudoku::from_bytes(bytes).unwrap())
+            .unwrap()
+            .solutions_count_up_to(2);
+        assert_eq!(solver.solutions_count_up_to(2), expected);
+    }
+    assert_eq!(solver.n_clues(), 0);
+
+    // conflicting clues are recorded, but leave no solutions
+    solver.push_clue(Cell::new(0), Digit::new(1)).unwrap();
+    assert!(solver.push_clue(Cell::new(1), Digit::new(1)).is_err());
+    assert_eq!(solver.n_clues(), 2);
+    assert_eq!(solver.solutions_count_up_to(2), 0);
+    solver.pop_clue();
+    assert_eq!(solver.solutions_count_up_to(2), 2);
+}
+
+#[test]
+fn detailed_parse_errors() {
+    let input = "___2___63
+3____54_1
+__1__398_
+_______9_
+___5x8___
+_3_______
+_263__5__
+5_37____8
+47___1___";
+    let err = Sudoku::from_str_block(input).unwrap_err();
+    assert_eq!(
+        err.detailed_display(input).to_string(),
+        "cell 40 contains invalid character 'x'
+  |
+5 | ___5x8___
+  |     ^"
+    );
+
+    let input = "\
+___|2__|_63
+3__|__5|4_1
+__1|__3|98_
+-----------
+___|___|_9_
+___|538|___
+_3_|___|___
+-----------
+_26|3__|5__
+5_3|7__|__8x
+47_|__1|___";
+    let err = Sudoku::from_str_block(input).unwrap_err();
+    assert_eq!(
+        err.detailed_display(input).to_string(),
+        "missing comment delimiter in line 10
+   |
+10 | 5_3|7__|__8x
+   |            ^"
+    );
+
+    let mut input = "___2___63x\n".to_string();
+    input.push_str(&"_________\n".repeat(8));
+    let err = Sudoku::from_str_block(&input).unwrap_err();
+    assert_eq!(
+        err.detailed_display(&input).to_string(),
+        "missing comment delimiter in line 1
+  |
+1 | ___2___63x
+  |          ^"
+    );
+
+    let input = "...2...633....54.1..1..398........9....538....3........263..5..5.37....8";
+    let err = Sudoku::from_str_line(input).unwrap_err();
+    let expected_caret = format!("\n  | {}^", " ".repeat(72));
+    assert!(err.detailed_display(input).to_string().ends_with(&expected_caret));
+}
+
+#[test]
+fn se_rating() {
+    use sudoku::strategy::{rating::SeRating, StrategySolver};
+
+    let easy = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in easy.into_iter().take(20) {
+        let rating = StrategySolver::from_sudoku(sudoku).se_rating();
+        assert!(rating.is_rated());
+        assert!((1.2..=5.4).contains(&rating.value()));
+    }
+
+    let hard = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let rating = StrategySolver::from_sudoku(hard[1]).se_rating();
+    assert_eq!(rating, SeRating::AtLeast(9.5));
+    let rating = StrategySolver::from_sudoku(hard[1])
+        .limit_forcing_chain_depth(2)
+        .se_rating();
+    assert_eq!(rating, SeRating::Rated(9.5));
+    let rating = StrategySolver::from_sudoku(hard[1])
+        .limit_forcing_chain_depth(0)
+        .se_rating();
+    assert_eq!(rating, SeRating::AtLeast(4.5));
+}
+
+#[test]
+fn custom_difficulty_model() {
+    use sudoku::batch::{Difficulty, Grade};
+    use sudoku::strategy::rating::{DifficultyModel, SeRating, SolveTrace, SudokuExplainer};
+    use sudoku::strategy::{Strategy, StrategySolver};
+
+    // only singles are allowed, every step counts as 1.0
+    struct SinglesOnly;
+
+    impl DifficultyModel for SinglesOnly {
+        fn strategies(&self) -> &[Strategy] {
+            &[Strategy::NakedSingles, Strategy::HiddenSingles]
+        }
+
+        fn rate(&self, trace: &SolveTrace<'_>) -> SeRating {
+            let n_steps = trace.deductions().len() as f32;
+            match trace.is_solved() {
+                true => SeRating::Rated(n_steps),
+                false => SeRating::AtLeast(n_steps),
+            }
+        }
+    }
+
+    let easy = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for &sudoku in &easy[..20] {
+        let solver = StrategySolver::from_sudoku(sudoku);
+        assert_eq!(
+            solver.clone().rate_with(&SudokuExplainer),
+            solver.clone().se_rating()
+        );
+
+        let (is_solved, deductions) = match solver
+            .clone()
+            .solve(&[Strategy::NakedSingles, Strategy::HiddenSingles])
+        {
+            Ok((_, deductions)) => (true, deductions),
+            Err((_, deductions)) => (false, deductions),
+        };
+        let rating = solver.rate_with(&SinglesOnly);
+        assert_eq!(rating.is_rated(), is_solved);
+        assert_eq!(rating.value(), deductions.len() as f32);
+    }
+
+    assert_eq!(Grade::with_model(easy[0], &SudokuExplainer), Grade::new(easy[0]));
+    let invalid = Sudoku::from_bytes([0; 81]).unwrap();
+    assert_eq!(
+        Grade::with_model(invalid, &SinglesOnly).difficulty,
+        Difficulty::Invalid
+    );
+}
+
+#[test]
+fn technique_counts() {
+    use sudoku::strategy::{SolveReport, Strategy, StrategySolver, TechniqueSet};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let (_, deductions) = StrategySolver::from_sudoku(sudokus[6])
+        .solve(&TechniqueSet::ALL.strategies())
+        .unwrap();
+    let counts = deductions.technique_counts();
+    assert_eq!(counts.total(), deductions.len());
+    assert!(counts.get(Strategy::XWing) > 0);
+    for (strategy, count) in counts.iter() {
+        assert!(count > 0);
+        let n_deductions = deductions
+            .iter()
+            .filter(|deduction| deduction.strategy() == strategy)
+            .count();
+        assert_eq!(count, n_deductions);
+    }
+
+    let report = SolveReport::new(sudokus[6]);
+    assert!(report.technique_counts.get(Strategy::XWing) > 0);
+    assert_eq!(report.technique_counts.get(Strategy::MutantSwordfish), 0);
+
+    let (_, deductions) = StrategySolver::from_sudoku(sudokus[0].solution().unwrap().into())
+        .solve(&[Strategy::NakedSingles])
+        .unwrap();
+    assert!(deductions.technique_counts().is_empty());
+}
+
+#[test]
+fn solve_until_stuck() {
+    use sudoku::board::CellState;
+    use sudoku::strategy::{Strategy, StrategySolver};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let solution = sudokus[6].solution().unwrap();
+    let mut solver = StrategySolver::from_sudoku(sudokus[6]);
+    let grid_state = solver.solve_until_stuck(&[Strategy::NakedSingles, Strategy::HiddenSingles]);
+    assert!(!solver.is_solved());
+    for (cell, state) in grid_state.iter().enumerate() {
+        let digit = solution.to_bytes()[cell];
+        match *state {
+            CellState::Digit(found) => assert_eq!(found.get(), digit),
+            CellState::Candidates(candidates) => {
+                assert!(candidates.len() > 1);
+                assert!(candidates.into_iter().any(|candidate| candidate.get() == digit));
+            }
+        }
+    }
+
+    // the solver can be continued
+    let grid_state = solver.solve_until_stuck(&[
+        Strategy::NakedSingles,
+        Strategy::HiddenSingles,
+        Strategy::LockedCandidates,
+        Strategy::XWing,
+    ]);
+    assert!(solver.is_solved());
+    assert!(grid_state
+        .iter()
+        .all(|state| matches!(state, CellState::Digit(_))));
+}
+
+#[test]
+fn valid_eliminations() {
+    use sudoku::board::{Candidate, Cell, Digit};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let sudoku = sudokus[1];
+    let solution = sudoku.solution().unwrap().to_bytes();
+    for cell in Cell::all().take(18) {
+        for digit in (1..=9).map(Digit::new) {
+            let is_solution = solution[cell.as_index()] == digit.get();
+            assert_eq!(
+                sudoku.is_valid_elimination(Candidate { cell, digit }),
+                !is_solution
+            );
+        }
+    }
+
+    // with several solutions, only digits that appear in none of them can be eliminated
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    let candidate = Candidate {
+        cell: Cell::new(40),
+        digit: Digit::new(5),
+    };
+    assert!(!empty.is_valid_elimination(candidate));
+}
+
+#[test]
+fn solutions_with_candidates() {
+    use sudoku::bitset::Set;
+    use sudoku::board::{CellState, Digit};
+    use sudoku::strategy::{Strategy, StrategySolver};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let sudoku = sudokus[1];
+    let solution = sudoku.solution().unwrap().to_bytes();
+
+    // candidates of a partially solved state
+    let grid_state = StrategySolver::from_sudoku(sudoku).solve_until_stuck(&[
+        Strategy::NakedSingles,
+        Strategy::HiddenSingles,
+        Strategy::LockedCandidates,
+    ]);
+    let mut candidates = [Set::NONE; 81];
+    for (digits, state) in candidates.iter_mut().zip(grid_state.iter()) {
+        *digits = match *state {
+            CellState::Digit(digit) => digit.as_set(),
+            CellState::Candidates(digits) => digits,
+        };
+    }
+    let solutions = sudoku.solutions_up_to_with_candidates(&candidates, 2);
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].to_bytes(), solution);
+
+    // removing a true candidate makes the state unsolvable
+    let cell = sudoku.iter().position(|digit| digit.is_none()).unwrap();
+    candidates[cell] = candidates[cell].without(Digit::new(solution[cell]).as_set());
+    assert_eq!(sudoku.solutions_count_up_to_with_candidates(&candidates, 2), 0);
+
+    // a clue that isn't among its candidates
+    let clue = sudoku.iter().position(|digit| digit.is_some()).unwrap();
+    let mut candidates = [Set::ALL; 81];
+    candidates[clue] = Set::ALL.without(Digit::new(solution[clue]).as_set());
+    assert_eq!(sudoku.solutions_count_up_to_with_candidates(&candidates, 2), 0);
+    assert_eq!(
+        sudoku.solutions_count_up_to_with_candidates(&[Set::ALL; 81], 2),
+        1
+    );
+}
+
+#[test]
+fn uniqueness_strategies() {
+    use sudoku::batch::Grade;
+    use sudoku::strategy::{rating::SeRating, StrategySolver};
+
+    // needs a bivalue universal grave
+    let sudoku = Sudoku::from_str_line(
+        "..8......5..9....2...123.9...78....1.3..5..4.1....46...9.718...8....6..4......3..",
+    )
+    .unwrap();
+    let rating = StrategySolver::from_sudoku(sudoku).se_rating();
+    let unique_rating = StrategySolver::from_sudoku(sudoku)
+        .assume_unique_solution(true)
+        .se_rating();
+    // without the bivalue universal grave, a chain is needed
+    assert_eq!(rating, SeRating::Rated(6.5));
+    assert_eq!(unique_rating, SeRating::Rated(5.6));
+    assert_eq!(Grade::new(sudoku).se_rating, Some(unique_rating));
+}
+
+#[test]
+fn solvable_with_techniques() {
+    use sudoku::strategy::{Strategy, TechniqueSet};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    assert!(sudokus[0].solvable_with(&TechniqueSet::SINGLES));
+    assert!(!sudokus[6].solvable_with(&TechniqueSet::SINGLES));
+    assert!(sudokus[6].solvable_with(&TechniqueSet::ALL));
+    // chains, almost locked sets and forcing chains can replace the x-wing
+    let basic = TechniqueSet::ALL
+        .without(Strategy::ForcingChains)
+        .without(Strategy::SimpleColoring)
+        .without(Strategy::RemotePairs)
+        .without(Strategy::XyChain)
+        .without(Strategy::AlternatingInferenceChain)
+        .without(Strategy::AlsXz)
+        .without(Strategy::AlsXyWing);
+    assert!(sudokus[6].solvable_with(&basic));
+    assert!(!sudokus[6].solvable_with(&basic.without(Strategy::XWing)));
+    assert!(sudokus[6].solvable_with(&TechniqueSet::SINGLES.with(Strategy::AlternatingInferenceChain)));
+
+    let set = TechniqueSet::new(&[Strategy::XWing, Strategy::NakedSingles]);
+    assert_eq!(set.strategies(), [Strategy::NakedSingles, Strategy::XWing]);
+    assert!(!TechniqueSet::NONE.contains(Strategy::NakedSingles));
+    assert_eq!(TechniqueSet::ALL.len(), 25);
+}
+
+#[test]
+fn interrupted_solving_and_generation() {
+    use std::time::{Duration, Instant};
+    use sudoku::errors::{GenerationFailed, Interrupted};
+    use sudoku::generator::SudokuGenerator;
+    use sudoku::strategy::TechniqueSet;
+    use sudoku::CancellationToken;
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let past = Instant::now() - Duration::from_millis(1);
+    assert_eq!(
+        sudokus[0].solutions_up_to_with_deadline(2, past),
+        Err(Interrupted::DeadlineExceeded)
+    );
+    let future = Instant::now() + Duration::from_secs(60);
+    assert_eq!(
+        sudokus[0].solutions_up_to_with_deadline(2, future),
+        Ok(sudokus[0].solutions_up_to(2))
+    );
+
+    let token = CancellationToken::new();
+    let mut builder = SudokuGenerator::builder().with_cancellation(&token);
+    assert!(builder.generate().unwrap().is_uniquely_solvable());
+    let mut singles_builder = SudokuGenerator::builder()
+        .with_allowed_techniques(TechniqueSet::SINGLES)
+        .with_cancellation(&token);
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(builder.generate(), Err(GenerationFailed { n_attempts: 0 }));
+    assert_eq!(
+        singles_builder.generate(),
+        Err(GenerationFailed { n_attempts: 0 })
+    );
+    assert_eq!(
+        sudokus[0].solutions_up_to_cancellable(2, &token),
+        Err(Interrupted::Cancelled)
+    );
+}
+
+#[test]
+fn node_limited_solving() {
+    use sudoku::errors::Interrupted;
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(20) {
+        let (solutions, nodes) = sudoku.solutions_up_to_limited(2, u64::MAX).unwrap();
+        assert_eq!(solutions, sudoku.solutions_up_to(2));
+        assert!(nodes >= 1);
+
+        // the same search fits into exactly the number of nodes it used
+        assert_eq!(sudoku.solutions_up_to_limited(2, nodes), Ok((solutions, nodes)));
+        assert_eq!(
+            sudoku.solutions_up_to_limited(2, nodes - 1),
+            Err(Interrupted::NodeLimitReached)
+        );
+    }
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn svg_rendering() {
+    use sudoku::board::CellState;
+    use sudoku::strategy::StrategySolver;
+    use sudoku::svg::SvgOptions;
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let group = |svg: &str, class: &str| {
+        let start = svg.find(&format!("<g class=\"{}\"", class)).unwrap();
+        let len = svg[start..].find("</g>").unwrap();
+        svg[start..start + len].matches("<text").count()
+    };
+
+    let svg = sudoku.to_svg(&SvgOptions::new());
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+    assert!(svg.contains(r#"width="450" height="450""#));
+    assert_eq!(group(&svg, "givens"), sudoku.n_clues() as usize);
+    assert_eq!(group(&svg, "solved"), 0);
+    assert!(!svg.contains("candidates"));
+
+    let solution: Sudoku = sudoku.solution().unwrap().into();
+    let svg = solution.to_svg(&SvgOptions::new().with_givens(sudoku).with_solved_color("red"));
+    assert_eq!(group(&svg, "givens"), sudoku.n_clues() as usize);
+    assert_eq!(group(&svg, "solved"), 81 - sudoku.n_clues() as usize);
+    assert!(svg.contains(r#"<g class="solved" fill="red""#));
+
+    let svg = sudoku.to_svg(&SvgOptions::new().with_candidates(true));
+    let n_candidates = StrategySolver::from_sudoku(sudoku)
+        .grid_state()
+        .iter()
+        .map(|state| match state {
+            CellState::Candidates(candidates) => candidates.len() as usize,
+            CellState::Digit(_) => 0,
+        })
+        .sum::<usize>();
+    assert_eq!(group(&svg, "candidates"), n_candidates);
+}
+
+#[cfg(feature = "print")]
+#[test]
+fn pdf_puzzle_sheets() {
+    use sudoku::print::{write_pdf, PageLayout};
+
+    fn pdf(sudokus: &[Sudoku], layout: &PageLayout) -> String {
+        let mut pdf = vec![];
+        write_pdf(sudokus, layout, &mut pdf).unwrap();
+        // the documents only contain ascii
+        String::from_utf8(pdf).unwrap()
+    }
+
+    // every entry of the cross reference table must point to the start of its object
+    fn check_xref(pdf: &str) {
+        let start = pdf.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let xref_offset: usize = pdf[start..].lines().next().unwrap().parse().unwrap();
+        let xref = &pdf[xref_offset..];
+        assert!(xref.starts_with("xref\n"));
+        let n_entries: usize = xref.lines().nth(1).unwrap()[2..].parse().unwrap();
+        for (id, entry) in xref.lines().skip(3).take(n_entries - 1).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", id + 1)));
+        }
+        assert!(pdf.ends_with("%%EOF\n"));
+    }
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let sudokus = &sudokus[..7];
+    let n_pages = |pdf: &str| pdf.matches("/Type /Page ").count();
+
+    let with_solutions = pdf(sudokus, &PageLayout::new());
+    check_xref(&with_solutions);
+    assert_eq!(n_pages(&with_solutions), 4);
+    assert!(with_solutions.contains("(7) Tj"));
+    assert!(with_solutions.contains("(Solution 7) Tj"));
+    assert!(with_solutions.contains("/MediaBox [0 0 595 842]"));
+
+    let layout = PageLayout::new()
+        .with_grid(3, 2)
+        .with_solutions(false)
+        .with_page_size(612.0, 792.0);
+    let without_solutions = pdf(sudokus, &layout);
+    check_xref(&without_solutions);
+    assert_eq!(n_pages(&without_solutions), 2);
+    assert!(!without_solutions.contains("Solution"));
+    assert!(without_solutions.contains("/MediaBox [0 0 612 792]"));
+
+    let empty = pdf(&[], &PageLayout::new());
+    check_xref(&empty);
+    assert_eq!(n_pages(&empty), 1);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn command_line_tool() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    fn run(args: &[&str], input: &str) -> (bool, String) {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_sudoku"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+        (output.status.success(), String::from_utf8(output.stdout).unwrap())
+    }
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let sudokus = &sudokus[..3];
+    let input = sudokus
+        .iter()
+        .map(|sudoku| format!("{}\n", sudoku))
+        .collect::<String>();
+
+    let (success, output) = run(&["generate", "--count", "2", "--symmetry", "none"], "");
+    assert!(success);
+    let generated = read_sudokus(&output);
+    assert_eq!(generated.len(), 2);
+    assert!(generated.iter().all(|sudoku| sudoku.is_uniquely_solvable()));
+
+    let (success, output) = run(&["solve"], &input);
+    assert!(success);
+    let solutions = sudokus
+        .iter()
+        .map(|sudoku| sudoku.solution().unwrap().into())
+        .collect::<Vec<_>>();
+    assert_eq!(read_sudokus(&output), solutions);
+
+    let (success, output) = run(&["grade"], &input);
+    assert!(success);
+    let first_line = output.lines().next().unwrap();
+    assert_eq!(first_line, format!("{}\tEasy\t1.5", sudokus[0]));
+
+    let (success, output) = run(&["canonicalize"], &input);
+    assert!(success);
+    for (line, sudoku) in output.lines().zip(sudokus) {
+        assert_eq!(line, sudoku.canonicalized().unwrap().0.to_str_line().to_string());
+    }
+
+    let (success, blocks) = run(&["convert", "--to", "block"], &input);
+    assert!(success);
+    let (success, output) = run(&["convert", "--input", "block"], &blocks);
+    assert!(success);
+    assert_eq!(output, input);
+
+    // failures produce an empty line and a failing exit code
+    let (success, output) = run(&["solve"], &format!("not a sudoku\n{}", input));
+    assert!(!success);
+    assert_eq!(output.lines().next(), Some(""));
+    assert_eq!(output.lines().count(), 4);
+
+    assert!(!run(&["frobnicate"], "").0);
+    assert!(!run(&["generate", "--symmetry", "spiral"], "").0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn solve_report_json() {
+    use sudoku::strategy::SolveReport;
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let report = SolveReport::new(sudokus[0]);
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(json["puzzle"], sudokus[0].to_str_line().to_string());
+    assert_eq!(
+        json["solution"],
+        sudokus[0].solution().unwrap().to_str_line().to_string()
+    );
+    assert_eq!(json["n_clues"], sudokus[0].n_clues());
+    assert_eq!(json["se_rating"]["Rated"], 1.5);
+    assert!(json["technique_counts"]["HiddenSingles"].as_u64().unwrap() > 0);
+}
+
+#[cfg(feature = "oracle")]
+#[test]
+fn oracle() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sudoku::oracle;
+
+    let report = oracle::run_with_rng(100, &mut StdRng::seed_from_u64(0));
+    assert_eq!(report.n_grids, 100);
+    assert_eq!(report.divergences, vec![]);
+
+    // the reference solver itself
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    assert_eq!(
+        oracle::reference_solutions_up_to(sudoku, 3),
+        vec![sudoku.solution().unwrap().into_sudoku()]
+    );
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert_eq!(oracle::reference_solutions_up_to(empty, 3).len(), 3);
+    let mut bytes = [0; 81];
+    bytes[..2].copy_from_slice(&[4, 4]);
+    let duplicates = Sudoku::from_bytes(bytes).unwrap();
+    assert_eq!(oracle::reference_solutions_up_to(duplicates, 3), vec![]);
+    assert_eq!(oracle::check(duplicates), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_replay() {
+    use std::time::Duration;
+    use sudoku::board::{Action, Cell, Digit, Replay};
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let cell = Cell::all()
+        .find(|cell| sudoku.to_bytes()[cell.as_index()] == 0)
+        .unwrap();
+    let mut replay = Replay::new(sudoku);
+    replay.record(Duration::from_millis(1500), Action::FillNotes);
+    let digit = Digit::new(sudoku.solution().unwrap().to_bytes()[cell.as_index()]);
+    replay.record(Duration::from_millis(4200), Action::Place { cell, digit });
+    replay.record(Duration::from_millis(5000), Action::Undo);
+
+    let json = serde_json::to_string(&replay).unwrap();
+    let restored: Replay = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, replay);
+    assert_eq!(restored.game_state(), replay.game_state());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_candidates_and_solver_state() {
+    use sudoku::board::{Candidate, CellState, Digit};
+    use sudoku::strategy::StrategySolver;
+
+    let candidate = Candidate::new(80, 9);
+    let json = serde_json::to_string(&candidate).unwrap();
+    assert_eq!(json, r#"{"cell":80,"digit":9}"#);
+    assert_eq!(serde_json::from_str::<Candidate>(&json).unwrap(), candidate);
+    assert!(serde_json::from_str::<Candidate>(r#"{"cell":81,"digit":9}"#).is_err());
+    assert!(serde_json::from_str::<Digit>("0").is_err());
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let solver = StrategySolver::from_sudoku(sudokus[6]);
+    let json = serde_json::to_string(&solver).unwrap();
+    let restored: StrategySolver = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.grid_state(), solver.grid_state());
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+    // solver settings survive the round-trip and default for older snapshots
+    let solver = solver.assume_unique_solution(true).limit_forcing_chain_depth(3);
+    let json = serde_json::to_value(&solver).unwrap();
+    assert_eq!(json["assume_unique_solution"], true);
+    assert_eq!(json["forcing_chain_depth"], 3);
+    let restored: StrategySolver = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+    let mut old_json = json;
+    old_json.as_object_mut().unwrap().remove("assume_unique_solution");
+    old_json.as_object_mut().unwrap().remove("forcing_chain_depth");
+    let restored: StrategySolver = serde_json::from_value(old_json).unwrap();
+    let restored_json = serde_json::to_value(&restored).unwrap();
+    assert_eq!(restored_json["assume_unique_solution"], false);
+    assert_eq!(restored_json["forcing_chain_depth"], 1);
+
+    let json = serde_json::to_value(solver.grid_state()[0]).unwrap();
+    match solver.grid_state()[0] {
+        CellState::Digit(digit) => assert_eq!(json["Digit"], digit.get()),
+        CellState::Candidates(cands) => {
+            let expected = cands.into_iter().map(Digit::get).collect::<Vec<_>>();
+            assert_eq!(json["Candidates"], serde_json::json!(expected));
+        }
+    }
+}
+
+#[test]
+fn clue_patterns() {
+    use sudoku::board::{Cell, Pattern};
+    use sudoku::Symmetry;
+
+    for symmetry in [
+        Symmetry::QuarterRotation,
+        Symmetry::DiagonalMirror,
+        Symmetry::Dihedral,
+    ] {
+        let pattern = Sudoku::generate_with_symmetry(symmetry).pattern();
+        assert!(pattern.has_symmetry(symmetry));
+        assert!(pattern.symmetries().contains(&symmetry));
+        assert!(pattern.has_symmetry(Symmetry::None));
+    }
+
+    let sudoku = Sudoku::generate();
+    let pattern = sudoku.pattern();
+    assert_eq!(pattern.n_clues(), sudoku.n_clues());
+    assert_eq!(Pattern::from_bools(pattern.to_bools()), pattern);
+    assert!(pattern.is_subpattern_of(Sudoku::generate_solved().pattern()));
+    for (cell, digit) in Cell::all().zip(sudoku.iter()) {
+        assert_eq!(pattern.is_filled(cell), digit.is_some());
+    }
+
+    let mut bools = [false; 81];
+    bools[0] = true;
+    bools[80] = true;
+    let pattern = Pattern::from_bools(bools);
+    assert_eq!(
+        pattern.symmetries(),
+        [
+            Symmetry::DiagonalMirror,
+            Symmetry::AntidiagonalMirror,
+            Symmetry::BidiagonalMirror,
+            Symmetry::HalfRotation
+        ]
+    );
+    assert_eq!(
+        pattern.to_string(),
+        "
+X.. ... ...
+... ... ...
+... ... ...
+
+... ... ...
+... ... ...
+... ... ...
+
+... ... ...
+... ... ...
+... ... ..X"
+    );
+}
+
+#[test]
+fn puzzles_with_same_solution() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(10) {
+        let solution = sudoku.solution().unwrap();
+        let other = Sudoku::generate_from(solution.into());
+        assert!(sudoku.same_solution_as(&other));
+        assert!(other.same_solution_as(&sudoku));
+        assert_eq!(sudoku.solution_fingerprint(), other.solution_fingerprint());
+        assert_eq!(sudoku.solution_fingerprint(), solution.solution_fingerprint());
+
+        let shuffled = sudoku.shuffled();
+        assert_eq!(
+            shuffled.same_solution_as(&sudoku),
+            shuffled.solution() == sudoku.solution()
+        );
+    }
+
+    // the fingerprint must be stable across versions
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    assert_eq!(sudoku.solution_fingerprint(), Some(0xb5fdf94638fca42c));
+
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert!(!empty.same_solution_as(&empty));
+    assert_eq!(empty.solution_fingerprint(), None);
+}
+
+#[test]
+fn solved_sudoku_type() {
+    use std::convert::TryFrom;
+    use sudoku::board::Cell;
+    use sudoku::errors::NotSolved;
+    use sudoku::SolvedSudoku;
+
+    let sudoku = Sudoku::generate();
+    let solution = sudoku.solution().unwrap();
+    let bytes = solution.to_bytes();
+    for (cell, digit) in Cell::all().zip(solution.digits()) {
+        assert_eq!(solution.digit(cell), digit);
+        assert_eq!(bytes[cell.as_index()], digit.get());
+    }
+    assert_eq!(SolvedSudoku::try_from(solution.into_sudoku()), Ok(solution));
+    assert_eq!(SolvedSudoku::try_from(sudoku), Err(NotSolved));
+    assert_eq!(solution.to_string(), solution.into_sudoku().to_string());
+}
+
+#[test]
+fn solution_and_pattern() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(10) {
+        let (solution, pattern) = sudoku.solution_and_pattern().unwrap();
+        assert_eq!(solution.with_pattern(&pattern), sudoku);
+        assert_eq!(solution.with_pattern(&solution.pattern()), solution.into_sudoku());
+    }
+
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert_eq!(empty.solution_and_pattern(), None);
+    let solved = Sudoku::generate_solved();
+    assert_eq!(solved.with_pattern(&empty.pattern()), empty);
+}
+
+#[test]
+fn hexadoku_parsing() {
+    use sudoku::errors::{HexadokuBlockParseError, HexadokuInvalidEntry, HexadokuLineParseError};
+    use sudoku::hexadoku::{Hexadoku, Symbols};
+
+    // a valid filled grid: value(row, col) = (4 * (row % 4) + row / 4 + col) % 16
+    let mut bytes = [0; 256];
+    for (idx, value) in bytes.iter_mut().enumerate() {
+        let (row, col) = (idx / 16, idx % 16);
+        *value = ((4 * (row % 4) + row / 4 + col) % 16) as u8 + 1;
+    }
+    for cell in (0..256).step_by(7) {
+        bytes[cell] = 0;
+    }
+    let hexadoku = Hexadoku::from_bytes(bytes).unwrap();
+
+    for &symbols in &[Symbols::ZeroToF, Symbols::OneToG, Symbols::AToP] {
+        let line = hexadoku.to_str_line(symbols);
+        assert_eq!(Hexadoku::from_str_line(&line), Ok(hexadoku));
+        assert_eq!(Hexadoku::from_str_line(&line.to_lowercase()), Ok(hexadoku));
+        assert_eq!(
+            Hexadoku::from_str_line(&format!("{} comment", line)),
+            Ok(hexadoku)
+        );
+
+        let block = line
+            .as_bytes()
+            .chunks(16)
+            .enumerate()
+            .map(|(row, chunk)| {
+                let row_str = chunk
+                    .chunks(4)
+                    .map(|part| std::str::from_utf8(part).unwrap())
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                match row % 4 == 3 && row != 15 {
+                    true => format!("{}\n----+-----+-----+----\n", row_str),
+                    false => format!("{}\n", row_str),
+                }
+            })
+            .collect::<String>();
+        assert_eq!(Hexadoku::from_str_block(&block), Ok(hexadoku));
+    }
+
+    let line = hexadoku.to_str_line(Symbols::ZeroToF);
+    assert_eq!(
+        Hexadoku::from_str_line(&line[..100]),
+        Err(HexadokuLineParseError::NotEnoughCells(100))
+    );
+    assert_eq!(
+        Hexadoku::from_str_line(&format!("{}0", line)),
+        Err(HexadokuLineParseError::TooManyCells)
+    );
+    assert_eq!(
+        Hexadoku::from_str_line(&format!("{}x", line)),
+        Err(HexadokuLineParseError::MissingCommentDelimiter)
+    );
+    assert_eq!(
+        Hexadoku::from_str_line_with_symbols(&line, Symbols::OneToG),
+        Err(HexadokuLineParseError::InvalidEntry(HexadokuInvalidEntry {
+            cell: 40,
+            ch: '0'
+        }))
+    );
+    assert_eq!(
+        Hexadoku::from_str_block(&line[..32]),
+        Err(HexadokuBlockParseError::InvalidLineLength(0))
+    );
+    assert_eq!(
+        Hexadoku::from_str_block(&line[..16]),
+        Err(HexadokuBlockParseError::NotEnoughRows(1))
+    );
+}
+
+#[test]
+fn batch_grading() {
+    use sudoku::batch::{grade_all, Difficulty, Grade};
+
+    let mut sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    sudokus.truncate(20);
+    sudokus.push(Sudoku::from_bytes([0; 81]).unwrap());
+
+    let histogram = grade_all(&sudokus);
+    assert_eq!(histogram.len(), sudokus.len());
+    assert_eq!(
+        histogram.counts().map(|(_, count)| count).sum::<usize>(),
+        sudokus.len()
+    );
+    assert_eq!(histogram.count(Difficulty::Invalid), 1);
+    for (&sudoku, &grade) in sudokus.iter().zip(histogram.grades()) {
+        assert_eq!(grade, Grade::new(sudoku));
+    }
+
+    assert!(grade_all(&[]).is_empty());
+}
+
+#[test]
+fn estimated_solve_times() {
+    use sudoku::batch::{Difficulty, TimeCalibration};
+
+    // harder difficulties take longer
+    let ranges = Difficulty::ALL
+        .iter()
+        .filter_map(|difficulty| difficulty.estimated_time_range())
+        .collect::<Vec<_>>();
+    assert_eq!(ranges.len(), 5);
+    for pair in ranges.windows(2) {
+        assert!(pair[0].start < pair[1].start);
+        assert!(pair[0].end < pair[1].end);
+    }
+    assert!(ranges.iter().all(|range| range.start < range.end));
+    assert_eq!(TimeCalibration::default(), TimeCalibration::DEFAULT);
+}
+
+#[test]
+fn analyzed_sudokus() {
+    use sudoku::batch::{AnalyzedSudoku, Grade};
+
+    let mut sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    sudokus.truncate(10);
+    let mut unsolvable = [0; 81];
+    unsolvable[..2].copy_from_slice(&[1, 1]);
+    sudokus.push(Sudoku::from_bytes(unsolvable).unwrap());
+    sudokus.push(Sudoku::from_bytes([0; 81]).unwrap());
+
+    for sudoku in sudokus {
+        let analyzed = AnalyzedSudoku::new(sudoku);
+        assert_eq!(analyzed.sudoku(), sudoku);
+        assert_eq!(analyzed.solution(), sudoku.solution());
+        assert_eq!(analyzed.is_uniquely_solvable(), sudoku.is_uniquely_solvable());
+        assert_eq!(analyzed.is_solvable(), sudoku.some_solution().is_some());
+        assert_eq!(analyzed.grade(), Grade::new(sudoku));
+        assert_eq!(analyzed.difficulty(), analyzed.grade().difficulty);
+        assert_eq!(analyzed.se_rating(), analyzed.grade().se_rating);
+    }
+    assert!(!AnalyzedSudoku::new(Sudoku::from_bytes(unsolvable).unwrap()).is_solvable());
+}
+
+#[test]
+fn observed_solving() {
+    use sudoku::board::{Cell, Digit};
+    use sudoku::SolverObserver;
+
+    // Replays the events on a stack of grids and collects the grids that were filled completely
+    struct Replay {
+        grids: Vec<[u8; 81]>,
+        filled: Vec<Sudoku>,
+    }
+
+    impl Replay {
+        fn enter(&mut self, cell: Cell, digit: Digit) {
+            let grid = self.grids.last_mut().unwrap();
+            assert_eq!(grid[cell.as_index()], 0);
+            grid[cell.as_index()] = digit.get();
+            if !grid.contains(&0) {
+                self.filled.push(Sudoku::from_bytes(*grid).unwrap());
+            }
+        }
+    }
+
+    impl SolverObserver for Replay {
+        fn on_placement(&mut self, cell: Cell, digit: Digit) {
+            self.enter(cell, digit);
+        }
+
+        fn on_guess(&mut self, cell: Cell, digit: Digit) {
+            let grid = *self.grids.last().unwrap();
+            self.grids.push(grid);
+            self.enter(cell, digit);
+        }
+
+        fn on_backtrack(&mut self) {
+            self.grids.pop();
+            assert!(!self.grids.is_empty());
+        }
+    }
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(20) {
+        let mut replay = Replay {
+            grids: vec![sudoku.to_bytes()],
+            filled: vec![],
+        };
+        let solutions = sudoku.solutions_up_to_observed(2, &mut replay);
+        assert_eq!(solutions, sudoku.solutions_up_to(2));
+        assert_eq!(replay.grids.len(), 1);
+        assert_eq!(replay.filled, solutions);
+    }
+}
+
+#[test]
+fn contradictions() {
+    use sudoku::board::positions::{HouseType, Row};
+    use sudoku::board::{Cell, Contradiction, Digit};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for &sudoku in sudokus.iter().take(10) {
+        assert_eq!(sudoku.contradiction(), None);
+    }
+
+    // row 0 contains 1-8, the 9 is blocked by the cell below
+    let sudoku = Sudoku::from_str_line(
+        ".123456789.......................................................................",
+    )
+    .unwrap();
+    assert_eq!(
+        sudoku.contradiction(),
+        Some(Contradiction::NoCandidates(Cell::new(0)))
+    );
+
+    // the 1 can't be placed anywhere in row 0, because of the 1 below
+    let sudoku = Sudoku::from_str_line(
+        "...4567891.......................................................................",
+    )
+    .unwrap();
+    match sudoku.contradiction() {
+        Some(Contradiction::NoPosition { house, digit }) => {
+            assert_eq!(house.categorize(), HouseType::Row(Row::new(0)));
+            assert_eq!(digit, Digit::new(1));
+        }
+        other => panic!("unexpected contradiction: {:?}", other),
+    }
+
+    // wrong digits in hard sudokus are typically only found by search
+    let mut n_no_solution = 0;
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"))
+        .into_iter()
+        .take(20)
+    {
+        let solution = sudoku.solution().unwrap();
+        let mut bytes = sudoku.to_bytes();
+        let cell = bytes.iter().position(|&digit| digit == 0).unwrap();
+        bytes[cell] = solution.to_bytes()[cell] % 9 + 1;
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+
+        let contradiction = sudoku.contradiction();
+        assert!(contradiction.is_some());
+        n_no_solution += (contradiction == Some(Contradiction::NoSolution)) as usize;
+    }
+    assert!(n_no_solution > 0);
+}
+
+#[test]
+fn sorted_solutions() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(5) {
+        // remove clues from the solution as long as the sudoku has a moderate number of solutions
+        let mut bytes = sudoku.solution().unwrap().to_bytes();
+        for cell in 0..81 {
+            let digit = std::mem::replace(&mut bytes[cell], 0);
+            if Sudoku::from_bytes(bytes).unwrap().solutions_count_up_to(200) == 200 {
+                bytes[cell] = digit;
+            }
+        }
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+
+        let mut all_solutions = sudoku.solutions_up_to(200);
+        assert!(all_solutions.len() > 10);
+        all_solutions.sort();
+        assert_eq!(sudoku.sorted_solutions_up_to(200), all_solutions);
+        assert_eq!(sudoku.sorted_solutions_up_to(10), all_solutions[..10]);
+    }
+}
+
+#[test]
+fn seeded_rngs() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sudoku::Symmetry;
+
+    let solved = Sudoku::generate_solved_with_rng(&mut StdRng::seed_from_u64(7));
+    assert_eq!(
+        solved,
+        Sudoku::generate_solved_with_rng(&mut StdRng::seed_from_u64(7))
+    );
+
+    let generate = |seed| {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        Sudoku::generate_with_symmetry_and_rng_from(solved.into(), Symmetry::None, rng)
+    };
+    let sudoku = generate(42);
+    assert_eq!(sudoku, generate(42));
+    assert!(sudoku.is_uniquely_solvable());
+
+    let shuffled = sudoku.shuffled_with_rng(&mut StdRng::seed_from_u64(3));
+    assert_eq!(shuffled, sudoku.shuffled_with_rng(&mut StdRng::seed_from_u64(3)));
+    assert_eq!(shuffled.canonicalized().unwrap(), sudoku.canonicalized().unwrap());
+}
+
+#[test]
+fn generation_report() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sudoku::batch::Difficulty;
+    use sudoku::generator::SudokuGenerator;
+    use sudoku::strategy::TechniqueSet;
+    use sudoku::Symmetry;
+
+    for symmetry in [Symmetry::None, Symmetry::HalfRotation, Symmetry::QuarterRotation] {
+        let builder = || {
+            SudokuGenerator::builder()
+                .with_rng(StdRng::seed_from_u64(5))
+                .with_symmetry(symmetry)
+        };
+        let sudoku = builder().generate().unwrap();
+        let (reported, report) = builder().generate_with_report().unwrap();
+        assert_eq!(sudoku, reported);
+        assert_eq!(report.n_attempts, 1);
+        assert_eq!(report.n_clues, sudoku.n_clues() as usize);
+        assert!(report.n_removals <= report.n_removal_attempts);
+        assert!(report.n_uniqueness_checks >= report.n_removal_attempts);
+        assert!(report.n_search_nodes > 0);
+    }
+
+    // the counts cover rejected attempts and generation with allowed techniques
+    let (sudoku, report) = SudokuGenerator::builder()
+        .with_rng(StdRng::seed_from_u64(5))
+        .with_allowed_techniques(TechniqueSet::SINGLES)
+        .with_difficulty(Difficulty::Easy..=Difficulty::Easy)
+        .generate_with_report()
+        .unwrap();
+    assert!(sudoku.solvable_with(&TechniqueSet::SINGLES));
+    assert!(report.n_attempts >= 1);
+    assert!(report.n_removals <= report.n_removal_attempts);
+    assert!(report.n_uniqueness_checks > report.n_removal_attempts);
+
+    let (sudoku, report) = Sudoku::generate_unique_report();
+    assert!(sudoku.is_uniquely_solvable());
+    assert_eq!(report.n_clues, sudoku.n_clues() as usize);
+}
+
+#[test]
+fn merge_and_intersect() {
+    use sudoku::errors::ConflictError;
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/medium_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(10) {
+        let solution: Sudoku = sudoku.solution().unwrap().into();
+        let empty = Sudoku::from_bytes([0; 81]).unwrap();
+
+        assert_eq!(sudoku.merge(&solution), Ok(solution));
+        assert_eq!(sudoku.merge(&empty), Ok(sudoku));
+        assert_eq!(empty.merge(&sudoku), Ok(sudoku));
+        assert_eq!(sudoku.intersect(&solution), sudoku);
+        assert_eq!(sudoku.intersect(&empty), empty);
+        assert_eq!(sudoku.intersect(&sudoku), sudoku);
+
+        // split the clues into two halves and put them back together
+        let mut first_half = sudoku.to_bytes();
+        let mut second_half = sudoku.to_bytes();
+        for cell in 0..81 {
+            match cell % 2 {
+                0 => first_half[cell] = 0,
+                _ => second_half[cell] = 0,
+            }
+        }
+        let first_half = Sudoku::from_bytes(first_half).unwrap();
+        let second_half = Sudoku::from_bytes(second_half).unwrap();
+        assert_eq!(first_half.merge(&second_half), Ok(sudoku));
+        assert_eq!(first_half.intersect(&second_half), empty);
+
+        let clue = sudoku.iter().position(|digit| digit.is_some()).unwrap();
+        let mut conflicting = sudoku.to_bytes();
+        conflicting[clue] = conflicting[clue] % 9 + 1;
+        let conflicting = Sudoku::from_bytes(conflicting).unwrap();
+        assert_eq!(
+            sudoku.merge(&conflicting),
+            Err(ConflictError {
+                cell: clue as u8,
+                digit: sudoku.to_bytes()[clue],
+                other_digit: conflicting.to_bytes()[clue],
+            })
+        );
+        assert_eq!(sudoku.intersect(&conflicting).n_clues(), sudoku.n_clues() - 1);
+    }
+}
+
+#[test]
+fn erase_cells() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sudoku::board::Cell;
+
+    let solution = Sudoku::generate_solved().into_sudoku();
+    let rng = &mut StdRng::seed_from_u64(0);
+
+    let puzzle = solution.erase_random_cells(50, rng);
+    assert_eq!(puzzle.n_clues(), 31);
+    assert_eq!(puzzle.merge(&solution), Ok(solution));
+
+    // only clues are picked
+    let harder = puzzle.erase_random_cells(10, rng);
+    assert_eq!(harder.n_clues(), 21);
+    assert_eq!(puzzle.intersect(&harder), harder);
+    assert_eq!(harder.erase_random_cells(100, rng).n_clues(), 0);
+
+    let erased = Cell::all().filter(|cell| cell.get() % 3 == 0).collect::<Vec<_>>();
+    let puzzle = solution.erase_cells(&erased);
+    assert_eq!(puzzle.n_clues(), 54);
+    for (cell, digit) in Cell::all().zip(puzzle.iter()) {
+        assert_eq!(digit.is_none(), erased.contains(&cell));
+    }
+}
+
+#[test]
+fn dedup_puzzles() {
+    use std::collections::HashSet;
+    use sudoku::batch::{dedup, dedup_with, DedupStats};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let sudokus = &sudokus[..40];
+    let mut input = String::new();
+    for sudoku in sudokus {
+        input += &format!("{}\n", sudoku);
+    }
+    let unique_output = input.clone();
+    for sudoku in sudokus.iter().rev() {
+        input += &format!("{};shuffled\n\n", sudoku.shuffled());
+    }
+    input += "not a sudoku\n";
+    input += &format!("{}\n", Sudoku::from_bytes([0; 81]).unwrap());
+
+    let expected_stats = DedupStats {
+        n_read: 82,
+        n_unique: 40,
+        n_duplicates: 40,
+        n_invalid: 2,
+    };
+    let mut output = vec![];
+    assert_eq!(dedup(input.as_bytes(), &mut output).unwrap(), expected_stats);
+    assert_eq!(String::from_utf8(output).unwrap(), unique_output);
+
+    #[cfg(feature = "bloom")]
+    {
+        use sudoku::batch::BloomFilter;
+
+        let mut output = vec![];
+        let mut filter = BloomFilter::new(1000, 0.001);
+        assert!(filter.size_in_bytes() < 2000);
+        assert_eq!(
+            dedup_with(input.as_bytes(), &mut output, &mut filter).unwrap(),
+            expected_stats
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), unique_output);
+    }
+
+    // a shared set of fingerprints finds duplicates across inputs
+    let mut seen = HashSet::new();
+    let first = dedup_with(unique_output.as_bytes(), std::io::sink(), &mut seen).unwrap();
+    let second = dedup_with(input.as_bytes(), std::io::sink(), &mut seen).unwrap();
+    assert_eq!(first.n_unique, 40);
+    assert_eq!(second.n_unique, 0);
+    assert_eq!(second.n_duplicates, 80);
+}
+
+#[test]
+fn dimacs_export() {
+    use sudoku::board::{Candidate, Constraints};
+    use sudoku::errors::FromSatModelError;
+
+    fn clauses(cnf: &str) -> Vec<Vec<i32>> {
+        let mut lines = cnf.lines().filter(|line| !line.starts_with('c'));
+        let header = lines.next().unwrap().split(' ').collect::<Vec<_>>();
+        assert_eq!(header[..3], ["p", "cnf", "729"]);
+        let clauses = lines
+            .map(|line| {
+                let literals = line
+                    .split(' ')
+                    .map(|literal| literal.parse().unwrap())
+                    .collect::<Vec<i32>>();
+                assert_eq!(literals.last(), Some(&0));
+                literals[..literals.len() - 1].to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(clauses.len(), header[3].parse::<usize>().unwrap());
+        clauses
+    }
+
+    fn model(grid: Sudoku) -> Vec<i32> {
+        let bytes = grid.to_bytes();
+        (1..=729)
+            .map(|variable| {
+                let candidate = Candidate::from_sat_variable(variable).unwrap();
+                match bytes[candidate.cell.as_index()] == candidate.digit.get() {
+                    true => variable as i32,
+                    false => -(variable as i32),
+                }
+            })
+            .collect()
+    }
+
+    fn satisfies(model: &[i32], clauses: &[Vec<i32>]) -> bool {
+        clauses
+            .iter()
+            .all(|clause| clause.iter().any(|literal| model.contains(literal)))
+    }
+
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let solution = sudoku.solution().unwrap().into_sudoku();
+    let cnf = clauses(&sudoku.to_dimacs());
+    assert!(satisfies(&model(solution), &cnf));
+    assert_eq!(Sudoku::from_sat_model(&model(solution)), Ok(solution));
+    // solvers terminate the model with a 0
+    let mut terminated = model(solution);
+    terminated.push(0);
+    assert_eq!(Sudoku::from_sat_model(&terminated), Ok(solution));
+
+    // a different solved grid violates the clues
+    let other = Sudoku::generate_solved().into_sudoku();
+    assert_eq!(satisfies(&model(other), &cnf), other == solution);
+
+    // a latin square that isn't a sudoku
+    let latin = Sudoku::from_bytes([0; 81])
+        .unwrap()
+        .solutions_up_to_with_constraints(Constraints::LATIN_SQUARE, 100)
+        .into_iter()
+        .find(|grid| !grid.is_solved())
+        .unwrap();
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    assert!(satisfies(
+        &model(latin),
+        &clauses(&empty.to_dimacs_with_constraints(Constraints::LATIN_SQUARE))
+    ));
+    assert!(!satisfies(&model(latin), &clauses(&empty.to_dimacs())));
+
+    for variable in 1..=729 {
+        let candidate = Candidate::from_sat_variable(variable).unwrap();
+        assert_eq!(candidate.sat_variable(), variable);
+    }
+    assert_eq!(Candidate::from_sat_variable(0), None);
+    assert_eq!(Candidate::from_sat_variable(730), None);
+
+    assert_eq!(
+        Sudoku::from_sat_model(&[730]),
+        Err(FromSatModelError::UnknownVariable(730))
+    );
+    assert_eq!(
+        Sudoku::from_sat_model(&[1, 2]),
+        Err(FromSatModelError::MultipleDigits(0))
+    );
+    assert_eq!(
+        Sudoku::from_sat_model(&[1, -10]),
+        Err(FromSatModelError::NoDigit(1))
+    );
+}
+
+#[test]
+fn convert_formats() {
+    use sudoku::batch::{convert, ConvertStats, Format};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let sudokus = &sudokus[..20];
+    let mut lines = String::new();
+    for sudoku in sudokus {
+        lines += &format!("{}\n", sudoku);
+    }
+
+    let formats = [Format::Line, Format::Block, Format::Sdk, Format::Packed];
+    for &from in &formats {
+        let mut converted = vec![];
+        convert(lines.as_bytes(), Format::Line, &mut converted, from).unwrap();
+        for &to in &formats {
+            let mut output = vec![];
+            let stats = convert(&converted[..], from, &mut output, to).unwrap();
+            assert_eq!(
+                stats,
+                ConvertStats {
+                    n_converted: 20,
+                    n_invalid: 0
+                }
+            );
+            let mut back = vec![];
+            convert(&output[..], to, &mut back, Format::Line).unwrap();
+            assert_eq!(String::from_utf8(back).unwrap(), lines);
+        }
+    }
+
+    let sdk = "#A author\n#D description\n..3.2.6..\n9..3.5..1\n..18.64..\n..81.29..\n7.......8\n..67.82..\n..26.95..\n8..2.3..9\n..5.1.3..\n";
+    let mut output = vec![];
+    convert(sdk.as_bytes(), Format::Sdk, &mut output, Format::Line).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..\n"
+    );
+
+    // invalid sudokus are skipped
+    let input = format!("not a sudoku\n{}\n", sudokus[0]);
+    let mut output = vec![];
+    let stats = convert(input.as_bytes(), Format::Line, &mut output, Format::Packed).unwrap();
+    assert_eq!((stats.n_converted, stats.n_invalid), (1, 1));
+    output.push(0);
+    let stats = convert(&output[..], Format::Packed, std::io::sink(), Format::Line).unwrap();
+    assert_eq!((stats.n_converted, stats.n_invalid), (1, 1));
+
+    // a malformed block doesn't affect the following ones
+    for &from in &[Format::Block, Format::Sdk] {
+        let mut blocks = vec![];
+        convert(lines.as_bytes(), Format::Line, &mut blocks, from).unwrap();
+        let mut blocks = String::from_utf8(blocks)
+            .unwrap()
+            .split_inclusive('\n')
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        // drop the last row of the third sudoku
+        let last_row = match from {
+            Format::Block => 3 * 12 - 2,
+            _ => 3 * 10 - 2,
+        };
+        blocks.remove(last_row);
+        let input = blocks.concat();
+        let mut output = vec![];
+        let stats = convert(input.as_bytes(), from, &mut output, Format::Line).unwrap();
+        assert_eq!((stats.n_converted, stats.n_invalid), (19, 1));
+        let expected = lines
+            .lines()
+            .enumerate()
+            .filter(|&(idx, _)| idx != 2)
+            .map(|(_, line)| format!("{}\n", line))
+            .collect::<String>();
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+}
+
+#[test]
+fn puzzle_records() {
+    use sudoku::batch::{convert, read_records, Format, PuzzleRecord};
+
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let mut lines = String::new();
+    for (idx, sudoku) in sudokus[..10].iter().enumerate() {
+        match idx % 3 {
+            0 => lines += &format!("{}\n", sudoku),
+            1 => lines += &format!("{}\t#{} rated 1.2\n", sudoku, idx),
+            _ => lines += &format!("{};#{}\r\n\n", sudoku, idx),
+        }
+    }
+    let records = read_records(lines.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 10);
+    for (idx, record) in records.iter().enumerate() {
+        assert_eq!(record.sudoku, sudokus[idx]);
+        let comment = match idx % 3 {
+            0 => String::new(),
+            1 => format!("#{} rated 1.2", idx),
+            _ => format!("#{}", idx),
+        };
+        assert_eq!(record.comment, comment);
+        assert_eq!(PuzzleRecord::from_line(&record.to_string()).as_ref(), Ok(record));
+    }
+
+    // comments survive a conversion between line format files
+    let mut output = vec![];
+    convert(lines.as_bytes(), Format::Line, &mut output, Format::Line).unwrap();
+    let converted = read_records(&output[..]).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(converted, records);
+
+    let error = read_records("not a sudoku".as_bytes())
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn sampled_solutions() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let solution = Sudoku::generate_solved_with_rng(rng).into_sudoku();
+
+    // few solutions, all of them are returned
+    let mut puzzle = solution;
+    for cell in sudoku::board::Cell::all() {
+        let erased = puzzle.erase_cells(&[cell]);
+        if erased.solutions_count_up_to(100) < 100 {
+            puzzle = erased;
+        }
+    }
+    let mut all_solutions = puzzle.solutions_up_to(1000);
+    assert!(all_solutions.len() > 1 && all_solutions.len() < 100);
+    let mut samples = puzzle.sample_solutions(100, rng);
+    samples.sort();
+    all_solutions.sort();
+    assert_eq!(samples, all_solutions);
+
+    // many solutions
+    let puzzle = solution.erase_random_cells(70, rng);
+    let samples = puzzle.sample_solutions(50, rng);
+    assert_eq!(samples.len(), 50);
+    assert_eq!(samples.iter().collect::<HashSet<_>>().len(), 50);
+    for sample in samples {
+        assert!(sample.is_solved());
+        assert_eq!(puzzle.merge(&sample), Ok(sample));
+    }
+
+    let unsolvable = Sudoku::from_str_line(
+        ".123456789.......................................................................",
+    )
+    .unwrap();
+    assert_eq!(unsolvable.sample_solutions(3, rng), vec![]);
+}
+
+#[test]
+fn fuzz_entry_points() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use sudoku::fuzz;
+
+    let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    let sudoku = Sudoku::from_str_line(line).unwrap();
+    assert_eq!(fuzz::parse_any(line.as_bytes()), Some(sudoku));
+    assert_eq!(
+        fuzz::parse_any(sudoku.display_block().to_string().as_bytes()),
+        Some(sudoku)
+    );
+    assert_eq!(fuzz::parse_and_solve_bounded(line.as_bytes()).unwrap().len(), 1);
+    // the search stops after the second solution
+    assert_eq!(fuzz::parse_and_solve_bounded(&[b'.'; 81]).unwrap().len(), 2);
+
+    // invalid UTF-8 and multi-byte characters in place of cells and comments
+    let tricky = [
+        vec![0xff, 0xfe, b'1'],
+        "é".repeat(81).into_bytes(),
+        format!("{} ü€𝄞", line).into_bytes(),
+        format!("{}\t\u{0300}", &line[..80]).into_bytes(),
+        "日本\n".repeat(9).into_bytes(),
+        vec![],
+    ];
+    for data in &tricky {
+        fuzz::parse_and_solve_bounded(data);
+        fuzz::parse_hexadoku(data);
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let alphabet = [
+        '.', '0', '1', '9', '_', '|', '-', '+', ' ', '\n', '\t', 'é', '€', '𝄞', 'A', 'G',
+    ];
+    for _ in 0..2000 {
+        let len = rng.gen_range(0..100);
+        let mut data = (0..len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+            .collect::<String>()
+            .into_bytes();
+        if rng.gen_bool(0.5) && !data.is_empty() {
+            // mutate a valid line
+            data = line.as_bytes().to_vec();
+            let idx = rng.gen_range(0..data.len());
+            data[idx] = rng.gen();
+        }
+        fuzz::parse_and_solve_bounded(&data);
+        fuzz::parse_hexadoku(&data);
+    }
+}
+
+#[test]
+fn str_round_trips() {
+    let sudokus = [
+        Sudoku::from_bytes([0; 81]).unwrap(),
+        Sudoku::generate_solved().into_sudoku(),
+        Sudoku::generate(),
+    ];
+    for sudoku in sudokus {
+        let line = sudoku.to_str_line();
+        assert_eq!(Sudoku::from_str_line(&line), Ok(sudoku));
+        for delimited in [false, true] {
+            let block = sudoku.to_str_block(delimited);
+            assert_eq!(Sudoku::from_str_block(&block), Ok(sudoku));
+            assert_eq!(Sudoku::from_str_block_permissive(&block), Ok(sudoku));
+        }
+        let block = sudoku.display_block().delimited().to_string();
+        assert_eq!(block, *sudoku.to_str_block(true));
+    }
+    assert_eq!(sudokus[0].to_str_block(false).lines().count(), 9);
+    assert_eq!(sudokus[0].to_str_block(true).lines().count(), 11);
+}
+
+#[test]
+fn parse_flexible() {
+    let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    let sudoku = Sudoku::from_str_line(line).unwrap();
+    for width in [1, 9, 40, 60] {
+        let wrapped = line
+            .as_bytes()
+            .chunks(width)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        assert_eq!(Sudoku::from_str_flexible(&wrapped), Ok(sudoku));
+    }
+    assert_eq!(Sudoku::from_str_flexible(&format!("\t {} \n", line)), Ok(sudoku));
+    assert_eq!(
+        Sudoku::from_str_flexible(&line[..80]),
+        Err(LineParseError::NotEnoughCells(80))
+    );
+    assert_eq!(
+        Sudoku::from_str_flexible(&format!("{}\n1", line)),
+        Err(LineParseError::TooManyCells)
+    );
+    assert_eq!(
+        Sudoku::from_str_flexible(&format!("x {}", &line[1..])),
+        Err(LineParseError::InvalidEntry(InvalidEntry { cell: 0, ch: 'x' }))
+    );
+}
+
+#[test]
+fn parse_all() {
+    use sudoku::errors::{BlockParseError, ParseAllError};
+
+    let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    let sudoku = Sudoku::from_str_line(line).unwrap();
+    let input = format!(
+        "{line}\n{line} comment\n\n{bare}\n{delimited}\n\n  {line}\r\n{line}",
+        line = line,
+        bare = sudoku.to_str_block(false),
+        delimited = sudoku.to_str_block(true),
+    );
+    let sudokus = Sudoku::parse_all(&input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(sudokus, vec![sudoku; 6]);
+
+    // errors don't stop parsing
+    let input = format!("{}x\n___|\n\n{}\n123\n", line, line);
+    let results = Sudoku::parse_all(&input).collect::<Vec<_>>();
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Err(ParseAllError::Line(0, _))));
+    assert!(matches!(
+        results[1],
+        Err(ParseAllError::Block(1, BlockParseError::InvalidLineLength(0)))
+    ));
+    assert_eq!(results[2], Ok(sudoku));
+    assert!(matches!(results[3], Err(ParseAllError::Block(4, _))));
+
+    assert_eq!(Sudoku::parse_all(" \n\n").count(), 0);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_round_trip() {
+    use sudoku::batch::csv::{CsvReader, CsvRecord, CsvWriter};
+    use sudoku::errors::CsvError;
+
+    let puzzle = Sudoku::generate();
+    let record = CsvRecord {
+        puzzle,
+        solution: puzzle.solution(),
+        difficulty: Some(1.5),
+        extra: vec!["a, \"quoted\" comment".to_string()],
+    };
+    let unsolved = CsvRecord {
+        solution: None,
+        difficulty: None,
+        ..record.clone()
+    };
+
+    let mut output = vec![];
+    let mut writer = CsvWriter::new(&mut output, &["comment"]).unwrap();
+    writer.write(&record).unwrap();
+    writer.write(&unsolved).unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    let reader = CsvReader::new(&output[..]).unwrap();
+    assert_eq!(reader.extra_headers(), ["comment"]);
+    let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(records, vec![record, unsolved]);
+
+    // the layout of the 1 million sudoku dataset on Kaggle
+    let solution = puzzle.solution().unwrap().to_str_line().replace('.', "0");
+    let input = format!(
+        "quizzes,solutions\n{},{}\n",
+        puzzle.to_str_line().replace('.', "0"),
+        solution
+    );
+    let records = CsvReader::new(input.as_bytes())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records[0].puzzle, puzzle);
+    assert_eq!(records[0].solution, puzzle.solution());
+
+    assert!(matches!(
+        CsvReader::new("id,solution\n".as_bytes()),
+        Err(CsvError::MissingPuzzleColumn)
+    ));
+    let input = format!(
+        "puzzle,solution,difficulty\n{},,x\n{},{},\n",
+        puzzle, puzzle, puzzle
+    );
+    let results = CsvReader::new(input.as_bytes()).unwrap().collect::<Vec<_>>();
+    assert!(matches!(results[0], Err(CsvError::InvalidDifficulty(2))));
+    assert!(matches!(results[1], Err(CsvError::InvalidSolution(3))));
+}
+
+#[test]
+fn puzzle_database() {
+    use std::io::{Cursor, ErrorKind};
+    use sudoku::batch::database::{Database, DatabaseEntry};
+
+    let path = std::env::temp_dir().join(format!("sudoku-test-{}.sdb", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let sudokus = (0..20).map(|_| Sudoku::generate()).collect::<Vec<_>>();
+    let entries = sudokus
+        .iter()
+        .enumerate()
+        .map(|(idx, &sudoku)| match idx % 2 {
+            0 => DatabaseEntry::new(sudoku),
+            _ => DatabaseEntry::analyzed(sudoku),
+        })
+        .collect::<Vec<_>>();
+
+    let mut database = Database::open(&path).unwrap();
+    assert!(database.is_empty());
+    for (idx, entry) in entries[..10].iter().enumerate() {
+        assert_eq!(database.append(entry).unwrap(), idx);
+    }
+    drop(database);
+    let mut database = Database::open(&path).unwrap();
+    for entry in &entries[10..] {
+        database.append(entry).unwrap();
+    }
+
+    assert_eq!(database.len(), 20);
+    assert_eq!(database.get(13).unwrap(), Some(entries[13]));
+    assert_eq!(database.get(20).unwrap(), None);
+    let read = database.iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(read, entries);
+    for (idx, entry) in entries.iter().enumerate() {
+        let fingerprint = entry.sudoku.canonical_fingerprint().unwrap();
+        let found = database.get_by_fingerprint(fingerprint).unwrap();
+        match idx % 2 {
+            0 => assert_eq!(found, None),
+            _ => assert_eq!(found, Some(*entry)),
+        }
+    }
+    drop(database);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut database = Database::new(Cursor::new(vec![])).unwrap();
+    database.append(&entries[1]).unwrap();
+    let bytes = database.into_inner().into_inner();
+    let open = |bytes: &[u8]| Database::new(Cursor::new(bytes.to_vec())).map(|database| database.len());
+    assert_eq!(open(&bytes).unwrap(), 1);
+    assert_eq!(open(&bytes[..bytes.len() - 1]).unwrap(), 0);
+
+    // an interrupted append is overwritten by the next one, even by a shorter entry
+    let mut truncated = bytes.clone();
+    truncated.extend_from_slice(&bytes[4..bytes.len() - 1]);
+    let mut database = Database::new(Cursor::new(truncated)).unwrap();
+    assert_eq!(database.len(), 1);
+    assert_eq!(database.append(&entries[0]).unwrap(), 1);
+    let mut database = Database::new(database.into_inner()).unwrap();
+    assert_eq!(database.append(&entries[2]).unwrap(), 2);
+    let read = database.iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(read, [entries[1], entries[0], entries[2]]);
+    let mut database = Database::new(database.into_inner()).unwrap();
+    assert_eq!(database.get(2).unwrap(), Some(entries[2]));
+
+    assert_eq!(
+        open(b"not a database").unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    let mut corrupted = bytes.clone();
+    corrupted[5] = 0xFF;
+    assert_eq!(open(&corrupted).unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn memory_mapped_puzzles() {
+    use sudoku::batch::database::{Database, DatabaseEntry};
+    use sudoku::batch::mmap::{MappedDatabase, MappedPuzzles};
+
+    let dir = std::env::temp_dir();
+    let records_path = dir.join(format!("sudoku-test-mmap-{}.bin", std::process::id()));
+    let database_path = dir.join(format!("sudoku-test-mmap-{}.sdb", std::process::id()));
+    let _ = std::fs::remove_file(&database_path);
+
+    let sudokus = (0..10).map(|_| Sudoku::generate()).collect::<Vec<_>>();
+    let bytes = sudokus
+        .iter()
+        .flat_map(|sudoku| sudoku.to_bytes())
+        .collect::<Vec<_>>();
+    std::fs::write(&records_path, &bytes).unwrap();
+    let mut database = Database::open(&database_path).unwrap();
+    for &sudoku in &sudokus {
+        database.append(&DatabaseEntry::analyzed(sudoku)).unwrap();
+    }
+    drop(database);
+
+    // SAFETY: the files are only modified after they are unmapped
+    let puzzles = unsafe { MappedPuzzles::open(&records_path) }.unwrap();
+    assert_eq!(puzzles.len(), 10);
+    assert_eq!(puzzles.records()[3], sudokus[3].to_bytes());
+    assert_eq!(puzzles.get(9), Some(sudokus[9]));
+    assert_eq!(puzzles.get(10), None);
+
+    let mapped = unsafe { MappedDatabase::open(&database_path) }.unwrap();
+    let read = Database::open(&database_path)
+        .unwrap()
+        .iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(mapped.iter().collect::<Vec<_>>(), read);
+    assert_eq!(mapped.get(4), Some(read[4]));
+    let fingerprint = sudokus[7].shuffled().canonical_fingerprint().unwrap();
+    assert_eq!(mapped.get_by_fingerprint(fingerprint), Some(read[7]));
+    drop((puzzles, mapped));
+
+    std::fs::write(&records_path, &bytes[..80]).unwrap();
+    assert!(unsafe { MappedPuzzles::open(&records_path) }.is_err());
+    assert!(unsafe { MappedDatabase::open(&records_path) }.is_err());
+    std::fs::remove_file(&records_path).unwrap();
+    std::fs::remove_file(&database_path).unwrap();
+}
+
+#[test]
+fn puzzle_pool() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+    use sudoku::batch::{pool::PuzzlePool, Difficulty};
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let generate = |rng: &mut StdRng| {
+        let solution = Sudoku::generate_solved_with_rng(rng).into_sudoku();
+        Sudoku::generate_with_symmetry_and_rng_from(solution, sudoku::Symmetry::None, rng)
+    };
+    let easy = (0..10).map(|_| generate(rng)).collect::<HashSet<_>>();
+    let hard = (0..10).map(|_| generate(rng)).collect::<HashSet<_>>();
+
+    let mut pool = PuzzlePool::new(0);
+    for &sudoku in &easy {
+        assert!(pool.insert(sudoku, Difficulty::Easy));
+    }
+    for &sudoku in &hard {
+        assert!(pool.insert(sudoku, Difficulty::Hard));
+    }
+    let sudoku = *easy.iter().next().unwrap();
+    assert!(!pool.insert(sudoku.shuffled(), Difficulty::Easy));
+    assert!(!pool.insert(Sudoku::from_bytes([0; 81]).unwrap(), Difficulty::Easy));
+    assert_eq!((pool.len(), pool.count(Difficulty::Easy)), (20, 10));
+
+    let weights = [
+        (Difficulty::Easy, 3.0),
+        (Difficulty::Hard, 1.0),
+        (Difficulty::Expert, 5.0),
+    ];
+    let n_easy = (0..4000)
+        .filter(|_| easy.contains(&pool.pick(&weights, rng).unwrap()))
+        .count();
+    assert!((2800..3200).contains(&n_easy), "{}", n_easy);
+    assert_eq!(pool.pick(&[(Difficulty::Expert, 1.0)], rng), None);
+    assert_eq!(
+        pool.pick(&[(Difficulty::Easy, 0.0), (Difficulty::Hard, f64::NAN)], rng),
+        None
+    );
+    // weights that add up to more than f64::MAX
+    let huge = [(Difficulty::Easy, f64::MAX), (Difficulty::Hard, f64::MAX)];
+    assert!((0..100).all(|_| pool.pick(&huge, rng).is_some()));
+
+    // every puzzle is served once before any is repeated
+    let mut pool = PuzzlePool::new(15);
+    for &sudoku in &easy {
+        pool.insert(sudoku, Difficulty::Easy);
+    }
+    for &sudoku in &hard {
+        pool.insert(sudoku, Difficulty::Hard);
+    }
+    let weights = [(Difficulty::Easy, 1.0), (Difficulty::Hard, 1.0)];
+    let mut served = vec![];
+    for _ in 0..100 {
+        served.push(pool.pick(&weights, rng).unwrap());
+        let recent = &served[served.len().saturating_sub(16)..];
+        assert_eq!(recent.iter().collect::<HashSet<_>>().len(), recent.len());
+    }
+    // only the easy puzzles are left, once all hard ones are recent
+    let mut pool = PuzzlePool::new(10);
+    for &sudoku in &hard {
+        pool.insert(sudoku, Difficulty::Hard);
+    }
+    let served = (0..10)
+        .map(|_| pool.pick(&weights, rng).unwrap())
+        .collect::<HashSet<_>>();
+    assert_eq!(served, hard);
+    assert_eq!(pool.pick(&weights, rng), None);
+}
+
+#[cfg(feature = "service")]
+#[test]
+fn service_handlers() {
+    use sudoku::batch::Difficulty;
+    use sudoku::errors::ServiceError;
+    use sudoku::service::{self, GenerateRequest, SolveRequest, SolveStatus};
+
+    let request: GenerateRequest = serde_json::from_str(r#"{"difficulty":"Easy","seed":7}"#).unwrap();
+    let response = service::generate(&request).unwrap();
+    assert_eq!(response.difficulty, Difficulty::Easy);
+    assert_eq!(response.puzzle.solution(), Some(response.solution));
+    assert_eq!(service::generate(&request).unwrap(), response);
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["puzzle"], response.puzzle.to_str_line().to_string());
+    let request = GenerateRequest {
+        difficulty: Some(Difficulty::Invalid),
+        seed: None,
+    };
+    assert_eq!(service::generate(&request), Err(ServiceError::GenerationFailed));
+
+    // without the 3 in row 6, column 2
+    let ambiguous = "...2...633....54.1..1..398........9....538.............263..5..5.37....847...1...";
+    let request = SolveRequest {
+        puzzle: ambiguous.to_string(),
+        max_solutions: Some(10),
+    };
+    let response = service::solve(&request).unwrap();
+    assert_eq!(response.status, SolveStatus::Multiple);
+    assert_eq!(response.solutions.len(), 2);
+    let request = SolveRequest {
+        puzzle: ambiguous.to_string(),
+        max_solutions: None,
+    };
+    let response = service::solve(&request).unwrap();
+    assert_eq!(
+        (response.status, response.solutions.len()),
+        (SolveStatus::Multiple, 1)
+    );
+    let request = SolveRequest {
+        puzzle: Sudoku::from_str_line(ambiguous)
+            .unwrap()
+            .to_str_block(true)
+            .to_string(),
+        max_solutions: None,
+    };
+    assert_eq!(service::solve(&request).unwrap().status, SolveStatus::Multiple);
+    let request = SolveRequest {
+        puzzle: ambiguous.replacen('.', "1", 1),
+        max_solutions: None,
+    };
+    let response = service::solve(&request).unwrap();
+    assert_eq!(
+        (response.status, response.solutions.len()),
+        (SolveStatus::Unsolvable, 0)
+    );
+
+    let request = SolveRequest {
+        puzzle: "..x".to_string(),
+        max_solutions: None,
+    };
+    let err = service::solve(&request).unwrap_err();
+    assert_eq!(err.http_status(), 400);
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["error"], "invalid_puzzle");
+    let request = SolveRequest {
+        puzzle: ambiguous.to_string(),
+        max_solutions: Some(0),
+    };
+    assert_eq!(
+        service::solve(&request),
+        Err(ServiceError::InvalidMaxSolutions { max_solutions: 0 })
+    );
+}
+
+#[test]
+fn pregeneration_cache() {
+    use std::time::{Duration, Instant};
+    use sudoku::batch::{Difficulty, Grade};
+    use sudoku::generator::PregenCache;
+
+    let cache = PregenCache::new(&[Difficulty::Easy], 2, 1.0);
+    let wait_until_full = || {
+        let start = Instant::now();
+        while cache.n_ready(Difficulty::Easy) < 2 {
+            assert!(start.elapsed() < Duration::from_secs(60), "cache wasn't filled");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    };
+    wait_until_full();
+    for _ in 0..2 {
+        let sudoku = cache.take(Difficulty::Easy).unwrap();
+        assert_eq!(Grade::new(sudoku).difficulty, Difficulty::Easy);
+    }
+    assert_eq!(cache.take(Difficulty::Medium), None);
+    let medium = cache.take_or_generate(Difficulty::Medium).unwrap();
+    assert_eq!(Grade::new(medium).difficulty, Difficulty::Medium);
+    // refilled after taking
+    wait_until_full();
+    assert_eq!(cache.n_ready(Difficulty::Easy), 2);
+    drop(cache);
+}
+
+#[test]
+fn check_eliminations() {
+    use sudoku::board::Candidate;
+    use sudoku::errors::NoUniqueSolution;
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let solution = sudoku.solution().unwrap().to_bytes();
+    // eliminate every wrong digit in every cell
+    let sound = (0..81u8)
+        .flat_map(|cell| (1..=9).map(move |digit| Candidate::new(cell, digit)))
+        .filter(|candidate| solution[candidate.cell.as_index()] != candidate.digit.get())
+        .collect::<Vec<_>>();
+    assert_eq!(sudoku.check_eliminations(&sound), Ok(None));
+    assert_eq!(sudoku.check_eliminations(&[]), Ok(None));
+
+    let mut eliminations = sound.clone();
+    let unsound = Candidate::new(40, solution[40]);
+    eliminations.insert(100, unsound);
+    eliminations.push(Candidate::new(0, solution[0]));
+    assert_eq!(sudoku.check_eliminations(&eliminations), Ok(Some(unsound)));
+
+    let ambiguous = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538.............263..5..5.37....847...1...",
+    )
+    .unwrap();
+    assert_eq!(ambiguous.check_eliminations(&sound), Err(NoUniqueSolution));
+}
+
+#[cfg(feature = "ansi")]
+#[test]
+fn colored_display() {
+    use sudoku::ansi::{Color, Highlight};
+    use sudoku::board::{Candidate, Cell};
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let plain = sudoku.display_block_colored(&[]).to_string();
+    assert!(!plain.contains('\x1b'));
+    let lines = plain.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 35);
+    assert_eq!(lines[11], "------------+-------------+------------");
+    // r1c1 has the candidates 7, 8 and 9, r1c4 contains the clue 2
+    assert_eq!(&lines[0][..3], "   ");
+    assert_eq!(&lines[1][..16], "    45  45  |  2");
+    assert_eq!(&lines[2][..3], "789");
+
+    let highlights = [
+        Highlight::Cell(Cell::new(3), Color::Red),
+        Highlight::Cell(Cell::new(3), Color::Green),
+        Highlight::Candidate(Candidate::new(3, 2), Color::Yellow),
+        // not a candidate, but shown because it is highlighted
+        Highlight::Candidate(Candidate::new(0, 1), Color::Blue),
+    ];
+    let colored = sudoku.display_block_colored(&highlights).to_string();
+    let lines = colored.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 35);
+    assert!(lines[0].starts_with("\x1b[1;34m1\x1b[22;39m  "));
+    assert!(lines[1].contains(" \x1b[42m \x1b[1;33m2\x1b[22;39m \x1b[49m "));
+    assert!(!colored.contains("\x1b[41m"));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn terminal_trainer() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use sudoku::board::{Cell, Digit, GameState};
+    use sudoku::tui::Trainer;
+
+    let sudoku = Sudoku::from_str_line(
+        "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    )
+    .unwrap();
+    let solution = sudoku.solution().unwrap().to_bytes();
+    let mut trainer = Trainer::new(GameState::new(sudoku).unwrap());
+    let press = |trainer: &mut Trainer, code| trainer.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    let mut terminal = Terminal::new(TestBackend::new(100, 35)).unwrap();
+    let mut screen = |trainer: &Trainer| {
+        terminal.draw(|frame| trainer.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..35)
+            .map(|y| {
+                (0..100)
+                    .map(|x| buffer.get(x, y).symbol.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // same layout as the colored display
+    press(&mut trainer, KeyCode::Char('f'));
+    let lines = screen(&trainer);
+    assert!(lines[1].starts_with("    45  45  |  2"));
+    assert!(lines[11].starts_with("------------+-------------+------------"));
+
+    press(&mut trainer, KeyCode::Char('h'));
+    assert!(screen(&trainer)[3].contains("Hint: "));
+
+    // a wrong entry in r1c2
+    press(&mut trainer, KeyCode::Right);
+    press(&mut trainer, KeyCode::Char((b'0' + solution[1] % 9 + 1) as char));
+    press(&mut trainer, KeyCode::Char('c'));
+    assert!(screen(&trainer)[3].contains("1 incorrect entries"));
+    press(&mut trainer, KeyCode::Backspace);
+    assert_eq!(trainer.game().entry(Cell::new(1)), None);
+
+    // clues can't be changed
+    press(&mut trainer, KeyCode::Right);
+    press(&mut trainer, KeyCode::Right);
+    press(&mut trainer, KeyCode::Char('1'));
+    assert!(screen(&trainer)[3].contains("r1c4 contains a clue"));
+
+    // notes are toggled in notes mode
+    press(&mut trainer, KeyCode::Left);
+    let notes = trainer.game().notes(Cell::new(2));
+    press(&mut trainer, KeyCode::Char('n'));
+    press(&mut trainer, KeyCode::Char('4'));
+    assert_eq!(trainer.game().notes(Cell::new(2)), notes ^ Digit::new(4));
+    press(&mut trainer, KeyCode::Char('n'));
+
+    // enter the solution row by row, starting from the top left cell
+    press(&mut trainer, KeyCode::Left);
+    press(&mut trainer, KeyCode::Left);
+    for (cell, (clue, digit)) in sudoku.to_bytes().iter().zip(solution.iter()).enumerate() {
+        if *clue == 0 {
+            assert!(!trainer.is_solved());
+            press(&mut trainer, KeyCode::Char((b'0' + digit) as char));
+        }
+        press(&mut trainer, KeyCode::Right);
+        if cell % 9 == 8 {
+            press(&mut trainer, KeyCode::Down);
+        }
+    }
+    assert!(trainer.is_solved());
+    assert!(screen(&trainer)[0].contains("Solved in: "));
+    // no more changes after solving
+    press(&mut trainer, KeyCode::Backspace);
+    assert_eq!(trainer.game().position(), Sudoku::from_bytes(solution).unwrap());
+    assert!(!press(&mut trainer, KeyCode::Char('q')));
+}